@@ -0,0 +1,63 @@
+use bumpalo::Bump;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use kiru::{chunk_into_arena, ChunkerBuilder, Source};
+use std::fs;
+use std::hint::black_box;
+use std::time::Duration;
+
+const LARGE_FILE_PATH: &str = "../test-data/realistic-5.0mb.txt";
+
+fn benchmark_arena_vs_string(c: &mut Criterion) {
+    let content = match fs::read_to_string(LARGE_FILE_PATH) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("⚠️  Failed to read {}: {}", LARGE_FILE_PATH, e);
+            eprintln!("   Skipping arena_vs_string benchmark");
+            return;
+        }
+    };
+
+    let mut group = c.benchmark_group("arena_vs_string");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(5));
+    group.throughput(Throughput::Bytes(content.len() as u64));
+
+    for chunk_size in [256, 1024, 4096] {
+        let chunker = ChunkerBuilder::by_bytes(chunk_size, chunk_size / 10).unwrap();
+
+        group.bench_with_input(
+            BenchmarkId::new("string", chunk_size),
+            &chunker,
+            |b, chunker| {
+                b.iter(|| {
+                    let chunks: Vec<String> = chunker
+                        .on_source(Source::Text(content.clone()))
+                        .unwrap()
+                        .collect();
+                    black_box(chunks)
+                });
+            },
+        );
+
+        let mut arena = Bump::new();
+        group.bench_with_input(
+            BenchmarkId::new("arena", chunk_size),
+            &chunker,
+            |b, chunker| {
+                b.iter(|| {
+                    arena.reset();
+                    let chunks: Vec<&str> =
+                        chunk_into_arena(&arena, Source::Text(content.clone()), chunker)
+                            .unwrap()
+                            .collect();
+                    black_box(chunks.len())
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_arena_vs_string);
+criterion_main!(benches);
@@ -86,9 +86,62 @@ fn benchmark_file_chunking_by_characters(c: &mut Criterion) {
     group.finish();
 }
 
+fn benchmark_file_chunking_ascii(c: &mut Criterion) {
+    // Check if file exists
+    if !std::path::Path::new(LARGE_FILE_PATH).exists() {
+        println!("⚠️  Large test file not found at: {}", LARGE_FILE_PATH);
+        println!("   Skipping file benchmarks");
+        return;
+    }
+
+    // An ASCII copy of the test file, its other bytes replaced
+    let content: Vec<u8> = fs::read(LARGE_FILE_PATH)
+        .unwrap()
+        .into_iter()
+        .map(|b| if b.is_ascii() { b } else { b'?' })
+        .collect();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("ascii.txt").to_string_lossy().into_owned();
+    fs::write(&path, &content).unwrap();
+
+    let mut group = c.benchmark_group("file_chunking_ascii");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(5));
+    group.throughput(Throughput::Bytes(content.len() as u64));
+
+    // The same chunking with and without the ASCII assertion
+    for ascii in [false, true] {
+        let name = if ascii { "ascii" } else { "utf8" };
+
+        group.bench_with_input(BenchmarkId::new("bytes", name), &ascii, |b, &ascii| {
+            b.iter(|| {
+                let chunker = BytesChunker::new(1024, 102).unwrap().with_ascii(ascii);
+                let stream = StreamType::from_source(&Source::File(path.clone()))
+                    .unwrap()
+                    .with_ascii(ascii);
+                let chunks: Vec<_> = chunker.chunk_stream(stream).collect();
+                black_box(chunks)
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("characters", name), &ascii, |b, &ascii| {
+            b.iter(|| {
+                let chunker = CharactersChunker::new(1024, 102).unwrap().with_ascii(ascii);
+                let stream = StreamType::from_source(&Source::File(path.clone()))
+                    .unwrap()
+                    .with_ascii(ascii);
+                let chunks: Vec<_> = chunker.chunk_stream(stream).collect();
+                black_box(chunks)
+            });
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     benchmark_file_chunking_by_bytes,
     benchmark_file_chunking_by_characters,
+    benchmark_file_chunking_ascii,
 );
 criterion_main!(benches);
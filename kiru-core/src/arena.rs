@@ -0,0 +1,22 @@
+use bumpalo::Bump;
+
+use crate::chunker::{Chunker, ChunkerWithStrategy, ChunkingError, Source};
+
+/// Chunk `source` with `chunker`, copying the text of every chunk into `arena`.
+///
+/// Chunks live as long as the arena and are freed with it at once, instead of one `String`
+/// each: the text a chunk is cut into is dropped as soon as it is copied, so the global
+/// allocator only ever recycles the block of the chunk in flight. Resetting the arena
+/// between sources reuses its memory.
+///
+/// An IO error that interrupts the source ends the iteration early, as with
+/// [`ChunkerWithStrategy::on_source`].
+pub fn chunk_into_arena<'a, C: Chunker>(
+    arena: &'a Bump,
+    source: Source,
+    chunker: &ChunkerWithStrategy<C>,
+) -> Result<impl Iterator<Item = &'a str> + 'a, ChunkingError> {
+    Ok(chunker
+        .on_source(source)?
+        .map(|text| &*arena.alloc_str(&text)))
+}
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use arrow_array::builder::{LargeStringBuilder, UInt64Builder};
+use arrow_array::{ArrayRef, RecordBatch, RecordBatchReader};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+
+use crate::chunker::{Chunk, ChunkingError};
+
+/// Schema of the batches of [`record_batches`]: `text` (large utf8), `start`, `end` and
+/// `index` (uint64), none nullable.
+pub fn chunk_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("text", DataType::LargeUtf8, false),
+        Field::new("start", DataType::UInt64, false),
+        Field::new("end", DataType::UInt64, false),
+        Field::new("index", DataType::UInt64, false),
+    ]))
+}
+
+/// Gather `chunks` into Arrow record batches of up to `batch_size` rows each; the last
+/// batch holds the remainder. Chunks are pulled one batch at a time.
+///
+/// An error from `chunks` is yielded after the batch of the chunks before it, and ends the
+/// batches.
+pub fn record_batches<I>(chunks: I, batch_size: usize) -> Result<ChunkBatches<I>, ChunkingError>
+where
+    I: Iterator<Item = Result<Chunk, ChunkingError>>,
+{
+    if batch_size == 0 {
+        return Err(ChunkingError::InvalidBatchSize);
+    }
+    Ok(ChunkBatches {
+        chunks,
+        batch_size,
+        schema: chunk_schema(),
+        error: None,
+        done: false,
+    })
+}
+
+/// Record batches of chunks, see [`record_batches`]. Also a [`RecordBatchReader`], to hand
+/// the batches over through the Arrow C stream interface.
+pub struct ChunkBatches<I> {
+    chunks: I,
+    batch_size: usize,
+    schema: SchemaRef,
+    /// Error met while filling the last batch, yielded after it.
+    error: Option<ChunkingError>,
+    done: bool,
+}
+
+impl<I: Iterator<Item = Result<Chunk, ChunkingError>>> ChunkBatches<I> {
+    fn batch(&self, chunks: &[Chunk]) -> Result<RecordBatch, ArrowError> {
+        let text_len = chunks.iter().map(|chunk| chunk.text.len()).sum();
+        let mut text = LargeStringBuilder::with_capacity(chunks.len(), text_len);
+        let mut start = UInt64Builder::with_capacity(chunks.len());
+        let mut end = UInt64Builder::with_capacity(chunks.len());
+        let mut index = UInt64Builder::with_capacity(chunks.len());
+
+        for chunk in chunks {
+            text.append_value(&chunk.text);
+            start.append_value(chunk.start as u64);
+            end.append_value(chunk.end as u64);
+            index.append_value(chunk.index as u64);
+        }
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(text.finish()),
+            Arc::new(start.finish()),
+            Arc::new(end.finish()),
+            Arc::new(index.finish()),
+        ];
+        RecordBatch::try_new(self.schema.clone(), columns)
+    }
+}
+
+impl<I: Iterator<Item = Result<Chunk, ChunkingError>>> Iterator for ChunkBatches<I> {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(error) = self.error.take() {
+            self.done = true;
+            return Some(Err(ArrowError::ExternalError(Box::new(error))));
+        }
+        if self.done {
+            return None;
+        }
+
+        let mut chunks = Vec::with_capacity(self.batch_size);
+        while chunks.len() < self.batch_size {
+            match self.chunks.next() {
+                Some(Ok(chunk)) => chunks.push(chunk),
+                Some(Err(error)) => {
+                    self.error = Some(error);
+                    break;
+                }
+                None => {
+                    self.done = true;
+                    break;
+                }
+            }
+        }
+
+        if chunks.is_empty() {
+            return self.next();
+        }
+        Some(self.batch(&chunks))
+    }
+}
+
+impl<I: Iterator<Item = Result<Chunk, ChunkingError>>> RecordBatchReader for ChunkBatches<I> {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
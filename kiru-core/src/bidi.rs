@@ -0,0 +1,61 @@
+/// Left-to-right, right-to-left and Arabic letter marks.
+const MARKS: [char; 3] = ['\u{200E}', '\u{200F}', '\u{061C}'];
+/// LRE, RLE, LRO and RLO, each closed by a PDF.
+const EMBEDDINGS: [char; 4] = ['\u{202A}', '\u{202B}', '\u{202D}', '\u{202E}'];
+const PDF: char = '\u{202C}';
+/// LRI, RLI and FSI, each closed by a PDI.
+const ISOLATES: [char; 3] = ['\u{2066}', '\u{2067}', '\u{2068}'];
+const PDI: char = '\u{2069}';
+
+pub(crate) fn is_bidi_control(c: char) -> bool {
+    MARKS.contains(&c) || EMBEDDINGS.contains(&c) || ISOLATES.contains(&c) || c == PDF || c == PDI
+}
+
+/// Move the cut at byte `pos` of `text` back before the bidi controls right before it, which
+/// belong with the char that follows them. The cut stays after `floor`: when only controls
+/// lie in between, `pos` is returned unchanged.
+pub(crate) fn attach_bidi_controls(text: &str, floor: usize, pos: usize) -> usize {
+    let kept = text[floor..pos].trim_end_matches(is_bidi_control);
+    if kept.is_empty() {
+        pos
+    } else {
+        floor + kept.len()
+    }
+}
+
+/// Balance the embedding, override and isolate controls of `text`: closers with no opener
+/// earlier in `text` are removed, and openers left open at its end are closed there.
+pub(crate) fn balance_bidi(text: &mut String) {
+    if !text.contains(|c| c == PDF || c == PDI || EMBEDDINGS.contains(&c) || ISOLATES.contains(&c))
+    {
+        return;
+    }
+
+    let mut open = Vec::new();
+    let mut balanced = String::with_capacity(text.len() + 3);
+    for c in text.chars() {
+        if EMBEDDINGS.contains(&c) || ISOLATES.contains(&c) {
+            open.push(c);
+        } else if c == PDF {
+            // A PDF only closes an embedding or override opened inside the current isolate
+            if !open.last().is_some_and(|o| EMBEDDINGS.contains(o)) {
+                continue;
+            }
+            open.pop();
+        } else if c == PDI {
+            // A PDI closes its isolate along with the embeddings still open inside it
+            let Some(isolate) = open.iter().rposition(|o| ISOLATES.contains(o)) else {
+                continue;
+            };
+            open.truncate(isolate);
+        }
+        balanced.push(c);
+    }
+    balanced.extend(
+        open.iter()
+            .rev()
+            .map(|o| if ISOLATES.contains(o) { PDI } else { PDF }),
+    );
+
+    *text = balanced;
+}
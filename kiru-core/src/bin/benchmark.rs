@@ -1,29 +1,59 @@
 // kiru-core/src/bin/benchmark.rs
 
-use kiru::{ChunkerBuilder, Source};
+use kiru::{ChunkerBuilder, Source, StreamType};
 use serde::Serialize;
+use std::cell::Cell;
 use std::env;
-use std::time::Instant;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 #[derive(Serialize)]
 struct BenchmarkResult {
     elapsed_secs: f64,
+    /// Time spent reading and decoding blocks from the source.
+    io_secs: f64,
+    /// Time spent computing chunks from the blocks.
+    cpu_secs: f64,
     num_chunks: usize,
     total_bytes: usize,
     throughput_mb_s: f64,
 }
 
+#[derive(Serialize)]
+struct HistogramBucket {
+    start: usize,
+    end: usize,
+    count: usize,
+}
+
+#[derive(Serialize)]
+struct HistogramResult {
+    num_chunks: usize,
+    min_bytes: usize,
+    max_bytes: usize,
+    mean_bytes: f64,
+    buckets: Vec<HistogramBucket>,
+}
+
 #[derive(Serialize)]
 struct BenchmarkError {
     error: String,
 }
 
+const HISTOGRAM_BUCKETS: usize = 20;
+
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+
+    // `benchmark hist ...` reports the chunk size distribution instead of throughput
+    let hist = args.get(1).is_some_and(|a| a == "hist");
+    if hist {
+        args.remove(1);
+    }
 
     if args.len() < 6 {
         let error = BenchmarkError {
-            error: "Usage: benchmark <strategy> <source_type> <path> <chunk_size> <overlap>"
+            error: "Usage: benchmark [hist] <strategy> <source_type> <path> <chunk_size> <overlap>"
                 .to_string(),
         };
         eprintln!("{}", serde_json::to_string(&error).unwrap());
@@ -54,11 +84,17 @@ fn main() {
         }
     };
 
-    let result = run_benchmark(strategy, source_type, path, chunk_size, overlap);
+    let result = if hist {
+        run_histogram(strategy, source_type, path, chunk_size, overlap)
+            .map(|r| serde_json::to_string(&r).unwrap())
+    } else {
+        run_benchmark(strategy, source_type, path, chunk_size, overlap)
+            .map(|r| serde_json::to_string(&r).unwrap())
+    };
 
     match result {
-        Ok(bench_result) => {
-            println!("{}", serde_json::to_string(&bench_result).unwrap());
+        Ok(json) => {
+            println!("{}", json);
         }
         Err(e) => {
             let error = BenchmarkError {
@@ -70,15 +106,8 @@ fn main() {
     }
 }
 
-fn run_benchmark(
-    strategy: &str,
-    source_type: &str,
-    path: &str,
-    chunk_size: usize,
-    overlap: usize,
-) -> Result<BenchmarkResult, Box<dyn std::error::Error>> {
-    // Parse the source based on source_type
-    let source = match source_type {
+fn parse_source(source_type: &str, path: &str) -> Result<Source, Box<dyn std::error::Error>> {
+    Ok(match source_type {
         "file" => Source::File(path.to_string()),
         "http" | "https" => Source::Http(path.to_string()),
         "string" => Source::Text(path.to_string()),
@@ -89,7 +118,17 @@ fn run_benchmark(
             )
             .into());
         }
-    };
+    })
+}
+
+fn run_benchmark(
+    strategy: &str,
+    source_type: &str,
+    path: &str,
+    chunk_size: usize,
+    overlap: usize,
+) -> Result<BenchmarkResult, Box<dyn std::error::Error>> {
+    let source = parse_source(source_type, path)?;
 
     // Create the chunker using ChunkerBuilder
     match strategy {
@@ -105,6 +144,23 @@ fn run_benchmark(
     }
 }
 
+/// Blocks of a source, timing how long each one takes to produce.
+struct TimedBlocks<I> {
+    blocks: I,
+    elapsed: Rc<Cell<Duration>>,
+}
+
+impl<I: Iterator<Item = String>> Iterator for TimedBlocks<I> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let start = Instant::now();
+        let block = self.blocks.next();
+        self.elapsed.set(self.elapsed.get() + start.elapsed());
+        block
+    }
+}
+
 // Generic benchmarking body specialized for the concrete chunker type.
 fn bench_with<C>(
     chunker: kiru::ChunkerWithStrategy<C>,
@@ -117,20 +173,89 @@ where
     let mut num_chunks = 0usize;
     let mut total_bytes = 0usize;
 
-    let iterator = chunker.on_source(source)?;
-    for chunk in iterator {
+    let io = Rc::new(Cell::new(Duration::ZERO));
+    let blocks = TimedBlocks {
+        blocks: StreamType::from_source(&source)?,
+        elapsed: io.clone(),
+    };
+    // Opening the source (e.g. the file or the HTTP request) counts as I/O
+    let open = start.elapsed();
+    io.set(open);
+
+    let chunking = Instant::now();
+    for chunk in chunker.chunker().clone().chunk_stream(blocks) {
         num_chunks += 1;
         total_bytes += chunk.len();
         std::hint::black_box(chunk.len());
     }
+    let chunking = chunking.elapsed();
 
     let elapsed_secs = start.elapsed().as_secs_f64();
     let throughput_mb_s = (total_bytes as f64) / (1024.0 * 1024.0) / elapsed_secs;
 
     Ok(BenchmarkResult {
         elapsed_secs,
+        io_secs: io.get().as_secs_f64(),
+        cpu_secs: chunking.saturating_sub(io.get() - open).as_secs_f64(),
         num_chunks,
         total_bytes,
         throughput_mb_s,
     })
 }
+
+fn run_histogram(
+    strategy: &str,
+    source_type: &str,
+    path: &str,
+    chunk_size: usize,
+    overlap: usize,
+) -> Result<HistogramResult, Box<dyn std::error::Error>> {
+    let source = parse_source(source_type, path)?;
+
+    let sizes: Vec<usize> = match strategy {
+        "bytes" => ChunkerBuilder::by_bytes(chunk_size, overlap)?
+            .on_source(source)?
+            .map(|chunk| chunk.len())
+            .collect(),
+        "chars" => ChunkerBuilder::by_characters(chunk_size, overlap)?
+            .on_source(source)?
+            .map(|chunk| chunk.len())
+            .collect(),
+        _ => return Err(format!("Invalid strategy '{}'. Use 'bytes' or 'chars'", strategy).into()),
+    };
+
+    Ok(histogram(&sizes))
+}
+
+// Equal-width buckets spanning [min, max] of the observed chunk byte sizes.
+fn histogram(sizes: &[usize]) -> HistogramResult {
+    let min_bytes = sizes.iter().copied().min().unwrap_or(0);
+    let max_bytes = sizes.iter().copied().max().unwrap_or(0);
+    let mean_bytes = if sizes.is_empty() {
+        0.0
+    } else {
+        sizes.iter().sum::<usize>() as f64 / sizes.len() as f64
+    };
+
+    let width = ((max_bytes - min_bytes) / HISTOGRAM_BUCKETS + 1).max(1);
+    let mut buckets: Vec<HistogramBucket> = (0..HISTOGRAM_BUCKETS)
+        .map(|i| HistogramBucket {
+            start: min_bytes + i * width,
+            end: min_bytes + (i + 1) * width,
+            count: 0,
+        })
+        .take_while(|b| b.start <= max_bytes)
+        .collect();
+
+    for &size in sizes {
+        buckets[(size - min_bytes) / width].count += 1;
+    }
+
+    HistogramResult {
+        num_chunks: sizes.len(),
+        min_bytes,
+        max_bytes,
+        mean_bytes,
+        buckets,
+    }
+}
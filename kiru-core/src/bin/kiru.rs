@@ -0,0 +1,13 @@
+// kiru-core/src/bin/kiru.rs
+
+use std::env;
+use std::io;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if let Err(e) = kiru::run_cli(&args, &mut io::stdout().lock()) {
+        eprintln!("kiru: {e}");
+        std::process::exit(e.exit_code());
+    }
+}
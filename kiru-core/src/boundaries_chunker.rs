@@ -0,0 +1,205 @@
+use crate::chunker::{
+    floor_char_boundary, stream_memory, Chunk, ChunkKind, Chunker, ChunkingError, StringBuffer,
+};
+use crate::BLOCK_SIZE;
+
+const MIN_BUFFER_SIZE: usize = 64 * 1024;
+
+struct BoundariesChunkIndices {
+    start: usize,
+    end: usize,
+    next_cut: usize,
+}
+
+/// Materializes chunks from externally computed cut points.
+///
+/// `boundaries` are byte offsets into the source. Each chunk spans from one cut to the
+/// next (the first chunk starts at 0, the last one runs to the end of the input), and
+/// every chunk after the first additionally repeats at least `overlap` bytes of the
+/// content preceding its cut. Cuts and overlap starts that fall inside a multi-byte
+/// character are snapped backwards to the previous char boundary.
+#[derive(Clone)]
+pub struct BoundariesChunker {
+    boundaries: Vec<usize>,
+    overlap: usize,
+}
+
+impl BoundariesChunker {
+    pub fn new(boundaries: Vec<usize>, overlap: usize) -> Result<Self, ChunkingError> {
+        if boundaries.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(ChunkingError::InvalidBoundaries);
+        }
+
+        Ok(Self {
+            boundaries,
+            overlap,
+        })
+    }
+
+    /// Compute the next chunk for the segment starting at `segment_start` (absolute offset).
+    /// `offset` is the absolute offset of `buffer[0]`; returned indices are buffer-relative.
+    fn next_chunk_indices(
+        &self,
+        buffer: &str,
+        offset: usize,
+        segment_start: usize,
+        cut: usize,
+    ) -> Option<BoundariesChunkIndices> {
+        let buffer_len = buffer.len();
+        let relative_start = segment_start - offset;
+
+        // Done
+        if relative_start >= buffer_len {
+            return None;
+        }
+
+        let mut cut = cut;
+        let end = loop {
+            match self.boundaries.get(cut) {
+                // Cut already behind us
+                Some(&b) if b <= segment_start => cut += 1,
+                Some(&b) => {
+                    let end = floor_char_boundary(buffer, (b - offset).min(buffer_len));
+                    // Cut snapped onto the segment start (inside its first char), skip it
+                    if end <= relative_start {
+                        cut += 1;
+                        continue;
+                    }
+                    break end;
+                }
+                None => break buffer_len,
+            }
+        };
+
+        let start = floor_char_boundary(buffer, relative_start.saturating_sub(self.overlap));
+
+        Some(BoundariesChunkIndices {
+            start,
+            end,
+            next_cut: cut + 1,
+        })
+    }
+}
+
+impl Chunker for BoundariesChunker {
+    /// One chunk per segment, each repeating at most `overlap + 3` bytes before its cut.
+    fn max_output(&self, source_len: usize) -> Option<(usize, usize)> {
+        let cuts = self
+            .boundaries
+            .iter()
+            .filter(|&&b| 0 < b && b < source_len)
+            .count();
+        let chunks = if source_len > 0 { cuts + 1 } else { 0 };
+        let bytes = source_len + cuts * (self.overlap + 3);
+        Some((chunks, bytes.min(chunks * source_len)))
+    }
+
+    fn estimate_memory(&self, source_len: usize) -> usize {
+        // The buffer must hold the longest segment (with its overlap) at once
+        let cuts = self.boundaries.iter().filter(|&&b| b < source_len);
+        let longest_segment = std::iter::once(&0)
+            .chain(cuts.clone())
+            .zip(cuts.chain(std::iter::once(&source_len)))
+            .map(|(from, to)| to - from)
+            .max()
+            .unwrap_or(0);
+        let chunk = (longest_segment + self.overlap).min(source_len);
+        let buffered = (MIN_BUFFER_SIZE.max(chunk) + BLOCK_SIZE + 4).min(source_len);
+
+        stream_memory(MIN_BUFFER_SIZE, buffered) + chunk
+    }
+
+    fn chunk_string_with_offsets(self, input: String) -> impl Iterator<Item = Chunk> {
+        let mut segment_start = 0;
+        let mut cut = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || {
+            let next = self.next_chunk_indices(&input, 0, segment_start, cut)?;
+            segment_start = next.end;
+            cut = next.next_cut;
+            let chunk = Chunk {
+                text: input[next.start..next.end].to_string(),
+                start: next.start,
+                end: next.end,
+                index,
+                lines: None,
+                chars: None,
+                running_hash: None,
+                links: None,
+                hierarchy: None,
+                source_stamp: None,
+                header_len: 0,
+                prefix_len: 0,
+                suffix_len: 0,
+                kind: ChunkKind::Main,
+            };
+            index += 1;
+            Some(chunk)
+        })
+    }
+
+    fn chunk_stream_with_offsets(
+        self,
+        input: impl Iterator<Item = String>,
+    ) -> impl Iterator<Item = Chunk> {
+        let mut string_buffer = StringBuffer::new(input, MIN_BUFFER_SIZE);
+        let mut segment_start = 0;
+        let mut cut = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || loop {
+            let buffer = string_buffer.buffer();
+            let offset = string_buffer.offset;
+            let next = self.next_chunk_indices(buffer, offset, segment_start, cut);
+
+            match next {
+                // if the stream is done and no more chunks can be made, return None
+                None if string_buffer.done => return None,
+
+                // if no chunk can be made but the stream is not done, fill more data and try again
+                None if !string_buffer.done => {
+                    string_buffer.fill();
+                    continue;
+                }
+
+                None => unreachable!(), // handled above
+
+                // if the chunk end reaches the buffer end but the stream is not done, the cut
+                // may lie further ahead: fill more data and try again
+                Some(BoundariesChunkIndices { end, .. })
+                    if !string_buffer.done && end == buffer.len() =>
+                {
+                    string_buffer.fill();
+                    continue;
+                }
+
+                // otherwise, return the chunk and keep the next chunk's overlap in the buffer
+                Some(ref n) => {
+                    let chunk = Chunk {
+                        text: buffer[n.start..n.end].to_string(),
+                        start: offset + n.start,
+                        end: offset + n.end,
+                        index,
+                        lines: None,
+                        chars: None,
+                        running_hash: None,
+                        links: None,
+                        hierarchy: None,
+                        source_stamp: None,
+                        header_len: 0,
+                        prefix_len: 0,
+                        suffix_len: 0,
+                        kind: ChunkKind::Main,
+                    };
+                    index += 1;
+                    let keep_from = floor_char_boundary(buffer, n.end.saturating_sub(self.overlap));
+                    segment_start = offset + n.end;
+                    cut = n.next_cut;
+                    string_buffer.set_position(keep_from);
+                    return Some(chunk);
+                }
+            };
+        })
+    }
+}
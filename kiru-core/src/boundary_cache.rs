@@ -0,0 +1,118 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use crate::chunker::{
+    content_hash, Chunk, ChunkKind, Chunker, ChunkerWithStrategy, ChunkingError, Source,
+};
+
+/// Boundaries of the chunks of a file, with what they were computed from.
+#[derive(Serialize, Deserialize)]
+struct BoundaryCache {
+    /// FNV-1a hash of the chunking parameters, stable across builds.
+    params_hash: u64,
+    source_len: u64,
+    /// Modification time of the source, in nanoseconds since the Unix epoch.
+    source_mtime: u64,
+    /// `(start, end, index)` of every chunk, in order.
+    chunks: Vec<(usize, usize, usize)>,
+}
+
+/// Size and modification time of the file at `path`.
+fn fingerprint(path: &Path) -> Result<(u64, u64), ChunkingError> {
+    let metadata = fs::metadata(path)?;
+    let mtime = metadata
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64);
+    Ok((metadata.len(), mtime))
+}
+
+/// Chunk the file at `source` and write the boundaries of its chunks to `cache_path`, along
+/// with a hash of `params` (any description of the chunking parameters, e.g.
+/// `"bytes:1024:128"`) and the size and modification time of the file. Returns the number
+/// of chunks.
+pub fn precompute_boundaries<C: Chunker>(
+    chunker: &ChunkerWithStrategy<C>,
+    source: impl AsRef<Path>,
+    params: &str,
+    cache_path: impl AsRef<Path>,
+) -> Result<usize, ChunkingError> {
+    let source = source.as_ref();
+    // Taken before chunking, so that a change made meanwhile invalidates the cache
+    let (source_len, source_mtime) = fingerprint(source)?;
+
+    let chunks = chunker
+        .on_source_with_offsets(Source::File(source.to_string_lossy().into_owned()))?
+        .map(|chunk| chunk.map(|c| (c.start, c.end, c.index)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let cache = BoundaryCache {
+        params_hash: content_hash(params),
+        source_len,
+        source_mtime,
+        chunks,
+    };
+
+    let mut writer = BufWriter::new(File::create(cache_path)?);
+    serde_json::to_writer(&mut writer, &cache).map_err(io::Error::from)?;
+    writer.flush()?;
+    Ok(cache.chunks.len())
+}
+
+/// Chunks of the file at `source`, sliced at the boundaries cached in `cache_path` by
+/// [`precompute_boundaries`] without chunking the file again.
+///
+/// Fails with [`ChunkingError::InvalidBoundaryCache`] if the cache was computed with other
+/// `params`, or the size or modification time of the file changed since. Chunks are read
+/// one at a time, so memory stays bounded by the largest chunk.
+pub fn from_cached_boundaries(
+    source: impl AsRef<Path>,
+    params: &str,
+    cache_path: impl AsRef<Path>,
+) -> Result<impl Iterator<Item = Result<Chunk, ChunkingError>>, ChunkingError> {
+    let source = source.as_ref();
+    let reader = BufReader::new(File::open(cache_path)?);
+    let cache: BoundaryCache = serde_json::from_reader(reader)
+        .map_err(|e| ChunkingError::InvalidBoundaryCache(e.to_string()))?;
+
+    if cache.params_hash != content_hash(params) {
+        return Err(ChunkingError::InvalidBoundaryCache(
+            "computed with other parameters".to_string(),
+        ));
+    }
+    if fingerprint(source)? != (cache.source_len, cache.source_mtime) {
+        return Err(ChunkingError::InvalidBoundaryCache(format!(
+            "{} changed since",
+            source.display()
+        )));
+    }
+
+    let mut file = File::open(source)?;
+    Ok(cache.chunks.into_iter().map(move |(start, end, index)| {
+        let mut bytes = vec![0; end - start];
+        file.seek(SeekFrom::Start(start as u64))?;
+        file.read_exact(&mut bytes)?;
+        let text = String::from_utf8(bytes)
+            .map_err(|e| ChunkingError::InvalidBoundaryCache(e.to_string()))?;
+
+        Ok(Chunk {
+            text,
+            start,
+            end,
+            index,
+            lines: None,
+            chars: None,
+            running_hash: None,
+            links: None,
+            hierarchy: None,
+            source_stamp: None,
+            header_len: 0,
+            prefix_len: 0,
+            suffix_len: 0,
+            kind: ChunkKind::Main,
+        })
+    }))
+}
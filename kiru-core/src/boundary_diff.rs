@@ -0,0 +1,85 @@
+use std::collections::{BTreeSet, HashSet};
+
+use serde::Serialize;
+
+/// How the chunk boundaries of one chunking changed in another, e.g. after tuning the chunk
+/// size or overlap.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BoundaryDiff {
+    /// Offsets that only the second chunking cuts at, and that no offset of the first moved to.
+    pub added: Vec<usize>,
+    /// Offsets that only the first chunking cuts at, and that did not move elsewhere.
+    pub removed: Vec<usize>,
+    /// `(old, new)` offsets of the cuts that moved.
+    pub shifted: Vec<(usize, usize)>,
+    /// Chunks of the second chunking with the same span as one of the first.
+    pub unchanged_chunks: usize,
+    /// Fraction of the chunks of the second chunking that are unchanged, i.e. whose cached
+    /// embeddings stay valid (`1.0` without chunks).
+    pub unchanged_fraction: f64,
+}
+
+/// Compare two chunkings given as the `(start, end)` spans of their chunks.
+///
+/// The boundaries of a chunking are the starts and ends of its chunks. Between two offsets
+/// that both chunkings cut at, the changed boundaries of each are paired in order as shifted
+/// ones, the side with more of them leaving out those farther from their counterpart, which
+/// are added or removed.
+pub fn diff_boundaries(a: &[(usize, usize)], b: &[(usize, usize)]) -> BoundaryDiff {
+    let offsets = |spans: &[(usize, usize)]| -> BTreeSet<usize> {
+        spans
+            .iter()
+            .flat_map(|&(start, end)| [start, end])
+            .collect()
+    };
+    let (offsets_a, offsets_b) = (offsets(a), offsets(b));
+
+    let mut diff = BoundaryDiff {
+        added: vec![],
+        removed: vec![],
+        shifted: vec![],
+        unchanged_chunks: 0,
+        unchanged_fraction: 1.0,
+    };
+    let mut pending_removed = vec![];
+    let mut pending_added = vec![];
+    let mut flush = |removed: &mut Vec<usize>, added: &mut Vec<usize>| {
+        let (mut i, mut j) = (0, 0);
+        while i < removed.len() && j < added.len() {
+            let (old, new) = (removed[i], added[j]);
+            // A side with more changes skips the ones that the next change fits better
+            let closer = |next: Option<&usize>, to: usize, than: usize| {
+                next.is_some_and(|&next| next.abs_diff(to) < than.abs_diff(to))
+            };
+            if added.len() - j > removed.len() - i && closer(added.get(j + 1), old, new) {
+                diff.added.push(new);
+                j += 1;
+            } else if removed.len() - i > added.len() - j && closer(removed.get(i + 1), new, old) {
+                diff.removed.push(old);
+                i += 1;
+            } else {
+                diff.shifted.push((old, new));
+                i += 1;
+                j += 1;
+            }
+        }
+        diff.removed.extend(removed.drain(..).skip(i));
+        diff.added.extend(added.drain(..).skip(j));
+    };
+
+    for &offset in offsets_a.union(&offsets_b) {
+        match (offsets_a.contains(&offset), offsets_b.contains(&offset)) {
+            (true, true) => flush(&mut pending_removed, &mut pending_added),
+            (true, false) => pending_removed.push(offset),
+            _ => pending_added.push(offset),
+        }
+    }
+    flush(&mut pending_removed, &mut pending_added);
+
+    let spans_a: HashSet<_> = a.iter().collect();
+    diff.unchanged_chunks = b.iter().filter(|span| spans_a.contains(span)).count();
+    if !b.is_empty() {
+        diff.unchanged_fraction = diff.unchanged_chunks as f64 / b.len() as f64;
+    }
+    diff
+}
@@ -1,15 +1,106 @@
-use crate::chunker::{Chunker, ChunkingError, StringBuffer};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::bidi::attach_bidi_controls;
+use crate::chunker::{
+    ceil_char_boundary, default_buffer_capacity, floor_char_boundary, max_overlap, overlap_at,
+    peak_buffered, sliding_count, sliding_output, stream_memory, Chunk, ChunkKind, Chunker,
+    ChunkingError, OverlapDirection, StringBuffer,
+};
+use crate::sentences::{nth_last_sentence_start, sentence_starts, SentenceTerminators};
+use crate::StrategyConfig;
+
+/// How many characters to walk back from the overlap start when looking for a word start.
+const WORD_SEARCH_LIMIT: usize = 64;
 
 struct BytesChunkIndices {
     start: usize,
     end: usize,
     new_position: usize,
+    /// How many of the chunk end and the next position were moved onto a char boundary.
+    adjustments: usize,
+    /// Bytes the chunk end was moved back by to end on a whole word.
+    word_snap: usize,
+}
+
+/// Parameters of a [`BytesChunker`], named after its `with_*` methods, see
+/// [`crate::ChunkerConfig`]. `0` (or an empty schedule) disables an option.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BytesConfig {
+    pub chunk_size: usize,
+    pub overlap: usize,
+    #[serde(default)]
+    pub overlap_schedule: Vec<(usize, usize)>,
+    #[serde(default)]
+    pub overlap_sentences: usize,
+    #[serde(default)]
+    pub overlap_chars: usize,
+    #[serde(default)]
+    pub overlap_whole_words: bool,
+    #[serde(default)]
+    pub snap_to_word: bool,
+    #[serde(default)]
+    pub soft_max_extra: usize,
+    #[serde(default)]
+    pub grid_align: usize,
+    #[serde(default)]
+    pub max_lines: usize,
+    #[serde(default)]
+    pub overlap_direction: OverlapDirection,
+    #[serde(default)]
+    pub ascii: bool,
+    #[serde(default)]
+    pub context_before_units: usize,
+    #[serde(default)]
+    pub context_after_units: usize,
+    #[serde(default)]
+    pub buffer_capacity: usize,
+}
+
+impl BytesConfig {
+    /// The chunker these parameters describe.
+    pub fn build(&self) -> Result<BytesChunker, ChunkingError> {
+        Ok(BytesChunker::new(self.chunk_size, self.overlap)?
+            .with_overlap_schedule(self.overlap_schedule.clone())?
+            .with_overlap_sentences(self.overlap_sentences)
+            .with_overlap_chars(self.overlap_chars)
+            .with_overlap_whole_words(self.overlap_whole_words)
+            .with_snap_to_word(self.snap_to_word)
+            .with_soft_max_extra(self.soft_max_extra)
+            .with_grid_align(self.grid_align)
+            .with_max_lines(self.max_lines)
+            .with_overlap_direction(self.overlap_direction)
+            .with_ascii(self.ascii)
+            .with_context_before_units(self.context_before_units)
+            .with_context_after_units(self.context_after_units)
+            .with_buffer_capacity(self.buffer_capacity))
+    }
 }
 
 #[derive(Clone)]
 pub struct BytesChunker {
     chunk_size: usize,
     overlap: usize,
+    /// `(from_index, overlap)` steps replacing `overlap`, sorted by index.
+    overlap_schedule: Vec<(usize, usize)>,
+    overlap_sentences: usize,
+    overlap_chars: usize,
+    overlap_whole_words: bool,
+    snap_to_word: bool,
+    soft_max_extra: usize,
+    grid_align: usize,
+    max_lines: usize,
+    overlap_direction: OverlapDirection,
+    ascii: bool,
+    context_before: usize,
+    context_after: usize,
+    buffer_capacity: usize,
+    /// Chunk ends and overlap starts moved onto a char boundary, across clones.
+    boundary_adjustments: Arc<AtomicUsize>,
+    /// Chunks shortened to end on a whole word, and bytes cut from them, across clones.
+    word_snaps: Arc<(AtomicUsize, AtomicUsize)>,
 }
 
 impl BytesChunker {
@@ -24,15 +115,289 @@ impl BytesChunker {
         Ok(Self {
             chunk_size,
             overlap,
+            overlap_schedule: Vec::new(),
+            overlap_sentences: 0,
+            overlap_chars: 0,
+            overlap_whole_words: false,
+            snap_to_word: false,
+            soft_max_extra: 0,
+            grid_align: 0,
+            max_lines: 0,
+            overlap_direction: OverlapDirection::Backward,
+            ascii: false,
+            context_before: 0,
+            context_after: 0,
+            buffer_capacity: 0,
+            boundary_adjustments: Arc::default(),
+            word_snaps: Arc::default(),
         })
     }
 
+    /// Vary the overlap with the chunk index: each `(from_index, overlap)` step sets the
+    /// overlap in bytes of chunk `from_index` and the chunks after it with the chunk before
+    /// them, until the next step. Chunks before the first step overlap by `overlap`.
+    ///
+    /// Fails if a scheduled overlap is not less than the chunk size.
+    pub fn with_overlap_schedule(
+        mut self,
+        mut schedule: Vec<(usize, usize)>,
+    ) -> Result<Self, ChunkingError> {
+        if let Some(&(_, overlap)) = schedule.iter().find(|&&(_, o)| o >= self.chunk_size) {
+            return Err(ChunkingError::InvalidArguments {
+                chunk_size: self.chunk_size,
+                overlap,
+            });
+        }
+        schedule.sort_by_key(|&(from_index, _)| from_index);
+        self.overlap_schedule = schedule;
+        Ok(self)
+    }
+
+    /// Overlap of chunk `index` with the chunk before it.
+    fn overlap_at(&self, index: usize) -> usize {
+        overlap_at(&self.overlap_schedule, self.overlap, index)
+    }
+
+    /// Start each chunk after the first at the `n`-th last sentence beginning inside the
+    /// previous chunk, so the overlap is made of whole sentences instead of `overlap` bytes.
+    /// When the previous chunk holds fewer than `n` sentence starts, the byte overlap is
+    /// used instead. `0` disables sentence overlap.
+    pub fn with_overlap_sentences(mut self, n: usize) -> Self {
+        self.overlap_sentences = n;
+        self
+    }
+
+    /// Overlap each chunk after the first by at least the last `n` characters of the
+    /// previous chunk instead of `overlap` bytes. When the previous chunk holds `n`
+    /// characters or fewer, the byte overlap is used instead. `0` disables it.
+    ///
+    /// Sentence overlap ([`Self::with_overlap_sentences`]) takes precedence when it applies.
+    pub fn with_overlap_chars(mut self, n: usize) -> Self {
+        self.overlap_chars = n;
+        self
+    }
+
+    /// Move the overlap start (from `overlap_chars`, or else `overlap` bytes) back to the
+    /// beginning of the word it falls into, so the overlap never starts mid-word. The search
+    /// walks back at most 64 characters and stays inside the previous chunk; if it finds no
+    /// word start, the overlap start is left as is.
+    pub fn with_overlap_whole_words(mut self, enabled: bool) -> Self {
+        self.overlap_whole_words = enabled;
+        self
+    }
+
+    /// Move a chunk end that falls inside a word back to the beginning of that word, so that
+    /// chunks end with complete words. The search walks back at most 64 characters and stays
+    /// inside the chunk; if it finds no word start, the chunk is cut mid-word as usual. The
+    /// shortened chunks are counted in [`ChunkStats`](crate::ChunkStats).
+    pub fn with_snap_to_word(mut self, enabled: bool) -> Self {
+        self.snap_to_word = enabled;
+        self
+    }
+
+    /// Let a chunk grow up to `extra` bytes past `chunk_size` to finish its current sentence:
+    /// it then ends where the next sentence starts. When no sentence starts within the
+    /// extension budget, the chunk is cut at `chunk_size` as usual. `0` disables it.
+    pub fn with_soft_max_extra(mut self, extra: usize) -> Self {
+        self.soft_max_extra = extra;
+        self
+    }
+
+    /// End chunks on a fixed grid of the source: each chunk end is snapped down to the last
+    /// multiple of `grid` bytes (counted from the start of the source) that it reaches and
+    /// that is a char boundary, so chunks of documents sharing a prefix line up. When no such
+    /// grid line lies within the chunk, e.g. for a `grid` larger than the chunk size, the
+    /// chunk ends as usual. `0` disables it.
+    pub fn with_grid_align(mut self, grid: usize) -> Self {
+        self.grid_align = grid;
+        self
+    }
+
+    /// End a chunk early, right after its `n`-th newline, when it holds `n` lines before
+    /// reaching `chunk_size` bytes: whichever limit is hit first ends the chunk. A chunk cut
+    /// at the line cap is not extended by [`Self::with_soft_max_extra`]. `0` disables it.
+    pub fn with_max_lines(mut self, n: usize) -> Self {
+        self.max_lines = n;
+        self
+    }
+
+    /// End of the `max_lines`-th line of `buffer` from `start`, if it ends before `end`.
+    fn line_cap(&self, buffer: &str, start: usize, end: usize) -> Option<usize> {
+        if self.max_lines == 0 {
+            return None;
+        }
+
+        buffer.as_bytes()[start..end]
+            .iter()
+            .enumerate()
+            .filter(|&(_, &b)| b == b'\n')
+            .nth(self.max_lines - 1)
+            .map(|(i, _)| start + i + 1)
+            .filter(|&line_end| line_end < end)
+    }
+
+    /// Last grid line after `start` and at most `end` (both relative to `buffer`, which
+    /// starts at byte `offset` of the source) that is a char boundary.
+    fn grid_end(&self, buffer: &str, offset: usize, start: usize, end: usize) -> Option<usize> {
+        if self.grid_align == 0 {
+            return None;
+        }
+
+        let last_line = (offset + end) / self.grid_align;
+        (0..=last_line)
+            .rev()
+            .map(|line| line * self.grid_align)
+            .take_while(|&line| line > offset + start)
+            .map(|line| line - offset)
+            .find(|&line| buffer.is_char_boundary(line))
+    }
+
+    /// Make the overlap a lookahead at the end of each chunk into the next one, see
+    /// [`OverlapDirection`]: chunks hold up to `chunk_size + overlap` bytes and start every
+    /// `chunk_size` bytes.
+    pub fn with_overlap_direction(mut self, direction: OverlapDirection) -> Self {
+        self.overlap_direction = direction;
+        self
+    }
+
+    /// Largest forward lookahead a chunk holds on top of its own `chunk_size` bytes.
+    fn lookahead(&self) -> usize {
+        match self.overlap_direction {
+            OverlapDirection::Backward => 0,
+            OverlapDirection::Forward => max_overlap(&self.overlap_schedule, self.overlap),
+        }
+    }
+
+    /// Trust that the input is pure ASCII, so that every byte offset is a char boundary:
+    /// cuts are then never snapped to one, and file and HTTP sources are only checked to be
+    /// ASCII instead of decoded as UTF-8. A file or HTTP source with another byte ends with
+    /// [`ChunkingError::NonAscii`]. On strings, debug builds check the assertion and panic
+    /// if it is wrong; release builds may panic on a cut inside a char.
+    pub fn with_ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Allocate the buffer that file and HTTP sources are streamed through with `bytes`
+    /// bytes up front, so that it does not grow while streaming. It defaults to twice its
+    /// minimum size of five chunks, and at least its usual peak, which reading ahead (e.g.
+    /// context after chunks) can exceed. `0` keeps the default. Chunks are not affected.
+    pub fn with_buffer_capacity(mut self, bytes: usize) -> Self {
+        self.buffer_capacity = bytes;
+        self
+    }
+
+    fn buffer_capacity(&self, min_buffer_size: usize) -> usize {
+        match self.buffer_capacity {
+            0 => default_buffer_capacity(min_buffer_size),
+            bytes => bytes,
+        }
+    }
+
+    /// Extend each chunk with up to `n` bytes of the text before it, as context. Only the
+    /// emitted text and offsets grow: the step between chunks, and so which text each chunk
+    /// covers on its own, stay the same. Context is cut short at the start of the source and
+    /// never splits a char. `0` disables it.
+    pub fn with_context_before_units(mut self, n: usize) -> Self {
+        self.context_before = n;
+        self
+    }
+
+    /// Extend each chunk with up to `n` bytes of the text after it, as context, like
+    /// [`Self::with_context_before_units`] does before it.
+    pub fn with_context_after_units(mut self, n: usize) -> Self {
+        self.context_after = n;
+        self
+    }
+
+    /// Char boundary right after the char starting at `pos` of `text`.
+    fn next_char_boundary(&self, text: &str, pos: usize) -> usize {
+        if self.ascii {
+            pos + 1
+        } else {
+            next_char_boundary(text, pos)
+        }
+    }
+
+    /// End of the chunk starting at `start` once extended to the next sentence start at or
+    /// after `end`, if there is one within the extension budget.
+    fn extend_to_sentence_end(&self, buffer: &str, start: usize, end: usize) -> usize {
+        let limit = end + self.soft_max_extra;
+        // One more char, so that a sentence starting right at `limit` is detected
+        let window_end = floor_char_boundary(buffer, limit + 4);
+
+        match sentence_starts(&buffer[start..window_end], &SentenceTerminators::default())
+            .map(|p| start + p)
+            .find(|&p| p >= end)
+        {
+            Some(p) if p <= limit => p,
+            // The text may continue with a sentence start within budget: the whole rest is
+            // needed (in a stream, this makes the caller read more data first)
+            _ if limit >= buffer.len() => buffer.len(),
+            _ => end,
+        }
+    }
+
+    /// Byte offset (relative to `chunk`) where the last `overlap_chars` characters begin.
+    fn char_overlap_start(&self, chunk: &str) -> Option<usize> {
+        if self.overlap_chars == 0 {
+            return None;
+        }
+
+        chunk
+            .char_indices()
+            .rev()
+            .nth(self.overlap_chars - 1)
+            .map(|(i, _)| i)
+            .filter(|&i| i > 0)
+    }
+
+    /// Count the char boundary adjustments of a chunk about to be emitted (a stream may
+    /// compute the same chunk several times before it has enough data).
+    fn record_adjustments(&self, indices: &BytesChunkIndices) {
+        if indices.adjustments > 0 {
+            self.boundary_adjustments
+                .fetch_add(indices.adjustments, Ordering::Relaxed);
+        }
+        if indices.word_snap > 0 {
+            self.word_snaps.0.fetch_add(1, Ordering::Relaxed);
+            self.word_snaps
+                .1
+                .fetch_add(indices.word_snap, Ordering::Relaxed);
+        }
+    }
+
+    /// Compute chunk `index`, starting at `current_position` of `buffer`, which starts at byte
+    /// `offset` of the source, with its context.
     fn next_chunk_indices(
         &self,
         buffer: &str,
+        offset: usize,
+        current_position: usize,
+        index: usize,
+    ) -> Option<BytesChunkIndices> {
+        let mut indices = self.core_chunk_indices(buffer, offset, current_position, index)?;
+        // Context widens the emitted span only: the next chunk starts where it would without
+        if self.context_before > 0 {
+            indices.start =
+                ceil_char_boundary(buffer, indices.start.saturating_sub(self.context_before));
+        }
+        if self.context_after > 0 {
+            indices.end = floor_char_boundary(buffer, indices.end + self.context_after);
+        }
+        Some(indices)
+    }
+
+    /// Compute chunk `index`, starting at `current_position` of `buffer`, without context.
+    fn core_chunk_indices(
+        &self,
+        buffer: &str,
+        offset: usize,
         current_position: usize,
+        index: usize,
     ) -> Option<BytesChunkIndices> {
         let buffer_len = buffer.len();
+        let overlap = self.overlap_at(index + 1);
 
         // Done
         if current_position >= buffer_len {
@@ -48,15 +413,37 @@ impl BytesChunker {
             start
         );
 
-        // Target end position (in bytes)
-        let target_end = (start + self.chunk_size).min(buffer_len);
+        let mut adjustments = 0;
 
-        // Adjust end backwards to char boundary
-        let end = if target_end == buffer_len {
+        // Target end position (in bytes): a forward lookahead comes on top of the chunk's own
+        // `chunk_size` bytes
+        let window = match self.overlap_direction {
+            OverlapDirection::Backward => self.chunk_size,
+            OverlapDirection::Forward => self.chunk_size + overlap,
+        };
+        let target_end = (start + window).min(buffer_len);
+        debug_assert!(
+            !self.ascii || buffer.as_bytes()[start..target_end].is_ascii(),
+            "non-ASCII input chunked under the ASCII assertion"
+        );
+        // The line cap ends the chunk early, right after a newline, so on a char boundary
+        let line_cap = self.line_cap(buffer, start, target_end);
+        let target_end = line_cap.unwrap_or(target_end);
+
+        // Adjust end backwards to the grid, or else to a char boundary
+        let grid_end = if target_end < buffer_len {
+            self.grid_end(buffer, offset, start, target_end)
+        } else {
+            None
+        };
+        let end = if let Some(grid_end) = grid_end {
+            grid_end
+        } else if target_end == buffer_len {
             buffer_len // End of string is always valid
-        } else if buffer.is_char_boundary(target_end) {
+        } else if self.ascii || buffer.is_char_boundary(target_end) {
             target_end // Lucky - already at boundary
         } else {
+            adjustments += 1;
             // Search backwards (max 3 bytes for UTF-8)
             (target_end.saturating_sub(3)..target_end)
                 .rev()
@@ -64,57 +451,304 @@ impl BytesChunker {
                 .expect("Bug: no char boundary found")
         };
 
+        // A chunk holds at least one char, even when chunk_size is smaller than it
+        let end = end.max(self.next_char_boundary(buffer, start));
+
+        // Snap a cut inside a word back to the start of that word, unless it is on the grid
+        let word_snap_end = if self.snap_to_word && grid_end.is_none() && splits_word(buffer, end) {
+            word_start_at_or_before(buffer, start, end)
+        } else {
+            end
+        };
+        let word_snap = end - word_snap_end;
+        let end = word_snap_end;
+
+        // Soft max: extend the chunk to finish its sentence
+        let end = if self.soft_max_extra > 0 && line_cap.is_none() && end < buffer_len {
+            self.extend_to_sentence_end(buffer, start, end)
+        } else {
+            end
+        };
+
+        // Bidi controls belong with the char after them: never leave them at a chunk's end
+        let end = if end < buffer_len && !self.ascii {
+            attach_bidi_controls(buffer, start, end)
+        } else {
+            end
+        };
+
         // If we've reached the end of text, we're done after this chunk
         if end >= buffer_len {
             return Some(BytesChunkIndices {
                 start,
                 end,
                 new_position: buffer_len,
+                adjustments,
+                word_snap,
             });
         }
 
+        // Sentence overlap: restart at the n-th last sentence of this chunk
+        if self.overlap_sentences > 0 {
+            if let Some(sentence_start) = nth_last_sentence_start(
+                &buffer[start..end],
+                &SentenceTerminators::default(),
+                self.overlap_sentences,
+            ) {
+                return Some(BytesChunkIndices {
+                    start,
+                    end,
+                    new_position: start + sentence_start,
+                    adjustments,
+                    word_snap,
+                });
+            }
+        }
+
         // Calculate next position
-        let actual_chunk_len = end - start;
-        let step = actual_chunk_len.saturating_sub(self.overlap);
+        let next_pos = match self.char_overlap_start(&buffer[start..end]) {
+            Some(char_overlap_start) => start + char_overlap_start,
+            None => {
+                let actual_chunk_len = end - start;
+                let step = actual_chunk_len.saturating_sub(overlap);
 
-        let target_next_pos = start + step;
+                let target_next_pos = start + step;
 
-        // Adjust next position forward to char boundary
-        let next_pos = if buffer.is_char_boundary(target_next_pos) {
-            target_next_pos
+                // Adjust next position forward to char boundary
+                if self.ascii || buffer.is_char_boundary(target_next_pos) {
+                    target_next_pos
+                } else {
+                    adjustments += 1;
+                    // Search backward (max 3 bytes) to ensure we get AT LEAST the requested overlap
+                    (target_next_pos.saturating_sub(3)..=target_next_pos)
+                        .rev()
+                        .find(|&i| buffer.is_char_boundary(i))
+                        .expect("Bug: no char boundary found")
+                }
+            }
+        };
+
+        let next_pos = if self.overlap_whole_words {
+            word_start_at_or_before(buffer, start, next_pos)
         } else {
-            // Search backward (max 3 bytes) to ensure we get AT LEAST the requested overlap
-            (target_next_pos.saturating_sub(3)..=target_next_pos)
-                .rev()
-                .find(|&i| buffer.is_char_boundary(i))
-                .expect("Bug: no char boundary found")
+            next_pos
+        };
+
+        // Nor at the end of the part before the overlap
+        let next_pos = if self.ascii {
+            next_pos
+        } else {
+            attach_bidi_controls(buffer, start, next_pos)
         };
 
+        // Step at least one char: with a large overlap, snapping back to a char boundary
+        // could otherwise land on `start` again and repeat the same chunk forever
+        let next_pos = next_pos.max(self.next_char_boundary(buffer, start));
+
         Some(BytesChunkIndices {
             start,
             end,
             new_position: next_pos,
+            adjustments,
+            word_snap,
         })
     }
 }
 
+/// Char boundary right after the char starting at `pos`.
+fn next_char_boundary(text: &str, pos: usize) -> usize {
+    pos + text[pos..].chars().next().map_or(0, char::len_utf8)
+}
+
+/// Whether `pos` falls between two non-whitespace chars, i.e. inside a word.
+fn splits_word(text: &str, pos: usize) -> bool {
+    text[..pos]
+        .chars()
+        .next_back()
+        .is_some_and(|c| !c.is_whitespace())
+        && text[pos..]
+            .chars()
+            .next()
+            .is_some_and(|c| !c.is_whitespace())
+}
+
+/// Nearest word start at or before `pos` and strictly after `floor`, looking at most
+/// `WORD_SEARCH_LIMIT` characters back. Returns `pos` when there is none in range.
+fn word_start_at_or_before(text: &str, floor: usize, pos: usize) -> usize {
+    let is_word_start = |p: usize| {
+        text[p..].chars().next().is_some_and(|c| !c.is_whitespace())
+            && text[..p]
+                .chars()
+                .next_back()
+                .is_some_and(char::is_whitespace)
+    };
+
+    std::iter::once(pos)
+        .chain(
+            text[floor..pos]
+                .char_indices()
+                .rev()
+                .map(|(i, _)| floor + i),
+        )
+        .take(WORD_SEARCH_LIMIT + 1)
+        .filter(|&p| p > floor)
+        .find(|&p| is_word_start(p))
+        .unwrap_or(pos)
+}
+
 impl Chunker for BytesChunker {
-    fn chunk_string(self, input: String) -> impl Iterator<Item = String> {
+    fn chunk_size(&self) -> Option<usize> {
+        Some(self.chunk_size)
+    }
+
+    fn config(&self) -> Option<StrategyConfig> {
+        Some(StrategyConfig::Bytes(BytesConfig {
+            chunk_size: self.chunk_size,
+            overlap: self.overlap,
+            overlap_schedule: self.overlap_schedule.clone(),
+            overlap_sentences: self.overlap_sentences,
+            overlap_chars: self.overlap_chars,
+            overlap_whole_words: self.overlap_whole_words,
+            snap_to_word: self.snap_to_word,
+            soft_max_extra: self.soft_max_extra,
+            grid_align: self.grid_align,
+            max_lines: self.max_lines,
+            overlap_direction: self.overlap_direction,
+            ascii: self.ascii,
+            context_before_units: self.context_before,
+            context_after_units: self.context_after,
+            buffer_capacity: self.buffer_capacity,
+        }))
+    }
+
+    /// Without char boundaries to snap to, chunks start every `chunk_size - overlap` bytes,
+    /// unless an overlap schedule or option, word snapping, the soft maximum, the grid or the
+    /// line cap move cuts with the content or the chunk index.
+    fn count_ascii(&self, source_len: usize) -> Option<usize> {
+        if !self.overlap_schedule.is_empty()
+            || self.max_lines > 0
+            || self.overlap_sentences > 0
+            || self.overlap_chars > 0
+            || self.overlap_whole_words
+            || self.snap_to_word
+            || self.soft_max_extra > 0
+            || self.grid_align > 0
+        {
+            return None;
+        }
+        Some(sliding_count(
+            source_len,
+            self.chunk_size,
+            self.overlap,
+            self.overlap_direction,
+        ))
+    }
+
+    /// The chunk end and the next start are each snapped back by at most 3 bytes, unless an
+    /// overlap option, word snapping, the grid or the line cap move them further back.
+    fn max_output(&self, source_len: usize) -> Option<(usize, usize)> {
+        let max_chunk = self.chunk_size.max(4)
+            + self.lookahead()
+            + self.soft_max_extra
+            + self.context_before
+            + self.context_after;
+        let min_step = if self.overlap_sentences > 0
+            || self.overlap_chars > 0
+            || self.overlap_whole_words
+            || self.snap_to_word
+            || self.grid_align > 0
+            || self.max_lines > 0
+        {
+            1
+        } else {
+            let overlap = max_overlap(&self.overlap_schedule, self.overlap);
+            (self.chunk_size - overlap).saturating_sub(6)
+        };
+        Some(sliding_output(source_len, max_chunk, min_step))
+    }
+
+    fn boundary_adjustments(&self) -> usize {
+        self.boundary_adjustments.load(Ordering::Relaxed)
+    }
+
+    fn assumes_ascii(&self) -> bool {
+        self.ascii
+    }
+
+    fn word_snaps(&self) -> (usize, usize) {
+        (
+            self.word_snaps.0.load(Ordering::Relaxed),
+            self.word_snaps.1.load(Ordering::Relaxed),
+        )
+    }
+
+    fn estimate_memory(&self, source_len: usize) -> usize {
+        let min_buffer_size = self.chunk_size * 5;
+        // Context before a chunk is kept in the buffer, context after it read ahead
+        let context = self.context_before + self.context_after;
+        let buffered = (peak_buffered(min_buffer_size, source_len) + context).min(source_len);
+        let chunk =
+            (self.chunk_size + self.lookahead() + self.soft_max_extra + context).min(source_len);
+
+        stream_memory(self.buffer_capacity(min_buffer_size), buffered) + chunk
+    }
+
+    fn chunk_string_with_offsets(self, input: String) -> impl Iterator<Item = Chunk> {
         let mut current_position = 0;
+        let mut index = 0;
 
         std::iter::from_fn(move || {
-            let next = self.next_chunk_indices(&input, current_position)?;
+            let next = self.next_chunk_indices(&input, 0, current_position, index)?;
             current_position = next.new_position;
-            Some(input[next.start..next.end].to_string())
+            self.record_adjustments(&next);
+            let chunk = Chunk {
+                text: input[next.start..next.end].to_string(),
+                start: next.start,
+                end: next.end,
+                index,
+                lines: None,
+                chars: None,
+                running_hash: None,
+                links: None,
+                hierarchy: None,
+                source_stamp: None,
+                header_len: 0,
+                prefix_len: 0,
+                suffix_len: 0,
+                kind: ChunkKind::Main,
+            };
+            index += 1;
+            Some(chunk)
         })
     }
 
-    fn chunk_stream(self, input: impl Iterator<Item = String>) -> impl Iterator<Item = String> {
-        let mut string_buffer = StringBuffer::new(input, self.chunk_size * 5);
+    fn chunk_stream_with_offsets(
+        self,
+        input: impl Iterator<Item = String>,
+    ) -> impl Iterator<Item = Chunk> {
+        self.chunk_stream_with_stride(input, 1)
+    }
+
+    fn chunk_stream_with_stride(
+        self,
+        input: impl Iterator<Item = String>,
+        stride: usize,
+    ) -> impl Iterator<Item = Chunk> {
+        let mut string_buffer = StringBuffer::with_capacity(
+            input,
+            self.chunk_size * 5,
+            self.buffer_capacity(self.chunk_size * 5),
+        );
+        let mut index = 0;
 
         std::iter::from_fn(move || loop {
             let buffer = string_buffer.buffer();
-            let next = self.next_chunk_indices(buffer, string_buffer.position);
+            let next = self.next_chunk_indices(
+                buffer,
+                string_buffer.offset,
+                string_buffer.position,
+                index,
+            );
 
             match next {
                 // if the stream is done and no more chunks can be made, return None
@@ -122,7 +756,7 @@ impl Chunker for BytesChunker {
 
                 // if no chunk can be made but the stream is not done, fill more data and try again
                 None if !string_buffer.done => {
-                    string_buffer.fill();
+                    string_buffer.fill_keeping(self.context_before);
                     continue;
                 }
 
@@ -132,15 +766,35 @@ impl Chunker for BytesChunker {
                 Some(BytesChunkIndices { end, .. })
                     if !string_buffer.done && end == buffer.len() =>
                 {
-                    string_buffer.fill();
+                    string_buffer.fill_keeping(self.context_before);
                     continue;
                 }
 
-                // otherwise, return the chunk
+                // otherwise, return the chunk, unless the stride skips it
                 Some(ref n) => {
-                    let chunk = buffer[n.start..n.end].to_string();
+                    self.record_adjustments(n);
+                    let chunk = (index % stride == 0).then(|| Chunk {
+                        text: buffer[n.start..n.end].to_string(),
+                        start: string_buffer.offset + n.start,
+                        end: string_buffer.offset + n.end,
+                        index,
+                        lines: None,
+                        chars: None,
+                        running_hash: None,
+                        links: None,
+                        hierarchy: None,
+                        source_stamp: None,
+                        header_len: 0,
+                        prefix_len: 0,
+                        suffix_len: 0,
+                        kind: ChunkKind::Main,
+                    });
+                    index += 1;
                     string_buffer.set_position(n.new_position);
-                    return Some(chunk);
+                    match chunk {
+                        Some(chunk) => return Some(chunk),
+                        None => continue,
+                    }
                 }
             };
         })
@@ -1,6 +1,14 @@
 use std::collections::VecDeque;
 
-use crate::chunker::{Chunker, ChunkingError, StringBuffer};
+use serde::{Deserialize, Serialize};
+
+use crate::bidi::{attach_bidi_controls, is_bidi_control};
+use crate::chunker::{
+    default_buffer_capacity, max_overlap, overlap_at, peak_buffered, sliding_count, sliding_output,
+    stream_memory, Chunk, ChunkKind, Chunker, ChunkingError, OverlapDirection, StringBuffer,
+};
+use crate::sentences::{nth_last_sentence_start, SentenceTerminators};
+use crate::{StrategyConfig, BLOCK_SIZE};
 
 #[derive(Debug, Clone, Copy)]
 struct CharPosition {
@@ -11,16 +19,76 @@ struct CharPosition {
 struct CharactersChunkIndices {
     start: usize,
     end: usize,
+    /// End of the chunk without its context.
+    core_end: usize,
     new_byte_position: usize,
     new_char_position: usize,
 }
 
+/// Parameters of a [`CharactersChunker`], named after its `with_*` methods, see
+/// [`crate::ChunkerConfig`]. `0` (or an empty schedule) disables an option.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CharactersConfig {
+    pub chunk_size: usize,
+    pub overlap: usize,
+    #[serde(default)]
+    pub overlap_schedule: Vec<(usize, usize)>,
+    #[serde(default)]
+    pub overlap_bytes: Option<usize>,
+    #[serde(default)]
+    pub overlap_sentences: usize,
+    #[serde(default)]
+    pub overlap_direction: OverlapDirection,
+    #[serde(default)]
+    pub ascii: bool,
+    #[serde(default)]
+    pub context_before_units: usize,
+    #[serde(default)]
+    pub context_after_units: usize,
+    #[serde(default)]
+    pub buffer_capacity: usize,
+    #[serde(default)]
+    pub strict_overlap: bool,
+}
+
+impl CharactersConfig {
+    /// The chunker these parameters describe.
+    pub fn build(&self) -> Result<CharactersChunker, ChunkingError> {
+        let mut chunker = CharactersChunker::new(self.chunk_size, self.overlap)?
+            .with_overlap_schedule(self.overlap_schedule.clone())?
+            .with_overlap_sentences(self.overlap_sentences)
+            .with_overlap_direction(self.overlap_direction)
+            .with_ascii(self.ascii)
+            .with_context_before_units(self.context_before_units)
+            .with_context_after_units(self.context_after_units)
+            .with_buffer_capacity(self.buffer_capacity)
+            .with_strict_overlap(self.strict_overlap);
+        if let Some(bytes) = self.overlap_bytes {
+            chunker = chunker.with_overlap_bytes(bytes)?;
+        }
+        Ok(chunker)
+    }
+}
+
 #[derive(Clone)]
 pub struct CharactersChunker {
     chunk_size: usize,
     overlap: usize,
+    /// `(from_index, overlap)` steps replacing `overlap`, sorted by index.
+    overlap_schedule: Vec<(usize, usize)>,
+    /// Overlap in bytes replacing `overlap` and the schedule, as whole trailing chars.
+    overlap_bytes: Option<usize>,
+    overlap_sentences: usize,
+    overlap_direction: OverlapDirection,
+    ascii: bool,
+    context_before: usize,
+    context_after: usize,
+    buffer_capacity: usize,
+    strict_overlap: bool,
     char_positions: VecDeque<CharPosition>,
     current_char_position: usize,
+    /// Absolute byte offset of the end of the previous chunk, without its context.
+    previous_end: Option<usize>,
 }
 
 impl CharactersChunker {
@@ -35,11 +103,229 @@ impl CharactersChunker {
         Ok(Self {
             chunk_size,
             overlap,
+            overlap_schedule: Vec::new(),
+            overlap_bytes: None,
+            overlap_sentences: 0,
+            overlap_direction: OverlapDirection::Backward,
+            ascii: false,
+            context_before: 0,
+            context_after: 0,
+            buffer_capacity: 0,
+            strict_overlap: false,
             char_positions: VecDeque::new(),
             current_char_position: 0,
+            previous_end: None,
         })
     }
+
+    /// Vary the overlap with the chunk index: each `(from_index, overlap)` step sets the
+    /// overlap in characters of chunk `from_index` and the chunks after it with the chunk
+    /// before them, until the next step. Chunks before the first step overlap by `overlap`.
+    ///
+    /// Fails if a scheduled overlap is not less than the chunk size.
+    pub fn with_overlap_schedule(
+        mut self,
+        mut schedule: Vec<(usize, usize)>,
+    ) -> Result<Self, ChunkingError> {
+        if let Some(&(_, overlap)) = schedule.iter().find(|&&(_, o)| o >= self.chunk_size) {
+            return Err(ChunkingError::InvalidArguments {
+                chunk_size: self.chunk_size,
+                overlap,
+            });
+        }
+        schedule.sort_by_key(|&(from_index, _)| from_index);
+        self.overlap_schedule = schedule;
+        Ok(self)
+    }
+
+    /// Start each chunk after the first at the `n`-th last sentence beginning inside the
+    /// previous chunk, so the overlap is made of whole sentences instead of `overlap`
+    /// characters. When the previous chunk holds fewer than `n` sentence starts, the
+    /// character overlap is used instead. `0` disables sentence overlap.
+    pub fn with_overlap_sentences(mut self, n: usize) -> Self {
+        self.overlap_sentences = n;
+        self
+    }
+
+    /// Make the overlap a lookahead at the end of each chunk into the next one, see
+    /// [`OverlapDirection`]: chunks hold up to `chunk_size + overlap` characters and start
+    /// every `chunk_size` characters.
+    pub fn with_overlap_direction(mut self, direction: OverlapDirection) -> Self {
+        self.overlap_direction = direction;
+        self
+    }
+
+    /// Trust that the input is pure ASCII, so that characters are bytes: they are then not
+    /// decoded, and file and HTTP sources are only checked to be ASCII instead of decoded as
+    /// UTF-8. A file or HTTP source with another byte ends with [`ChunkingError::NonAscii`].
+    /// On strings, debug builds check the assertion and panic if it is wrong; release builds
+    /// may panic on a cut inside a char.
+    pub fn with_ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Overlap each chunk after the first by as many of the last characters of the previous
+    /// chunk as fit in `bytes` bytes, instead of `overlap` characters (and the overlap
+    /// schedule), so the overlap region is bounded in bytes whatever the width of its
+    /// characters. Sentence overlap takes precedence when it applies.
+    ///
+    /// Fails if `bytes` is not less than the chunk size: a chunk of ASCII text would then be
+    /// overlapped whole.
+    pub fn with_overlap_bytes(mut self, bytes: usize) -> Result<Self, ChunkingError> {
+        if bytes >= self.chunk_size {
+            return Err(ChunkingError::InvalidArguments {
+                chunk_size: self.chunk_size,
+                overlap: bytes,
+            });
+        }
+        self.overlap_bytes = Some(bytes);
+        Ok(self)
+    }
+
+    /// Number of chars of the lookahead of chunk `index` into the next chunk, after its own
+    /// part ending at `own_end`, with [`OverlapDirection::Forward`]: `overlap` characters (or
+    /// as many as fit in `overlap_bytes`), fewer at the end of the text.
+    fn lookahead(&self, index: usize, own_end: usize) -> usize {
+        let available = self.char_positions.len() - own_end;
+        match self.overlap_bytes {
+            Some(bytes) => {
+                let mut lookahead_len = 0;
+                (own_end..self.char_positions.len())
+                    .take_while(|&i| {
+                        lookahead_len += self.char_positions[i].len;
+                        lookahead_len <= bytes
+                    })
+                    .count()
+            }
+            None => overlap_at(&self.overlap_schedule, self.overlap, index + 1).min(available),
+        }
+    }
+
+    /// Largest number of chars of a forward lookahead, on top of the own `chunk_size`
+    /// characters of a chunk.
+    fn max_lookahead(&self) -> usize {
+        match (self.overlap_direction, self.overlap_bytes) {
+            (OverlapDirection::Backward, _) => 0,
+            // Lookahead chars are at least a byte each
+            (OverlapDirection::Forward, Some(bytes)) => bytes,
+            (OverlapDirection::Forward, None) => max_overlap(&self.overlap_schedule, self.overlap),
+        }
+    }
+
+    /// Position of the char after chunk `index`, which spans `start_idx..end_idx` (or would,
+    /// short of the end of the text), where the next chunk starts without sentence overlap.
+    fn step_end(&self, index: usize, start_idx: usize, end_idx: usize) -> usize {
+        if self.overlap_direction == OverlapDirection::Forward {
+            // Right after the own part, which a cut moved back may leave short
+            return (start_idx + self.chunk_size).min(end_idx);
+        }
+        match self.overlap_bytes {
+            Some(bytes) => {
+                // As many whole chars from the end as fit, leaving at least one behind
+                let mut overlap_len = 0;
+                let overlap = (start_idx + 1..end_idx)
+                    .rev()
+                    .take_while(|&i| {
+                        overlap_len += self.char_positions[i].len;
+                        overlap_len <= bytes
+                    })
+                    .count();
+                end_idx - overlap
+            }
+            None => {
+                let overlap = overlap_at(&self.overlap_schedule, self.overlap, index + 1);
+                start_idx + self.chunk_size.saturating_sub(overlap)
+            }
+        }
+    }
+
+    /// Allocate the buffer that file and HTTP sources are streamed through with `bytes`
+    /// bytes up front, so that it does not grow while streaming. It defaults to twice its
+    /// minimum size of five chunks, and at least its usual peak, which reading ahead (e.g.
+    /// context after chunks) can exceed. `0` keeps the default. Chunks are not affected.
+    pub fn with_buffer_capacity(mut self, bytes: usize) -> Self {
+        self.buffer_capacity = bytes;
+        self
+    }
+
+    fn buffer_capacity(&self, min_buffer_size: usize) -> usize {
+        match self.buffer_capacity {
+            0 => default_buffer_capacity(min_buffer_size),
+            bytes => bytes,
+        }
+    }
+
+    /// Extend each chunk with up to `n` characters of the text before it, as context. Only
+    /// the emitted text and offsets grow: the step between chunks, and so which text each
+    /// chunk covers on its own, stay the same. Context is cut short at the start of the
+    /// source. `0` disables it.
+    pub fn with_context_before_units(mut self, n: usize) -> Self {
+        self.context_before = n;
+        self
+    }
+
+    /// Extend each chunk with up to `n` characters of the text after it, as context, like
+    /// [`Self::with_context_before_units`] does before it.
+    pub fn with_context_after_units(mut self, n: usize) -> Self {
+        self.context_after = n;
+        self
+    }
+
+    /// Panic if a chunk overlaps the chunk before it by other than the requested number of
+    /// characters, from `overlap` or the overlap schedule, whether the source is chunked in
+    /// memory or streamed. Overlaps in sentences or bytes, and cuts moved to keep bidi
+    /// controls with the char after them, are not checked.
+    /// Meant for debugging: it costs a char count of each overlap.
+    pub fn with_strict_overlap(mut self, strict: bool) -> Self {
+        self.strict_overlap = strict;
+        self
+    }
+
+    /// Check the overlap of chunk `index` with the previous one, for the strict overlap.
+    /// The chunk starts at the current char position and ends at `core_end`, bytes of
+    /// `buffer` starting at byte `offset` of the source.
+    fn check_overlap(&mut self, buffer: &str, offset: usize, index: usize, core_end: usize) {
+        if !self.strict_overlap || self.overlap_sentences > 0 || self.overlap_bytes.is_some() {
+            return;
+        }
+        let start = self.char_positions[self.current_char_position].start;
+        let previous_end = self.previous_end.replace(offset + core_end);
+        let Some(previous_end) = previous_end.map(|end| end - offset) else {
+            return;
+        };
+        if core_end <= previous_end {
+            return;
+        }
+        let overlap = buffer.get(start..previous_end).unwrap_or_default();
+        if overlap.starts_with(is_bidi_control)
+            || buffer[previous_end..].starts_with(is_bidi_control)
+        {
+            return;
+        }
+
+        let expected = overlap_at(&self.overlap_schedule, self.overlap, index);
+        let chars = overlap.chars().count();
+        assert!(
+            chars == expected,
+            "Bug: chunk {index} overlaps the chunk before it by {chars} characters instead of {expected}"
+        );
+    }
+
     fn build_char_positions(&mut self, text: &str, offset: usize) {
+        if self.ascii {
+            debug_assert!(
+                text.is_ascii(),
+                "non-ASCII input chunked under the ASCII assertion"
+            );
+            let cp = (0..text.len()).map(|pos| CharPosition {
+                start: pos + offset,
+                len: 1,
+            });
+            self.char_positions.extend(cp);
+            return;
+        }
+
         let cp = text.char_indices().map(|(pos, ch)| CharPosition {
             start: pos + offset,
             len: ch.len_utf8(),
@@ -48,13 +334,16 @@ impl CharactersChunker {
     }
 
     fn compact(&mut self, string_buffer: &mut StringBuffer<impl Iterator<Item = String>>) {
-        // Compact char positions and string buffer if we've consumed more than half
-        if self.current_char_position > self.char_positions.len() / 2 {
-            let keep_from_chars = self.current_char_position;
+        // Compact char positions and string buffer if we've consumed more than half, keeping
+        // the context before the next chunk
+        let keep_from_chars = self
+            .current_char_position
+            .saturating_sub(self.context_before);
+        if keep_from_chars > self.char_positions.len() / 2 {
             let keep_from_bytes = self.char_positions[keep_from_chars].start;
-            self.char_positions.drain(0..self.current_char_position);
+            self.char_positions.drain(0..keep_from_chars);
             string_buffer.compact_to(keep_from_bytes);
-            self.current_char_position = 0;
+            self.current_char_position -= keep_from_chars;
 
             // shift all char positions back by keep_from_bytes
             for cp in self.char_positions.iter_mut() {
@@ -67,7 +356,41 @@ impl CharactersChunker {
         }
     }
 
-    fn next_chunk_indices(&self, buffer: &str) -> Option<CharactersChunkIndices> {
+    /// Move the cut before the char at position `idx` back before the bidi controls right
+    /// before it, staying after the char at position `floor`.
+    fn attach_bidi_controls(&self, buffer: &str, floor: usize, idx: usize) -> usize {
+        if self.ascii {
+            return idx;
+        }
+        let byte = |i: usize| self.char_positions[i].start;
+        let attached = attach_bidi_controls(buffer, byte(floor), byte(idx));
+        idx - buffer[attached..byte(idx)].chars().count()
+    }
+
+    /// Compute chunk `index`, the next one of `buffer`, with its context.
+    fn next_chunk_indices(&self, buffer: &str, index: usize) -> Option<CharactersChunkIndices> {
+        let mut indices = self.core_chunk_indices(buffer, index)?;
+        // Context widens the emitted span only: the next chunk starts where it would without
+        if self.context_before > 0 {
+            let first = self
+                .current_char_position
+                .saturating_sub(self.context_before);
+            indices.start = self.char_positions[first].start;
+        }
+        if self.context_after > 0 {
+            let end_idx = self
+                .char_positions
+                .partition_point(|cp| cp.start < indices.end);
+            indices.end = self
+                .char_positions
+                .get(end_idx + self.context_after)
+                .map_or(buffer.len(), |cp| cp.start);
+        }
+        Some(indices)
+    }
+
+    /// Compute chunk `index`, the next one of `buffer`, without context.
+    fn core_chunk_indices(&self, buffer: &str, index: usize) -> Option<CharactersChunkIndices> {
         let buffer_len = buffer.len();
         let chars_len = self.char_positions.len();
 
@@ -78,6 +401,17 @@ impl CharactersChunker {
 
         let start_idx = self.current_char_position;
         let end_idx = (start_idx + self.chunk_size).min(chars_len);
+        // A forward lookahead comes on top of the chunk's own `chunk_size` characters
+        let end_idx = match self.overlap_direction {
+            OverlapDirection::Backward => end_idx,
+            OverlapDirection::Forward => end_idx + self.lookahead(index, end_idx),
+        };
+        // Bidi controls belong with the char after them: never leave them at a chunk's end
+        let end_idx = if end_idx < chars_len {
+            self.attach_bidi_controls(buffer, start_idx, end_idx)
+        } else {
+            end_idx
+        };
         let start_byte = self.char_positions[start_idx].start;
 
         let end_byte = if end_idx >= chars_len {
@@ -92,21 +426,54 @@ impl CharactersChunker {
             return Some(CharactersChunkIndices {
                 start: start_byte,
                 end: end_byte,
+                core_end: end_byte,
                 new_byte_position: buffer_len,
                 new_char_position: chars_len,
             });
         }
 
-        // Calculate next position
-        let step = self.chunk_size.saturating_sub(self.overlap);
+        // Sentence overlap: restart at the n-th last sentence of this chunk
+        if self.overlap_sentences > 0 {
+            if let Some(sentence_start) = nth_last_sentence_start(
+                &buffer[start_byte..end_byte],
+                &SentenceTerminators::default(),
+                self.overlap_sentences,
+            ) {
+                let next_byte_position = start_byte + sentence_start;
+                let next_char_position = self
+                    .char_positions
+                    .binary_search_by_key(&next_byte_position, |cp| cp.start)
+                    .expect("Bug: sentence start is not at a char position");
+
+                return Some(CharactersChunkIndices {
+                    start: start_byte,
+                    end: end_byte,
+                    core_end: end_byte,
+                    new_byte_position: next_byte_position,
+                    new_char_position: next_char_position,
+                });
+            }
+        }
 
-        // return Some((start_byte, end_byte));
-        let next_char_position = start_idx + step;
+        // Calculate next position
+        let step_end = self.step_end(index, start_idx, end_idx);
+        let next_char_position = match self.attach_bidi_controls(buffer, start_idx, step_end) {
+            // Controls moved into the overlap must not take it past its size in bytes
+            attached
+                if self.overlap_bytes.is_some_and(|bytes| {
+                    end_byte - self.char_positions[attached].start > bytes
+                }) =>
+            {
+                step_end
+            }
+            attached => attached,
+        };
         let next_byte_position = self.char_positions[next_char_position].start;
 
         Some(CharactersChunkIndices {
             start: start_byte,
             end: end_byte,
+            core_end: end_byte,
             new_byte_position: next_byte_position,
             new_char_position: next_char_position,
         })
@@ -114,22 +481,136 @@ impl CharactersChunker {
 }
 
 impl Chunker for CharactersChunker {
-    fn chunk_string(mut self, input: String) -> impl Iterator<Item = String> {
+    fn chunk_size(&self) -> Option<usize> {
+        Some(self.chunk_size)
+    }
+
+    fn config(&self) -> Option<StrategyConfig> {
+        Some(StrategyConfig::Characters(CharactersConfig {
+            chunk_size: self.chunk_size,
+            overlap: self.overlap,
+            overlap_schedule: self.overlap_schedule.clone(),
+            overlap_bytes: self.overlap_bytes,
+            overlap_sentences: self.overlap_sentences,
+            overlap_direction: self.overlap_direction,
+            ascii: self.ascii,
+            context_before_units: self.context_before,
+            context_after_units: self.context_after,
+            buffer_capacity: self.buffer_capacity,
+            strict_overlap: self.strict_overlap,
+        }))
+    }
+
+    fn measure(&self, text: &str) -> usize {
+        if self.ascii {
+            text.len()
+        } else {
+            text.chars().count()
+        }
+    }
+
+    fn assumes_ascii(&self) -> bool {
+        self.ascii
+    }
+
+    /// ASCII text has one character per byte, so chunks start every `chunk_size - overlap`
+    /// bytes (or `overlap_bytes`) without an overlap schedule or sentence overlap.
+    fn count_ascii(&self, source_len: usize) -> Option<usize> {
+        if self.overlap_sentences > 0
+            || (!self.overlap_schedule.is_empty() && self.overlap_bytes.is_none())
+        {
+            return None;
+        }
+        Some(sliding_count(
+            source_len,
+            self.chunk_size,
+            self.overlap_bytes.unwrap_or(self.overlap),
+            self.overlap_direction,
+        ))
+    }
+
+    /// Chunks hold at most 4 bytes per character, and start at least `chunk_size - overlap`
+    /// characters (so as many bytes) apart without sentence overlap.
+    fn max_output(&self, source_len: usize) -> Option<(usize, usize)> {
+        let min_step = match self.overlap_bytes {
+            _ if self.overlap_sentences > 0 => 1,
+            // Overlapping chars are at least a byte each
+            Some(bytes) => self.chunk_size - bytes,
+            None => self.chunk_size - max_overlap(&self.overlap_schedule, self.overlap),
+        };
+        let max_chunk =
+            (self.chunk_size + self.max_lookahead() + self.context_before + self.context_after) * 4;
+        Some(sliding_output(source_len, max_chunk, min_step))
+    }
+
+    /// Assumes mostly single-byte text: every buffered byte then has its own position entry.
+    fn estimate_memory(&self, source_len: usize) -> usize {
+        let min_buffer_size = self.chunk_size * 5;
+        // The buffer is only compacted once more than half of it was consumed, so it can
+        // take one more block than a compacting one before that happens, and it keeps the
+        // context before a chunk and reads the context after it ahead
+        let context = self.context_before + self.context_after;
+        let buffered =
+            (peak_buffered(min_buffer_size, source_len) + BLOCK_SIZE + context).min(source_len);
+        // The position deque doubles its capacity as it grows, holding both the old and the
+        // new allocation while it moves
+        let positions = buffered.next_power_of_two() * std::mem::size_of::<CharPosition>();
+        let chunk = (self.chunk_size + self.max_lookahead() + context).min(source_len);
+
+        stream_memory(self.buffer_capacity(min_buffer_size), buffered) + positions * 3 / 2 + chunk
+    }
+
+    fn chunk_string_with_offsets(mut self, input: String) -> impl Iterator<Item = Chunk> {
         self.build_char_positions(&input, 0);
+        let mut index = 0;
 
         std::iter::from_fn(move || {
-            let next = self.next_chunk_indices(&input)?;
+            let next = self.next_chunk_indices(&input, index)?;
+            self.check_overlap(&input, 0, index, next.core_end);
             self.current_char_position = next.new_char_position;
-            Some(input[next.start..next.end].to_string())
+            let chunk = Chunk {
+                text: input[next.start..next.end].to_string(),
+                start: next.start,
+                end: next.end,
+                index,
+                lines: None,
+                chars: None,
+                running_hash: None,
+                links: None,
+                hierarchy: None,
+                source_stamp: None,
+                header_len: 0,
+                prefix_len: 0,
+                suffix_len: 0,
+                kind: ChunkKind::Main,
+            };
+            index += 1;
+            Some(chunk)
         })
     }
 
-    fn chunk_stream(mut self, input: impl Iterator<Item = String>) -> impl Iterator<Item = String> {
-        let mut string_buffer = StringBuffer::new(input, self.chunk_size * 5);
+    fn chunk_stream_with_offsets(
+        self,
+        input: impl Iterator<Item = String>,
+    ) -> impl Iterator<Item = Chunk> {
+        self.chunk_stream_with_stride(input, 1)
+    }
+
+    fn chunk_stream_with_stride(
+        mut self,
+        input: impl Iterator<Item = String>,
+        stride: usize,
+    ) -> impl Iterator<Item = Chunk> {
+        let mut string_buffer = StringBuffer::with_capacity(
+            input,
+            self.chunk_size * 5,
+            self.buffer_capacity(self.chunk_size * 5),
+        );
+        let mut index = 0;
 
         std::iter::from_fn(move || loop {
             let buffer = string_buffer.buffer();
-            let next = self.next_chunk_indices(buffer);
+            let next = self.next_chunk_indices(buffer, index);
 
             match next {
                 // if the stream is done and no more chunks can be made, return None
@@ -165,12 +646,32 @@ impl Chunker for CharactersChunker {
                     continue;
                 }
 
-                // otherwise, return the chunk
+                // otherwise, return the chunk, unless the stride skips it
                 Some(ref n) => {
-                    let chunk = buffer[n.start..n.end].to_string();
+                    self.check_overlap(buffer, string_buffer.offset, index, n.core_end);
+                    let chunk = (index % stride == 0).then(|| Chunk {
+                        text: buffer[n.start..n.end].to_string(),
+                        start: string_buffer.offset + n.start,
+                        end: string_buffer.offset + n.end,
+                        index,
+                        lines: None,
+                        chars: None,
+                        running_hash: None,
+                        links: None,
+                        hierarchy: None,
+                        source_stamp: None,
+                        header_len: 0,
+                        prefix_len: 0,
+                        suffix_len: 0,
+                        kind: ChunkKind::Main,
+                    });
+                    index += 1;
                     string_buffer.set_position(n.new_byte_position);
                     self.current_char_position = n.new_char_position;
-                    return Some(chunk);
+                    match chunk {
+                        Some(chunk) => return Some(chunk),
+                        None => continue,
+                    }
                 }
             };
         })
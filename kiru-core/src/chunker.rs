@@ -1,11 +1,31 @@
 use crossbeam_channel::bounded;
-use glob::glob;
 use rayon::prelude::*;
-use std::io;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, SystemTime};
 use thiserror::Error;
 
-use crate::{BytesChunker, CharactersChunker, StreamType};
+use crate::bidi::balance_bidi;
+use crate::glob_source::expand_glob;
+use crate::merge::{
+    BridgeInterleaver, ChunkLinker, RedundantLastChunk, SmallChunkMerger, WhitespaceAttacher,
+};
+use crate::stats::StatsCounters;
+use crate::stream::Blocks;
+use crate::{
+    BoundariesChunker, BoundaryPredicate, BreakPredicate, BytesChunker, CharactersChunker,
+    ChunkStats, ChunkerConfig, ChunkerOptions, FollowReader, GraphemesChunker, IncompleteUtf8,
+    JsonArrayChunker, LinesChunker, LinesPackedChunker, NearDuplicateFilter, ParagraphsChunker,
+    PredicateChunker, RecursiveChunker, RegexChunker, RetryPolicy, SemanticChunker, SemanticUnit,
+    SentencesChunker, SourceReader, StrategyConfig, StreamType, TranscriptChunker, Utf8BlockReader,
+    WordsChunker, BLOCK_SIZE,
+};
 
 #[derive(Debug, Clone)]
 pub enum Source {
@@ -25,51 +45,107 @@ pub enum HigherOrderSource {
     SourceGenerator(SourceGenerator),
 }
 
+/// Sources expanded from [`HigherOrderSource`]s, along with the paths matched by a glob that
+/// are not chunked.
+#[derive(Debug, Default)]
+pub struct ExpandedSources {
+    pub sources: Vec<Source>,
+    /// Matches that are not regular files (directories, FIFOs, devices, sockets), symlinks
+    /// that are not followed or are broken, files already included through another
+    /// symlink, and paths that could not be read.
+    pub skipped: Vec<PathBuf>,
+}
+
 impl HigherOrderSource {
     pub fn into_sources(self) -> Result<Vec<Source>, ChunkingError> {
-        match self {
-            HigherOrderSource::Source(s) => Ok(vec![s]),
-            HigherOrderSource::SourceGenerator(SourceGenerator::Glob(pattern)) => {
-                let paths = glob(&pattern).map_err(|_| {
-                    ChunkingError::Io(io::Error::new(
-                        io::ErrorKind::InvalidInput,
-                        "Invalid glob pattern",
-                    ))
-                })?;
-                let sources = paths
-                    .filter_map(|entry| match entry {
-                        Ok(path) => Some(Source::File(path.to_string_lossy().to_string())),
-                        Err(_) => None,
-                    })
-                    .collect::<Vec<_>>();
-                Ok(sources)
-            }
-            HigherOrderSource::SourceGenerator(SourceGenerator::Sitemap(url)) => {
-                println!("Fetching sitemap from URL: {}", url);
-                Err(ChunkingError::Unknown) // Placeholder for future implementation
-            }
-        }
+        Ok(Self::expand(vec![self], false)?.sources)
     }
 
     pub fn into_flattened_sources(
         higher_order_sources: Vec<HigherOrderSource>,
     ) -> Result<Vec<Source>, ChunkingError> {
-        higher_order_sources
-            .into_iter()
-            .map(|hos| hos.into_sources())
-            .collect::<Result<Vec<Vec<Source>>, ChunkingError>>()
-            .map(|vecs| vecs.into_iter().flatten().collect())
+        Ok(Self::expand(higher_order_sources, false)?.sources)
+    }
+
+    /// Expand globs into the regular files they match, keeping the other sources as they
+    /// are. Symlinks, and files under symlinked directories, are only chunked with
+    /// `follow_symlinks`, in which case each file is included once however many links lead
+    /// to it, which also stops symlink cycles.
+    pub fn expand(
+        higher_order_sources: Vec<HigherOrderSource>,
+        follow_symlinks: bool,
+    ) -> Result<ExpandedSources, ChunkingError> {
+        let mut expanded = ExpandedSources::default();
+        let mut seen = HashSet::new();
+
+        for hos in higher_order_sources {
+            match hos {
+                HigherOrderSource::Source(s) => expanded.sources.push(s),
+                HigherOrderSource::SourceGenerator(SourceGenerator::Glob(pattern)) => {
+                    expand_glob(&pattern, follow_symlinks, &mut seen, &mut expanded)?;
+                }
+                HigherOrderSource::SourceGenerator(SourceGenerator::Sitemap(url)) => {
+                    println!("Fetching sitemap from URL: {}", url);
+                    return Err(ChunkingError::Unknown); // Placeholder for future implementation
+                }
+            }
+        }
+        Ok(expanded)
     }
 }
 
 #[derive(Error, Debug)]
 pub enum ChunkingError {
-    #[error("error reading file")]
+    #[error("error reading file: {0}")]
     Io(#[from] io::Error),
     #[error("error making HTTP request: {0}")]
     Http(String),
     #[error("the overlap ({overlap}) must be less than the chunk size ({chunk_size})")]
     InvalidArguments { chunk_size: usize, overlap: usize },
+    #[error("boundaries must be strictly increasing byte offsets")]
+    InvalidBoundaries,
+    #[error("invalid size band ({min_ratio}, {max_ratio}): ratios must satisfy 0 <= min <= max")]
+    InvalidSizeBand { min_ratio: f64, max_ratio: f64 },
+    #[error("a size band needs a strategy with a chunk size")]
+    NoChunkSize,
+    #[error(
+        "invalid time window ({window_secs}s, overlap {overlap_secs}s): the window must be \
+         positive and the overlap at least 0 and less than the window"
+    )]
+    InvalidTimeWindow { window_secs: f64, overlap_secs: f64 },
+    #[error("invalid pattern: {0}")]
+    InvalidPattern(String),
+    #[error("shard size must be at least 1")]
+    InvalidShardSize,
+    #[error("compression level must be between 0 and 9, got {0}")]
+    InvalidCompressionLevel(u32),
+    #[error("batch size must be at least 1")]
+    InvalidBatchSize,
+    #[error("stride must be at least 1")]
+    InvalidStride,
+    #[error("invalid boundary cache: {0}")]
+    InvalidBoundaryCache(String),
+    #[error("number of workers must be at least 1")]
+    InvalidWorkerCount,
+    #[error("ring buffer capacity must be at least 1")]
+    InvalidRingCapacity,
+    #[error("the child chunk size ({child_size}) must be less than the parent chunk size ({parent_size})")]
+    InvalidChildSize {
+        parent_size: usize,
+        child_size: usize,
+    },
+    #[error("target number of chunks must be at least 1")]
+    InvalidTargetChunks,
+    #[error("characters per token must be a positive number, got {0}")]
+    InvalidCharsPerToken(f64),
+    #[error("source ends with an incomplete UTF-8 sequence of {bytes} bytes")]
+    IncompleteUtf8 { bytes: usize },
+    #[error("non-ASCII byte at offset {offset} of a source asserted to be ASCII")]
+    NonAscii { offset: usize },
+    #[error("invalid chunker config: {0}")]
+    InvalidConfig(String),
+    #[error("tokenizer error: {0}")]
+    Tokenizer(String),
     #[error("unknown data store error")]
     Unknown,
 }
@@ -83,6 +159,9 @@ where
     min_buffer_size: usize,
     pub done: bool,
     pub position: usize,
+    /// Number of bytes drained from the front of the buffer so far, i.e. the
+    /// offset of `buffer[0]` within the whole stream.
+    pub offset: usize,
 }
 
 impl<I> StringBuffer<I>
@@ -90,20 +169,49 @@ where
     I: Iterator<Item = String>,
 {
     pub fn new(stream: I, min_buffer_size: usize) -> Self {
+        Self::with_capacity(
+            stream,
+            min_buffer_size,
+            default_buffer_capacity(min_buffer_size),
+        )
+    }
+
+    /// Like [`Self::new`], but allocates `capacity` bytes up front.
+    pub fn with_capacity(stream: I, min_buffer_size: usize, capacity: usize) -> Self {
         Self {
             stream,
-            buffer: String::with_capacity(min_buffer_size * 2),
+            buffer: String::with_capacity(capacity),
             min_buffer_size,
             done: false,
             position: 0,
+            offset: 0,
         }
     }
 
     pub fn fill(&mut self) {
-        self.compact();
+        self.fill_keeping(0);
+    }
+
+    /// Like [`Self::fill`], but keeps the `keep` bytes (or fewer, to stay on a char boundary)
+    /// before the position in the buffer.
+    pub fn fill_keeping(&mut self, keep: usize) {
+        self.compact(keep);
         self.fill_no_compact();
     }
 
+    /// Like [`Self::fill`], but adds a single block however small the buffer, for strategies
+    /// that cut a chunk as soon as its text is read, e.g. from a pipe.
+    pub fn fill_block(&mut self) {
+        self.compact(0);
+        if self.done {
+            return;
+        }
+        match self.stream.next() {
+            Some(block) => self.buffer.push_str(&block),
+            None => self.done = true,
+        }
+    }
+
     pub fn fill_no_compact(&mut self) {
         // if we are not done and buffer already meets min size, try to add one block
         if !self.done && self.buffer.len() >= self.min_buffer_size {
@@ -132,16 +240,19 @@ where
         &self.buffer
     }
 
-    fn compact(&mut self) {
-        if self.position > self.buffer.len() / 2 {
-            self.buffer.drain(0..self.position);
-            self.position = 0;
+    fn compact(&mut self, keep: usize) {
+        let drain_to = ceil_char_boundary(&self.buffer, self.position.saturating_sub(keep));
+        if drain_to > self.buffer.len() / 2 {
+            self.buffer.drain(0..drain_to);
+            self.offset += drain_to;
+            self.position -= drain_to;
         }
     }
 
     pub fn compact_to(&mut self, byte_position: usize) {
         if byte_position > 0 {
             self.buffer.drain(0..byte_position);
+            self.offset += byte_position;
             self.position = self.position.saturating_sub(byte_position);
         }
     }
@@ -159,9 +270,479 @@ where
     }
 }
 
+/// Largest number of bytes a `StringBuffer` created with `min_buffer_size` holds while
+/// streaming a source of `source_len` bytes: it is refilled up to its minimum size, and
+/// then by one block at a time.
+pub(crate) fn peak_buffered(min_buffer_size: usize, source_len: usize) -> usize {
+    (min_buffer_size + BLOCK_SIZE + 4).min(source_len)
+}
+
+/// Bytes a `StringBuffer` created with `min_buffer_size` allocates up front: the
+/// `BUFFER_FACTOR` multiple of its minimum size, and at least its peak while streaming, so
+/// that it does not grow block by block.
+pub(crate) fn default_buffer_capacity(min_buffer_size: usize) -> usize {
+    (min_buffer_size * BUFFER_FACTOR).max(peak_buffered(min_buffer_size, usize::MAX))
+}
+
+/// How many times its minimum size a `StringBuffer` allocates by default.
+const BUFFER_FACTOR: usize = 2;
+
+/// Heap bytes used by a `StringBuffer` allocated with `capacity` bytes that holds up to
+/// `buffered` bytes, plus the block reader feeding it (its read buffer, the block being
+/// validated and the block handed over).
+pub(crate) fn stream_memory(capacity: usize, buffered: usize) -> usize {
+    // A `String` grows to max(2 * capacity, required) when it runs out of room
+    let mut capacity = capacity.max(1);
+    while capacity < buffered {
+        capacity = (capacity * 2).max(buffered);
+    }
+    capacity + 3 * BLOCK_SIZE
+}
+
+/// Number of windows of `chunk_size` units starting every `chunk_size - overlap` units that
+/// cut a source of `len` units, as the bytes and characters strategies do on ASCII text. A
+/// forward lookahead makes the windows `chunk_size + overlap` units long, starting every
+/// `chunk_size` units.
+pub(crate) fn sliding_count(
+    len: usize,
+    chunk_size: usize,
+    overlap: usize,
+    direction: OverlapDirection,
+) -> usize {
+    let (window, step) = match direction {
+        OverlapDirection::Backward => (chunk_size, chunk_size - overlap),
+        OverlapDirection::Forward => (chunk_size + overlap, chunk_size),
+    };
+    match len {
+        0 => 0,
+        _ => 1 + len.saturating_sub(window).div_ceil(step),
+    }
+}
+
+/// Chunk size for which the bytes and characters strategies cut a source of `source_len`
+/// units into about `target_chunks` chunks overlapping by `overlap` units, by inverting
+/// [`sliding_count`]: the chunks then cover `chunk_size + (n - 1) * (chunk_size - overlap)`
+/// units. The size is rounded up, so there are never more than `target_chunks` chunks on
+/// ASCII text, and only fewer when the step gets down to a few units (or the source is
+/// shorter than the overlap, where the smallest valid size gives a single chunk). Snapping
+/// to char boundaries shortens steps on other text, which can add a chunk.
+pub fn suggest_chunk_size(
+    source_len: usize,
+    target_chunks: usize,
+    overlap: usize,
+) -> Result<usize, ChunkingError> {
+    if target_chunks == 0 {
+        return Err(ChunkingError::InvalidTargetChunks);
+    }
+    let step = source_len.saturating_sub(overlap).div_ceil(target_chunks);
+    Ok(overlap + step.max(1))
+}
+
+/// Whether the file at `path` holds only ASCII bytes, reading it up to the first other one.
+fn is_ascii_file(path: &Path) -> Result<bool, ChunkingError> {
+    let mut file = File::open(path)?;
+    let mut block = vec![0; BLOCK_SIZE];
+    loop {
+        match file.read(&mut block)? {
+            0 => return Ok(true),
+            n if !block[..n].is_ascii() => return Ok(false),
+            _ => {}
+        }
+    }
+}
+
+/// Largest number of chunks, and of bytes in all of them, of a source of `source_len` bytes
+/// whose chunks hold at most `max_chunk` bytes and start at least `min_step` bytes apart.
+pub(crate) fn sliding_output(
+    source_len: usize,
+    max_chunk: usize,
+    min_step: usize,
+) -> (usize, usize) {
+    let (len, max_chunk, step) = (
+        source_len as u128,
+        max_chunk as u128,
+        min_step.max(1) as u128,
+    );
+    let chunks = len.div_ceil(step);
+    // Chunk `i` starts at least `i * step` bytes in, so it holds at most
+    // `min(max_chunk, len - i * step)` bytes: full chunks first, then a shrinking tail
+    let full = if len >= max_chunk {
+        ((len - max_chunk) / step + 1).min(chunks)
+    } else {
+        0
+    };
+    let tail = (chunks - full) * len - step * (full + chunks - 1) * (chunks - full) / 2;
+    let bytes = full * max_chunk + tail;
+
+    (chunks as usize, bytes.min(usize::MAX as u128) as usize)
+}
+
+/// Overlap of chunk `index` with the chunk before it, under the `(from_index, overlap)` steps
+/// of `schedule` (sorted by index), `overlap` before the first step.
+pub(crate) fn overlap_at(schedule: &[(usize, usize)], overlap: usize, index: usize) -> usize {
+    schedule
+        .iter()
+        .take_while(|&&(from_index, _)| from_index <= index)
+        .last()
+        .map_or(overlap, |&(_, overlap)| overlap)
+}
+
+/// Largest overlap of any chunk under `schedule`, see [`overlap_at`].
+pub(crate) fn max_overlap(schedule: &[(usize, usize)], overlap: usize) -> usize {
+    schedule.iter().map(|&(_, o)| o).fold(overlap, usize::max)
+}
+
+/// Largest char boundary of `text` that is `<= index` (UTF-8 sequences are at most 4 bytes).
+pub(crate) fn floor_char_boundary(text: &str, index: usize) -> usize {
+    if index >= text.len() {
+        return text.len();
+    }
+    (index.saturating_sub(3)..=index)
+        .rev()
+        .find(|&i| text.is_char_boundary(i))
+        .expect("Bug: no char boundary found")
+}
+
+/// Smallest char boundary of `text` at or after `index`, or its length past the end.
+pub(crate) fn ceil_char_boundary(text: &str, index: usize) -> usize {
+    if index >= text.len() {
+        return text.len();
+    }
+    (index..=index + 3)
+        .find(|&i| text.is_char_boundary(i))
+        .expect("Bug: no char boundary found")
+}
+
+/// Initial state of [`content_hash`].
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Continue an FNV-1a hash with `bytes`.
+pub(crate) fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// 64-bit FNV-1a hash of `text`, stable across builds and platforms. Hashing texts one
+/// after the other continues the hash, so the running hash of chunks (see
+/// [`ChunkerWithStrategy::with_running_hash`]) is the hash of their concatenation.
+pub fn content_hash(text: &str) -> u64 {
+    fnv1a(FNV_OFFSET_BASIS, text.as_bytes())
+}
+
+/// A chunk of text together with its position in the source it was cut from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Chunk {
+    pub text: String,
+    /// Byte offset of the first byte of the chunk within its source.
+    pub start: usize,
+    /// Byte offset one past the last byte of the chunk within its source.
+    pub end: usize,
+    /// Position of the chunk among the chunks of its source, starting at 0.
+    pub index: usize,
+    /// Lines the chunk spans, set when requested with [`ChunkerWithStrategy::with_line_numbers`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lines: Option<LineRange>,
+    /// Character offsets of the chunk within its source, set when requested with
+    /// [`ChunkerWithStrategy::with_char_offsets`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chars: Option<CharRange>,
+    /// Whether the chunk is one of the chunks the source is cut into, or a bridge chunk
+    /// added around the boundary of two of them.
+    #[serde(skip_serializing_if = "ChunkKind::is_main")]
+    pub kind: ChunkKind,
+    /// [`content_hash`] of the text of every chunk yielded so far by the iteration, this one
+    /// included, set when requested with [`ChunkerWithStrategy::with_running_hash`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub running_hash: Option<u64>,
+    /// Neighbours of the chunk in its source, set when requested with
+    /// [`ChunkerWithStrategy::with_links`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<ChunkLinks>,
+    /// Level of the chunk and the index of its parent, set by [`hierarchical`](crate::hierarchical).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hierarchy: Option<ChunkHierarchy>,
+    /// Modification time and size of the file the chunk was cut from, set when requested
+    /// with [`ChunkerWithStrategy::with_source_stamps`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_stamp: Option<SourceStamp>,
+    /// Bytes of the header repeated at the top of the content of the chunk, which is not
+    /// part of the source range `start..end`, as repeated by strategies such as
+    /// [`LinesChunker::with_repeat_header`], or 0.
+    #[serde(skip)]
+    pub header_len: usize,
+    /// Bytes of the prefix and preamble before the content of the chunk, see
+    /// [`ChunkerWithStrategy::with_prefix`].
+    #[serde(skip)]
+    pub prefix_len: usize,
+    /// Bytes of the suffix after the content of the chunk, see
+    /// [`ChunkerWithStrategy::with_suffix`].
+    #[serde(skip)]
+    pub suffix_len: usize,
+}
+
+impl Chunk {
+    /// The text of the chunk without its prefix, preamble and suffix: a repeated header,
+    /// then the text of the source range `start..end`.
+    pub fn content(&self) -> &str {
+        &self.text[self.prefix_len..self.text.len() - self.suffix_len]
+    }
+
+    /// The part of the chunk not covered by the previous chunk of the same source, which
+    /// ends at `previous_end` (`None` for the first chunk), leaving out a repeated header and
+    /// the affixes. Concatenating the unique text of every chunk of a source restores the
+    /// source exactly.
+    pub fn unique_text(&self, previous_end: Option<usize>) -> &str {
+        let content = &self.content()[self.header_len..];
+        let covered = previous_end.map_or(0, |end| end.saturating_sub(self.start));
+        &content[covered.min(content.len())..]
+    }
+}
+
+/// Indices of the chunks before and after a chunk of one source, e.g. to link chunks into a
+/// graph, `None` at the ends of the source. A bridge chunk links the two main chunks it
+/// straddles, and main chunks link each other over it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ChunkLinks {
+    pub prev_index: Option<usize>,
+    pub next_index: Option<usize>,
+}
+
+/// Modification time and size of a file source, read once when it is opened, e.g. to skip
+/// files unchanged since the last run of an incremental index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SourceStamp {
+    pub mtime: SystemTime,
+    pub size: u64,
+}
+
+impl SourceStamp {
+    /// Read the stamp of the file at `path`.
+    pub fn of(path: impl AsRef<Path>) -> Result<Self, ChunkingError> {
+        let metadata = fs::metadata(path)?;
+        Ok(Self {
+            mtime: metadata.modified()?,
+            size: metadata.len(),
+        })
+    }
+}
+
+/// Where a chunk cut by [`hierarchical`](crate::hierarchical) sits: a parent chunk (level 0)
+/// or a child chunk (level 1) cut from the parent at `parent_index`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ChunkHierarchy {
+    pub level: u8,
+    pub parent_index: Option<usize>,
+}
+
+/// What a [`Chunk`] is, see [`ChunkerWithStrategy::with_bridge_chunks`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChunkKind {
+    /// One of the chunks the source is cut into.
+    #[default]
+    Main,
+    /// A chunk straddling the boundary between two main chunks.
+    Bridge,
+}
+
+impl ChunkKind {
+    pub fn is_main(&self) -> bool {
+        *self == ChunkKind::Main
+    }
+}
+
+/// 1-based, inclusive range of the lines a chunk spans. A trailing newline belongs to the
+/// line it ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LineRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Offsets of a chunk within its source in characters (Unicode scalar values), as a
+/// Python `str` counts them: the chunk is `source[start..end]` counting characters, as it
+/// is `source[chunk.start..chunk.end]` counting bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CharRange {
+    pub start: usize,
+    pub end: usize,
+}
+
 pub trait Chunker: Clone + Sync + Send + 'static {
-    fn chunk_string(self, input: String) -> impl Iterator<Item = String>;
-    fn chunk_stream(self, input: impl Iterator<Item = String>) -> impl Iterator<Item = String>;
+    fn chunk_string_with_offsets(self, input: String) -> impl Iterator<Item = Chunk>;
+    fn chunk_stream_with_offsets(
+        self,
+        input: impl Iterator<Item = String>,
+    ) -> impl Iterator<Item = Chunk>;
+
+    /// Like [`Self::chunk_stream_with_offsets`], but only yields the chunks at indices 0,
+    /// `stride`, `2 * stride`, ... Strategies that can cut a chunk without building its text
+    /// skip building the text of the others.
+    fn chunk_stream_with_stride(
+        self,
+        input: impl Iterator<Item = String>,
+        stride: usize,
+    ) -> impl Iterator<Item = Chunk> {
+        self.chunk_stream_with_offsets(input).step_by(stride)
+    }
+
+    /// Approximate peak heap bytes used while streaming a source of `source_len` bytes,
+    /// excluding chunks kept around by the consumer.
+    fn estimate_memory(&self, source_len: usize) -> usize;
+
+    /// Number of chunks of a pure-ASCII source of `source_len` bytes, computed without
+    /// reading it, or `None` if it depends on the content.
+    fn count_ascii(&self, source_len: usize) -> Option<usize> {
+        let _ = source_len;
+        None
+    }
+
+    /// Largest number of chunks, and of bytes in all of them, that a source of `source_len`
+    /// bytes can make, i.e. the source plus every repeated overlap, or `None` if the strategy
+    /// does not bound chunk sizes. Runs of bidi controls moved along a cut are not counted.
+    fn max_output(&self, source_len: usize) -> Option<(usize, usize)> {
+        let _ = source_len;
+        None
+    }
+
+    /// Size chunks aim for, in the unit of [`Self::measure`], if the strategy has one.
+    fn chunk_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// Parameters of the strategy as plain data, if it can be described by them.
+    fn config(&self) -> Option<StrategyConfig> {
+        None
+    }
+
+    /// Chunk ends and overlap starts moved off their exact byte target onto a char boundary
+    /// by every iteration run so far with this strategy (or one of its clones).
+    fn boundary_adjustments(&self) -> usize {
+        0
+    }
+
+    /// Chunks whose end was moved back to end on a whole word, and the bytes cut from them
+    /// in total, by every iteration run so far with this strategy (or one of its clones).
+    fn word_snaps(&self) -> (usize, usize) {
+        (0, 0)
+    }
+
+    /// Units longer than a hard cap on the chunk size, cut at the cap instead of kept whole,
+    /// by every iteration run so far with this strategy (or one of its clones).
+    fn hard_splits(&self) -> usize {
+        0
+    }
+
+    /// Error that cut the chunks of the source short (e.g. a tokenizer failing to encode it),
+    /// taken by the iterator to report it once the source yields no more chunks. The
+    /// iterator chunks each source with its own [`Self::for_source`] copy, so errors stay
+    /// with their source.
+    fn take_error(&self) -> Option<ChunkingError> {
+        None
+    }
+
+    /// Copy of the strategy to chunk one source with, whose clones share state (e.g. the
+    /// error of [`Self::take_error`]) with each other but not with the copies for other
+    /// sources.
+    fn for_source(&self) -> Self {
+        self.clone()
+    }
+
+    /// Rewrite the blocks read from a source as the strategy does before cutting them (e.g.
+    /// collapsing separators), returning the rewritten blocks and a copy of the strategy that
+    /// cuts them as they are. Offsets of chunks are positions in the rewritten text.
+    fn rewrite_blocks(
+        self,
+        blocks: impl Iterator<Item = String>,
+    ) -> (impl Iterator<Item = String>, Self) {
+        (blocks, self)
+    }
+
+    /// Whether the strategy was told its input is pure ASCII, so that sources are read
+    /// without UTF-8 validation.
+    fn assumes_ascii(&self) -> bool {
+        false
+    }
+
+    /// Length of the header (e.g. the column names of CSV lines) that `first_chunk`, the
+    /// first chunk of a source, starts with, if the strategy repeats it at the top of every
+    /// later chunk of the source.
+    fn header_len(&self, first_chunk: &str) -> Option<usize> {
+        let _ = first_chunk;
+        None
+    }
+
+    /// Size of `text` in the unit the strategy sizes chunks in (bytes unless overridden).
+    fn measure(&self, text: &str) -> usize {
+        text.len()
+    }
+
+    /// Smallest step between the starts of consecutive chunks of `sample`, in the unit of
+    /// [`Self::measure`], or `None` if the sample makes fewer than two chunks.
+    ///
+    /// Snapping to char (or other) boundaries can make the step differ from
+    /// `chunk_size - overlap`; chunking a representative sample shows the actual one.
+    fn effective_step(&self, sample: &str) -> Option<usize> {
+        let mut starts = self
+            .clone()
+            .chunk_string_with_offsets(sample.to_string())
+            .map(|chunk| chunk.start);
+        let mut previous = starts.next()?;
+
+        starts
+            .map(|start| {
+                let step = self.measure(&sample[previous..start]);
+                previous = start;
+                step
+            })
+            .min()
+    }
+
+    fn chunk_string(self, input: String) -> impl Iterator<Item = String> {
+        self.chunk_string_with_offsets(input)
+            .map(|chunk| chunk.text)
+    }
+
+    fn chunk_stream(self, input: impl Iterator<Item = String>) -> impl Iterator<Item = String> {
+        self.chunk_stream_with_offsets(input)
+            .map(|chunk| chunk.text)
+    }
+}
+
+/// Which chunk the overlap between two consecutive chunks belongs to.
+///
+/// Either way, the tail of a chunk is the head of the next one, and chunking ends with the
+/// chunk that reaches the end of the text. With `Backward` overlap, the head of a chunk
+/// repeats context from the previous chunk: chunks hold `chunk_size` units and start every
+/// `chunk_size - overlap` units. With `Forward` overlap, the tail of a chunk is a lookahead
+/// into the next chunk, on top of its own `chunk_size` units: chunks hold up to
+/// `chunk_size + overlap` units and start every `chunk_size` units, and the last chunk has
+/// no lookahead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverlapDirection {
+    #[default]
+    Backward,
+    Forward,
+}
+
+/// What happens to the last chunk of a source when it is redundant: when it holds nothing
+/// beyond the previous chunk, e.g. when context after the previous chunk reaches the end of
+/// the text, or is mostly overlap.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LastChunk {
+    /// Yield it anyway.
+    #[default]
+    Keep,
+    /// Leave it out.
+    DropIfRedundant,
+    /// Fold it into the previous chunk, which then ends the source, when it adds less new
+    /// text than it repeats from the previous chunk: a redundant chunk is dropped, and the
+    /// few units a mostly repeated one adds are appended to the previous chunk, which grows
+    /// past the chunk size by less than the overlap.
+    Merge,
 }
 
 #[derive(Clone)]
@@ -177,83 +758,1100 @@ impl ChunkerBuilder {
         chunk_size: usize,
         overlap: usize,
     ) -> Result<ChunkerWithStrategy<BytesChunker>, ChunkingError> {
-        Ok(ChunkerWithStrategy {
-            chunker: BytesChunker::new(chunk_size, overlap)?,
-        })
+        Ok(ChunkerWithStrategy::new(BytesChunker::new(
+            chunk_size, overlap,
+        )?))
+    }
+
+    /// Chunks of about `target_tokens` tokens overlapping by about `overlap_tokens`, with
+    /// tokens estimated at `chars_per_token` characters each (about 4 for English text and
+    /// GPT tokenizers) instead of loading a tokenizer. The budget is converted to bytes, one
+    /// per character of ASCII text and erring on the short side for other text, and chunks
+    /// end and overlap on whole words.
+    pub fn by_approx_tokens(
+        target_tokens: usize,
+        overlap_tokens: usize,
+        chars_per_token: f64,
+    ) -> Result<ChunkerWithStrategy<BytesChunker>, ChunkingError> {
+        if !(chars_per_token.is_finite() && chars_per_token > 0.0) {
+            return Err(ChunkingError::InvalidCharsPerToken(chars_per_token));
+        }
+        if target_tokens == 0 || overlap_tokens >= target_tokens {
+            return Err(ChunkingError::InvalidArguments {
+                chunk_size: target_tokens,
+                overlap: overlap_tokens,
+            });
+        }
+        let chunk_size = (target_tokens as f64 * chars_per_token).round().max(1.0) as usize;
+        let overlap = (overlap_tokens as f64 * chars_per_token).round() as usize;
+        Ok(ChunkerWithStrategy::new(
+            BytesChunker::new(chunk_size, overlap)?
+                .with_snap_to_word(true)
+                .with_overlap_whole_words(true),
+        ))
     }
 
     pub fn by_characters(
         chunk_size: usize,
         overlap: usize,
     ) -> Result<ChunkerWithStrategy<CharactersChunker>, ChunkingError> {
-        Ok(ChunkerWithStrategy {
-            chunker: CharactersChunker::new(chunk_size, overlap)?,
-        })
+        Ok(ChunkerWithStrategy::new(CharactersChunker::new(
+            chunk_size, overlap,
+        )?))
+    }
+
+    pub fn by_graphemes(
+        chunk_size: usize,
+        overlap: usize,
+    ) -> Result<ChunkerWithStrategy<GraphemesChunker>, ChunkingError> {
+        Ok(ChunkerWithStrategy::new(GraphemesChunker::new(
+            chunk_size, overlap,
+        )?))
+    }
+
+    pub fn by_boundaries(
+        boundaries: Vec<usize>,
+        overlap: usize,
+    ) -> Result<ChunkerWithStrategy<BoundariesChunker>, ChunkingError> {
+        Ok(ChunkerWithStrategy::new(BoundariesChunker::new(
+            boundaries, overlap,
+        )?))
+    }
+    pub fn by_json_array(
+        chunk_size: usize,
+        overlap: usize,
+    ) -> Result<ChunkerWithStrategy<JsonArrayChunker>, ChunkingError> {
+        Ok(ChunkerWithStrategy::new(JsonArrayChunker::new(
+            chunk_size, overlap,
+        )?))
+    }
+
+    pub fn by_lines_packed(
+        target_bytes: usize,
+        overlap_lines: usize,
+    ) -> Result<ChunkerWithStrategy<LinesPackedChunker>, ChunkingError> {
+        Ok(ChunkerWithStrategy::new(LinesPackedChunker::new(
+            target_bytes,
+            overlap_lines,
+        )?))
+    }
+
+    pub fn by_lines(
+        chunk_size: usize,
+        overlap: usize,
+    ) -> Result<ChunkerWithStrategy<LinesChunker>, ChunkingError> {
+        Ok(ChunkerWithStrategy::new(LinesChunker::new(
+            chunk_size, overlap,
+        )?))
+    }
+
+    pub fn by_paragraphs(
+        chunk_size: usize,
+        overlap: usize,
+    ) -> Result<ChunkerWithStrategy<ParagraphsChunker>, ChunkingError> {
+        Ok(ChunkerWithStrategy::new(ParagraphsChunker::new(
+            chunk_size, overlap,
+        )?))
+    }
+
+    pub fn by_predicate<P: BoundaryPredicate>(
+        predicate: P,
+        chunk_size: usize,
+        overlap: usize,
+    ) -> Result<ChunkerWithStrategy<PredicateChunker<P>>, ChunkingError> {
+        Ok(ChunkerWithStrategy::new(PredicateChunker::new(
+            predicate, chunk_size, overlap,
+        )?))
+    }
+
+    /// Chunks of at most `chunk_size` bytes, split on the first of paragraphs, lines, words
+    /// and characters that makes them fit (see [`RecursiveChunker::with_separators`] for
+    /// other separators).
+    pub fn by_recursive(
+        chunk_size: usize,
+        overlap: usize,
+    ) -> Result<ChunkerWithStrategy<RecursiveChunker>, ChunkingError> {
+        Ok(ChunkerWithStrategy::new(RecursiveChunker::new(
+            chunk_size, overlap,
+        )?))
+    }
+
+    /// Chunks of `chunk_size` pieces of the text split on the matches of `pattern`, which
+    /// pieces keep (see [`RegexChunker::with_keep_separator`] to drop them).
+    pub fn by_regex(
+        pattern: &str,
+        chunk_size: usize,
+        overlap: usize,
+    ) -> Result<ChunkerWithStrategy<RegexChunker>, ChunkingError> {
+        Ok(ChunkerWithStrategy::new(RegexChunker::new(
+            pattern, chunk_size, overlap,
+        )?))
+    }
+
+    pub fn by_semantic<P: BreakPredicate>(
+        predicate: P,
+        unit: SemanticUnit,
+        overlap: usize,
+    ) -> ChunkerWithStrategy<SemanticChunker<P>> {
+        ChunkerWithStrategy::new(SemanticChunker::new(predicate, unit, overlap))
+    }
+
+    pub fn by_sentences(
+        chunk_size: usize,
+        overlap: usize,
+    ) -> Result<ChunkerWithStrategy<SentencesChunker>, ChunkingError> {
+        Ok(ChunkerWithStrategy::new(SentencesChunker::new(
+            chunk_size, overlap,
+        )?))
+    }
+
+    /// Chunks of at most `chunk_size` tokens of the HuggingFace tokenizer saved at
+    /// `tokenizer_path` (a `tokenizer.json` file).
+    #[cfg(feature = "tokenizers")]
+    pub fn by_tokens(
+        tokenizer_path: impl AsRef<std::path::Path>,
+        chunk_size: usize,
+        overlap: usize,
+    ) -> Result<ChunkerWithStrategy<crate::TokensChunker>, ChunkingError> {
+        Ok(ChunkerWithStrategy::new(crate::TokensChunker::from_file(
+            tokenizer_path,
+            chunk_size,
+            overlap,
+        )?))
+    }
+
+    pub fn by_transcript(
+        window_secs: f64,
+        overlap_secs: f64,
+    ) -> Result<ChunkerWithStrategy<TranscriptChunker>, ChunkingError> {
+        Ok(ChunkerWithStrategy::new(TranscriptChunker::new(
+            window_secs,
+            overlap_secs,
+        )?))
+    }
+
+    pub fn by_words(
+        chunk_size: usize,
+        overlap: usize,
+    ) -> Result<ChunkerWithStrategy<WordsChunker>, ChunkingError> {
+        Ok(ChunkerWithStrategy::new(WordsChunker::new(
+            chunk_size, overlap,
+        )?))
+    }
+}
+
+/// Counts the lines and characters of one source before the start of each of its chunks,
+/// from a copy of the blocks the strategy cuts, so text no chunk covers (e.g. a dropped
+/// separator) is counted too. Only the text from the last chunk start on is kept. Chunk
+/// starts must not decrease: an earlier one is counted as the last one.
+struct PositionCounter {
+    /// Blocks read by the strategy and not yet moved to `text`.
+    read: Arc<Mutex<Vec<String>>>,
+    /// Text of the source from `offset` on.
+    text: String,
+    offset: usize,
+    /// 1-based line and character offset at `offset`.
+    line: usize,
+    char: usize,
+}
+
+impl Default for PositionCounter {
+    fn default() -> Self {
+        Self {
+            read: Arc::default(),
+            text: String::new(),
+            offset: 0,
+            line: 1,
+            char: 0,
+        }
+    }
+}
+
+impl PositionCounter {
+    /// Where the strategy's blocks are copied to as it reads them.
+    fn blocks(&self) -> Arc<Mutex<Vec<String>>> {
+        self.read.clone()
+    }
+
+    /// Count the text up to the start of `chunk`.
+    fn advance_to(&mut self, chunk: &Chunk) {
+        for block in self.read.lock().unwrap().drain(..) {
+            self.text.push_str(&block);
+        }
+        let skipped = floor_char_boundary(&self.text, chunk.start.saturating_sub(self.offset));
+        let skipped_text = &self.text[..skipped];
+        self.line += skipped_text.matches('\n').count();
+        self.char += skipped_text.chars().count();
+        self.text.drain(..skipped);
+        self.offset += skipped;
+    }
+
+    fn lines_of(&self, chunk: &Chunk) -> LineRange {
+        // A trailing newline closes the chunk's last line rather than opening a new one
+        let text = chunk.text.strip_suffix('\n').unwrap_or(&chunk.text);
+        let inner_newlines = text.matches('\n').count();
+        LineRange {
+            start: self.line,
+            end: self.line + inner_newlines,
+        }
+    }
+
+    fn chars_of(&self, chunk: &Chunk) -> CharRange {
+        CharRange {
+            start: self.char,
+            end: self.char + chunk.text.chars().count(),
+        }
     }
 }
 
+/// Transform applied to every block read from a source before it is chunked, see
+/// [`ChunkerWithStrategy::with_block_transform`].
+pub type BlockTransform = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
 // Update ChunkerWithStrategy to use ChunkerEnum
+#[derive(Clone)]
 pub struct ChunkerWithStrategy<C: Chunker> {
     chunker: C,
+    near_dedup: Option<NearDuplicateFilter>,
+    line_numbers: bool,
+    char_offsets: bool,
+    /// Smallest and largest chunk size kept, in the unit of [`Chunker::measure`].
+    size_band: Option<(usize, usize)>,
+    emit_empty: bool,
+    /// Size below which consecutive chunks are merged, and largest size of a merged chunk.
+    merge_small: Option<(usize, usize)>,
+    attach_trailing_whitespace: bool,
+    last_chunk: LastChunk,
+    running_hash: bool,
+    links: bool,
+    source_stamps: bool,
+    /// Bytes bridge chunks extend past the boundary between two chunks, if they are added.
+    bridge_margin: Option<usize>,
+    bidi_balance: bool,
+    /// Only chunks whose index is a multiple of it are kept.
+    stride: usize,
+    prefix: String,
+    preamble: String,
+    /// Leave the preamble out of chunks starting at the start of their source.
+    preamble_skip_first: bool,
+    suffix: String,
+    block_transform: Option<BlockTransform>,
+    /// Transliterate blocks to ASCII, after the block transform.
+    transliterate: bool,
+    incomplete_utf8: IncompleteUtf8,
+    /// Retries of the opens and reads of file sources, e.g. the files matched by a glob.
+    file_retry: RetryPolicy,
+    stats: Arc<StatsCounters>,
 }
 
 impl<C: Chunker> ChunkerWithStrategy<C> {
+    /// Wrap an already configured strategy, e.g. one customized through its `with_*` options.
+    pub fn new(chunker: C) -> Self {
+        Self {
+            chunker,
+            near_dedup: None,
+            line_numbers: false,
+            char_offsets: false,
+            size_band: None,
+            emit_empty: false,
+            merge_small: None,
+            attach_trailing_whitespace: false,
+            last_chunk: LastChunk::Keep,
+            running_hash: false,
+            links: false,
+            source_stamps: false,
+            bridge_margin: None,
+            bidi_balance: false,
+            stride: 1,
+            prefix: String::new(),
+            preamble: String::new(),
+            preamble_skip_first: false,
+            suffix: String::new(),
+            block_transform: None,
+            transliterate: false,
+            incomplete_utf8: IncompleteUtf8::Drop,
+            file_retry: RetryPolicy::none(),
+            stats: Arc::default(),
+        }
+    }
+
+    /// Only keep chunks whose size lies within `[min_ratio, max_ratio] * chunk_size`, e.g.
+    /// `(0.5, 1.5)`. Sizes are measured in the strategy's unit (bytes or characters), and
+    /// dropped chunks are counted in [`Self::stats`].
+    pub fn with_size_band(mut self, min_ratio: f64, max_ratio: f64) -> Result<Self, ChunkingError> {
+        if !(0.0 <= min_ratio && min_ratio <= max_ratio) {
+            return Err(ChunkingError::InvalidSizeBand {
+                min_ratio,
+                max_ratio,
+            });
+        }
+        let chunk_size = self
+            .chunker
+            .chunk_size()
+            .ok_or(ChunkingError::NoChunkSize)? as f64;
+
+        self.size_band = Some((
+            (min_ratio * chunk_size).ceil() as usize,
+            (max_ratio * chunk_size).floor() as usize,
+        ));
+        Ok(self)
+    }
+
+    /// The strategy the chunks are cut with.
+    pub fn chunker(&self) -> &C {
+        &self.chunker
+    }
+
+    /// Strategy and options of this chunker as a [`ChunkerConfig`], to log or rebuild it
+    /// with [`ChunkerConfig::build`]. Fails for a strategy other than bytes and characters,
+    /// or with a block transform, since they are code.
+    pub fn config(&self) -> Result<ChunkerConfig, ChunkingError> {
+        let strategy = self.chunker.config().ok_or_else(|| {
+            ChunkingError::InvalidConfig(
+                "only the bytes and characters strategies can be described".to_string(),
+            )
+        })?;
+        if self.block_transform.is_some() {
+            return Err(ChunkingError::InvalidConfig(
+                "a block transform cannot be described".to_string(),
+            ));
+        }
+
+        let options = ChunkerOptions {
+            near_dedup: self
+                .near_dedup
+                .as_ref()
+                .map(|f| (f.max_distance(), f.capacity())),
+            line_numbers: self.line_numbers,
+            char_offsets: self.char_offsets,
+            size_band: self.size_band,
+            emit_empty: self.emit_empty,
+            merge_small: self.merge_small,
+            attach_trailing_whitespace: self.attach_trailing_whitespace,
+            last_chunk: self.last_chunk,
+            running_hash: self.running_hash,
+            links: self.links,
+            source_stamps: self.source_stamps,
+            bridge_chunks: self.bridge_margin,
+            bidi_balance: self.bidi_balance,
+            stride: self.stride,
+            prefix: self.prefix.clone(),
+            preamble: self.preamble.clone(),
+            preamble_skip_first: self.preamble_skip_first,
+            suffix: self.suffix.clone(),
+            transliterate: self.transliterate,
+            incomplete_utf8: self.incomplete_utf8,
+        };
+        Ok(ChunkerConfig { strategy, options })
+    }
+
+    /// Set the options described by `options`, for [`ChunkerConfig::build`].
+    pub(crate) fn with_options(mut self, options: &ChunkerOptions) -> Result<Self, ChunkingError> {
+        if let Some((min, max)) = options.size_band {
+            if min > max {
+                return Err(ChunkingError::InvalidConfig(format!(
+                    "size band ({min}, {max}) is out of order"
+                )));
+            }
+        }
+        self.near_dedup = options
+            .near_dedup
+            .map(|(max_distance, capacity)| NearDuplicateFilter::new(max_distance, capacity));
+        self.line_numbers = options.line_numbers;
+        self.char_offsets = options.char_offsets;
+        self.size_band = options.size_band;
+        self.emit_empty = options.emit_empty;
+        self.merge_small = options.merge_small;
+        self.attach_trailing_whitespace = options.attach_trailing_whitespace;
+        self.last_chunk = options.last_chunk;
+        self.running_hash = options.running_hash;
+        self.links = options.links;
+        self.source_stamps = options.source_stamps;
+        self.bridge_margin = options.bridge_chunks;
+        self.bidi_balance = options.bidi_balance;
+        self.prefix.clone_from(&options.prefix);
+        self.preamble.clone_from(&options.preamble);
+        self.preamble_skip_first = options.preamble_skip_first;
+        self.suffix.clone_from(&options.suffix);
+        self.incomplete_utf8 = options.incomplete_utf8;
+        if options.transliterate {
+            #[cfg(feature = "transliterate")]
+            {
+                self.transliterate = true;
+            }
+            #[cfg(not(feature = "transliterate"))]
+            return Err(ChunkingError::InvalidConfig(
+                "transliteration needs the `transliterate` feature".to_string(),
+            ));
+        }
+        self.with_stride(options.stride)
+    }
+
+    /// Counts of the chunks produced and dropped by every iteration run so far with this
+    /// builder (or one of its clones).
+    pub fn stats(&self) -> ChunkStats {
+        let (word_snap_adjustments, word_snap_bytes) = self.chunker.word_snaps();
+        ChunkStats {
+            boundary_adjustments: self.chunker.boundary_adjustments(),
+            word_snap_adjustments,
+            word_snap_bytes,
+            hard_splits: self.chunker.hard_splits(),
+            ..self.stats.snapshot()
+        }
+    }
+
+    /// Approximate peak heap bytes used to chunk a file or HTTP source of `source_len` bytes,
+    /// excluding chunks kept around by the consumer. A text source additionally holds the
+    /// text itself.
+    pub fn estimate_memory(&self, source_len: usize) -> usize {
+        let dedup = self
+            .near_dedup
+            .as_ref()
+            .map_or(0, |f| f.capacity() * std::mem::size_of::<u64>());
+        self.chunker.estimate_memory(source_len) + dedup
+    }
+
+    /// Worst-case total bytes of the chunks of a source of `source_len` bytes, for capacity
+    /// planning: the source, every repeated overlap and the affixes (prefix, preamble and
+    /// suffix) of every chunk.
+    /// `None` if the strategy does not bound chunk sizes (see [`Chunker::max_output`]).
+    pub fn output_size_estimate(&self, source_len: usize) -> Option<usize> {
+        let (chunks, bytes) = self.chunker.max_output(source_len)?;
+        // Filters and merging only drop bytes, but each kept chunk gets the affixes
+        let chunks = chunks.div_ceil(self.stride).max(self.emit_empty as usize);
+        let affixes = self.prefix.len() + self.preamble.len() + self.suffix.len();
+        Some(bytes + chunks * affixes)
+    }
+
+    /// Number of chunks of the file at `path`.
+    ///
+    /// When the file is pure ASCII, as asserted by `ascii` or the strategy (see
+    /// [`Chunker::assumes_ascii`]) or else checked by a scan that stops at the first other byte, the count is computed from the file length alone for
+    /// strategies that allow it (see [`Chunker::count_ascii`]), unless a size band,
+    /// near-duplicate filter or merging makes it depend on the content. Otherwise the file
+    /// is chunked. A wrong `ascii` assertion gives a wrong count.
+    pub fn count_file(&self, path: impl AsRef<Path>, ascii: bool) -> Result<usize, ChunkingError> {
+        let path = path.as_ref();
+        let content_free = self.size_band.is_none()
+            && self.near_dedup.is_none()
+            && self.merge_small.is_none()
+            && !self.attach_trailing_whitespace
+            && self.bridge_margin.is_none()
+            && self.block_transform.is_none();
+        let source_len = std::fs::metadata(path)?.len() as usize;
+
+        if let Some(count) = self
+            .chunker
+            .count_ascii(source_len)
+            .filter(|_| content_free)
+        {
+            if ascii || self.chunker.assumes_ascii() || is_ascii_file(path)? {
+                let count = count.div_ceil(self.stride);
+                return Ok(count.max(self.emit_empty as usize));
+            }
+        }
+
+        let source = Source::File(path.to_string_lossy().into_owned());
+        self.on_source_with_offsets(source)?
+            .try_fold(0, |count, chunk| chunk.map(|_| count + 1))
+    }
+
+    /// Yield a single empty chunk for a source that produces no chunks (an empty input),
+    /// instead of none. Like any other chunk, it is subject to the size band.
+    pub fn with_emit_empty(mut self, enabled: bool) -> Self {
+        self.emit_empty = enabled;
+        self
+    }
+
+    /// Merge every run of consecutive chunks that are each smaller than `min_chunk_size`
+    /// into chunks of at most `max_chunk_size` (typically the chunk size), sizes being
+    /// measured in the strategy's unit. A merged chunk spans from the start of the first
+    /// chunk of its run to the end of the last one, so it overlaps the chunks around it
+    /// like they did. Chunks are renumbered, and merging runs before the size band.
+    pub fn with_merge_small(mut self, min_chunk_size: usize, max_chunk_size: usize) -> Self {
+        self.merge_small = Some((min_chunk_size, max_chunk_size));
+        self
+    }
+
+    /// Append the whitespace that follows the text of a chunk, e.g. blank lines after a
+    /// paragraph, to that chunk instead of starting the next chunk with it. A chunk adding
+    /// only whitespace to the previous one is merged into it, so trailing blank lines never
+    /// make a chunk of their own. Chunks after a merged one are renumbered, and attaching
+    /// runs after merging small chunks.
+    pub fn with_attach_trailing_whitespace(mut self, enabled: bool) -> Self {
+        self.attach_trailing_whitespace = enabled;
+        self
+    }
+
+    /// Choose what happens to the last chunk of a source when it holds nothing beyond the
+    /// previous chunk, or little beyond its overlap with it (see [`LastChunk`]): keep it
+    /// (the default), drop it, or merge it into the previous chunk. Only consecutive chunks
+    /// are compared, so a stride leaves the last chunk alone, and the policy applies before
+    /// merging small chunks.
+    pub fn with_last_chunk(mut self, policy: LastChunk) -> Self {
+        self.last_chunk = policy;
+        self
+    }
+
+    /// Apply `transform` (e.g. unescaping or lowercasing) to every block of text read from a
+    /// source, before it is buffered for chunking, so that a file is transformed as it
+    /// streams instead of as a whole. Blocks are cut at arbitrary char boundaries, so the
+    /// transform should work character by character. Offsets are those of the transformed
+    /// text: a transform that changes the length of the text shifts them off the source.
+    pub fn with_block_transform(
+        mut self,
+        transform: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.block_transform = Some(Arc::new(transform));
+        self
+    }
+
+    /// Set [`Chunk::running_hash`] on every chunk: the [`content_hash`] of the text of all
+    /// chunks yielded so far by the iteration, as they are yielded (with prefix, preamble and
+    /// suffix), across sources. Two runs yielded the same chunks up to a point exactly when
+    /// their running hashes there match, up to hash collisions.
+    pub fn with_running_hash(mut self, enabled: bool) -> Self {
+        self.running_hash = enabled;
+        self
+    }
+
+    /// Set [`Chunk::links`] on every chunk: the indices of the chunks before and after it in
+    /// its source. The links are set before the size band and near-duplicate filter, which
+    /// may drop a linked chunk. Each chunk is held back until the next one is cut.
+    pub fn with_links(mut self, enabled: bool) -> Self {
+        self.links = enabled;
+        self
+    }
+
+    /// Set [`Chunk::source_stamp`] on the chunks of file sources, e.g. those matched by a
+    /// glob: the modification time and size of the file, read once when it is opened.
+    pub fn with_source_stamps(mut self, enabled: bool) -> Self {
+        self.source_stamps = enabled;
+        self
+    }
+
+    /// Transliterate the text of every source to ASCII as it streams, after the block
+    /// transform, so that chunks only hold ASCII, e.g. for systems that cannot handle any
+    /// other text. This is lossy, see [`crate::transliterate`]: accents are removed and
+    /// characters without an ASCII spelling dropped. Chunks are sized, and offsets given,
+    /// in the transliterated text, which no longer lines up with the source.
+    #[cfg(feature = "transliterate")]
+    pub fn with_transliteration(mut self, enabled: bool) -> Self {
+        self.transliterate = enabled;
+        self
+    }
+
+    /// What to do with the bytes of a character cut off by the end of a file, HTTP or reader
+    /// source, e.g. a truncated file: drop them (the default), end the text with a
+    /// replacement character, or yield [`ChunkingError::IncompleteUtf8`] after the chunks.
+    /// Such sources are counted in [`ChunkStats::truncated_sources`] either way.
+    pub fn with_incomplete_utf8(mut self, policy: IncompleteUtf8) -> Self {
+        self.incomplete_utf8 = policy;
+        self
+    }
+
+    /// Retry opening and reading file sources, e.g. the files matched by a glob, when they
+    /// fail with a transient error such as a timeout, instead of failing at once. A missing
+    /// file or a denied permission is not retried.
+    pub fn with_file_retry(mut self, policy: RetryPolicy) -> Self {
+        self.file_retry = policy;
+        self
+    }
+
+    /// Add a bridge chunk ([`ChunkKind::Bridge`]) between every two consecutive chunks that
+    /// overlap or touch, so that queries spanning their boundary match one chunk: it spans
+    /// their overlap (or the point where they meet) and up to `margin` bytes on each side,
+    /// without leaving the two chunks. A bridge has the index of the chunk before it and
+    /// comes right after it; like any chunk, it is subject to the size band. Bridges are
+    /// added after attaching trailing whitespace, and do not repeat the header of a strategy
+    /// that repeats one (e.g. [`LinesChunker::with_repeat_header`]).
+    pub fn with_bridge_chunks(mut self, margin: usize) -> Self {
+        self.bridge_margin = Some(margin);
+        self
+    }
+
+    /// Only keep every `stride`-th chunk of each source, i.e. the chunks at indices 0,
+    /// `stride`, `2 * stride`, ..., e.g. for a quick look at a huge file. The whole source is
+    /// still read, but the bytes and characters strategies do not build the text of the
+    /// chunks in between. Chunks keep their index, and sampling runs before all the other
+    /// options; line numbers are not reported, since they need the chunks in between.
+    pub fn with_stride(mut self, stride: usize) -> Result<Self, ChunkingError> {
+        if stride == 0 {
+            return Err(ChunkingError::InvalidStride);
+        }
+        self.stride = stride;
+        Ok(self)
+    }
+
+    /// Balance the embedding, override and isolate controls (LRE, RLE, LRO, RLO, LRI, RLI
+    /// and FSI) within each chunk, so right-to-left text renders the same on its own: closers
+    /// (PDF, PDI) whose opener is in an earlier chunk are removed, and openers still open at
+    /// the end of a chunk are closed there. The text of such chunks then no longer matches
+    /// their offsets in the source.
+    pub fn with_bidi_balance(mut self, enabled: bool) -> Self {
+        self.bidi_balance = enabled;
+        self
+    }
+
+    /// Prepend `prefix` (e.g. the document title) to the text of every chunk. It does not
+    /// count towards the chunk size, the size band or near-duplicate detection. The offsets
+    /// of a chunk still span its content only, and [`Chunk::unique_text`] does not account
+    /// for the prefix.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Prepend `preamble` (e.g. the import block of a code file, as context) to the text of
+    /// every chunk, after the prefix. Like the prefix, it does not count towards the chunk
+    /// size. With `skip_first`, chunks starting at the start of their source go without it,
+    /// since they already hold it when it is the head of the source.
+    pub fn with_preamble(mut self, preamble: impl Into<String>, skip_first: bool) -> Self {
+        self.preamble = preamble.into();
+        self.preamble_skip_first = skip_first;
+        self
+    }
+
+    /// Append `suffix` to the text of every chunk, like [`Self::with_prefix`].
+    pub fn with_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = suffix.into();
+        self
+    }
+
+    /// The text prepended to every chunk (empty unless set with [`Self::with_prefix`]).
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// The text prepended to chunks after the prefix, and whether chunks starting at the
+    /// start of their source go without it (see [`Self::with_preamble`]).
+    pub fn preamble(&self) -> (&str, bool) {
+        (&self.preamble, self.preamble_skip_first)
+    }
+
+    /// The text appended to every chunk (empty unless set with [`Self::with_suffix`]).
+    pub fn suffix(&self) -> &str {
+        &self.suffix
+    }
+
+    /// Annotate chunks with the range of lines they span in their source (see [`LineRange`]).
+    /// Lines are counted from the text of the source as it streams by, text that no chunk
+    /// covers included.
+    pub fn with_line_numbers(mut self) -> Self {
+        self.line_numbers = true;
+        self
+    }
+
+    /// Annotate chunks with their offsets in characters as well as bytes (see
+    /// [`CharRange`]), e.g. to slice a Python `str`. Characters are counted like lines
+    /// (see [`Self::with_line_numbers`]).
+    pub fn with_char_offsets(mut self) -> Self {
+        self.char_offsets = true;
+        self
+    }
+
+    /// Drop chunks that are near-duplicates of one of the last `capacity` chunks, i.e. whose
+    /// fingerprints differ in at most `max_distance` of 64 bits (see [`NearDuplicateFilter`]).
+    /// Dropped chunks leave gaps in the indices of the remaining ones.
+    pub fn with_near_dedup(mut self, max_distance: u32, capacity: usize) -> Self {
+        self.near_dedup = Some(NearDuplicateFilter::new(max_distance, capacity));
+        self
+    }
+
+    /// Chunk a single source.
+    ///
+    /// An IO error that interrupts the source ends the iteration early; use
+    /// [`Self::on_source_with_offsets`] to observe it.
     pub fn on_source(&self, source: Source) -> Result<impl Iterator<Item = String>, ChunkingError> {
-        let stream = StreamType::from_source(&source)?;
+        Ok(self
+            .on_source_with_offsets(source)?
+            .map_while(Result::ok)
+            .map(|chunk| chunk.text))
+    }
+
+    /// Like [`Self::on_source`], but yields [`Chunk`]s carrying their byte offsets and index.
+    /// If reading the source fails midway, the chunks of everything read so far are yielded
+    /// first, followed by the error.
+    pub fn on_source_with_offsets(
+        &self,
+        source: Source,
+    ) -> Result<impl Iterator<Item = Result<Chunk, ChunkingError>>, ChunkingError> {
+        Ok(self.filter_chunks(self.chunk_source(source)?))
+    }
+
+    /// The first chunk of `source`, if any. A file or HTTP source is only read as far as
+    /// needed to cut it, i.e. its first buffer fill.
+    pub fn first(&self, source: Source) -> Result<Option<String>, ChunkingError> {
+        let first = self.on_source_with_offsets(source)?.next().transpose()?;
+        Ok(first.map(|chunk| chunk.text))
+    }
+
+    /// Chunk any byte reader, e.g. a pipe or a decompressor, with the same error reporting
+    /// as [`Self::on_source_with_offsets`].
+    pub fn on_reader_with_offsets<R: Read>(
+        &self,
+        reader: R,
+    ) -> impl Iterator<Item = Result<Chunk, ChunkingError>> {
+        self.chunk_blocks(
+            Utf8BlockReader::from_reader(reader, BLOCK_SIZE)
+                .with_ascii(self.chunker.assumes_ascii())
+                .with_incomplete_utf8(self.incomplete_utf8)
+                .with_stats(self.stats.clone()),
+        )
+    }
+
+    /// Chunk standard input as it arrives, e.g. from `tail -f app.log | ...`. The lines
+    /// strategy yields a chunk as soon as its lines are read; other strategies once the
+    /// text after a chunk fills their buffer, or at the end of the input.
+    pub fn on_stdin_with_offsets(&self) -> impl Iterator<Item = Result<Chunk, ChunkingError>> {
+        self.on_reader_with_offsets(io::stdin())
+    }
+
+    /// Chunk a file that is still being written, like `tail -f`: at the end of the data
+    /// written so far, the file is polled for more every `poll_interval`, until `stop` is
+    /// set. A character cut by the end of the data is completed by the next write.
+    ///
+    /// A chunk is yielded once the text after it fills the chunker's buffer, a few chunks, or
+    /// once following stops, when the rest of the file is chunked as usual.
+    pub fn on_file_follow(
+        &self,
+        path: impl AsRef<Path>,
+        poll_interval: Duration,
+        stop: Arc<AtomicBool>,
+    ) -> Result<impl Iterator<Item = Result<Chunk, ChunkingError>>, ChunkingError> {
+        let file = File::open(path)?;
+        Ok(self.on_reader_with_offsets(FollowReader::new(file, poll_interval, stop)))
+    }
+
+    /// Chunk the blocks of any [`SourceReader`], e.g. a custom source, the same way as files
+    /// and URLs. An error returned by the reader is yielded after the chunks of everything
+    /// read before it.
+    pub fn chunk_blocks<R: SourceReader>(
+        &self,
+        reader: R,
+    ) -> impl Iterator<Item = Result<Chunk, ChunkingError>> {
+        self.filter_chunks(self.chunk_checked(reader))
+    }
+
+    fn chunk_source(
+        &self,
+        source: Source,
+    ) -> Result<impl Iterator<Item = Result<Chunk, ChunkingError>>, ChunkingError> {
+        let stream = self.open(&source)?;
+        let stamp = match &source {
+            Source::File(path) if self.source_stamps => Some(SourceStamp::of(path)?),
+            _ => None,
+        };
+        Ok(self.chunk_checked(stream).map(move |chunk| {
+            chunk.map(|mut chunk| {
+                chunk.source_stamp = stamp;
+                chunk
+            })
+        }))
+    }
+
+    /// Open `source` to be read as the strategy expects it.
+    fn open(&self, source: &Source) -> Result<StreamType, ChunkingError> {
+        Ok(StreamType::from_source_with_retry(source, self.file_retry)?
+            .with_ascii(self.chunker.assumes_ascii())
+            .with_incomplete_utf8(self.incomplete_utf8)
+            .with_stats(self.stats.clone()))
+    }
+
+    /// Chunk the blocks of `reader`: the streaming core of every source.
+    fn chunk_checked<R: SourceReader>(
+        &self,
+        reader: R,
+    ) -> impl Iterator<Item = Result<Chunk, ChunkingError>> {
+        let stream = Blocks::new(reader);
+        let read_error = stream.read_error();
+        let transform = self.block_transform.clone();
+        let stream = stream.map(move |block| match &transform {
+            Some(transform) => transform(&block),
+            None => block,
+        });
+        #[cfg(feature = "transliterate")]
+        let transliterate = self.transliterate;
+        #[cfg(feature = "transliterate")]
+        let stream = stream.map(move |block| match transliterate {
+            true => crate::transliterate(&block),
+            false => block,
+        });
+        let (stream, chunker) = self.chunker.for_source().rewrite_blocks(stream);
+        let mut counter = (self.line_numbers || self.char_offsets).then(PositionCounter::default);
+        let copies = counter.as_ref().map(PositionCounter::blocks);
+        let stream = stream.inspect(move |block| {
+            if let Some(copies) = &copies {
+                copies.lock().unwrap().push(block.clone());
+            }
+        });
+        let chunks = chunker
+            .clone()
+            .chunk_stream_with_stride(stream, self.stride);
+        let chunks = RedundantLastChunk::new(chunks, self.last_chunk);
+        let chunks = SmallChunkMerger::new(chunks, self.chunker.clone(), self.merge_small);
+        let chunks = WhitespaceAttacher::new(chunks, self.attach_trailing_whitespace);
+        let chunks = BridgeInterleaver::new(chunks, self.bridge_margin);
+        let mut chunks = ChunkLinker::new(chunks, self.links);
+        let mut read_error = Some(read_error);
+        let mut emit_empty = self.emit_empty;
+        let (line_numbers, char_offsets) = (self.line_numbers, self.char_offsets);
+        let bidi_balance = self.bidi_balance;
+        let mut header: Option<String> = None;
+        let mut first_main = true;
+
+        std::iter::from_fn(move || {
+            let mut chunk = match chunks.next() {
+                Some(chunk) => chunk,
+                None => {
+                    // Runs once the stream is exhausted, surfacing the error that cut it short
+                    if let Some(e) = read_error.take().and_then(|slot| slot.take()) {
+                        emit_empty = false;
+                        return Some(Err(e));
+                    }
+                    if let Some(e) = chunker.take_error() {
+                        emit_empty = false;
+                        return Some(Err(e));
+                    }
+                    if !std::mem::take(&mut emit_empty) {
+                        return None;
+                    }
+                    Chunk {
+                        text: String::new(),
+                        start: 0,
+                        end: 0,
+                        index: 0,
+                        lines: None,
+                        chars: None,
+                        running_hash: None,
+                        links: None,
+                        hierarchy: None,
+                        source_stamp: None,
+                        header_len: 0,
+                        prefix_len: 0,
+                        suffix_len: 0,
+                        kind: ChunkKind::Main,
+                    }
+                }
+            };
+            emit_empty = false;
+
+            if let Some(counter) = &mut counter {
+                counter.advance_to(&chunk);
+                chunk.lines = line_numbers.then(|| counter.lines_of(&chunk));
+                chunk.chars = char_offsets.then(|| counter.chars_of(&chunk));
+            }
+            // Added once offsets are counted, since the header is not part of the chunk there.
+            // Bridges are cut across a boundary between chunks, and are left as they are.
+            if chunk.kind == ChunkKind::Main {
+                if std::mem::take(&mut first_main) {
+                    header = chunker
+                        .header_len(&chunk.text)
+                        .map(|len| chunk.text[..len].to_string());
+                } else if let Some(header) = &header {
+                    chunk.text.insert_str(0, header);
+                    chunk.header_len = header.len();
+                }
+            }
+            if bidi_balance {
+                balance_bidi(&mut chunk.text);
+            }
+            Some(Ok(chunk))
+        })
+    }
+
+    /// Drop the chunks filtered out by the size band and near-duplicate options, count what
+    /// happens to each chunk, add the prefix, preamble and suffix to the chunks kept, and
+    /// their running hash.
+    fn filter_chunks(
+        &self,
+        chunks: impl Iterator<Item = Result<Chunk, ChunkingError>>,
+    ) -> impl Iterator<Item = Result<Chunk, ChunkingError>> {
+        let chunker = self.chunker.clone();
+        let size_band = self.size_band;
+        let mut near_dedup = self.near_dedup.clone();
+        let stats = self.stats.clone();
+
+        let prefix = self.prefix.clone();
+        let preamble = self.preamble.clone();
+        let preamble_skip_first = self.preamble_skip_first;
+        let suffix = self.suffix.clone();
+        let mut running_hash = self.running_hash.then_some(FNV_OFFSET_BASIS);
 
-        Ok(self.chunker.clone().chunk_stream(stream))
+        chunks
+            .filter(move |chunk| {
+                let Ok(chunk) = chunk else {
+                    return true;
+                };
+
+                if let Some((min, max)) = size_band {
+                    let size = chunker.measure(&chunk.text);
+                    if size < min || size > max {
+                        stats.record_out_of_band();
+                        return false;
+                    }
+                }
+                if let Some(filter) = &mut near_dedup {
+                    if filter.is_near_duplicate(&chunk.text) {
+                        stats.record_near_duplicate();
+                        return false;
+                    }
+                }
+
+                stats.record_chunk();
+                true
+            })
+            .map(move |chunk| {
+                chunk.map(|mut chunk| {
+                    let preamble = if preamble_skip_first && chunk.start == 0 {
+                        ""
+                    } else {
+                        &preamble
+                    };
+                    if !prefix.is_empty() || !preamble.is_empty() || !suffix.is_empty() {
+                        chunk.text = format!("{prefix}{preamble}{}{suffix}", chunk.text);
+                        chunk.prefix_len = prefix.len() + preamble.len();
+                        chunk.suffix_len = suffix.len();
+                    }
+                    if let Some(hash) = &mut running_hash {
+                        *hash = fnv1a(*hash, chunk.text.as_bytes());
+                        chunk.running_hash = Some(*hash);
+                    }
+                    chunk
+                })
+            })
     }
 
+    /// Chunk several sources one after the other.
+    ///
+    /// An IO error that interrupts a source ends the iteration early; use
+    /// [`Self::on_sources_with_offsets`] to observe it.
     pub fn on_sources(
         &self,
         sources: Vec<Source>,
     ) -> Result<impl Iterator<Item = String>, ChunkingError> {
+        Ok(self
+            .on_sources_with_offsets(sources)?
+            .map_while(Result::ok)
+            .map(|chunk| chunk.text))
+    }
+
+    /// Like [`Self::on_sources`], but yields [`Chunk`]s. Offsets and indices are relative
+    /// to the source each chunk was cut from.
+    pub fn on_sources_with_offsets(
+        &self,
+        sources: Vec<Source>,
+    ) -> Result<impl Iterator<Item = Result<Chunk, ChunkingError>>, ChunkingError> {
         let iterators = sources
             .into_iter()
-            .map(|s| self.on_source(s))
+            .map(|s| self.chunk_source(s))
             .collect::<Result<Vec<_>, _>>()?;
 
         // Chain all iterators together
-        Ok(iterators.into_iter().flatten())
+        Ok(self.filter_chunks(iterators.into_iter().flatten()))
+    }
+
+    /// Chunk several sources in turn, taking one chunk from each source before moving on to
+    /// the next, and skipping sources that are exhausted. Sources are all opened up front
+    /// but only read as their chunks are pulled.
+    ///
+    /// An IO error that interrupts a source ends the iteration early; use
+    /// [`Self::on_sources_round_robin_with_offsets`] to observe it.
+    pub fn on_sources_round_robin(
+        &self,
+        sources: Vec<Source>,
+    ) -> Result<impl Iterator<Item = String>, ChunkingError> {
+        Ok(self
+            .on_sources_round_robin_with_offsets(sources)?
+            .map_while(Result::ok)
+            .map(|chunk| chunk.text))
+    }
+
+    /// Like [`Self::on_sources_round_robin`], but yields [`Chunk`]s. Offsets and indices are
+    /// relative to the source each chunk was cut from.
+    pub fn on_sources_round_robin_with_offsets(
+        &self,
+        sources: Vec<Source>,
+    ) -> Result<impl Iterator<Item = Result<Chunk, ChunkingError>>, ChunkingError> {
+        let mut iterators = sources
+            .into_iter()
+            .map(|s| self.chunk_source(s))
+            .collect::<Result<VecDeque<_>, _>>()?;
+
+        let interleaved = std::iter::from_fn(move || {
+            while let Some(mut iterator) = iterators.pop_front() {
+                if let Some(chunk) = iterator.next() {
+                    iterators.push_back(iterator);
+                    return Some(chunk);
+                }
+            }
+            None
+        });
+
+        Ok(self.filter_chunks(interleaved))
     }
 
     pub fn on_sources_par(&self, sources: Vec<Source>) -> Result<Vec<String>, ChunkingError> {
-        sources
+        let chunks = sources
             .into_par_iter()
             .map(|source| {
                 // Each thread: fetches source + chunks it + collects
-                let iter = self.on_source(source)?;
-                Ok(iter.collect::<Vec<String>>())
+                self.chunk_source(source)?
+                    .collect::<Result<Vec<Chunk>, ChunkingError>>()
             })
-            .collect::<Result<Vec<Vec<String>>, ChunkingError>>()
-            .map(|chunks| chunks.into_iter().flatten().collect())
+            .collect::<Result<Vec<Vec<Chunk>>, ChunkingError>>()?;
+
+        self.filter_chunks(chunks.into_iter().flatten().map(Ok))
+            .map(|chunk| chunk.map(|c| c.text))
+            .collect()
     }
 
+    /// Chunk several sources in parallel, streaming chunks through a bounded channel.
+    ///
+    /// An IO error that interrupts a source ends the iteration early; use
+    /// [`Self::on_sources_par_stream_with_offsets`] to observe it.
     pub fn on_sources_par_stream(
         &self,
         sources: Vec<Source>,
         channel_size: usize,
     ) -> Result<impl Iterator<Item = String>, ChunkingError> {
+        Ok(self
+            .on_sources_par_stream_with_offsets(sources, channel_size)?
+            .map_while(Result::ok)
+            .map(|chunk| chunk.text))
+    }
+
+    /// Like [`Self::on_sources_par_stream`], but yields [`Chunk`]s. Offsets and indices are
+    /// relative to the source each chunk was cut from.
+    pub fn on_sources_par_stream_with_offsets(
+        &self,
+        sources: Vec<Source>,
+        channel_size: usize,
+    ) -> Result<impl Iterator<Item = Result<Chunk, ChunkingError>>, ChunkingError> {
         // Pre-validate: check all sources are accessible
         for source in &sources {
-            StreamType::from_source(source)?; // This validates the source
+            self.open(source)?; // This validates the source
         }
 
         let (sender, receiver) = bounded(channel_size);
-        let chunker = self.chunker.clone();
+        let strategy = self.clone();
 
         thread::spawn({
             move || {
                 sources.into_par_iter().for_each(|source| {
                     let sender = sender.clone();
 
-                    // Should not fail since we pre-validated
-                    if let Ok(stream) = StreamType::from_source(&source) {
-                        for chunk in chunker.clone().chunk_stream(stream) {
-                            if sender.send(chunk).is_err() {
-                                break;
+                    match strategy.chunk_source(source) {
+                        Ok(chunks) => {
+                            for chunk in chunks {
+                                if sender.send(chunk).is_err() {
+                                    break;
+                                }
                             }
                         }
+                        Err(error) => {
+                            let _ = sender.send(Err(error));
+                        }
                     }
                 });
             }
@@ -261,7 +1859,7 @@ impl<C: Chunker> ChunkerWithStrategy<C> {
 
         let iterator = std::iter::from_fn(move || receiver.recv().ok());
 
-        Ok(Box::new(iterator))
+        Ok(self.filter_chunks(iterator))
     }
 }
 
@@ -0,0 +1,192 @@
+use std::io::{self, Write};
+
+use thiserror::Error;
+
+use crate::chunker::{Chunk, ChunkerBuilder, ChunkingError, Source};
+
+pub const CLI_USAGE: &str = "\
+Usage: kiru chunk [options] <input>
+
+Options:
+  --strategy <bytes|characters>   Chunking strategy (default: bytes)
+  --chunk-size <n>                Chunk size in bytes or characters (default: 1000)
+  --overlap <n>                   Overlap in bytes or characters (default: 0)
+  --source <file|http|string>     How to read <input> (default: file)
+  --format <text|lines|jsonl>     Print chunks one after the other, each followed by a
+                                  newline; one per line, with backslashes, newlines and
+                                  carriage returns escaped as \\\\, \\n and \\r, and flushed
+                                  as soon as cut; or as JSON lines with offsets
+                                  (default: text)";
+
+#[derive(Error, Debug)]
+pub enum CliError {
+    #[error("{0}\n\n{CLI_USAGE}")]
+    Usage(String),
+    #[error(transparent)]
+    Chunking(#[from] ChunkingError),
+    #[error("error writing output: {0}")]
+    Output(#[from] io::Error),
+}
+
+impl CliError {
+    /// Process exit code for the error: 2 for usage errors, 1 otherwise.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Usage(_) => 2,
+            _ => 1,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Format {
+    Text,
+    Lines,
+    Jsonl,
+}
+
+struct Options {
+    strategy: String,
+    chunk_size: usize,
+    overlap: usize,
+    source: Source,
+    format: Format,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, CliError> {
+    let mut args = args.iter();
+
+    match args.next().map(String::as_str) {
+        Some("chunk") => {}
+        Some(command) => return Err(CliError::Usage(format!("unknown command '{command}'"))),
+        None => return Err(CliError::Usage("missing command".to_string())),
+    }
+
+    let mut strategy = "bytes".to_string();
+    let mut chunk_size = 1000;
+    let mut overlap = 0;
+    let mut source_type = "file".to_string();
+    let mut format = Format::Text;
+    let mut input = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .cloned()
+                .ok_or_else(|| CliError::Usage(format!("missing value for {arg}")))
+        };
+        let number = |value: String| {
+            value
+                .parse::<usize>()
+                .map_err(|e| CliError::Usage(format!("invalid value for {arg}: {e}")))
+        };
+
+        match arg.as_str() {
+            "--strategy" => strategy = value()?,
+            "--chunk-size" => chunk_size = number(value()?)?,
+            "--overlap" => overlap = number(value()?)?,
+            "--source" => source_type = value()?,
+            "--format" => {
+                format = match value()?.as_str() {
+                    "text" => Format::Text,
+                    "lines" => Format::Lines,
+                    "jsonl" => Format::Jsonl,
+                    other => return Err(CliError::Usage(format!("invalid format '{other}'"))),
+                }
+            }
+            flag if flag.starts_with("--") => {
+                return Err(CliError::Usage(format!("unknown option '{flag}'")))
+            }
+            _ if input.is_some() => {
+                return Err(CliError::Usage(format!("unexpected argument '{arg}'")))
+            }
+            _ => input = Some(arg.clone()),
+        }
+    }
+
+    let input = input.ok_or_else(|| CliError::Usage("missing input".to_string()))?;
+    let source = match source_type.as_str() {
+        "file" => Source::File(input),
+        "http" => Source::Http(input),
+        "string" => Source::Text(input),
+        other => return Err(CliError::Usage(format!("invalid source '{other}'"))),
+    };
+
+    Ok(Options {
+        strategy,
+        chunk_size,
+        overlap,
+        source,
+        format,
+    })
+}
+
+/// Run the `kiru` command line with `args` (without the program name), writing the chunks
+/// to `out` as they are cut. See [`CLI_USAGE`] for the arguments.
+///
+/// Once `out` is closed by its reader (a broken pipe, e.g. into `head`), chunking stops
+/// without an error.
+pub fn run_cli(args: &[String], out: &mut dyn Write) -> Result<(), CliError> {
+    let options = parse_args(args)?;
+
+    match options.strategy.as_str() {
+        "bytes" => {
+            let chunker = ChunkerBuilder::by_bytes(options.chunk_size, options.overlap)?;
+            write_chunks(
+                chunker.on_source_with_offsets(options.source)?,
+                options.format,
+                out,
+            )
+        }
+        "characters" | "chars" => {
+            let chunker = ChunkerBuilder::by_characters(options.chunk_size, options.overlap)?;
+            write_chunks(
+                chunker.on_source_with_offsets(options.source)?,
+                options.format,
+                out,
+            )
+        }
+        other => Err(CliError::Usage(format!("invalid strategy '{other}'"))),
+    }
+}
+
+fn write_chunks(
+    chunks: impl Iterator<Item = Result<Chunk, ChunkingError>>,
+    format: Format,
+    out: &mut dyn Write,
+) -> Result<(), CliError> {
+    let mut out = io::BufWriter::new(out);
+    let written = chunks.into_iter().try_for_each(|chunk| {
+        let chunk = chunk?;
+        match format {
+            Format::Text => out.write_all(chunk.text.as_bytes())?,
+            Format::Lines => write_escaped(&mut out, &chunk.text)?,
+            Format::Jsonl => serde_json::to_writer(&mut out, &chunk).map_err(io::Error::from)?,
+        }
+        out.write_all(b"\n")?;
+        if let Format::Lines = format {
+            out.flush()?;
+        }
+        Ok(())
+    });
+
+    match written.and_then(|()| Ok(out.flush()?)) {
+        Err(CliError::Output(e)) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => result,
+    }
+}
+
+/// Write `text` on a single line, escaping backslashes, newlines and carriage returns.
+fn write_escaped(out: &mut impl Write, text: &str) -> io::Result<()> {
+    let mut rest = text.as_bytes();
+    while let Some(i) = rest.iter().position(|b| matches!(b, b'\\' | b'\n' | b'\r')) {
+        out.write_all(&rest[..i])?;
+        out.write_all(match rest[i] {
+            b'\\' => b"\\\\",
+            b'\n' => b"\\n",
+            _ => b"\\r",
+        })?;
+        rest = &rest[i + 1..];
+    }
+    out.write_all(rest)
+}
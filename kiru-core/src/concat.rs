@@ -0,0 +1,115 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, Read};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+
+use crate::chunker::ChunkingError;
+
+/// The part of a chunk cut from [`ConcatenatedFiles`] that comes from one file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileSpan {
+    pub path: String,
+    /// Byte offset of the first byte of the part within the file.
+    pub start: usize,
+    /// Byte offset one past the last byte of the part within the file.
+    pub end: usize,
+}
+
+/// Where each file of a [`ConcatenatedFiles`] starts in the concatenation, recorded as
+/// reading reaches it. Shared with the reader, so it can be queried once the reader was
+/// handed to a chunker.
+#[derive(Debug, Clone, Default)]
+pub struct FileOffsets(Arc<Mutex<Vec<(String, usize)>>>);
+
+impl FileOffsets {
+    fn push(&self, path: String, start: usize) {
+        self.0.lock().unwrap().push((path, start));
+    }
+
+    /// Break the bytes `start..end` of the concatenation down into the parts of each file
+    /// they span, in order. Only covers the files reading has reached, which includes every
+    /// file of the chunks yielded so far.
+    pub fn spans(&self, start: usize, end: usize) -> Vec<FileSpan> {
+        let files = self.0.lock().unwrap();
+        let file_ends = files
+            .iter()
+            .skip(1)
+            .map(|&(_, file_start)| file_start)
+            .chain([usize::MAX]);
+
+        files
+            .iter()
+            .zip(file_ends)
+            .filter_map(|((path, file_start), file_end)| {
+                let span_start = start.max(*file_start);
+                let span_end = end.min(file_end);
+                (span_start < span_end).then(|| FileSpan {
+                    path: path.clone(),
+                    start: span_start - file_start,
+                    end: span_end - file_start,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Reads several files one after the other, as a single source whose byte offsets run
+/// across all of them. Chunk it with [`crate::ChunkerWithStrategy::on_reader_with_offsets`]
+/// and map chunk offsets back to files with [`Self::offsets`].
+pub struct ConcatenatedFiles {
+    files: VecDeque<(String, File)>,
+    /// Whether the start of the front file was recorded.
+    started: bool,
+    position: usize,
+    offsets: FileOffsets,
+}
+
+impl ConcatenatedFiles {
+    /// Open all the files up front, so that a missing one fails before any chunking.
+    pub fn open(paths: Vec<String>) -> Result<Self, ChunkingError> {
+        let files = paths
+            .into_iter()
+            .map(|path| File::open(&path).map(|file| (path, file)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            files,
+            started: false,
+            position: 0,
+            offsets: FileOffsets::default(),
+        })
+    }
+
+    /// Handle to the start offsets of the files, filled in as they are read.
+    pub fn offsets(&self) -> FileOffsets {
+        self.offsets.clone()
+    }
+}
+
+impl Read for ConcatenatedFiles {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        while let Some((path, file)) = self.files.front_mut() {
+            if !self.started {
+                self.offsets.push(path.clone(), self.position);
+                self.started = true;
+            }
+
+            let read = file.read(buf)?;
+            if read > 0 {
+                self.position += read;
+                return Ok(read);
+            }
+
+            // Move on to the next file
+            self.files.pop_front();
+            self.started = false;
+        }
+        Ok(0)
+    }
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+use crate::chunker::{ChunkerWithStrategy, ChunkingError};
+use crate::{
+    BytesChunker, BytesConfig, CharactersChunker, CharactersConfig, IncompleteUtf8, LastChunk,
+};
+
+/// Parameters of a chunker: its strategy and the options of its builder, as plain data that
+/// serializes to a flat JSON object (e.g. to log experiment configs) and rebuilds it, see
+/// [`ChunkerWithStrategy::config`] and [`Self::build`]. Only the bytes and characters
+/// strategies are described; options left out of a deserialized config keep their default.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChunkerConfig {
+    #[serde(flatten)]
+    pub strategy: StrategyConfig,
+    #[serde(flatten)]
+    pub options: ChunkerOptions,
+}
+
+/// Parameters of a strategy, tagged with its name under `strategy`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum StrategyConfig {
+    Bytes(BytesConfig),
+    Characters(CharactersConfig),
+}
+
+/// Options of a [`ChunkerWithStrategy`], named after their `with_*` method. Sizes are in
+/// the unit of the strategy; the size band is kept as the smallest and largest chunk size
+/// rather than ratios of the chunk size.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChunkerOptions {
+    pub near_dedup: Option<(u32, usize)>,
+    pub line_numbers: bool,
+    pub char_offsets: bool,
+    pub size_band: Option<(usize, usize)>,
+    pub emit_empty: bool,
+    pub merge_small: Option<(usize, usize)>,
+    pub attach_trailing_whitespace: bool,
+    pub last_chunk: LastChunk,
+    pub running_hash: bool,
+    pub links: bool,
+    pub source_stamps: bool,
+    /// Margin of the bridge chunks, if they are added.
+    pub bridge_chunks: Option<usize>,
+    pub bidi_balance: bool,
+    pub stride: usize,
+    pub prefix: String,
+    pub preamble: String,
+    pub preamble_skip_first: bool,
+    pub suffix: String,
+    /// Whether text is transliterated to ASCII, which needs the `transliterate` feature.
+    pub transliterate: bool,
+    pub incomplete_utf8: IncompleteUtf8,
+}
+
+impl Default for ChunkerOptions {
+    fn default() -> Self {
+        Self {
+            near_dedup: None,
+            line_numbers: false,
+            char_offsets: false,
+            size_band: None,
+            emit_empty: false,
+            merge_small: None,
+            attach_trailing_whitespace: false,
+            last_chunk: LastChunk::Keep,
+            running_hash: false,
+            links: false,
+            source_stamps: false,
+            bridge_chunks: None,
+            bidi_balance: false,
+            stride: 1,
+            prefix: String::new(),
+            preamble: String::new(),
+            preamble_skip_first: false,
+            suffix: String::new(),
+            transliterate: false,
+            incomplete_utf8: IncompleteUtf8::Drop,
+        }
+    }
+}
+
+/// A chunker rebuilt from a [`ChunkerConfig`], typed after its strategy.
+#[derive(Clone)]
+pub enum ConfiguredChunker {
+    Bytes(ChunkerWithStrategy<BytesChunker>),
+    Characters(ChunkerWithStrategy<CharactersChunker>),
+}
+
+impl ChunkerConfig {
+    /// Rebuild the chunker, validating the parameters like the `with_*` methods do.
+    pub fn build(&self) -> Result<ConfiguredChunker, ChunkingError> {
+        Ok(match &self.strategy {
+            StrategyConfig::Bytes(config) => ConfiguredChunker::Bytes(
+                ChunkerWithStrategy::new(config.build()?).with_options(&self.options)?,
+            ),
+            StrategyConfig::Characters(config) => ConfiguredChunker::Characters(
+                ChunkerWithStrategy::new(config.build()?).with_options(&self.options)?,
+            ),
+        })
+    }
+
+    /// The config as a flat JSON object.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Bug: config does not serialize")
+    }
+
+    /// Parse a config written by [`Self::to_json`], or by hand.
+    pub fn from_json(json: &str) -> Result<Self, ChunkingError> {
+        serde_json::from_str(json).map_err(|e| ChunkingError::InvalidConfig(e.to_string()))
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+
+use crate::chunker::{Chunk, ChunkingError};
+
+/// Width in bytes of the rolling hash window.
+const SHINGLE_SIZE: usize = 8;
+/// Multiplier of the polynomial rolling hash.
+const BASE: u64 = 0x0100_0000_01b3;
+
+/// Suppresses chunks that are nearly identical to a recently seen one.
+///
+/// Every chunk is reduced to a 64-bit SimHash of the Rabin-Karp hashes of its
+/// `SHINGLE_SIZE`-byte windows, so small edits only flip a few bits. A chunk whose
+/// fingerprint is within `max_distance` bits (Hamming distance) of one of the last
+/// `capacity` fingerprints is a near-duplicate. Fingerprints are kept in LRU order:
+/// a match refreshes the fingerprint it matched, and the least recently used one is
+/// evicted when the window is full. Each lookup scans the whole window.
+#[derive(Debug, Clone)]
+pub struct NearDuplicateFilter {
+    max_distance: u32,
+    capacity: usize,
+    recent: VecDeque<u64>,
+}
+
+impl NearDuplicateFilter {
+    pub fn new(max_distance: u32, capacity: usize) -> Self {
+        Self {
+            max_distance,
+            capacity,
+            recent: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Number of fingerprints remembered.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Largest number of differing fingerprint bits of a near-duplicate.
+    pub fn max_distance(&self) -> u32 {
+        self.max_distance
+    }
+
+    /// Record `text` and report whether it is a near-duplicate of a recent chunk.
+    pub fn is_near_duplicate(&mut self, text: &str) -> bool {
+        let fp = fingerprint(text);
+
+        let matched = self
+            .recent
+            .iter()
+            .position(|&seen| (seen ^ fp).count_ones() <= self.max_distance);
+
+        match matched {
+            Some(i) => {
+                let seen = self.recent.remove(i).expect("index from position");
+                self.recent.push_back(seen);
+                true
+            }
+            None => {
+                if self.capacity > 0 {
+                    if self.recent.len() == self.capacity {
+                        self.recent.pop_front();
+                    }
+                    self.recent.push_back(fp);
+                }
+                false
+            }
+        }
+    }
+}
+
+/// SimHash of the rolling hashes of every `SHINGLE_SIZE`-byte window of `text`.
+fn fingerprint(text: &str) -> u64 {
+    let bytes = text.as_bytes();
+    let window = SHINGLE_SIZE.min(bytes.len()).max(1);
+    let drop_factor = BASE.wrapping_pow(window as u32 - 1);
+
+    let mut votes = [0i32; 64];
+    let mut hash = 0u64;
+    for (i, &byte) in bytes.iter().enumerate() {
+        if i >= window {
+            hash = hash.wrapping_sub((bytes[i - window] as u64).wrapping_mul(drop_factor));
+        }
+        hash = hash.wrapping_mul(BASE).wrapping_add(byte as u64);
+
+        if i + 1 >= window {
+            let mixed = mix(hash);
+            for (bit, vote) in votes.iter_mut().enumerate() {
+                *vote += if mixed >> bit & 1 == 1 { 1 } else { -1 };
+            }
+        }
+    }
+
+    votes
+        .iter()
+        .enumerate()
+        .filter(|(_, &vote)| vote > 0)
+        .fold(0, |fp, (bit, _)| fp | 1 << bit)
+}
+
+/// splitmix64 finalizer, spreading the polynomial hash over all 64 bits.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// Only the chunks of `chunks` whose text was not yielded before, in order. Errors are
+/// passed through.
+///
+/// Texts are remembered by a 64-bit hash rather than kept whole, so memory grows by 8 bytes
+/// per distinct chunk, not by its size; two distinct texts colliding (with odds of about
+/// `n^2 / 2^65` for `n` distinct chunks) would drop the later one.
+pub fn unique_chunks(
+    chunks: impl Iterator<Item = Result<Chunk, ChunkingError>>,
+) -> impl Iterator<Item = Result<Chunk, ChunkingError>> {
+    let mut seen = HashSet::new();
+    chunks.filter(move |chunk| match chunk {
+        Ok(chunk) => {
+            let mut hasher = DefaultHasher::new();
+            chunk.text.hash(&mut hasher);
+            seen.insert(hasher.finish())
+        }
+        Err(_) => true,
+    })
+}
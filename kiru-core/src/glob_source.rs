@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use glob::glob;
+
+use crate::chunker::{ChunkingError, ExpandedSources, Source};
+
+/// Add the regular files matched by `pattern` to `expanded`, and the other matches to its
+/// skipped paths: directories, FIFOs, devices, sockets, unreadable paths and symlinks, or
+/// paths under a symlinked directory, unless `follow_symlinks`.
+///
+/// When following symlinks, files are deduplicated by canonical path in `seen`, so a file
+/// reached again, e.g. through a symlink cycle, is skipped.
+pub(crate) fn expand_glob(
+    pattern: &str,
+    follow_symlinks: bool,
+    seen: &mut HashSet<PathBuf>,
+    expanded: &mut ExpandedSources,
+) -> Result<(), ChunkingError> {
+    let paths = glob(pattern).map_err(|_| {
+        ChunkingError::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Invalid glob pattern",
+        ))
+    })?;
+    let root = literal_root(pattern);
+
+    for entry in paths {
+        let path = match entry {
+            Ok(path) => path,
+            Err(e) => {
+                expanded.skipped.push(e.path().to_path_buf());
+                continue;
+            }
+        };
+
+        let included = if follow_symlinks {
+            // `metadata` follows symlinks, and fails for broken ones
+            fs::metadata(&path).is_ok_and(|m| m.is_file())
+                && fs::canonicalize(&path).is_ok_and(|target| seen.insert(target))
+        } else {
+            fs::symlink_metadata(&path).is_ok_and(|m| m.is_file()) && !under_symlink(&path, &root)
+        };
+
+        if included {
+            expanded
+                .sources
+                .push(Source::File(path.to_string_lossy().to_string()));
+        } else {
+            expanded.skipped.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// The leading components of `pattern` without wildcards, which the glob does not walk.
+fn literal_root(pattern: &str) -> PathBuf {
+    Path::new(pattern)
+        .components()
+        .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?', '[']))
+        .collect()
+}
+
+/// Whether a directory between `root` and `path` is a symlink.
+fn under_symlink(path: &Path, root: &Path) -> bool {
+    path.ancestors()
+        .skip(1)
+        .take_while(|dir| *dir != root && !dir.as_os_str().is_empty())
+        .any(|dir| fs::symlink_metadata(dir).is_ok_and(|m| m.file_type().is_symlink()))
+}
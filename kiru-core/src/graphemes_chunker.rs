@@ -0,0 +1,180 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::chunker::{
+    peak_buffered, stream_memory, Chunk, ChunkKind, Chunker, ChunkingError, StringBuffer,
+};
+use crate::BLOCK_SIZE;
+
+struct GraphemesChunkIndices {
+    start: usize,
+    end: usize,
+    next_start: usize,
+}
+
+/// Cuts chunks of `chunk_size` extended grapheme clusters (what a reader sees as one
+/// character, e.g. `e` followed by a combining accent, a flag or a family emoji joined by
+/// ZWJs), each chunk after the first starting on the last `overlap` clusters of the
+/// previous one. Chunks without overlap concatenate to the source. The overlap is whole
+/// clusters, so its length in bytes (the `end` of a chunk minus the `start` of the next)
+/// varies with the clusters it holds.
+///
+/// A cluster is only counted once the character after it is read, so a cluster cut by the
+/// end of a block is never split: the boundary before a character only depends on the
+/// characters up to it.
+#[derive(Clone)]
+pub struct GraphemesChunker {
+    chunk_size: usize,
+    overlap: usize,
+}
+
+impl GraphemesChunker {
+    pub fn new(chunk_size: usize, overlap: usize) -> Result<Self, ChunkingError> {
+        if chunk_size == 0 || overlap >= chunk_size {
+            return Err(ChunkingError::InvalidArguments {
+                chunk_size,
+                overlap,
+            });
+        }
+
+        Ok(Self {
+            chunk_size,
+            overlap,
+        })
+    }
+
+    /// Compute the chunk starting at `start`, or `None` once done or when more text is
+    /// needed (`done` tells whether `buffer` runs to the end of the source).
+    fn next_chunk_indices(
+        &self,
+        buffer: &str,
+        start: usize,
+        done: bool,
+    ) -> Option<GraphemesChunkIndices> {
+        let step = self.chunk_size - self.overlap;
+        let mut next_start = None;
+        let mut graphemes = 0;
+
+        for (i, _) in buffer[start..].grapheme_indices(true) {
+            // A cluster starts at `i`
+            let i = start + i;
+            if graphemes == step {
+                next_start = Some(i);
+            }
+            // It is the first cluster after the chunk
+            if graphemes == self.chunk_size {
+                return Some(GraphemesChunkIndices {
+                    start,
+                    end: i,
+                    next_start: next_start.unwrap_or(i),
+                });
+            }
+            graphemes += 1;
+        }
+
+        // The last cluster may go on in the next block
+        if !done || graphemes == 0 {
+            return None;
+        }
+        Some(GraphemesChunkIndices {
+            start,
+            end: buffer.len(),
+            next_start: buffer.len(),
+        })
+    }
+}
+
+impl Chunker for GraphemesChunker {
+    fn chunk_size(&self) -> Option<usize> {
+        Some(self.chunk_size)
+    }
+
+    /// Extended grapheme clusters of `text`.
+    fn measure(&self, text: &str) -> usize {
+        text.graphemes(true).count()
+    }
+
+    /// Assumes a chunk fits in a block: a longer one is buffered whole.
+    fn estimate_memory(&self, source_len: usize) -> usize {
+        let buffered = peak_buffered(BLOCK_SIZE, source_len);
+
+        stream_memory(BLOCK_SIZE, buffered) + BLOCK_SIZE.min(source_len)
+    }
+
+    fn chunk_string_with_offsets(self, input: String) -> impl Iterator<Item = Chunk> {
+        let mut start = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || {
+            let next = self.next_chunk_indices(&input, start, true)?;
+            start = next.next_start;
+            let chunk = Chunk {
+                text: input[next.start..next.end].to_string(),
+                start: next.start,
+                end: next.end,
+                index,
+                lines: None,
+                chars: None,
+                running_hash: None,
+                links: None,
+                hierarchy: None,
+                source_stamp: None,
+                header_len: 0,
+                prefix_len: 0,
+                suffix_len: 0,
+                kind: ChunkKind::Main,
+            };
+            index += 1;
+            Some(chunk)
+        })
+    }
+
+    fn chunk_stream_with_offsets(
+        self,
+        input: impl Iterator<Item = String>,
+    ) -> impl Iterator<Item = Chunk> {
+        let mut string_buffer = StringBuffer::new(input, BLOCK_SIZE);
+        let mut start = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || loop {
+            let buffer = string_buffer.buffer();
+            let offset = string_buffer.offset;
+            let next = self.next_chunk_indices(buffer, start - offset, string_buffer.done);
+
+            match next {
+                // if the stream is done and no more chunks can be made, return None
+                None if string_buffer.done => return None,
+
+                // if no chunk can be made yet, fill more data and try again
+                None => {
+                    string_buffer.fill();
+                    continue;
+                }
+
+                // otherwise, return the chunk and keep the overlapping clusters in the buffer
+                Some(n) => {
+                    let chunk = Chunk {
+                        text: buffer[n.start..n.end].to_string(),
+                        start: offset + n.start,
+                        end: offset + n.end,
+                        index,
+                        lines: None,
+                        chars: None,
+                        running_hash: None,
+                        links: None,
+                        hierarchy: None,
+                        source_stamp: None,
+                        header_len: 0,
+                        prefix_len: 0,
+                        suffix_len: 0,
+                        kind: ChunkKind::Main,
+                    };
+                    index += 1;
+                    start = offset + n.next_start;
+                    string_buffer.set_position(n.next_start);
+                    return Some(chunk);
+                }
+            };
+        })
+    }
+}
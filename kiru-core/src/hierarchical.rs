@@ -0,0 +1,61 @@
+use crate::chunker::{Chunk, ChunkHierarchy, Chunker, ChunkerWithStrategy, ChunkingError, Source};
+
+/// Chunk `source` into parent chunks of `parent_size` and each parent into child chunks of
+/// `child_size`, both with `overlap`, with the chunkers `strategy` makes for `(size, overlap)`
+/// (e.g. `BytesChunker::new`), e.g. to retrieve small chunks and hand their parent to a model.
+///
+/// The source is read once: each parent is yielded followed by its children, which are cut
+/// from its text. Parents are tagged with level 0 and children with level 1 and the index of
+/// their parent, and children are indexed among the children of the source, from 0. Offsets
+/// of children are within the source, like those of parents.
+pub fn hierarchical<C, F>(
+    source: Source,
+    parent_size: usize,
+    child_size: usize,
+    overlap: usize,
+    strategy: F,
+) -> Result<impl Iterator<Item = Result<Chunk, ChunkingError>>, ChunkingError>
+where
+    C: Chunker,
+    F: Fn(usize, usize) -> Result<C, ChunkingError>,
+{
+    if child_size >= parent_size {
+        return Err(ChunkingError::InvalidChildSize {
+            parent_size,
+            child_size,
+        });
+    }
+    let parents = ChunkerWithStrategy::new(strategy(parent_size, overlap)?);
+    let children = strategy(child_size, overlap)?;
+    let mut child_index = 0;
+
+    Ok(parents
+        .on_source_with_offsets(source)?
+        .flat_map(move |parent| {
+            let mut parent = match parent {
+                Ok(parent) => parent,
+                Err(e) => return vec![Err(e)],
+            };
+            let text = parent.text.clone();
+            parent.hierarchy = Some(ChunkHierarchy {
+                level: 0,
+                parent_index: None,
+            });
+            let parent_index = parent.index;
+            let parent_start = parent.start;
+
+            let mut chunks = vec![Ok(parent)];
+            for mut child in children.clone().chunk_string_with_offsets(text) {
+                child.start += parent_start;
+                child.end += parent_start;
+                child.index = child_index;
+                child.hierarchy = Some(ChunkHierarchy {
+                    level: 1,
+                    parent_index: Some(parent_index),
+                });
+                child_index += 1;
+                chunks.push(Ok(child));
+            }
+            chunks
+        }))
+}
@@ -0,0 +1,291 @@
+use serde::de::IgnoredAny;
+
+use crate::chunker::{
+    peak_buffered, stream_memory, Chunk, ChunkKind, Chunker, ChunkingError, StringBuffer,
+};
+use crate::BLOCK_SIZE;
+
+struct JsonArrayChunkIndices {
+    /// Start and end of every element of the chunk.
+    elements: Vec<(usize, usize)>,
+    next_start: usize,
+}
+
+/// What follows some offset of a JSON array.
+enum ElementScan {
+    /// An element spanning `start..end` (whitespace excluded), the next one to be looked
+    /// for from `next`, past its comma.
+    Element {
+        start: usize,
+        end: usize,
+        next: usize,
+    },
+    /// The closing bracket of the array, or the end of the source.
+    ArrayEnd,
+    /// Not known until more text is read.
+    NeedMore,
+}
+
+/// Scan the element of `buffer` after `from`, skipping the whitespace and commas before it.
+/// `done` tells whether `buffer` runs to the end of the source.
+///
+/// Only strings and brackets are tracked, so that an element ends at the first comma or
+/// closing bracket outside of them, whatever its content.
+fn scan_element(buffer: &str, from: usize, done: bool) -> ElementScan {
+    let bytes = buffer.as_bytes();
+    let Some(start) =
+        (from..bytes.len()).find(|&i| !matches!(bytes[i], b',' | b' ' | b'\t' | b'\n' | b'\r'))
+    else {
+        return if done {
+            ElementScan::ArrayEnd
+        } else {
+            ElementScan::NeedMore
+        };
+    };
+    if bytes[start] == b']' {
+        return ElementScan::ArrayEnd;
+    }
+
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut stop = None;
+    for (i, &b) in bytes.iter().enumerate().skip(start) {
+        if in_string {
+            match b {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match b {
+            b'"' => in_string = true,
+            b'[' | b'{' => depth += 1,
+            b']' | b'}' if depth > 0 => depth -= 1,
+            b']' | b',' if depth == 0 => {
+                stop = Some(i);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    // A value at the end of the buffer may go on in the text still to be read
+    let stop = match stop {
+        Some(stop) => stop,
+        None if done => bytes.len(),
+        None => return ElementScan::NeedMore,
+    };
+    let end = start + buffer[start..stop].trim_end().len();
+    let next = if bytes.get(stop) == Some(&b',') {
+        stop + 1
+    } else {
+        stop
+    };
+    ElementScan::Element { start, end, next }
+}
+
+/// Append `element` to `out` without the whitespace outside of its strings, or as it is if
+/// it is not valid JSON.
+fn push_compact(element: &str, out: &mut String) {
+    if serde_json::from_str::<IgnoredAny>(element).is_err() {
+        out.push_str(element);
+        return;
+    }
+
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in element.chars() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c.is_ascii_whitespace() {
+            continue;
+        }
+        out.push(c);
+    }
+}
+
+/// Groups the elements of a JSON array into chunks of `chunk_size` elements, each chunk
+/// after the first starting with the last `overlap` elements of the previous one.
+///
+/// The array is scanned element by element, so it is never held whole in memory, and
+/// elements may be any JSON values, nested ones included. A chunk is the JSON array of its
+/// elements re-serialized without whitespace (an element that is not valid JSON is kept as
+/// it is), with the offsets of its first element's start and last element's end. Text after
+/// the closing bracket of the array is ignored.
+#[derive(Clone)]
+pub struct JsonArrayChunker {
+    chunk_size: usize,
+    overlap: usize,
+}
+
+impl JsonArrayChunker {
+    pub fn new(chunk_size: usize, overlap: usize) -> Result<Self, ChunkingError> {
+        if overlap >= chunk_size {
+            return Err(ChunkingError::InvalidArguments {
+                chunk_size,
+                overlap,
+            });
+        }
+
+        Ok(Self {
+            chunk_size,
+            overlap,
+        })
+    }
+
+    /// Compute the chunk whose first element is the first one after `start`, or `None` once
+    /// done or when more text is needed (`done` tells whether `buffer` runs to the end of the
+    /// source). `opening` tells whether `start` is the start of the source, before the
+    /// opening bracket of the array.
+    fn next_chunk_indices(
+        &self,
+        buffer: &str,
+        start: usize,
+        opening: bool,
+        done: bool,
+    ) -> Option<JsonArrayChunkIndices> {
+        let mut position = start;
+        if opening {
+            let skipped = buffer.len() - buffer.trim_start().len();
+            match buffer.as_bytes().get(skipped) {
+                Some(b'[') => position = skipped + 1,
+                Some(_) => {}
+                None => return None,
+            }
+        }
+
+        let mut elements = Vec::with_capacity(self.chunk_size);
+        let mut next = position;
+        // One more element is looked for, to tell whether the chunk is the last one
+        let following = loop {
+            match scan_element(buffer, next, done) {
+                ElementScan::Element { start, .. } if elements.len() == self.chunk_size => {
+                    break Some(start)
+                }
+                ElementScan::Element {
+                    start,
+                    end,
+                    next: after,
+                } => {
+                    elements.push((start, end));
+                    next = after;
+                }
+                ElementScan::ArrayEnd => break None,
+                ElementScan::NeedMore => return None,
+            }
+        };
+        if elements.is_empty() {
+            return None;
+        }
+
+        let next_start = match following {
+            // Without overlap, the next chunk starts at the element that did not fit
+            Some(following) => elements
+                .get(self.chunk_size - self.overlap)
+                .map_or(following, |&(start, _)| start),
+            // The last chunk has no chunk after it to overlap with
+            None => next,
+        };
+        Some(JsonArrayChunkIndices {
+            elements,
+            next_start,
+        })
+    }
+
+    /// The chunk of the elements of `n`, `offset` being the absolute offset of `buffer[0]`.
+    fn chunk(buffer: &str, offset: usize, n: &JsonArrayChunkIndices, index: usize) -> Chunk {
+        let mut text = String::from("[");
+        for (i, &(start, end)) in n.elements.iter().enumerate() {
+            if i > 0 {
+                text.push(',');
+            }
+            push_compact(&buffer[start..end], &mut text);
+        }
+        text.push(']');
+
+        Chunk {
+            text,
+            start: offset + n.elements[0].0,
+            end: offset + n.elements[n.elements.len() - 1].1,
+            index,
+            lines: None,
+            chars: None,
+            running_hash: None,
+            links: None,
+            hierarchy: None,
+            source_stamp: None,
+            header_len: 0,
+            prefix_len: 0,
+            suffix_len: 0,
+            kind: ChunkKind::Main,
+        }
+    }
+}
+
+impl Chunker for JsonArrayChunker {
+    /// Assumes a chunk's elements fit in a block: longer ones are buffered whole.
+    fn estimate_memory(&self, source_len: usize) -> usize {
+        let buffered = peak_buffered(BLOCK_SIZE, source_len);
+
+        stream_memory(BLOCK_SIZE, buffered) + BLOCK_SIZE.min(source_len)
+    }
+
+    fn chunk_string_with_offsets(self, input: String) -> impl Iterator<Item = Chunk> {
+        let mut start = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || {
+            let next = self.next_chunk_indices(&input, start, index == 0, true)?;
+            start = next.next_start;
+            let chunk = Self::chunk(&input, 0, &next, index);
+            index += 1;
+            Some(chunk)
+        })
+    }
+
+    fn chunk_stream_with_offsets(
+        self,
+        input: impl Iterator<Item = String>,
+    ) -> impl Iterator<Item = Chunk> {
+        let mut string_buffer = StringBuffer::new(input, BLOCK_SIZE);
+        let mut start = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || loop {
+            let buffer = string_buffer.buffer();
+            let offset = string_buffer.offset;
+            let next =
+                self.next_chunk_indices(buffer, start - offset, index == 0, string_buffer.done);
+
+            match next {
+                // if the stream is done and no more chunks can be made, return None
+                None if string_buffer.done => return None,
+
+                // if no chunk can be made yet, fill more data and try again
+                None => {
+                    string_buffer.fill();
+                    continue;
+                }
+
+                // otherwise, return the chunk and keep the overlap in the buffer
+                Some(n) => {
+                    let chunk = Self::chunk(buffer, offset, &n, index);
+                    index += 1;
+                    start = offset + n.next_start;
+                    string_buffer.set_position(n.next_start);
+                    return Some(chunk);
+                }
+            };
+        })
+    }
+}
@@ -0,0 +1,179 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "gzip")]
+use flate2::write::GzEncoder;
+#[cfg(feature = "gzip")]
+use flate2::Compression;
+
+use serde::{Deserialize, Serialize};
+
+use crate::chunker::{Chunk, ChunkKind, ChunkingError};
+
+/// How much of each chunk [`write_jsonl`] stores.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StoreMode {
+    /// The whole chunk, overlap included.
+    #[default]
+    Full,
+    /// Only the part of the chunk not covered by the previous chunk of its source, with
+    /// `start` moved to where that part starts: the texts of the main chunks of a source
+    /// concatenate to it. Bridge chunks, which only repeat text of the main chunks around
+    /// them, are stored whole.
+    Core,
+    /// The core text of [`Self::Core`], plus the text covered by the previous chunk in an
+    /// `overlap` field, so that the chunk is `overlap` followed by `text`.
+    Both,
+}
+
+/// A chunk as [`write_jsonl`] stores it.
+#[derive(Serialize)]
+struct StoredChunk<'a> {
+    #[serde(flatten)]
+    chunk: &'a Chunk,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    overlap: Option<String>,
+}
+
+/// Write `chunk` to `writer` as one JSON line.
+fn write_jsonl_line(writer: &mut impl Write, chunk: &Chunk) -> Result<(), ChunkingError> {
+    serde_json::to_writer(&mut *writer, chunk).map_err(io::Error::from)?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Write `chunks` as JSON lines into rolling files `shard_0000.jsonl`, `shard_0001.jsonl`, ...
+/// of `dir` (created if missing), each holding up to `shard_size` chunks; the last shard
+/// holds the remainder. Returns the paths written, in order (none for no chunks).
+///
+/// Stops at the first error, which leaves the shards written so far on disk.
+pub fn write_jsonl_sharded(
+    chunks: impl Iterator<Item = Result<Chunk, ChunkingError>>,
+    dir: impl AsRef<Path>,
+    shard_size: usize,
+) -> Result<Vec<PathBuf>, ChunkingError> {
+    if shard_size == 0 {
+        return Err(ChunkingError::InvalidShardSize);
+    }
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+
+    let mut paths = Vec::new();
+    let mut shard: Option<BufWriter<File>> = None;
+    let mut in_shard = 0;
+
+    for chunk in chunks {
+        let chunk = chunk?;
+
+        // Rotate to a new shard once the current one is full
+        if in_shard == shard_size {
+            if let Some(mut full) = shard.take() {
+                full.flush()?;
+            }
+            in_shard = 0;
+        }
+        let writer = match &mut shard {
+            Some(writer) => writer,
+            None => {
+                let path = dir.join(format!("shard_{:04}.jsonl", paths.len()));
+                let writer = shard.insert(BufWriter::new(File::create(&path)?));
+                paths.push(path);
+                writer
+            }
+        };
+
+        write_jsonl_line(writer, &chunk)?;
+        in_shard += 1;
+    }
+
+    if let Some(mut last) = shard {
+        last.flush()?;
+    }
+    Ok(paths)
+}
+
+/// Write `chunks` as JSON lines to a file at `path`, storing as much of each chunk as `mode`
+/// says. Returns the number of chunks written.
+///
+/// The overlap is the source text before the end of the previous chunk of the same source;
+/// a prefix, preamble, repeated header and suffix stay. Stops at the first error, which leaves
+/// an unfinished file at `path`.
+pub fn write_jsonl(
+    chunks: impl Iterator<Item = Result<Chunk, ChunkingError>>,
+    path: impl AsRef<Path>,
+    mode: StoreMode,
+) -> Result<usize, ChunkingError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    let mut previous_end = None;
+
+    let mut written = 0;
+    for chunk in chunks {
+        let mut chunk = chunk?;
+        let main = chunk.kind == ChunkKind::Main;
+        if main && chunk.index == 0 {
+            previous_end = None;
+        }
+        let end = chunk.end;
+        let overlap = match mode {
+            StoreMode::Full => None,
+            // Bridges share the index of the chunk before them, and do not move the overlap
+            _ if !main => None,
+            StoreMode::Core | StoreMode::Both => {
+                // The affixes and a repeated header stay, only source text is overlap
+                let content_start = chunk.prefix_len + chunk.header_len;
+                let covered = chunk.text.len()
+                    - chunk.suffix_len
+                    - content_start
+                    - chunk.unique_text(previous_end).len();
+                chunk.start += covered;
+                Some(
+                    chunk
+                        .text
+                        .drain(content_start..content_start + covered)
+                        .collect(),
+                )
+            }
+        };
+        if main {
+            previous_end = Some(end);
+        }
+
+        let stored = StoredChunk {
+            chunk: &chunk,
+            overlap: overlap.filter(|_| mode == StoreMode::Both),
+        };
+        serde_json::to_writer(&mut writer, &stored).map_err(io::Error::from)?;
+        writer.write_all(b"\n")?;
+        written += 1;
+    }
+    writer.flush()?;
+    Ok(written)
+}
+
+/// Write `chunks` as JSON lines to a gzip file at `path`, compressed at `level` (`0`, no
+/// compression, to `9`, the smallest output) as they are written. Returns the number of
+/// chunks written.
+///
+/// Stops at the first error, which leaves an unfinished file at `path`.
+#[cfg(feature = "gzip")]
+pub fn write_jsonl_gz(
+    chunks: impl Iterator<Item = Result<Chunk, ChunkingError>>,
+    path: impl AsRef<Path>,
+    level: u32,
+) -> Result<usize, ChunkingError> {
+    if level > 9 {
+        return Err(ChunkingError::InvalidCompressionLevel(level));
+    }
+    let file = BufWriter::new(File::create(path)?);
+    let mut writer = GzEncoder::new(file, Compression::new(level));
+
+    let mut written = 0;
+    for chunk in chunks {
+        write_jsonl_line(&mut writer, &chunk?)?;
+        written += 1;
+    }
+    writer.finish()?.flush()?;
+    Ok(written)
+}
@@ -1,11 +1,96 @@
 // mod _chunker;
+#[cfg(feature = "arena")]
+mod arena;
+#[cfg(feature = "arrow")]
+mod arrow;
+mod bidi;
+mod boundaries_chunker;
+mod boundary_cache;
+mod boundary_diff;
 mod bytes_chunker;
 mod characters_chunker;
 mod chunker;
+mod cli;
+mod concat;
+mod config;
+mod dedup;
+mod glob_source;
+mod graphemes_chunker;
+mod hierarchical;
+mod json_array_chunker;
+mod jsonl;
+mod lines_chunker;
+mod lines_packed_chunker;
+mod lossless;
+mod merge;
+mod paragraphs_chunker;
+mod parallel_consume;
+#[cfg(feature = "parquet")]
+mod parquet;
+mod predicate_chunker;
+mod recursive_chunker;
+mod regex_chunker;
+mod ring;
+mod semantic_chunker;
+mod sentences;
+mod sentences_chunker;
+mod shared_chunks;
+mod source_stats;
+mod stats;
+mod strategy_info;
 mod stream;
+#[cfg(feature = "term-counts")]
+mod terms;
+#[cfg(feature = "tokenizers")]
+mod tokens_chunker;
+mod transcript_chunker;
+#[cfg(feature = "transliterate")]
+mod transliterate;
+mod words_chunker;
 // pub use _chunker::*;
 
+#[cfg(feature = "arena")]
+pub use arena::*;
+#[cfg(feature = "arrow")]
+pub use arrow::*;
+pub use boundaries_chunker::*;
+pub use boundary_cache::*;
+pub use boundary_diff::*;
 pub use bytes_chunker::*;
 pub use characters_chunker::*;
 pub use chunker::*;
+pub use cli::*;
+pub use concat::*;
+pub use config::*;
+pub use dedup::*;
+pub use graphemes_chunker::*;
+pub use hierarchical::*;
+pub use json_array_chunker::*;
+pub use jsonl::*;
+pub use lines_chunker::*;
+pub use lines_packed_chunker::*;
+pub use lossless::*;
+pub use paragraphs_chunker::*;
+pub use parallel_consume::*;
+#[cfg(feature = "parquet")]
+pub use parquet::*;
+pub use predicate_chunker::*;
+pub use recursive_chunker::*;
+pub use regex_chunker::*;
+pub use ring::*;
+pub use semantic_chunker::*;
+pub use sentences::SentenceTerminators;
+pub use sentences_chunker::*;
+pub use shared_chunks::*;
+pub use source_stats::*;
+pub use stats::ChunkStats;
+pub use strategy_info::*;
 pub use stream::*;
+#[cfg(feature = "term-counts")]
+pub use terms::*;
+#[cfg(feature = "tokenizers")]
+pub use tokens_chunker::*;
+pub use transcript_chunker::*;
+#[cfg(feature = "transliterate")]
+pub use transliterate::*;
+pub use words_chunker::*;
@@ -0,0 +1,347 @@
+use crate::chunker::{
+    peak_buffered, stream_memory, Chunk, ChunkKind, Chunker, ChunkingError, StringBuffer,
+};
+use crate::BLOCK_SIZE;
+
+/// What ends a line for [`LinesChunker`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n`, so that `\r\n` ends a line too, its `\r` kept in the line.
+    #[default]
+    Lf,
+    /// `\r\n` only: a lone `\n` is part of the line.
+    CrLf,
+}
+
+impl LineEnding {
+    fn terminator(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Blocks of `input` in which a run of blank lines (holding nothing but whitespace before
+/// `terminator`) keeps only its first `keep` lines, blank lines at the start of the text
+/// being dropped, or `input` as is for `None`. The text after the last terminator of a
+/// block is held back until its line is complete.
+pub(crate) fn collapse_blank_lines(
+    input: impl Iterator<Item = String>,
+    terminator: &'static str,
+    keep: Option<usize>,
+) -> impl Iterator<Item = String> {
+    let mut input = input.fuse();
+    let mut pending = String::new();
+    let mut blank_run = keep.unwrap_or(0);
+
+    let mut collapse = move |lines: &str| {
+        let keep = keep.unwrap_or(usize::MAX);
+        let mut collapsed = String::with_capacity(lines.len());
+        for line in lines.split_inclusive(terminator) {
+            if !line.trim().is_empty() {
+                blank_run = 0;
+            } else if blank_run < keep {
+                blank_run += 1;
+            } else {
+                continue;
+            }
+            collapsed.push_str(line);
+        }
+        collapsed
+    };
+
+    std::iter::from_fn(move || loop {
+        if keep.is_none() {
+            return input.next();
+        }
+        let lines = match input.next() {
+            Some(block) => {
+                pending.push_str(&block);
+                let Some(i) = pending.rfind(terminator) else {
+                    continue;
+                };
+                let lines = collapse(&pending[..i + terminator.len()]);
+                pending.drain(..i + terminator.len());
+                lines
+            }
+            None if pending.is_empty() => return None,
+            None => collapse(&std::mem::take(&mut pending)),
+        };
+        if !lines.is_empty() {
+            return Some(lines);
+        }
+    })
+}
+
+struct LinesChunkIndices {
+    start: usize,
+    end: usize,
+    next_start: usize,
+}
+
+/// Cuts chunks of `chunk_size` lines, each chunk after the first starting on the last
+/// `overlap` lines of the previous one.
+///
+/// Lines keep their terminator, so chunks without overlap concatenate to the source. The
+/// last chunk holds the remaining lines, however few, the last of which may have no
+/// terminator. Streamed, a chunk is cut as soon as its last line is read, so chunks of a
+/// pipe are yielded as its lines arrive.
+#[derive(Clone)]
+pub struct LinesChunker {
+    chunk_size: usize,
+    overlap: usize,
+    line_ending: LineEnding,
+    repeat_header: bool,
+    collapse_separators: bool,
+}
+
+impl LinesChunker {
+    pub fn new(chunk_size: usize, overlap: usize) -> Result<Self, ChunkingError> {
+        if chunk_size == 0 || overlap >= chunk_size {
+            return Err(ChunkingError::InvalidArguments {
+                chunk_size,
+                overlap,
+            });
+        }
+
+        Ok(Self {
+            chunk_size,
+            overlap,
+            line_ending: LineEnding::default(),
+            repeat_header: false,
+            collapse_separators: false,
+        })
+    }
+
+    /// End lines at `line_ending` instead of `\n`.
+    pub fn with_line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Take the first line as a header (e.g. the column names of CSV data): the first chunk
+    /// holds it besides its `chunk_size` lines, and chunks cut from a source by
+    /// [`crate::ChunkerWithStrategy`] repeat it at the top of every later chunk. Offsets
+    /// still span the lines of a chunk only.
+    pub fn with_repeat_header(mut self, repeat_header: bool) -> Self {
+        self.repeat_header = repeat_header;
+        self
+    }
+
+    /// Drop blank lines (holding nothing but whitespace before their terminator), so that a
+    /// run of terminators ends a single line and no line is blank. The text is collapsed
+    /// before it is cut: offsets and line numbers are positions in the collapsed text, which
+    /// chunks without overlap concatenate to, not in the source.
+    pub fn with_collapse_separators(mut self, collapse: bool) -> Self {
+        self.collapse_separators = collapse;
+        self
+    }
+
+    /// The strategy cutting the first chunk, which holds the header besides its lines.
+    fn first_chunker(&self) -> Self {
+        Self {
+            chunk_size: self.chunk_size + usize::from(self.repeat_header),
+            ..self.clone()
+        }
+    }
+
+    /// Compute the chunk starting at `start`, or `None` once done or when more text is
+    /// needed (`done` tells whether `buffer` runs to the end of the source).
+    fn next_chunk_indices(
+        &self,
+        buffer: &str,
+        start: usize,
+        done: bool,
+    ) -> Option<LinesChunkIndices> {
+        if start >= buffer.len() {
+            return None;
+        }
+
+        let terminator = self.line_ending.terminator();
+        let step = self.chunk_size - self.overlap;
+        let mut end = start;
+        let mut next_start = None;
+
+        for line in 1..=self.chunk_size {
+            match buffer[end..].find(terminator) {
+                Some(i) => end += i + terminator.len(),
+                None => break,
+            }
+            if line == step {
+                next_start = Some(end);
+            }
+            if line == self.chunk_size {
+                return Some(LinesChunkIndices {
+                    start,
+                    end,
+                    next_start: next_start.unwrap_or(end),
+                });
+            }
+        }
+
+        if !done {
+            return None;
+        }
+        Some(LinesChunkIndices {
+            start,
+            end: buffer.len(),
+            next_start: buffer.len(),
+        })
+    }
+}
+
+impl Chunker for LinesChunker {
+    fn chunk_size(&self) -> Option<usize> {
+        Some(self.chunk_size)
+    }
+
+    /// The first line, when repeating it.
+    fn header_len(&self, first_chunk: &str) -> Option<usize> {
+        if !self.repeat_header {
+            return None;
+        }
+        let first_line = Self {
+            chunk_size: 1,
+            overlap: 0,
+            ..self.clone()
+        };
+        first_line
+            .next_chunk_indices(first_chunk, 0, true)
+            .map(|n| n.end)
+    }
+
+    /// Lines of `text`, a last line without terminator included.
+    fn measure(&self, text: &str) -> usize {
+        let terminator = self.line_ending.terminator();
+        let ended = text.matches(terminator).count();
+        ended + usize::from(!text.is_empty() && !text.ends_with(terminator))
+    }
+
+    /// Assumes a chunk fits in a block: a longer one is buffered whole.
+    fn estimate_memory(&self, source_len: usize) -> usize {
+        let buffered = peak_buffered(BLOCK_SIZE, source_len);
+
+        stream_memory(BLOCK_SIZE, buffered) + BLOCK_SIZE.min(source_len)
+    }
+
+    fn rewrite_blocks(
+        self,
+        blocks: impl Iterator<Item = String>,
+    ) -> (impl Iterator<Item = String>, Self) {
+        let keep = self.collapse_separators.then_some(0);
+        let blocks = collapse_blank_lines(blocks, self.line_ending.terminator(), keep);
+        let chunker = Self {
+            collapse_separators: false,
+            ..self
+        };
+        (blocks, chunker)
+    }
+
+    fn chunk_string_with_offsets(self, input: String) -> impl Iterator<Item = Chunk> {
+        let input = match self.collapse_separators {
+            true => collapse_blank_lines(
+                std::iter::once(input),
+                self.line_ending.terminator(),
+                Some(0),
+            )
+            .collect(),
+            false => input,
+        };
+        let first = self.first_chunker();
+        let mut start = 0;
+        let mut covered = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || {
+            let chunker = if index == 0 { &first } else { &self };
+            let next = chunker.next_chunk_indices(&input, start, true)?;
+            // The overlap left after a chunk ending with the text
+            if index > 0 && next.end <= covered {
+                return None;
+            }
+            start = next.next_start;
+            covered = next.end;
+            let chunk = Chunk {
+                text: input[next.start..next.end].to_string(),
+                start: next.start,
+                end: next.end,
+                index,
+                lines: None,
+                chars: None,
+                running_hash: None,
+                links: None,
+                hierarchy: None,
+                source_stamp: None,
+                header_len: 0,
+                prefix_len: 0,
+                suffix_len: 0,
+                kind: ChunkKind::Main,
+            };
+            index += 1;
+            Some(chunk)
+        })
+    }
+
+    fn chunk_stream_with_offsets(
+        self,
+        input: impl Iterator<Item = String>,
+    ) -> impl Iterator<Item = Chunk> {
+        let first = self.first_chunker();
+        let input = collapse_blank_lines(
+            input,
+            self.line_ending.terminator(),
+            self.collapse_separators.then_some(0),
+        );
+        let mut string_buffer = StringBuffer::new(input, BLOCK_SIZE);
+        let mut start = 0;
+        let mut covered = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || loop {
+            let buffer = string_buffer.buffer();
+            let offset = string_buffer.offset;
+            let chunker = if index == 0 { &first } else { &self };
+            let next = chunker.next_chunk_indices(buffer, start - offset, string_buffer.done);
+
+            match next {
+                // if the stream is done and no more chunks can be made, return None
+                None if string_buffer.done => return None,
+
+                // if no chunk can be made yet, read more data and try again
+                None => {
+                    string_buffer.fill_block();
+                    continue;
+                }
+
+                // the overlap left after a chunk ending with the stream
+                Some(n) if index > 0 && offset + n.end <= covered => return None,
+
+                // otherwise, return the chunk and keep the overlapping lines in the buffer
+                Some(n) => {
+                    let chunk = Chunk {
+                        text: buffer[n.start..n.end].to_string(),
+                        start: offset + n.start,
+                        end: offset + n.end,
+                        index,
+                        lines: None,
+                        chars: None,
+                        running_hash: None,
+                        links: None,
+                        hierarchy: None,
+                        source_stamp: None,
+                        header_len: 0,
+                        prefix_len: 0,
+                        suffix_len: 0,
+                        kind: ChunkKind::Main,
+                    };
+                    index += 1;
+                    start = offset + n.next_start;
+                    covered = offset + n.end;
+                    string_buffer.set_position(n.next_start);
+                    return Some(chunk);
+                }
+            };
+        })
+    }
+}
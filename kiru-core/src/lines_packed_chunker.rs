@@ -0,0 +1,215 @@
+use crate::chunker::{
+    peak_buffered, stream_memory, Chunk, ChunkKind, Chunker, ChunkingError, StringBuffer,
+};
+
+struct LinesChunkIndices {
+    start: usize,
+    end: usize,
+    next_start: usize,
+}
+
+/// Where the line starting at some offset ends, relative to a limit.
+enum LineEnd {
+    /// Offset just past its newline (or the end of the source), at most the limit.
+    Within(usize),
+    /// Past the limit.
+    Beyond,
+    /// Not known until more text is read.
+    NeedMore,
+}
+
+/// End of the line of `buffer` starting at `from`, if it is at most `limit`. `done` tells
+/// whether `buffer` runs to the end of the source.
+fn line_end_within(buffer: &str, from: usize, limit: usize, done: bool) -> LineEnd {
+    let search_end = limit.min(buffer.len());
+    match buffer.as_bytes()[from..search_end]
+        .iter()
+        .position(|&b| b == b'\n')
+    {
+        Some(i) => LineEnd::Within(from + i + 1),
+        None if done && buffer.len() <= limit => LineEnd::Within(buffer.len()),
+        None if buffer.len() > limit => LineEnd::Beyond,
+        None => LineEnd::NeedMore,
+    }
+}
+
+/// Packs whole lines into chunks of at most `target_bytes` bytes.
+///
+/// Lines (including their newline) are accumulated greedily until the next one would not
+/// fit; a single line longer than `target_bytes` becomes a chunk of its own. Each chunk
+/// after the first starts with the last `overlap_lines` lines of the previous one, fewer
+/// when the line that follows them would not fit otherwise.
+#[derive(Clone)]
+pub struct LinesPackedChunker {
+    target_bytes: usize,
+    overlap_lines: usize,
+}
+
+impl LinesPackedChunker {
+    pub fn new(target_bytes: usize, overlap_lines: usize) -> Result<Self, ChunkingError> {
+        if target_bytes == 0 {
+            return Err(ChunkingError::InvalidArguments {
+                chunk_size: target_bytes,
+                overlap: overlap_lines,
+            });
+        }
+
+        Ok(Self {
+            target_bytes,
+            overlap_lines,
+        })
+    }
+
+    /// Compute the chunk starting at `start`, or `None` once done or when more text is
+    /// needed (`done` tells whether `buffer` runs to the end of the source).
+    fn next_chunk_indices(
+        &self,
+        buffer: &str,
+        start: usize,
+        done: bool,
+    ) -> Option<LinesChunkIndices> {
+        if start >= buffer.len() {
+            return None;
+        }
+
+        // The first line goes in whatever its length
+        let LineEnd::Within(mut end) = line_end_within(buffer, start, usize::MAX, done) else {
+            return None;
+        };
+        let mut line_starts = vec![start];
+
+        // Then whole lines while they fit
+        let limit = start + self.target_bytes;
+        while end < limit && !(done && end == buffer.len()) {
+            match line_end_within(buffer, end, limit, done) {
+                LineEnd::Within(line_end) => {
+                    line_starts.push(end);
+                    end = line_end;
+                }
+                LineEnd::Beyond => break,
+                LineEnd::NeedMore => return None,
+            }
+        }
+
+        // Last chunk
+        if done && end == buffer.len() {
+            return Some(LinesChunkIndices {
+                start,
+                end,
+                next_start: end,
+            });
+        }
+
+        // Carry the last lines over, as many as leave room for the line after this chunk
+        let carried = self.overlap_lines.min(line_starts.len() - 1);
+        let mut next_start = end;
+        for &line_start in &line_starts[line_starts.len() - carried..] {
+            match line_end_within(buffer, end, line_start + self.target_bytes, done) {
+                LineEnd::Within(_) => {
+                    next_start = line_start;
+                    break;
+                }
+                LineEnd::Beyond => continue,
+                LineEnd::NeedMore => return None,
+            }
+        }
+
+        Some(LinesChunkIndices {
+            start,
+            end,
+            next_start,
+        })
+    }
+}
+
+impl Chunker for LinesPackedChunker {
+    fn chunk_size(&self) -> Option<usize> {
+        Some(self.target_bytes)
+    }
+
+    /// Assumes lines shorter than `target_bytes`: an oversized line is buffered whole.
+    fn estimate_memory(&self, source_len: usize) -> usize {
+        let min_buffer_size = self.target_bytes * 5;
+        let buffered = peak_buffered(min_buffer_size, source_len);
+
+        stream_memory(min_buffer_size, buffered) + self.target_bytes.min(source_len)
+    }
+
+    fn chunk_string_with_offsets(self, input: String) -> impl Iterator<Item = Chunk> {
+        let mut start = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || {
+            let next = self.next_chunk_indices(&input, start, true)?;
+            start = next.next_start;
+            let chunk = Chunk {
+                text: input[next.start..next.end].to_string(),
+                start: next.start,
+                end: next.end,
+                index,
+                lines: None,
+                chars: None,
+                running_hash: None,
+                links: None,
+                hierarchy: None,
+                source_stamp: None,
+                header_len: 0,
+                prefix_len: 0,
+                suffix_len: 0,
+                kind: ChunkKind::Main,
+            };
+            index += 1;
+            Some(chunk)
+        })
+    }
+
+    fn chunk_stream_with_offsets(
+        self,
+        input: impl Iterator<Item = String>,
+    ) -> impl Iterator<Item = Chunk> {
+        let mut string_buffer = StringBuffer::new(input, self.target_bytes * 5);
+        let mut start = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || loop {
+            let buffer = string_buffer.buffer();
+            let offset = string_buffer.offset;
+            let next = self.next_chunk_indices(buffer, start - offset, string_buffer.done);
+
+            match next {
+                // if the stream is done and no more chunks can be made, return None
+                None if string_buffer.done => return None,
+
+                // if no chunk can be made yet, fill more data and try again
+                None => {
+                    string_buffer.fill();
+                    continue;
+                }
+
+                // otherwise, return the chunk and keep the carried lines in the buffer
+                Some(n) => {
+                    let chunk = Chunk {
+                        text: buffer[n.start..n.end].to_string(),
+                        start: offset + n.start,
+                        end: offset + n.end,
+                        index,
+                        lines: None,
+                        chars: None,
+                        running_hash: None,
+                        links: None,
+                        hierarchy: None,
+                        source_stamp: None,
+                        header_len: 0,
+                        prefix_len: 0,
+                        suffix_len: 0,
+                        kind: ChunkKind::Main,
+                    };
+                    index += 1;
+                    start = offset + n.next_start;
+                    string_buffer.set_position(n.next_start);
+                    return Some(chunk);
+                }
+            };
+        })
+    }
+}
@@ -0,0 +1,88 @@
+use crate::chunker::{floor_char_boundary, Chunker, ChunkerWithStrategy, ChunkingError, Source};
+use crate::stream::{SourceReader, StreamType};
+
+/// Bytes of text reported on each side of a mismatch.
+const SNIPPET_LEN: usize = 32;
+
+/// Where the concatenated chunks of a source first differ from it, see [`verify_lossless`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LosslessMismatch {
+    /// Byte offset in the source of the first character that differs.
+    pub offset: usize,
+    /// Up to 32 bytes of the source from `offset` (empty past its end).
+    pub expected: String,
+    /// Up to 32 bytes of the concatenated chunks from `offset` (empty past their end).
+    pub found: String,
+}
+
+impl LosslessMismatch {
+    fn new(offset: usize, expected: &str, found: &str) -> Self {
+        Self {
+            offset,
+            expected: snippet(expected),
+            found: snippet(found),
+        }
+    }
+}
+
+fn snippet(text: &str) -> String {
+    text[..floor_char_boundary(text, SNIPPET_LEN.min(text.len()))].to_string()
+}
+
+/// Chunk `source` without overlap, with the chunker `strategy` makes for `(chunk_size, 0)`
+/// (e.g. `BytesChunker::new`), and check that the chunks concatenate to the source. Returns
+/// `None` if they do, i.e. the chunking is lossless, and else where they first differ.
+///
+/// The chunks are compared as they are cut with a second read of the source, so neither
+/// is held whole from a file or URL source; a URL is fetched twice.
+pub fn verify_lossless<C, F>(
+    source: Source,
+    chunk_size: usize,
+    strategy: F,
+) -> Result<Option<LosslessMismatch>, ChunkingError>
+where
+    C: Chunker,
+    F: FnOnce(usize, usize) -> Result<C, ChunkingError>,
+{
+    let chunker = ChunkerWithStrategy::new(strategy(chunk_size, 0)?);
+    let mut reference = StreamType::from_source(&source)?;
+    // Source text not compared yet, starting at `offset`
+    let mut pending = String::new();
+    let mut offset = 0;
+
+    for chunk in chunker.on_source_with_offsets(source)? {
+        let chunk = chunk?;
+        let mut text = chunk.text.as_str();
+
+        while !text.is_empty() {
+            if pending.is_empty() {
+                match reference.next_block()? {
+                    Some(block) => pending = block,
+                    None => return Ok(Some(LosslessMismatch::new(offset, "", text))),
+                }
+            }
+
+            // Both are whole chars up to the shorter one's end, so `n` is a char boundary
+            // of the other one too as long as they match
+            let n = text.len().min(pending.len());
+            let (expected, found) = (&pending.as_bytes()[..n], &text.as_bytes()[..n]);
+            if let Some(diff) = expected.iter().zip(found).position(|(a, b)| a != b) {
+                let diff = floor_char_boundary(&pending, diff);
+                return Ok(Some(LosslessMismatch::new(
+                    offset + diff,
+                    &pending[diff..],
+                    &text[diff..],
+                )));
+            }
+            pending.drain(..n);
+            text = &text[n..];
+            offset += n;
+        }
+    }
+
+    // The chunks may end before the source does
+    if pending.is_empty() {
+        pending = reference.next_block()?.unwrap_or_default();
+    }
+    Ok((!pending.is_empty()).then(|| LosslessMismatch::new(offset, &pending, "")))
+}
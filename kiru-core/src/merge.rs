@@ -0,0 +1,419 @@
+use std::collections::VecDeque;
+
+use crate::chunker::{
+    ceil_char_boundary, floor_char_boundary, Chunk, ChunkKind, ChunkLinks, Chunker, LastChunk,
+};
+
+/// Merges runs of consecutive chunks of one source that are each smaller than `min_size`
+/// into single chunks of at most `max_size`, sizes being measured with [`Chunker::measure`].
+///
+/// A merged chunk spans from the start of the first chunk of its run to the end of the last
+/// one, so the overlap with the chunks around it is unchanged. Chunks are renumbered in
+/// order. Only the run being merged is held back, so memory stays bounded by `max_size`.
+pub(crate) struct SmallChunkMerger<I, C> {
+    chunks: I,
+    chunker: C,
+    /// Smallest size a chunk keeps on its own, and largest size of a merged chunk.
+    limits: Option<(usize, usize)>,
+    run: Option<Chunk>,
+    ready: VecDeque<Chunk>,
+    index: usize,
+}
+
+impl<I: Iterator<Item = Chunk>, C: Chunker> SmallChunkMerger<I, C> {
+    /// Merge the small chunks of `chunks`, or pass them through when `limits` is `None`.
+    pub(crate) fn new(chunks: I, chunker: C, limits: Option<(usize, usize)>) -> Self {
+        Self {
+            chunks,
+            chunker,
+            limits,
+            run: None,
+            ready: VecDeque::new(),
+            index: 0,
+        }
+    }
+
+    /// Append `chunk` to `run`, if the result stays within `max_size`.
+    fn merged(&self, run: &Chunk, chunk: &Chunk, max_size: usize) -> Option<Chunk> {
+        let text = format!("{}{}", run.text, chunk.unique_text(Some(run.end)));
+        if self.chunker.measure(&text) > max_size {
+            return None;
+        }
+
+        Some(Chunk {
+            text,
+            start: run.start,
+            end: chunk.end,
+            index: run.index,
+            lines: None,
+            chars: None,
+            running_hash: None,
+            links: None,
+            hierarchy: None,
+            source_stamp: run.source_stamp,
+            header_len: 0,
+            prefix_len: 0,
+            suffix_len: 0,
+            kind: ChunkKind::Main,
+        })
+    }
+
+    fn renumber(&mut self, mut chunk: Chunk) -> Chunk {
+        chunk.index = self.index;
+        self.index += 1;
+        chunk
+    }
+}
+
+impl<I: Iterator<Item = Chunk>, C: Chunker> Iterator for SmallChunkMerger<I, C> {
+    type Item = Chunk;
+
+    fn next(&mut self) -> Option<Chunk> {
+        let Some((min_size, max_size)) = self.limits else {
+            return self.chunks.next();
+        };
+
+        loop {
+            if let Some(chunk) = self.ready.pop_front() {
+                return Some(self.renumber(chunk));
+            }
+
+            let Some(chunk) = self.chunks.next() else {
+                let run = self.run.take()?;
+                return Some(self.renumber(run));
+            };
+
+            if self.chunker.measure(&chunk.text) >= min_size {
+                self.ready.extend(self.run.take());
+                self.ready.push_back(chunk);
+                continue;
+            }
+
+            match self.run.take() {
+                Some(run) => match self.merged(&run, &chunk, max_size) {
+                    Some(merged) => self.run = Some(merged),
+                    None => {
+                        self.ready.push_back(run);
+                        self.run = Some(chunk);
+                    }
+                },
+                None => self.run = Some(chunk),
+            }
+        }
+    }
+}
+
+/// Applies a [`LastChunk`] policy to the last chunk of one source, comparing it with the
+/// chunk before it. The last two chunks are held back.
+pub(crate) struct RedundantLastChunk<I> {
+    chunks: I,
+    policy: LastChunk,
+    pending: VecDeque<Chunk>,
+    done: bool,
+}
+
+impl<I: Iterator<Item = Chunk>> RedundantLastChunk<I> {
+    /// Apply `policy` to the last of `chunks`, or pass them through for [`LastChunk::Keep`].
+    pub(crate) fn new(chunks: I, policy: LastChunk) -> Self {
+        Self {
+            chunks,
+            policy,
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Drop or merge `last` into `previous` as the policy says, returning it if kept.
+    fn resolve(&self, previous: &mut Chunk, last: Chunk) -> Option<Chunk> {
+        if last.index != previous.index + 1 || last.start < previous.start {
+            return Some(last);
+        }
+        let repeated = previous.end.saturating_sub(last.start);
+        let new = last.end.saturating_sub(previous.end);
+        match self.policy {
+            LastChunk::DropIfRedundant if new == 0 => None,
+            LastChunk::Merge if repeated > 0 && new < repeated => {
+                if new > 0 {
+                    previous.text.push_str(last.unique_text(Some(previous.end)));
+                    previous.end = last.end;
+                }
+                None
+            }
+            _ => Some(last),
+        }
+    }
+}
+
+impl<I: Iterator<Item = Chunk>> Iterator for RedundantLastChunk<I> {
+    type Item = Chunk;
+
+    fn next(&mut self) -> Option<Chunk> {
+        if self.policy == LastChunk::Keep {
+            return self.chunks.next();
+        }
+
+        while !self.done && self.pending.len() < 3 {
+            match self.chunks.next() {
+                Some(chunk) => self.pending.push_back(chunk),
+                None => {
+                    self.done = true;
+                    if let (Some(last), Some(mut previous)) =
+                        (self.pending.pop_back(), self.pending.pop_back())
+                    {
+                        let last = self.resolve(&mut previous, last);
+                        self.pending.push_back(previous);
+                        self.pending.extend(last);
+                    }
+                }
+            }
+        }
+        self.pending.pop_front()
+    }
+}
+
+/// Appends the whitespace a chunk of one source starts its new text with, i.e. the text
+/// past the end of the previous chunk, to that previous chunk. A chunk whose new text is
+/// whitespace only is then merged into the previous chunk entirely, and a chunk that does not
+/// overlap the previous one starts after the whitespace instead of with it, but never past
+/// the start of the chunk after it, so that starts never decrease.
+///
+/// Only chunks that overlap or touch the previous one are attached to it. Chunks keep their
+/// index, less the number of chunks merged before them. Only the previous chunk is held
+/// back.
+pub(crate) struct WhitespaceAttacher<I> {
+    chunks: I,
+    enabled: bool,
+    previous: Option<Chunk>,
+    /// Bytes of whitespace the previous chunk starts with that the chunk before it took,
+    /// cut once the start of the next chunk is known.
+    attached: usize,
+    merged: usize,
+}
+
+impl<I: Iterator<Item = Chunk>> WhitespaceAttacher<I> {
+    /// Attach the whitespace of `chunks`, or pass them through unless `enabled`.
+    pub(crate) fn new(chunks: I, enabled: bool) -> Self {
+        Self {
+            chunks,
+            enabled,
+            previous: None,
+            attached: 0,
+            merged: 0,
+        }
+    }
+
+    /// Emit the previous chunk, started after its attached whitespace up to `next_start`,
+    /// and hold `next` back instead.
+    fn replace_previous(&mut self, next: Option<Chunk>, next_start: usize) -> Option<Chunk> {
+        let mut chunk = std::mem::replace(&mut self.previous, next)?;
+        let cut = self
+            .attached
+            .min(next_start.saturating_sub(chunk.start))
+            .min(chunk.text.len());
+        chunk.text.drain(..cut);
+        chunk.start += cut;
+        self.attached = 0;
+        Some(chunk)
+    }
+}
+
+impl<I: Iterator<Item = Chunk>> Iterator for WhitespaceAttacher<I> {
+    type Item = Chunk;
+
+    fn next(&mut self) -> Option<Chunk> {
+        if !self.enabled {
+            return self.chunks.next();
+        }
+
+        loop {
+            let Some(mut chunk) = self.chunks.next() else {
+                return self.replace_previous(None, usize::MAX);
+            };
+            chunk.index -= self.merged;
+            let Some(previous) = &mut self.previous else {
+                self.previous = Some(chunk);
+                continue;
+            };
+            if chunk.start > previous.end {
+                let start = chunk.start;
+                return self.replace_previous(Some(chunk), start);
+            }
+
+            let new_text = chunk.unique_text(Some(previous.end));
+            let whitespace = new_text.len() - new_text.trim_start().len();
+            if whitespace == new_text.len() && whitespace > 0 {
+                previous.text.push_str(new_text);
+                previous.end = chunk.end;
+                self.merged += 1;
+                continue;
+            }
+            previous.text.push_str(&new_text[..whitespace]);
+            previous.end += whitespace;
+            let attached = if chunk.start == previous.end - whitespace {
+                whitespace
+            } else {
+                0
+            };
+            let start = chunk.start;
+            let emitted = self.replace_previous(Some(chunk), start);
+            self.attached = attached;
+            return emitted;
+        }
+    }
+}
+
+/// Yields a bridge chunk between every two consecutive chunks of one source that overlap or
+/// touch: it spans their overlap, or the point where they meet, extended by up to `margin`
+/// bytes on each side without leaving the two chunks, so text around their boundary is
+/// found in one piece. Bridges have [`ChunkKind::Bridge`] and the index of the chunk before
+/// them. Only the previous chunk is held back.
+pub(crate) struct BridgeInterleaver<I> {
+    chunks: I,
+    margin: Option<usize>,
+    previous: Option<Chunk>,
+    ready: VecDeque<Chunk>,
+}
+
+impl<I: Iterator<Item = Chunk>> BridgeInterleaver<I> {
+    /// Add bridges of `margin` bytes between `chunks`, or pass them through if `None`.
+    pub(crate) fn new(chunks: I, margin: Option<usize>) -> Self {
+        Self {
+            chunks,
+            margin,
+            previous: None,
+            ready: VecDeque::new(),
+        }
+    }
+}
+
+impl<I: Iterator<Item = Chunk>> Iterator for BridgeInterleaver<I> {
+    type Item = Chunk;
+
+    fn next(&mut self) -> Option<Chunk> {
+        let Some(margin) = self.margin else {
+            return self.chunks.next();
+        };
+
+        loop {
+            if let Some(chunk) = self.ready.pop_front() {
+                return Some(chunk);
+            }
+            let Some(chunk) = self.chunks.next() else {
+                return self.previous.take();
+            };
+            if let Some(previous) = self.previous.take() {
+                let bridge = bridge(&previous, &chunk, margin);
+                self.ready.push_back(previous);
+                self.ready.extend(bridge);
+            }
+            self.previous = Some(chunk);
+        }
+    }
+}
+
+/// Sets the [`ChunkLinks`] of the chunks of one source. A main chunk is held back, along
+/// with the bridges after it, until the next main chunk is cut.
+pub(crate) struct ChunkLinker<I> {
+    chunks: I,
+    enabled: bool,
+    /// The main chunk waiting for the next one, then the bridges after it.
+    held: VecDeque<Chunk>,
+    ready: VecDeque<Chunk>,
+    prev_index: Option<usize>,
+}
+
+impl<I: Iterator<Item = Chunk>> ChunkLinker<I> {
+    /// Link `chunks`, or pass them through unless `enabled`.
+    pub(crate) fn new(chunks: I, enabled: bool) -> Self {
+        Self {
+            chunks,
+            enabled,
+            held: VecDeque::new(),
+            ready: VecDeque::new(),
+            prev_index: None,
+        }
+    }
+
+    /// Link the held chunks to the main chunk `next_index` after them, and release them.
+    fn release(&mut self, next_index: Option<usize>) {
+        let Some(mut main) = self.held.pop_front() else {
+            return;
+        };
+        main.links = Some(ChunkLinks {
+            prev_index: self.prev_index,
+            next_index,
+        });
+        self.prev_index = Some(main.index);
+        let main_index = main.index;
+        self.ready.push_back(main);
+        for mut bridge in self.held.drain(..) {
+            bridge.links = Some(ChunkLinks {
+                prev_index: Some(main_index),
+                next_index,
+            });
+            self.ready.push_back(bridge);
+        }
+    }
+}
+
+impl<I: Iterator<Item = Chunk>> Iterator for ChunkLinker<I> {
+    type Item = Chunk;
+
+    fn next(&mut self) -> Option<Chunk> {
+        if !self.enabled {
+            return self.chunks.next();
+        }
+
+        loop {
+            if let Some(chunk) = self.ready.pop_front() {
+                return Some(chunk);
+            }
+            match self.chunks.next() {
+                Some(chunk) if chunk.kind == ChunkKind::Bridge && !self.held.is_empty() => {
+                    self.held.push_back(chunk);
+                }
+                Some(chunk) => {
+                    self.release(Some(chunk.index));
+                    self.held.push_back(chunk);
+                }
+                None if self.held.is_empty() => return None,
+                None => self.release(None),
+            }
+        }
+    }
+}
+
+/// Bridge chunk around the boundary between `previous` and `next`, if they overlap or touch
+/// and it is not empty.
+fn bridge(previous: &Chunk, next: &Chunk, margin: usize) -> Option<Chunk> {
+    if next.start > previous.end || next.start < previous.start || next.end < previous.end {
+        return None;
+    }
+
+    let start = next.start.saturating_sub(margin).max(previous.start) - previous.start;
+    let start = previous.start + ceil_char_boundary(&previous.text, start);
+    let end = (previous.end + margin).min(next.end) - next.start;
+    let end = next.start + floor_char_boundary(&next.text, end);
+    if start == end {
+        return None;
+    }
+
+    let mut text = previous.text[start - previous.start..].to_string();
+    text.push_str(&next.text[previous.end - next.start..end - next.start]);
+    Some(Chunk {
+        text,
+        start,
+        end,
+        index: previous.index,
+        lines: None,
+        chars: None,
+        running_hash: None,
+        links: None,
+        hierarchy: None,
+        source_stamp: previous.source_stamp,
+        header_len: 0,
+        prefix_len: 0,
+        suffix_len: 0,
+        kind: ChunkKind::Bridge,
+    })
+}
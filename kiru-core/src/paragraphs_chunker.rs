@@ -0,0 +1,246 @@
+use crate::chunker::{
+    peak_buffered, stream_memory, Chunk, ChunkKind, Chunker, ChunkingError, StringBuffer,
+};
+use crate::lines_chunker::collapse_blank_lines;
+use crate::BLOCK_SIZE;
+
+struct ParagraphsChunkIndices {
+    start: usize,
+    end: usize,
+    next_start: usize,
+}
+
+/// Cuts chunks of `chunk_size` paragraphs, each chunk after the first starting on the last
+/// `overlap` paragraphs of the previous one. Paragraphs are separated by one or more blank
+/// lines, i.e. by whitespace holding two or more newlines, so `\r\n` line endings and lines
+/// of spaces separate them too.
+///
+/// A paragraph starts at the start of its first line, and a chunk runs up to the first
+/// paragraph of the next chunk that does not overlap it, keeping the separators between and
+/// after its paragraphs as they are. Only the blank lines before the first paragraph and
+/// after the last one are left out, so the first chunk does not start at 0 when the source
+/// starts with blank lines, and line numbers are then counted from its first paragraph.
+#[derive(Clone)]
+pub struct ParagraphsChunker {
+    chunk_size: usize,
+    overlap: usize,
+    collapse_separators: bool,
+}
+
+impl ParagraphsChunker {
+    pub fn new(chunk_size: usize, overlap: usize) -> Result<Self, ChunkingError> {
+        if chunk_size == 0 || overlap >= chunk_size {
+            return Err(ChunkingError::InvalidArguments {
+                chunk_size,
+                overlap,
+            });
+        }
+
+        Ok(Self {
+            chunk_size,
+            overlap,
+            collapse_separators: false,
+        })
+    }
+
+    /// Collapse each run of blank lines into its first one, so that paragraphs are separated
+    /// by a single blank line. The text is collapsed before it is cut: offsets and line
+    /// numbers are positions in the collapsed text, not in the source.
+    pub fn with_collapse_separators(mut self, collapse: bool) -> Self {
+        self.collapse_separators = collapse;
+        self
+    }
+
+    /// Compute the chunk of the paragraphs from `start`, or `None` once done or when more
+    /// text is needed (`done` tells whether `buffer` runs to the end of the source).
+    fn next_chunk_indices(
+        &self,
+        buffer: &str,
+        start: usize,
+        done: bool,
+    ) -> Option<ParagraphsChunkIndices> {
+        let step = self.chunk_size - self.overlap;
+        let mut paragraphs = 0;
+        let mut chunk_start = start;
+        let mut next_start = None;
+        // Newlines since the last non-whitespace character, as if a paragraph had just ended
+        let mut newlines = 2;
+        let mut line_start = start;
+        // End of the line of the last non-whitespace character, once its newline is read
+        let mut line_end = None;
+
+        for (i, c) in buffer[start..].char_indices() {
+            let i = start + i;
+            if c == '\n' {
+                newlines += 1;
+                line_start = i + 1;
+                line_end = line_end.or(Some(i + 1));
+                continue;
+            }
+            if c.is_whitespace() {
+                continue;
+            }
+
+            if newlines >= 2 {
+                // A paragraph starts with the line at `line_start`
+                if paragraphs == 0 {
+                    chunk_start = line_start;
+                }
+                if paragraphs == step {
+                    next_start = Some(line_start);
+                }
+                // It is the first paragraph after the chunk
+                if paragraphs == self.chunk_size {
+                    return Some(ParagraphsChunkIndices {
+                        start: chunk_start,
+                        end: line_start,
+                        next_start: next_start.unwrap_or(line_start),
+                    });
+                }
+                paragraphs += 1;
+            }
+            newlines = 0;
+            line_end = None;
+        }
+
+        // Whether a paragraph follows, or the last one goes on, is not known before the end
+        if !done || paragraphs == 0 {
+            return None;
+        }
+        Some(ParagraphsChunkIndices {
+            start: chunk_start,
+            end: line_end.unwrap_or(buffer.len()),
+            next_start: buffer.len(),
+        })
+    }
+}
+
+/// Paragraphs of `text`, as [`ParagraphsChunker`] separates them.
+fn count_paragraphs(text: &str) -> usize {
+    let mut paragraphs = 0;
+    let mut newlines = 2;
+    for c in text.chars() {
+        if c == '\n' {
+            newlines += 1;
+        } else if !c.is_whitespace() {
+            if newlines >= 2 {
+                paragraphs += 1;
+            }
+            newlines = 0;
+        }
+    }
+    paragraphs
+}
+
+impl Chunker for ParagraphsChunker {
+    fn chunk_size(&self) -> Option<usize> {
+        Some(self.chunk_size)
+    }
+
+    /// Paragraphs of `text`.
+    fn measure(&self, text: &str) -> usize {
+        count_paragraphs(text)
+    }
+
+    /// Assumes a chunk fits in a block: a longer one is buffered whole.
+    fn estimate_memory(&self, source_len: usize) -> usize {
+        let buffered = peak_buffered(BLOCK_SIZE, source_len);
+
+        stream_memory(BLOCK_SIZE, buffered) + BLOCK_SIZE.min(source_len)
+    }
+
+    fn rewrite_blocks(
+        self,
+        blocks: impl Iterator<Item = String>,
+    ) -> (impl Iterator<Item = String>, Self) {
+        let blocks = collapse_blank_lines(blocks, "\n", self.collapse_separators.then_some(1));
+        let chunker = Self {
+            collapse_separators: false,
+            ..self
+        };
+        (blocks, chunker)
+    }
+
+    fn chunk_string_with_offsets(self, input: String) -> impl Iterator<Item = Chunk> {
+        let input = match self.collapse_separators {
+            true => collapse_blank_lines(std::iter::once(input), "\n", Some(1)).collect(),
+            false => input,
+        };
+        let mut start = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || {
+            let next = self.next_chunk_indices(&input, start, true)?;
+            start = next.next_start;
+            let chunk = Chunk {
+                text: input[next.start..next.end].to_string(),
+                start: next.start,
+                end: next.end,
+                index,
+                lines: None,
+                chars: None,
+                running_hash: None,
+                links: None,
+                hierarchy: None,
+                source_stamp: None,
+                header_len: 0,
+                prefix_len: 0,
+                suffix_len: 0,
+                kind: ChunkKind::Main,
+            };
+            index += 1;
+            Some(chunk)
+        })
+    }
+
+    fn chunk_stream_with_offsets(
+        self,
+        input: impl Iterator<Item = String>,
+    ) -> impl Iterator<Item = Chunk> {
+        let input = collapse_blank_lines(input, "\n", self.collapse_separators.then_some(1));
+        let mut string_buffer = StringBuffer::new(input, BLOCK_SIZE);
+        let mut start = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || loop {
+            let buffer = string_buffer.buffer();
+            let offset = string_buffer.offset;
+            let next = self.next_chunk_indices(buffer, start - offset, string_buffer.done);
+
+            match next {
+                // if the stream is done and no more chunks can be made, return None
+                None if string_buffer.done => return None,
+
+                // if no chunk can be made yet, fill more data and try again
+                None => {
+                    string_buffer.fill();
+                    continue;
+                }
+
+                // otherwise, return the chunk and keep the overlapping paragraphs in the buffer
+                Some(n) => {
+                    let chunk = Chunk {
+                        text: buffer[n.start..n.end].to_string(),
+                        start: offset + n.start,
+                        end: offset + n.end,
+                        index,
+                        lines: None,
+                        chars: None,
+                        running_hash: None,
+                        links: None,
+                        hierarchy: None,
+                        source_stamp: None,
+                        header_len: 0,
+                        prefix_len: 0,
+                        suffix_len: 0,
+                        kind: ChunkKind::Main,
+                    };
+                    index += 1;
+                    start = offset + n.next_start;
+                    string_buffer.set_position(n.next_start);
+                    return Some(chunk);
+                }
+            };
+        })
+    }
+}
@@ -0,0 +1,72 @@
+use std::thread;
+
+use crossbeam_channel::{bounded, unbounded};
+
+use crate::chunker::{Chunk, Chunker, ChunkerWithStrategy, ChunkingError, Source};
+
+/// Chunk `source` on the calling thread and hand the chunks over to `num_workers` threads
+/// running `worker_fn`, e.g. to embed them. Returns what `worker_fn` returned for every
+/// chunk: in chunk order if `ordered`, else in the order the workers finished.
+///
+/// Chunks go through a channel bounded to `channel_size` chunks (`0` hands each chunk over
+/// directly), so chunking waits for the workers when they fall behind instead of piling
+/// chunks up. Every chunk is processed exactly once, by whichever worker is free first.
+///
+/// A chunking error stops the chunking: the chunks cut before it are still processed, then
+/// the error is returned. A panic in `worker_fn` is propagated once the other workers are
+/// done.
+pub fn chunk_parallel_consume<C, F, R>(
+    source: Source,
+    chunker: &ChunkerWithStrategy<C>,
+    num_workers: usize,
+    channel_size: usize,
+    ordered: bool,
+    worker_fn: F,
+) -> Result<Vec<R>, ChunkingError>
+where
+    C: Chunker,
+    F: Fn(Chunk) -> R + Sync,
+    R: Send,
+{
+    if num_workers == 0 {
+        return Err(ChunkingError::InvalidWorkerCount);
+    }
+    let chunks = chunker.on_source_with_offsets(source)?;
+
+    let (sender, receiver) = bounded::<(usize, Chunk)>(channel_size);
+    let (result_sender, result_receiver) = unbounded();
+    let worker_fn = &worker_fn;
+
+    let produced = thread::scope(|scope| {
+        for _ in 0..num_workers {
+            let receiver = receiver.clone();
+            let result_sender = result_sender.clone();
+            scope.spawn(move || {
+                for (position, chunk) in receiver {
+                    // The results are only read once every worker is done
+                    let _ = result_sender.send((position, worker_fn(chunk)));
+                }
+            });
+        }
+        // Only the workers receive, so that sending fails rather than blocks without them
+        drop(receiver);
+
+        // Workers stop once the chunks are all handed over and the sender dropped
+        let produced: Result<(), ChunkingError> =
+            chunks.enumerate().try_for_each(|(position, chunk)| {
+                // Sending only fails once every worker panicked, which the scope propagates
+                let _ = sender.send((position, chunk?));
+                Ok(())
+            });
+        drop(sender);
+        produced
+    });
+    drop(result_sender);
+
+    let mut results: Vec<_> = result_receiver.into_iter().collect();
+    produced?;
+    if ordered {
+        results.sort_unstable_by_key(|&(position, _)| position);
+    }
+    Ok(results.into_iter().map(|(_, result)| result).collect())
+}
@@ -0,0 +1,74 @@
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, RecordBatch, StringArray};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+use parquet::file::properties::WriterProperties;
+
+use crate::arrow::{chunk_schema, record_batches};
+use crate::chunker::{Chunk, ChunkingError};
+
+/// Schema of the files of [`write_parquet`]: the columns of [`chunk_schema`], then `source`
+/// (utf8, null when not given).
+pub fn parquet_chunk_schema() -> SchemaRef {
+    let mut fields: Vec<Field> = chunk_schema()
+        .fields()
+        .iter()
+        .map(|field| field.as_ref().clone())
+        .collect();
+    fields.push(Field::new("source", DataType::Utf8, true));
+    Arc::new(Schema::new(fields))
+}
+
+/// Write `chunks` to a Parquet file at `path`, in row groups of up to `row_group_size` rows,
+/// with `source` (e.g. the path or URL the chunks were cut from) in the `source` column of
+/// every row. Returns the number of chunks written.
+///
+/// Chunks are pulled one row group at a time, so memory stays bounded by a row group. The
+/// offsets and index are stored as unsigned 64-bit integers. Stops at the first error, which
+/// leaves an unfinished file at `path`.
+pub fn write_parquet(
+    chunks: impl Iterator<Item = Result<Chunk, ChunkingError>>,
+    source: Option<&str>,
+    path: impl AsRef<Path>,
+    row_group_size: usize,
+) -> Result<usize, ChunkingError> {
+    let batches = record_batches(chunks, row_group_size)?;
+    let schema = parquet_chunk_schema();
+    let properties = WriterProperties::builder()
+        .set_max_row_group_size(row_group_size)
+        .build();
+    let mut writer = ArrowWriter::try_new(File::create(path)?, schema.clone(), Some(properties))
+        .map_err(io::Error::other)?;
+
+    let mut written = 0;
+    for batch in batches {
+        let batch = batch.map_err(into_chunking_error)?;
+        let rows = batch.num_rows();
+        let sources: ArrayRef = Arc::new(StringArray::from(vec![source; rows]));
+        let mut columns = batch.columns().to_vec();
+        columns.push(sources);
+        let batch = RecordBatch::try_new(schema.clone(), columns).map_err(io::Error::other)?;
+
+        writer.write(&batch).map_err(io::Error::other)?;
+        // Close the row group now rather than once the writer's buffer fills up
+        writer.flush().map_err(io::Error::other)?;
+        written += rows;
+    }
+    writer.close().map_err(io::Error::other)?;
+    Ok(written)
+}
+
+/// The error of the chunks behind a failed batch.
+fn into_chunking_error(error: ArrowError) -> ChunkingError {
+    match error {
+        ArrowError::ExternalError(error) => match error.downcast::<ChunkingError>() {
+            Ok(error) => *error,
+            Err(error) => io::Error::other(error).into(),
+        },
+        error => io::Error::other(error).into(),
+    }
+}
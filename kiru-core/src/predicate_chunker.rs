@@ -0,0 +1,293 @@
+use std::collections::VecDeque;
+
+use crate::chunker::{
+    default_buffer_capacity, floor_char_boundary, peak_buffered, sliding_output, stream_memory,
+    Chunk, ChunkKind, Chunker, ChunkingError, StringBuffer,
+};
+
+/// Decides where the text may be cut into segments.
+///
+/// Implemented for any `Fn(prev_char, next_char, pos) -> bool` closure, which is asked about
+/// every pair of adjacent characters, `pos` being the byte offset of `next_char` in the
+/// source. Implement it directly to handle a whole block of text per call, e.g. to amortize
+/// the cost of reaching a callback.
+pub trait BoundaryPredicate: Clone + Send + Sync + 'static {
+    /// Byte offsets of the boundaries within `text`, a slice of the source starting at byte
+    /// `offset` and preceded by the character `previous` (`None` at the start of the source).
+    /// Offsets are absolute and increasing; a boundary at `offset` falls between `previous`
+    /// and the first character of `text`.
+    fn boundaries(&self, previous: Option<char>, text: &str, offset: usize) -> Vec<usize>;
+}
+
+impl<F> BoundaryPredicate for F
+where
+    F: Fn(char, char, usize) -> bool + Clone + Send + Sync + 'static,
+{
+    fn boundaries(&self, previous: Option<char>, text: &str, offset: usize) -> Vec<usize> {
+        let mut previous = previous;
+        let mut boundaries = Vec::new();
+        for (i, c) in text.char_indices() {
+            if previous.is_some_and(|p| self(p, c, offset + i)) {
+                boundaries.push(offset + i);
+            }
+            previous = Some(c);
+        }
+        boundaries
+    }
+}
+
+struct PredicateChunkIndices {
+    start: usize,
+    end: usize,
+    next_start: usize,
+}
+
+/// Boundaries found so far, ahead of the next chunk start.
+struct PredicateState {
+    /// Absolute offset of the next chunk.
+    start: usize,
+    /// Absolute offset up to which the text has been handed to the predicate.
+    scanned: usize,
+    /// Character just before `scanned`.
+    last_char: Option<char>,
+    /// Absolute offsets of the boundaries after `start`, in increasing order.
+    boundaries: VecDeque<usize>,
+}
+
+/// Cuts the text into segments wherever a [`BoundaryPredicate`] says so, then packs
+/// consecutive segments into chunks of at most `chunk_size` bytes.
+///
+/// A segment longer than `chunk_size` is hard cut at a char boundary. Each chunk after the
+/// first repeats the trailing segments of the previous chunk that fit within `overlap`
+/// bytes, so the overlap is made of whole segments (and is empty when the last segment
+/// alone is longer than `overlap`). With [`Self::with_overlap_segments`], it repeats a
+/// number of trailing segments instead.
+#[derive(Clone)]
+pub struct PredicateChunker<P: BoundaryPredicate> {
+    predicate: P,
+    chunk_size: usize,
+    overlap: usize,
+    overlap_segments: usize,
+}
+
+impl<P: BoundaryPredicate> PredicateChunker<P> {
+    pub fn new(predicate: P, chunk_size: usize, overlap: usize) -> Result<Self, ChunkingError> {
+        if overlap >= chunk_size {
+            return Err(ChunkingError::InvalidArguments {
+                chunk_size,
+                overlap,
+            });
+        }
+
+        Ok(Self {
+            predicate,
+            chunk_size,
+            overlap,
+            overlap_segments: 0,
+        })
+    }
+
+    /// Start each chunk after the first at the `n`-th last boundary inside the previous
+    /// chunk, so that it repeats the last `n` segments of the previous chunk, each with the
+    /// delimiter the predicate cut after, instead of the segments that fit within `overlap`
+    /// bytes. Fewer segments are repeated when the previous chunk holds fewer, or to leave
+    /// room for the segment after the previous chunk. `0` disables segment overlap.
+    pub fn with_overlap_segments(mut self, n: usize) -> Self {
+        self.overlap_segments = n;
+        self
+    }
+
+    pub fn predicate(&self) -> &P {
+        &self.predicate
+    }
+
+    /// Hand the text of `buffer` not seen yet to the predicate, in one call.
+    fn scan(&self, buffer: &str, offset: usize, state: &mut PredicateState) {
+        let text = &buffer[state.scanned - offset..];
+        if text.is_empty() {
+            return;
+        }
+
+        let boundaries = self
+            .predicate
+            .boundaries(state.last_char, text, state.scanned);
+        state
+            .boundaries
+            .extend(boundaries.into_iter().filter(|&b| b > state.start));
+        state.last_char = text.chars().next_back();
+        state.scanned = offset + buffer.len();
+    }
+
+    /// Compute the next chunk starting at `state.start`, or `None` once done or when more
+    /// text is needed (`done` tells whether `buffer` runs to the end of the source). `offset`
+    /// is the absolute offset of `buffer[0]`; returned indices are buffer-relative.
+    fn next_chunk_indices(
+        &self,
+        buffer: &str,
+        offset: usize,
+        done: bool,
+        state: &mut PredicateState,
+    ) -> Option<PredicateChunkIndices> {
+        let buffer_len = buffer.len();
+        let start = state.start - offset;
+
+        // Boundaries are needed up to one chunk past the end of this one, to pick the overlap
+        if start >= buffer_len || (!done && start + 2 * self.chunk_size >= buffer_len) {
+            return None;
+        }
+
+        self.scan(buffer, offset, state);
+
+        // The rest fits in one chunk
+        let limit = start + self.chunk_size;
+        if limit >= buffer_len {
+            return Some(PredicateChunkIndices {
+                start,
+                end: buffer_len,
+                next_start: buffer_len,
+            });
+        }
+
+        let boundaries = || state.boundaries.iter().map(|&b| b - offset);
+
+        // Pack as many whole segments as fit, or hard cut the first one
+        let end = match boundaries().rev().find(|&b| b <= limit) {
+            Some(boundary) => boundary,
+            None => match floor_char_boundary(buffer, limit) {
+                end if end > start => end,
+                // `chunk_size` is smaller than the first character
+                _ => start + buffer[start..].chars().next().map_or(1, char::len_utf8),
+            },
+        };
+
+        // Repeat the trailing segments that fit within the overlap, leaving room for the
+        // segment after this chunk
+        let next_end = boundaries().find(|&b| b > end).unwrap_or(buffer_len);
+        let fits = |b: usize| next_end - b <= self.chunk_size;
+        let next_start = if self.overlap_segments > 0 {
+            let inside: Vec<usize> = boundaries().take_while(|&b| b < end).collect();
+            let first = inside.len().saturating_sub(self.overlap_segments);
+            inside[first..].iter().copied().find(|&b| fits(b))
+        } else {
+            boundaries().find(|&b| b < end && end - b <= self.overlap && fits(b))
+        }
+        .unwrap_or(end);
+
+        Some(PredicateChunkIndices {
+            start,
+            end,
+            next_start,
+        })
+    }
+}
+
+impl<P: BoundaryPredicate> Chunker for PredicateChunker<P> {
+    fn chunk_size(&self) -> Option<usize> {
+        Some(self.chunk_size)
+    }
+
+    /// Chunks only start at least a byte apart, as the overlap depends on the segments.
+    fn max_output(&self, source_len: usize) -> Option<(usize, usize)> {
+        Some(sliding_output(source_len, self.chunk_size.max(4), 1))
+    }
+
+    /// Excludes the boundary offsets found ahead of the current chunk, whose number depends
+    /// on the predicate.
+    fn estimate_memory(&self, source_len: usize) -> usize {
+        let min_buffer_size = self.chunk_size * 5;
+        let buffered = peak_buffered(min_buffer_size, source_len);
+
+        stream_memory(default_buffer_capacity(min_buffer_size), buffered)
+            + self.chunk_size.min(source_len)
+    }
+
+    fn chunk_string_with_offsets(self, input: String) -> impl Iterator<Item = Chunk> {
+        let mut state = PredicateState {
+            start: 0,
+            scanned: 0,
+            last_char: None,
+            boundaries: VecDeque::new(),
+        };
+        let mut index = 0;
+
+        std::iter::from_fn(move || {
+            let next = self.next_chunk_indices(&input, 0, true, &mut state)?;
+            state.start = next.next_start;
+            state.boundaries.retain(|&b| b > next.next_start);
+            let chunk = Chunk {
+                text: input[next.start..next.end].to_string(),
+                start: next.start,
+                end: next.end,
+                index,
+                lines: None,
+                chars: None,
+                running_hash: None,
+                links: None,
+                hierarchy: None,
+                source_stamp: None,
+                header_len: 0,
+                prefix_len: 0,
+                suffix_len: 0,
+                kind: ChunkKind::Main,
+            };
+            index += 1;
+            Some(chunk)
+        })
+    }
+
+    fn chunk_stream_with_offsets(
+        self,
+        input: impl Iterator<Item = String>,
+    ) -> impl Iterator<Item = Chunk> {
+        let mut string_buffer = StringBuffer::new(input, self.chunk_size * 5);
+        let mut state = PredicateState {
+            start: 0,
+            scanned: 0,
+            last_char: None,
+            boundaries: VecDeque::new(),
+        };
+        let mut index = 0;
+
+        std::iter::from_fn(move || loop {
+            let buffer = string_buffer.buffer();
+            let offset = string_buffer.offset;
+            let next = self.next_chunk_indices(buffer, offset, string_buffer.done, &mut state);
+
+            match next {
+                // if the stream is done and no more chunks can be made, return None
+                None if string_buffer.done => return None,
+
+                // if no chunk can be made yet, fill more data and try again
+                None => {
+                    string_buffer.fill();
+                    continue;
+                }
+
+                // otherwise, return the chunk and keep the next chunk's overlap in the buffer
+                Some(ref n) => {
+                    let chunk = Chunk {
+                        text: buffer[n.start..n.end].to_string(),
+                        start: offset + n.start,
+                        end: offset + n.end,
+                        index,
+                        lines: None,
+                        chars: None,
+                        running_hash: None,
+                        links: None,
+                        hierarchy: None,
+                        source_stamp: None,
+                        header_len: 0,
+                        prefix_len: 0,
+                        suffix_len: 0,
+                        kind: ChunkKind::Main,
+                    };
+                    index += 1;
+                    state.start = offset + n.next_start;
+                    state.boundaries.retain(|&b| b > offset + n.next_start);
+                    string_buffer.set_position(n.next_start);
+                    return Some(chunk);
+                }
+            };
+        })
+    }
+}
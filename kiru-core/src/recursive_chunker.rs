@@ -0,0 +1,190 @@
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use crate::chunker::{Chunk, ChunkKind, Chunker, ChunkingError};
+
+/// Separators tried in turn by default: paragraphs, lines, words, then characters.
+pub const DEFAULT_RECURSIVE_SEPARATORS: [&str; 4] = ["\n\n", "\n", " ", ""];
+
+/// Splits the text on the first of its separators found in it, packs the splits back into
+/// chunks of at most `chunk_size` bytes, and splits the splits that are still too long on
+/// the next separators, in the manner of LangChain's `RecursiveCharacterTextSplitter`.
+///
+/// Splits keep the separator that ends them, so chunks are slices of the source and chunks
+/// without overlap concatenate to it. The empty separator splits between characters; when
+/// it is not among the separators, a split without any of them is a chunk however long.
+///
+/// Consecutive splits are packed greedily: a chunk takes splits until the next one would
+/// make it longer than `chunk_size`, and the next chunk starts with the last splits of the
+/// previous one that add up to at most `overlap` bytes. A split too long to pack ends the
+/// chunk in progress, and its own chunks do not overlap the chunks around it.
+///
+/// The separator to split a piece on depends on the whole piece, so the source is read
+/// whole before the first chunk is cut.
+#[derive(Clone)]
+pub struct RecursiveChunker {
+    chunk_size: usize,
+    overlap: usize,
+    separators: Vec<String>,
+}
+
+impl RecursiveChunker {
+    pub fn new(chunk_size: usize, overlap: usize) -> Result<Self, ChunkingError> {
+        if chunk_size == 0 || overlap >= chunk_size {
+            return Err(ChunkingError::InvalidArguments {
+                chunk_size,
+                overlap,
+            });
+        }
+
+        Ok(Self {
+            chunk_size,
+            overlap,
+            separators: DEFAULT_RECURSIVE_SEPARATORS
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        })
+    }
+
+    /// Try `separators` in this order instead of [`DEFAULT_RECURSIVE_SEPARATORS`].
+    pub fn with_separators(mut self, separators: Vec<String>) -> Self {
+        self.separators = separators;
+        self
+    }
+
+    /// The ranges of the chunks of `text`.
+    fn chunk_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        let mut chunks = Vec::new();
+        if !text.is_empty() {
+            self.split(text, 0..text.len(), &self.separators, &mut chunks);
+        }
+        chunks
+    }
+
+    /// Push the chunks of `text[range]` to `chunks`, splitting it on the first of
+    /// `separators` found in it.
+    fn split(
+        &self,
+        text: &str,
+        range: Range<usize>,
+        separators: &[String],
+        chunks: &mut Vec<Range<usize>>,
+    ) {
+        let piece = &text[range.clone()];
+        let position = separators
+            .iter()
+            .position(|s| s.is_empty() || piece.contains(s.as_str()));
+        let (separator, rest) = match position {
+            Some(i) => (separators[i].as_str(), &separators[i + 1..]),
+            None => {
+                chunks.push(range);
+                return;
+            }
+        };
+
+        let mut packed = Vec::new();
+        for split in
+            split_ends(piece, separator).map(|r| range.start + r.start..range.start + r.end)
+        {
+            if split.len() <= self.chunk_size {
+                packed.push(split);
+                continue;
+            }
+            self.pack(&packed, chunks);
+            packed.clear();
+            if separator.is_empty() || rest.is_empty() {
+                // A char longer than the chunk size, or no separator left to split on
+                chunks.push(split);
+            } else {
+                self.split(text, split, rest, chunks);
+            }
+        }
+        self.pack(&packed, chunks);
+    }
+
+    /// Push the chunks packing the consecutive `splits` to `chunks`.
+    fn pack(&self, splits: &[Range<usize>], chunks: &mut Vec<Range<usize>>) {
+        let mut current: VecDeque<Range<usize>> = VecDeque::new();
+        let mut total = 0;
+
+        for split in splits {
+            let len = split.len();
+            if total + len > self.chunk_size && !current.is_empty() {
+                chunks.push(current[0].start..current[current.len() - 1].end);
+                // Keep the last splits as the overlap, leaving room for the next one
+                while total > self.overlap || (total > 0 && total + len > self.chunk_size) {
+                    let first = current.pop_front().expect("Bug: total counts a split");
+                    total -= first.len();
+                }
+            }
+            current.push_back(split.clone());
+            total += len;
+        }
+        if let (Some(first), Some(last)) = (current.front(), current.back()) {
+            chunks.push(first.start..last.end);
+        }
+    }
+}
+
+/// The ranges of `text` split after each `separator`, or between chars if it is empty.
+fn split_ends<'a>(text: &'a str, separator: &'a str) -> impl Iterator<Item = Range<usize>> + 'a {
+    let ends: Box<dyn Iterator<Item = usize>> = if separator.is_empty() {
+        Box::new(text.char_indices().map(|(i, c)| i + c.len_utf8()))
+    } else {
+        Box::new(
+            text.match_indices(separator)
+                .map(|(i, s)| i + s.len())
+                .filter(move |&end| end < text.len())
+                .chain([text.len()]),
+        )
+    };
+    let mut start = 0;
+    ends.map(move |end| {
+        let split = start..end;
+        start = end;
+        split
+    })
+}
+
+impl Chunker for RecursiveChunker {
+    fn chunk_size(&self) -> Option<usize> {
+        Some(self.chunk_size)
+    }
+
+    /// Reads the source whole, into a string that may grow to twice its size.
+    fn estimate_memory(&self, source_len: usize) -> usize {
+        2 * source_len
+    }
+
+    fn chunk_string_with_offsets(self, input: String) -> impl Iterator<Item = Chunk> {
+        let ranges = self.chunk_ranges(&input);
+
+        ranges
+            .into_iter()
+            .enumerate()
+            .map(move |(index, range)| Chunk {
+                text: input[range.clone()].to_string(),
+                start: range.start,
+                end: range.end,
+                index,
+                lines: None,
+                chars: None,
+                running_hash: None,
+                links: None,
+                hierarchy: None,
+                source_stamp: None,
+                header_len: 0,
+                prefix_len: 0,
+                suffix_len: 0,
+                kind: ChunkKind::Main,
+            })
+    }
+
+    fn chunk_stream_with_offsets(
+        self,
+        input: impl Iterator<Item = String>,
+    ) -> impl Iterator<Item = Chunk> {
+        self.chunk_string_with_offsets(input.collect())
+    }
+}
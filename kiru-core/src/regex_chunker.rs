@@ -0,0 +1,285 @@
+use regex::Regex;
+
+use crate::chunker::{
+    peak_buffered, stream_memory, Chunk, ChunkKind, Chunker, ChunkingError, StringBuffer,
+};
+use crate::BLOCK_SIZE;
+
+struct RegexChunkIndices {
+    start: usize,
+    end: usize,
+    next_start: usize,
+}
+
+/// Splits the text on the matches of a pattern (e.g. `\n---\n` or `(?m)^#{1,3} `) and
+/// cuts chunks of `chunk_size` pieces, each chunk after the first starting on the last
+/// `overlap` pieces of the previous one. Pieces are the non-empty texts between matches,
+/// so consecutive separators make one.
+///
+/// By default a piece keeps the separators that follow it (and the first piece the ones
+/// that lead the text), so chunks without overlap concatenate to the source. Without
+/// [`RegexChunker::with_keep_separator`], a chunk starts on the text of its first piece
+/// and ends after the text of its last one: the separators between its pieces stay, the
+/// others are dropped, and line numbers do not count the newlines they hold.
+///
+/// When streaming, a piece is only counted once the text after its separator is read, so
+/// separators cut by the end of a block are matched whole; a pattern that could match
+/// past the start of the next piece (e.g. `(?s)---.*`) matches within the buffered text.
+#[derive(Clone)]
+pub struct RegexChunker {
+    pattern: Regex,
+    chunk_size: usize,
+    overlap: usize,
+    keep_separator: bool,
+    repeat_header: bool,
+}
+
+impl RegexChunker {
+    pub fn new(pattern: &str, chunk_size: usize, overlap: usize) -> Result<Self, ChunkingError> {
+        if chunk_size == 0 || overlap >= chunk_size {
+            return Err(ChunkingError::InvalidArguments {
+                chunk_size,
+                overlap,
+            });
+        }
+        let pattern =
+            Regex::new(pattern).map_err(|e| ChunkingError::InvalidPattern(e.to_string()))?;
+
+        Ok(Self {
+            pattern,
+            chunk_size,
+            overlap,
+            keep_separator: true,
+            repeat_header: false,
+        })
+    }
+
+    /// Whether pieces keep the separators that follow them (the default) or chunks leave
+    /// out the separators around them.
+    pub fn with_keep_separator(mut self, keep_separator: bool) -> Self {
+        self.keep_separator = keep_separator;
+        self
+    }
+
+    /// Take the first piece, with the separators after it, as a header: the first chunk
+    /// holds it besides its `chunk_size` pieces, and chunks cut from a source by
+    /// [`crate::ChunkerWithStrategy`] repeat it at the top of every later chunk. Offsets
+    /// still span the pieces of a chunk only.
+    pub fn with_repeat_header(mut self, repeat_header: bool) -> Self {
+        self.repeat_header = repeat_header;
+        self
+    }
+
+    /// The strategy cutting the first chunk, which holds the header besides its pieces.
+    fn first_chunker(&self) -> Self {
+        Self {
+            chunk_size: self.chunk_size + usize::from(self.repeat_header),
+            ..self.clone()
+        }
+    }
+
+    /// Compute the chunk starting at `start`, or `None` once done or when more text is
+    /// needed (`done` tells whether `buffer` runs to the end of the source).
+    fn next_chunk_indices(
+        &self,
+        buffer: &str,
+        start: usize,
+        done: bool,
+    ) -> Option<RegexChunkIndices> {
+        let step = self.chunk_size - self.overlap;
+        let mut chunk_start = start;
+        let mut next_start = None;
+        let mut pieces = 0;
+        // Where the text of the current piece starts, and the end of the previous one
+        let mut piece_start = start;
+        let mut text_end = start;
+        let mut search_from = start;
+
+        loop {
+            let found = if search_from <= buffer.len() {
+                self.pattern.find_at(buffer, search_from)
+            } else {
+                None
+            };
+            let (separator_start, separator_end) =
+                found.map_or((buffer.len(), buffer.len()), |m| (m.start(), m.end()));
+
+            // A piece starts at `piece_start`
+            if separator_start > piece_start {
+                if pieces == 0 && !self.keep_separator {
+                    chunk_start = piece_start;
+                }
+                if pieces == step {
+                    next_start = Some(piece_start);
+                }
+                // It is the first piece after the chunk
+                if pieces == self.chunk_size {
+                    return Some(RegexChunkIndices {
+                        start: chunk_start,
+                        end: if self.keep_separator {
+                            piece_start
+                        } else {
+                            text_end
+                        },
+                        next_start: next_start.unwrap_or(piece_start),
+                    });
+                }
+                pieces += 1;
+                text_end = separator_start;
+            }
+
+            if found.is_none() {
+                break;
+            }
+            piece_start = separator_end;
+            // An empty match is found again at its end: search from the next char
+            search_from = if separator_start == separator_end {
+                separator_end
+                    + buffer[separator_end..]
+                        .chars()
+                        .next()
+                        .map_or(1, char::len_utf8)
+            } else {
+                separator_end
+            };
+        }
+
+        // The last piece may go on in the next block
+        if !done || pieces == 0 {
+            return None;
+        }
+        Some(RegexChunkIndices {
+            start: chunk_start,
+            end: if self.keep_separator {
+                buffer.len()
+            } else {
+                text_end
+            },
+            next_start: buffer.len(),
+        })
+    }
+}
+
+impl Chunker for RegexChunker {
+    fn chunk_size(&self) -> Option<usize> {
+        Some(self.chunk_size)
+    }
+
+    /// The first piece and the separators after it, when repeating them.
+    fn header_len(&self, first_chunk: &str) -> Option<usize> {
+        if !self.repeat_header {
+            return None;
+        }
+        let first_piece = Self {
+            chunk_size: 1,
+            overlap: 0,
+            ..self.clone()
+        };
+        first_piece
+            .next_chunk_indices(first_chunk, 0, true)
+            .map(|n| n.next_start)
+    }
+
+    /// Pieces of `text` between matches of the pattern.
+    fn measure(&self, text: &str) -> usize {
+        let mut pieces = 0;
+        let mut piece_start = 0;
+
+        for m in self.pattern.find_iter(text) {
+            if m.start() > piece_start {
+                pieces += 1;
+            }
+            piece_start = m.end();
+        }
+        pieces + usize::from(text.len() > piece_start)
+    }
+
+    /// Assumes a chunk fits in a block: a longer one is buffered whole.
+    fn estimate_memory(&self, source_len: usize) -> usize {
+        let buffered = peak_buffered(BLOCK_SIZE, source_len);
+
+        stream_memory(BLOCK_SIZE, buffered) + BLOCK_SIZE.min(source_len)
+    }
+
+    fn chunk_string_with_offsets(self, input: String) -> impl Iterator<Item = Chunk> {
+        let first = self.first_chunker();
+        let mut start = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || {
+            let chunker = if index == 0 { &first } else { &self };
+            let next = chunker.next_chunk_indices(&input, start, true)?;
+            start = next.next_start;
+            let chunk = Chunk {
+                text: input[next.start..next.end].to_string(),
+                start: next.start,
+                end: next.end,
+                index,
+                lines: None,
+                chars: None,
+                running_hash: None,
+                links: None,
+                hierarchy: None,
+                source_stamp: None,
+                header_len: 0,
+                prefix_len: 0,
+                suffix_len: 0,
+                kind: ChunkKind::Main,
+            };
+            index += 1;
+            Some(chunk)
+        })
+    }
+
+    fn chunk_stream_with_offsets(
+        self,
+        input: impl Iterator<Item = String>,
+    ) -> impl Iterator<Item = Chunk> {
+        let first = self.first_chunker();
+        let mut string_buffer = StringBuffer::new(input, BLOCK_SIZE);
+        let mut start = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || loop {
+            let buffer = string_buffer.buffer();
+            let offset = string_buffer.offset;
+            let chunker = if index == 0 { &first } else { &self };
+            let next = chunker.next_chunk_indices(buffer, start - offset, string_buffer.done);
+
+            match next {
+                // if the stream is done and no more chunks can be made, return None
+                None if string_buffer.done => return None,
+
+                // if no chunk can be made yet, fill more data and try again
+                None => {
+                    string_buffer.fill();
+                    continue;
+                }
+
+                // otherwise, return the chunk and keep the overlapping pieces in the buffer
+                Some(n) => {
+                    let chunk = Chunk {
+                        text: buffer[n.start..n.end].to_string(),
+                        start: offset + n.start,
+                        end: offset + n.end,
+                        index,
+                        lines: None,
+                        chars: None,
+                        running_hash: None,
+                        links: None,
+                        hierarchy: None,
+                        source_stamp: None,
+                        header_len: 0,
+                        prefix_len: 0,
+                        suffix_len: 0,
+                        kind: ChunkKind::Main,
+                    };
+                    index += 1;
+                    start = offset + n.next_start;
+                    string_buffer.set_position(n.next_start);
+                    return Some(chunk);
+                }
+            };
+        })
+    }
+}
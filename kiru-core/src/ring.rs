@@ -0,0 +1,146 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use crossbeam_channel::{bounded, never, Receiver, TrySendError};
+
+use crate::chunker::{Chunk, Chunker, ChunkerWithStrategy, ChunkingError, Source};
+
+/// What a [`RingChunker`] does with a new chunk when its buffer is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RingOverflow {
+    /// Wait for the consumer to make room: nothing is lost, but chunking runs at the pace of
+    /// the consumer.
+    Block,
+    /// Evict the oldest chunk in the buffer: chunking never waits, and a consumer that falls
+    /// behind misses chunks (counted in [`RingChunker::dropped`]) but only ever gets recent
+    /// ones.
+    DropOldest,
+}
+
+/// Chunks a source on a background thread into a ring buffer of at most `capacity` chunks,
+/// which the consumer drains with [`Self::recv`] or by iterating, so that a slow consumer
+/// bounds memory and, with [`RingOverflow::DropOldest`], the age of the chunks it gets.
+///
+/// Dropping it stops the chunking at the next chunk. An error that cuts the source short
+/// ends the chunks and is returned by [`Self::finish`].
+pub struct RingChunker {
+    receiver: Receiver<Chunk>,
+    dropped: Arc<AtomicUsize>,
+    stop: Arc<AtomicBool>,
+    producer: Option<JoinHandle<Result<(), ChunkingError>>>,
+}
+
+impl RingChunker {
+    /// Start chunking `source` with `chunker` into a buffer of `capacity` chunks.
+    pub fn new<C: Chunker>(
+        source: Source,
+        chunker: &ChunkerWithStrategy<C>,
+        capacity: usize,
+        overflow: RingOverflow,
+    ) -> Result<Self, ChunkingError> {
+        if capacity == 0 {
+            return Err(ChunkingError::InvalidRingCapacity);
+        }
+        let (sender, receiver) = bounded(capacity);
+        // The producer evicts the oldest chunk through a receiver of its own
+        let evict = (overflow == RingOverflow::DropOldest).then(|| receiver.clone());
+        let (opened_sender, opened) = bounded(1);
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let producer = {
+            let chunker = chunker.clone();
+            let dropped = dropped.clone();
+            let stop = stop.clone();
+            // The chunks are cut where they are consumed, so the source is opened there too
+            thread::spawn(move || {
+                let chunks = match chunker.on_source_with_offsets(source) {
+                    Ok(chunks) => {
+                        let _ = opened_sender.send(Ok(()));
+                        chunks
+                    }
+                    Err(e) => {
+                        let _ = opened_sender.send(Err(e));
+                        return Ok(());
+                    }
+                };
+                for chunk in chunks {
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let Some(evict) = &evict else {
+                        // Sending only fails once the consumer is gone
+                        if sender.send(chunk?).is_err() {
+                            break;
+                        }
+                        continue;
+                    };
+                    let mut chunk = chunk?;
+                    while let Err(TrySendError::Full(rejected)) = sender.try_send(chunk) {
+                        // The consumer may have made room meanwhile, then nothing is evicted
+                        if evict.try_recv().is_ok() {
+                            dropped.fetch_add(1, Ordering::Relaxed);
+                        }
+                        chunk = rejected;
+                    }
+                }
+                Ok(())
+            })
+        };
+        opened
+            .recv()
+            .expect("Bug: ring producer ended before opening the source")?;
+
+        Ok(Self {
+            receiver,
+            dropped,
+            stop,
+            producer: Some(producer),
+        })
+    }
+
+    /// The oldest chunk in the buffer, waiting for one if it is empty, or `None` once the
+    /// chunking ended and the buffer is drained.
+    pub fn recv(&self) -> Option<Chunk> {
+        self.receiver.recv().ok()
+    }
+
+    /// The oldest chunk in the buffer, or `None` if it is empty right now.
+    pub fn try_recv(&self) -> Option<Chunk> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Chunks evicted from the full buffer so far, with [`RingOverflow::DropOldest`].
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Stop the chunking, discarding the chunks left in the buffer, and return the number
+    /// of chunks dropped, or the error that cut the source short. Called once the chunks are
+    /// drained, it tells whether the source was chunked to its end.
+    pub fn finish(mut self) -> Result<usize, ChunkingError> {
+        self.stop.store(true, Ordering::Relaxed);
+        // A blocked producer fails to send once the buffer has no consumer left
+        drop(std::mem::replace(&mut self.receiver, never()));
+        let producer = self.producer.take().expect("Bug: producer already joined");
+        match producer.join() {
+            Ok(result) => result.map(|()| self.dropped()),
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    }
+}
+
+impl Iterator for RingChunker {
+    type Item = Chunk;
+
+    fn next(&mut self) -> Option<Chunk> {
+        self.recv()
+    }
+}
+
+impl Drop for RingChunker {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::VecDeque;
+
+use crate::chunker::{peak_buffered, stream_memory, Chunk, ChunkKind, Chunker, StringBuffer};
+use crate::sentences::{sentence_starts, SentenceTerminators};
+use crate::BLOCK_SIZE;
+
+/// Decides when a growing chunk is finished.
+///
+/// Implemented for any `Fn(current_chunk, candidate_next) -> bool` closure, which is asked
+/// about every unit that could join the chunk and returns true to finalize the chunk before
+/// it. Implement it directly to evaluate a whole batch of candidates per call, e.g. to
+/// amortize the cost of reaching a callback.
+pub trait BreakPredicate: Clone + Send + Sync + 'static {
+    /// Index of the first candidate unit that should start a new chunk, if any.
+    ///
+    /// `text` starts at the start of the chunk, whose first `accepted` bytes already joined
+    /// it. `ends` are the increasing offsets (within `text`) at which the candidate units
+    /// end, the first one starting at `accepted` and each other one where the previous ends.
+    fn first_break(&self, text: &str, accepted: usize, ends: &[usize]) -> Option<usize>;
+}
+
+impl<F> BreakPredicate for F
+where
+    F: Fn(&str, &str) -> bool + Clone + Send + Sync + 'static,
+{
+    fn first_break(&self, text: &str, accepted: usize, ends: &[usize]) -> Option<usize> {
+        let mut end = accepted;
+        for (i, &next_end) in ends.iter().enumerate() {
+            if self(&text[..end], &text[end..next_end]) {
+                return Some(i);
+            }
+            end = next_end;
+        }
+        None
+    }
+}
+
+/// Unit a semantic chunk grows by, and overlaps in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SemanticUnit {
+    Character,
+    /// A sentence with the whitespace after it, sentences starting as in the sentence
+    /// overlap of the bytes strategy.
+    Sentence,
+}
+
+struct SemanticChunkIndices {
+    start: usize,
+    end: usize,
+    next_start: usize,
+}
+
+/// The chunk being grown.
+struct SemanticState {
+    /// Absolute offset of the chunk.
+    start: usize,
+    /// Absolute offset up to which units joined the chunk.
+    accepted: usize,
+    /// Number of units that joined the chunk.
+    units: usize,
+    /// Absolute offsets of the last `overlap + 1` unit boundaries of the chunk, its start
+    /// included.
+    boundaries: VecDeque<usize>,
+}
+
+impl SemanticState {
+    fn new(start: usize) -> Self {
+        Self {
+            start,
+            accepted: start,
+            units: 0,
+            boundaries: VecDeque::from([start]),
+        }
+    }
+}
+
+/// Grows each chunk unit by unit (characters or sentences) until a [`BreakPredicate`] says
+/// to stop, then starts the next chunk `overlap` units before the end of the previous one.
+///
+/// The first unit always joins a chunk, and a chunk of `overlap` units or less repeats all
+/// but its first unit, so that chunks always move forward. A chunk is held whole in memory
+/// until the predicate breaks it.
+#[derive(Clone)]
+pub struct SemanticChunker<P: BreakPredicate> {
+    predicate: P,
+    unit: SemanticUnit,
+    overlap: usize,
+}
+
+impl<P: BreakPredicate> SemanticChunker<P> {
+    pub fn new(predicate: P, unit: SemanticUnit, overlap: usize) -> Self {
+        Self {
+            predicate,
+            unit,
+            overlap,
+        }
+    }
+
+    pub fn predicate(&self) -> &P {
+        &self.predicate
+    }
+
+    /// Offsets (within `text`) at which the units of `text` end, `text` starting on a unit.
+    /// The last unit only counts as ended when `done` tells that no text follows.
+    fn unit_ends(&self, text: &str, done: bool) -> Vec<usize> {
+        let mut ends: Vec<usize> = match self.unit {
+            SemanticUnit::Character => text.char_indices().map(|(i, c)| i + c.len_utf8()).collect(),
+            SemanticUnit::Sentence => {
+                sentence_starts(text, &SentenceTerminators::default()).collect()
+            }
+        };
+        if done && ends.last().copied().unwrap_or(0) < text.len() {
+            ends.push(text.len());
+        }
+        ends
+    }
+
+    fn accept(&self, state: &mut SemanticState, end: usize) {
+        state.accepted = end;
+        state.units += 1;
+        state.boundaries.push_back(end);
+        if state.boundaries.len() > self.overlap + 1 {
+            state.boundaries.pop_front();
+        }
+    }
+
+    /// Absolute offset of the chunk after the one in `state`, which does not reach the end
+    /// of the source.
+    fn next_start(&self, state: &SemanticState) -> usize {
+        if state.units > self.overlap {
+            state.boundaries[0]
+        } else {
+            state.boundaries[1]
+        }
+    }
+
+    /// Grow the chunk in `state` over `buffer`, returning its buffer-relative indices once
+    /// finished, or `None` once done or when more text is needed (`done` tells whether
+    /// `buffer` runs to the end of the source). `offset` is the absolute offset of `buffer[0]`.
+    fn next_chunk_indices(
+        &self,
+        buffer: &str,
+        offset: usize,
+        done: bool,
+        state: &mut SemanticState,
+    ) -> Option<SemanticChunkIndices> {
+        let start = state.start - offset;
+        if start >= buffer.len() {
+            return None;
+        }
+
+        // Only the text past the accepted units is split, so each unit is found once
+        let accepted = state.accepted - offset;
+        let mut ends = self
+            .unit_ends(&buffer[accepted..], done)
+            .into_iter()
+            .map(|end| accepted + end);
+
+        if state.units == 0 {
+            let first = ends.next()?;
+            self.accept(state, offset + first);
+        }
+
+        let ends: Vec<usize> = ends.collect();
+        let candidates: Vec<usize> = ends.iter().map(|end| end - start).collect();
+        let accepted = state.accepted - offset - start;
+        let joining = match self
+            .predicate
+            .first_break(&buffer[start..], accepted, &candidates)
+        {
+            Some(i) => &ends[..i],
+            None => &ends[..],
+        };
+        let finished = joining.len() < ends.len() || done;
+        for &end in joining {
+            self.accept(state, offset + end);
+        }
+        if !finished {
+            return None;
+        }
+
+        let end = state.accepted - offset;
+        // The last chunk has no chunk after it to overlap with
+        let next_start = if done && end == buffer.len() {
+            end
+        } else {
+            self.next_start(state) - offset
+        };
+        Some(SemanticChunkIndices {
+            start,
+            end,
+            next_start,
+        })
+    }
+}
+
+impl<P: BreakPredicate> Chunker for SemanticChunker<P> {
+    /// Assumes a chunk fits in a block: a longer one is buffered whole.
+    fn estimate_memory(&self, source_len: usize) -> usize {
+        let buffered = peak_buffered(BLOCK_SIZE, source_len);
+
+        stream_memory(BLOCK_SIZE, buffered) + BLOCK_SIZE.min(source_len)
+    }
+
+    fn chunk_string_with_offsets(self, input: String) -> impl Iterator<Item = Chunk> {
+        let mut state = SemanticState::new(0);
+        let mut index = 0;
+
+        std::iter::from_fn(move || {
+            let next = self.next_chunk_indices(&input, 0, true, &mut state)?;
+            state = SemanticState::new(next.next_start);
+            let chunk = Chunk {
+                text: input[next.start..next.end].to_string(),
+                start: next.start,
+                end: next.end,
+                index,
+                lines: None,
+                chars: None,
+                running_hash: None,
+                links: None,
+                hierarchy: None,
+                source_stamp: None,
+                header_len: 0,
+                prefix_len: 0,
+                suffix_len: 0,
+                kind: ChunkKind::Main,
+            };
+            index += 1;
+            Some(chunk)
+        })
+    }
+
+    fn chunk_stream_with_offsets(
+        self,
+        input: impl Iterator<Item = String>,
+    ) -> impl Iterator<Item = Chunk> {
+        let mut string_buffer = StringBuffer::new(input, BLOCK_SIZE);
+        let mut state = SemanticState::new(0);
+        let mut index = 0;
+
+        std::iter::from_fn(move || loop {
+            let buffer = string_buffer.buffer();
+            let offset = string_buffer.offset;
+            let next = self.next_chunk_indices(buffer, offset, string_buffer.done, &mut state);
+
+            match next {
+                // if the stream is done and no more chunks can be made, return None
+                None if string_buffer.done => return None,
+
+                // if the chunk is not finished yet, fill more data and try again
+                None => {
+                    string_buffer.fill();
+                    continue;
+                }
+
+                // otherwise, return the chunk and keep the next chunk's overlap in the buffer
+                Some(n) => {
+                    let chunk = Chunk {
+                        text: buffer[n.start..n.end].to_string(),
+                        start: offset + n.start,
+                        end: offset + n.end,
+                        index,
+                        lines: None,
+                        chars: None,
+                        running_hash: None,
+                        links: None,
+                        hierarchy: None,
+                        source_stamp: None,
+                        header_len: 0,
+                        prefix_len: 0,
+                        suffix_len: 0,
+                        kind: ChunkKind::Main,
+                    };
+                    index += 1;
+                    state = SemanticState::new(offset + n.next_start);
+                    string_buffer.set_position(n.next_start);
+                    return Some(chunk);
+                }
+            };
+        })
+    }
+}
@@ -0,0 +1,168 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+
+const TERMINATORS: [char; 3] = ['.', '!', '?'];
+const CLOSERS: [char; 7] = ['"', '\'', ')', ']', '’', '”', '»'];
+const CJK_TERMINATORS: [char; 4] = ['。', '！', '？', '｡'];
+const CJK_CLOSERS: [char; 7] = ['」', '』', '）', '】', '〕', '〉', '》'];
+
+/// The chars that end a sentence.
+///
+/// Defaults to '.', '!' and '?', which only end a sentence when whitespace follows them.
+/// With [`SentenceTerminators::with_cjk`], the CJK full stop, exclamation and question
+/// marks ('。', '！', '？' and the halfwidth '｡') end one too, whether whitespace follows
+/// them or not, as CJK text does not separate sentences with spaces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentenceTerminators {
+    terminators: Cow<'static, [char]>,
+    cjk: bool,
+}
+
+impl Default for SentenceTerminators {
+    fn default() -> Self {
+        Self {
+            terminators: Cow::Borrowed(&TERMINATORS),
+            cjk: false,
+        }
+    }
+}
+
+impl SentenceTerminators {
+    /// End sentences on `terminators` followed by whitespace, e.g. `['.', '…', ';']`.
+    pub fn new(terminators: impl IntoIterator<Item = char>) -> Self {
+        Self {
+            terminators: Cow::Owned(terminators.into_iter().collect()),
+            cjk: false,
+        }
+    }
+
+    /// Also end sentences on the CJK terminators, followed by whitespace or not.
+    pub fn with_cjk(mut self, cjk: bool) -> Self {
+        self.cjk = cjk;
+        self
+    }
+
+    fn is_cjk_terminator(&self, c: char) -> bool {
+        self.cjk && CJK_TERMINATORS.contains(&c)
+    }
+
+    fn is_terminator(&self, c: char) -> bool {
+        self.terminators.contains(&c) || self.is_cjk_terminator(c)
+    }
+
+    fn is_closer(&self, c: char) -> bool {
+        CLOSERS.contains(&c) || (self.cjk && CJK_CLOSERS.contains(&c))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    InSentence,
+    /// In a run of terminators and closers, which holds a CJK terminator if `true`.
+    AfterTerminator(bool),
+    Gap,
+}
+
+/// Byte offsets (relative to `text`) at which a new sentence starts.
+///
+/// A sentence starts at the first non-whitespace char following a run of `terminators`
+/// (optionally closed by quotes or brackets) and at least one whitespace char, so a
+/// terminator inside a token such as "3.14" does not split. After a run holding a CJK
+/// terminator, it starts at the next char even without whitespace. Offset 0 is never
+/// reported.
+pub(crate) fn sentence_starts<'a>(
+    text: &'a str,
+    terminators: &'a SentenceTerminators,
+) -> impl Iterator<Item = usize> + 'a {
+    let mut state = State::InSentence;
+
+    text.char_indices().filter_map(move |(pos, c)| {
+        let is_terminator = terminators.is_terminator(c);
+        let cjk = terminators.is_cjk_terminator(c);
+        let (next_state, start) = match state {
+            State::InSentence if is_terminator => (State::AfterTerminator(cjk), None),
+            State::InSentence => (State::InSentence, None),
+            State::AfterTerminator(wide) if is_terminator => {
+                (State::AfterTerminator(wide || cjk), None)
+            }
+            State::AfterTerminator(wide) if terminators.is_closer(c) => {
+                (State::AfterTerminator(wide), None)
+            }
+            State::AfterTerminator(_) if c.is_whitespace() => (State::Gap, None),
+            State::AfterTerminator(true) => (State::InSentence, Some(pos)),
+            State::AfterTerminator(false) => (State::InSentence, None),
+            State::Gap if c.is_whitespace() => (State::Gap, None),
+            State::Gap if is_terminator => (State::AfterTerminator(cjk), Some(pos)),
+            State::Gap => (State::InSentence, Some(pos)),
+        };
+        state = next_state;
+        start
+    })
+}
+
+/// Start offset of the `n`-th last sentence beginning inside `text` (after offset 0),
+/// or `None` if `text` contains fewer than `n` sentence starts.
+pub(crate) fn nth_last_sentence_start(
+    text: &str,
+    terminators: &SentenceTerminators,
+    n: usize,
+) -> Option<usize> {
+    let mut last = VecDeque::with_capacity(n);
+    for start in sentence_starts(text, terminators) {
+        if last.len() == n {
+            last.pop_front();
+        }
+        last.push_back(start);
+    }
+
+    if n > 0 && last.len() == n {
+        last.front().copied()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sentence_starts() {
+        let terminators = SentenceTerminators::default();
+        let text = "One. Two!  \"Three?\" Four 3.14 e.g. five";
+        let starts: Vec<_> = sentence_starts(text, &terminators)
+            .map(|p| &text[p..p + 4])
+            .collect();
+        assert_eq!(starts, vec!["Two!", "\"Thr", "Four", "five"]);
+
+        assert_eq!(nth_last_sentence_start(text, &terminators, 2), Some(20));
+        assert_eq!(
+            nth_last_sentence_start("No terminator here", &terminators, 1),
+            None
+        );
+    }
+
+    #[test]
+    fn test_custom_and_cjk_terminators() {
+        let text = "One; two… three. 今日は晴れ。「明日は？」雨です。 Four";
+        let starts = |terminators: &SentenceTerminators| -> Vec<&str> {
+            sentence_starts(text, terminators)
+                .map(|p| text[p..].split_whitespace().next().unwrap())
+                .collect()
+        };
+
+        assert_eq!(
+            starts(&SentenceTerminators::new([';', '…'])),
+            ["two…", "three."]
+        );
+        assert_eq!(
+            starts(&SentenceTerminators::default().with_cjk(true)),
+            [
+                "今日は晴れ。「明日は？」雨です。",
+                "「明日は？」雨です。",
+                "雨です。",
+                "Four"
+            ]
+        );
+    }
+}
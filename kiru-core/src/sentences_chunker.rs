@@ -0,0 +1,241 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::chunker::{
+    ceil_char_boundary, floor_char_boundary, peak_buffered, stream_memory, Chunk, ChunkKind,
+    Chunker, ChunkingError, StringBuffer,
+};
+use crate::sentences::{sentence_starts, SentenceTerminators};
+use crate::BLOCK_SIZE;
+
+struct SentencesChunkIndices {
+    start: usize,
+    end: usize,
+    next_start: usize,
+}
+
+/// Cuts chunks of `chunk_size` sentences, each chunk after the first starting on the last
+/// `overlap` sentences of the previous one. Sentences end on the terminators of a
+/// [`SentenceTerminators`], '.', '!' and '?' followed by whitespace by default, and keep the
+/// whitespace after them, so chunks without overlap concatenate to the source.
+///
+/// A chunk is held whole in memory until its sentences are read, unless
+/// [`SentencesChunker::with_max_bytes`] caps it.
+#[derive(Clone)]
+pub struct SentencesChunker {
+    chunk_size: usize,
+    overlap: usize,
+    terminators: SentenceTerminators,
+    max_bytes: Option<usize>,
+    /// Sentences cut at `max_bytes`, shared by clones so that the builder sees the count.
+    hard_splits: Arc<AtomicUsize>,
+}
+
+impl SentencesChunker {
+    pub fn new(chunk_size: usize, overlap: usize) -> Result<Self, ChunkingError> {
+        if chunk_size == 0 || overlap >= chunk_size {
+            return Err(ChunkingError::InvalidArguments {
+                chunk_size,
+                overlap,
+            });
+        }
+
+        Ok(Self {
+            chunk_size,
+            overlap,
+            terminators: SentenceTerminators::default(),
+            max_bytes: None,
+            hard_splits: Arc::default(),
+        })
+    }
+
+    /// End sentences on `terminators` instead of '.', '!' and '?', e.g. with CJK ones.
+    pub fn with_terminators(mut self, terminators: SentenceTerminators) -> Self {
+        self.terminators = terminators;
+        self
+    }
+
+    /// Never let a chunk grow past `max_bytes`: a chunk ends before the first sentence that
+    /// would take it past the cap, however few sentences it holds, and a sentence longer than
+    /// the cap (e.g. a "sentence" of minified code) falls back to chunks of `max_bytes`
+    /// bytes, moved back onto char boundaries, without overlap. A cap smaller than a char
+    /// cuts after the char. Cuts are counted in [`crate::ChunkStats::hard_splits`].
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Compute the chunk of the sentences from `start`, or `None` once done or when more
+    /// text is needed (`done` tells whether `buffer` runs to the end of the source).
+    fn next_chunk_indices(
+        &self,
+        buffer: &str,
+        start: usize,
+        done: bool,
+    ) -> Option<SentencesChunkIndices> {
+        if start >= buffer.len() {
+            return None;
+        }
+
+        // Sentences ending past the cap never join the chunk, so the text past it is not read
+        let (scan_end, limit) = match self.max_bytes {
+            Some(max_bytes) if start + max_bytes < buffer.len() => (
+                ceil_char_boundary(buffer, start + max_bytes + 1),
+                start + max_bytes,
+            ),
+            _ => (buffer.len(), usize::MAX),
+        };
+        let step = self.chunk_size - self.overlap;
+        // Ends of the sentences of the chunk, after its start
+        let mut ends = vec![start];
+
+        // A sentence ends where the next one starts
+        for end in sentence_starts(&buffer[start..scan_end], &self.terminators) {
+            let end = start + end;
+            if end > limit {
+                break;
+            }
+            ends.push(end);
+            if ends.len() > self.chunk_size {
+                return Some(SentencesChunkIndices {
+                    start,
+                    end,
+                    next_start: ends[step],
+                });
+            }
+        }
+
+        // The cap falls inside the sentence after the last one that fits
+        if limit < buffer.len() {
+            let sentences = ends.len() - 1;
+            if sentences == 0 {
+                let mut end = floor_char_boundary(buffer, limit);
+                if end == start {
+                    end = ceil_char_boundary(buffer, start + 1);
+                }
+                self.hard_splits.fetch_add(1, Ordering::Relaxed);
+                return Some(SentencesChunkIndices {
+                    start,
+                    end,
+                    next_start: end,
+                });
+            }
+            // A chunk of no more sentences than the overlap repeats all but its first one
+            return Some(SentencesChunkIndices {
+                start,
+                end: ends[sentences],
+                next_start: ends[sentences.saturating_sub(self.overlap).max(1)],
+            });
+        }
+
+        // Whether the last sentence goes on is not known before the end
+        if !done {
+            return None;
+        }
+        Some(SentencesChunkIndices {
+            start,
+            end: buffer.len(),
+            next_start: buffer.len(),
+        })
+    }
+}
+
+impl Chunker for SentencesChunker {
+    fn chunk_size(&self) -> Option<usize> {
+        Some(self.chunk_size)
+    }
+
+    fn hard_splits(&self) -> usize {
+        self.hard_splits.load(Ordering::Relaxed)
+    }
+
+    /// Sentences of `text`.
+    fn measure(&self, text: &str) -> usize {
+        usize::from(!text.is_empty()) + sentence_starts(text, &self.terminators).count()
+    }
+
+    /// Assumes a chunk fits in a block: a longer one is buffered whole.
+    fn estimate_memory(&self, source_len: usize) -> usize {
+        let buffered = peak_buffered(BLOCK_SIZE, source_len);
+
+        stream_memory(BLOCK_SIZE, buffered) + BLOCK_SIZE.min(source_len)
+    }
+
+    fn chunk_string_with_offsets(self, input: String) -> impl Iterator<Item = Chunk> {
+        let mut start = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || {
+            let next = self.next_chunk_indices(&input, start, true)?;
+            start = next.next_start;
+            let chunk = Chunk {
+                text: input[next.start..next.end].to_string(),
+                start: next.start,
+                end: next.end,
+                index,
+                lines: None,
+                chars: None,
+                running_hash: None,
+                links: None,
+                hierarchy: None,
+                source_stamp: None,
+                header_len: 0,
+                prefix_len: 0,
+                suffix_len: 0,
+                kind: ChunkKind::Main,
+            };
+            index += 1;
+            Some(chunk)
+        })
+    }
+
+    fn chunk_stream_with_offsets(
+        self,
+        input: impl Iterator<Item = String>,
+    ) -> impl Iterator<Item = Chunk> {
+        let mut string_buffer = StringBuffer::new(input, BLOCK_SIZE);
+        let mut start = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || loop {
+            let buffer = string_buffer.buffer();
+            let offset = string_buffer.offset;
+            let next = self.next_chunk_indices(buffer, start - offset, string_buffer.done);
+
+            match next {
+                // if the stream is done and no more chunks can be made, return None
+                None if string_buffer.done => return None,
+
+                // if no chunk can be made yet, fill more data and try again
+                None => {
+                    string_buffer.fill();
+                    continue;
+                }
+
+                // otherwise, return the chunk and keep the overlapping sentences in the buffer
+                Some(n) => {
+                    let chunk = Chunk {
+                        text: buffer[n.start..n.end].to_string(),
+                        start: offset + n.start,
+                        end: offset + n.end,
+                        index,
+                        lines: None,
+                        chars: None,
+                        running_hash: None,
+                        links: None,
+                        hierarchy: None,
+                        source_stamp: None,
+                        header_len: 0,
+                        prefix_len: 0,
+                        suffix_len: 0,
+                        kind: ChunkKind::Main,
+                    };
+                    index += 1;
+                    start = offset + n.next_start;
+                    string_buffer.set_position(n.next_start);
+                    return Some(chunk);
+                }
+            };
+        })
+    }
+}
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use crate::chunker::{Chunk, Chunker, ChunkerWithStrategy, ChunkingError, Source};
+
+/// A chunk whose text is the range `start..end` of a buffer shared with the chunks it
+/// overlaps, so that their overlap is held once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedChunk {
+    pub buf: Arc<str>,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl SharedChunk {
+    pub fn text(&self) -> &str {
+        &self.buf[self.start..self.end]
+    }
+}
+
+/// Where the text of a chunk is kept until the buffers are frozen.
+enum Slot {
+    /// A range of a segment, relative to its start.
+    Segment {
+        segment: usize,
+        start: usize,
+        end: usize,
+    },
+    /// Text that is not a slice of the source, e.g. with a prefix.
+    Owned(String),
+}
+
+/// A contiguous run of the source, at `offset` in it.
+struct Segment {
+    offset: usize,
+    text: String,
+}
+
+/// Chunk `source` with `chunker`, keeping chunks as ranges of shared buffers instead of one
+/// `String` each: with an overlap of 90%, the text of the chunks takes about a tenth of the
+/// memory it would.
+///
+/// Chunks that follow on from one another share one buffer holding the source text they
+/// span; a chunk starting past the end of the previous one starts a new buffer. Chunks
+/// whose text is not a slice of the source (e.g. with a prefix, or from the JSON array
+/// strategy) get a buffer of their own.
+pub fn chunk_shared<C: Chunker>(
+    source: Source,
+    chunker: &ChunkerWithStrategy<C>,
+) -> Result<Vec<SharedChunk>, ChunkingError> {
+    let mut segments: Vec<Segment> = Vec::new();
+    let mut slots = Vec::new();
+
+    for chunk in chunker.on_source_with_offsets(source)? {
+        slots.push(slot_of(chunk?, &mut segments));
+    }
+
+    let buffers: Vec<Arc<str>> = segments
+        .into_iter()
+        .map(|segment| Arc::from(segment.text))
+        .collect();
+    Ok(slots
+        .into_iter()
+        .map(|slot| match slot {
+            Slot::Segment {
+                segment,
+                start,
+                end,
+            } => SharedChunk {
+                buf: buffers[segment].clone(),
+                start,
+                end,
+            },
+            Slot::Owned(text) => SharedChunk {
+                start: 0,
+                end: text.len(),
+                buf: Arc::from(text),
+            },
+        })
+        .collect())
+}
+
+/// Add the text of `chunk` missing from the last segment, or start a new one.
+fn slot_of(chunk: Chunk, segments: &mut Vec<Segment>) -> Slot {
+    if chunk.end < chunk.start || chunk.end - chunk.start != chunk.text.len() {
+        return Slot::Owned(chunk.text);
+    }
+
+    if let Some(last) = segments.last_mut() {
+        let covered = last.offset + last.text.len();
+        if (last.offset..=covered).contains(&chunk.start) {
+            let start = chunk.start - last.offset;
+            let shared = covered.min(chunk.end) - chunk.start;
+            let matches = match (last.text.get(start..), chunk.text.get(..shared)) {
+                (Some(covered), Some(shared)) => covered.starts_with(shared),
+                _ => false,
+            };
+            if !matches {
+                return Slot::Owned(chunk.text);
+            }
+            last.text.push_str(&chunk.text[shared..]);
+            return Slot::Segment {
+                segment: segments.len() - 1,
+                start,
+                end: start + chunk.text.len(),
+            };
+        }
+    }
+
+    let end = chunk.text.len();
+    segments.push(Segment {
+        offset: chunk.start,
+        text: chunk.text,
+    });
+    Slot::Segment {
+        segment: segments.len() - 1,
+        start: 0,
+        end,
+    }
+}
@@ -0,0 +1,49 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::chunker::ChunkingError;
+use crate::BLOCK_SIZE;
+
+/// Sizes of a file in bytes, characters and lines, see [`source_stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SourceStats {
+    pub bytes: usize,
+    pub chars: usize,
+    /// Lines as counted by [`str::lines`]: a last line without a newline counts, an empty
+    /// file has none.
+    pub lines: usize,
+}
+
+/// Count the bytes, characters and lines of the file at `path` in one streaming pass, e.g.
+/// to gauge from `chars / bytes` how much multi-byte text it holds before choosing between
+/// the bytes and characters strategies.
+///
+/// Characters are counted as the bytes starting a UTF-8 sequence, without validating the
+/// file: an invalid byte that is not a continuation byte counts as one character.
+pub fn source_stats(path: impl AsRef<Path>) -> Result<SourceStats, ChunkingError> {
+    let mut file = File::open(path)?;
+    let mut buffer = vec![0u8; BLOCK_SIZE];
+    let mut stats = SourceStats::default();
+    let mut last = None;
+
+    loop {
+        let n = match file.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        let block = &buffer[..n];
+        stats.bytes += n;
+        stats.chars += block.iter().filter(|&&b| b & 0xC0 != 0x80).count();
+        stats.lines += block.iter().filter(|&&b| b == b'\n').count();
+        last = block.last().copied();
+    }
+
+    // The last line need not end with a newline
+    if last.is_some_and(|b| b != b'\n') {
+        stats.lines += 1;
+    }
+    Ok(stats)
+}
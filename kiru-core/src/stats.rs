@@ -0,0 +1,79 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::Serialize;
+
+/// Counts of what happened to the chunks produced by a builder.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ChunkStats {
+    /// Chunks handed to the caller.
+    pub chunks: usize,
+    /// Chunks dropped for falling outside the size band.
+    pub dropped_out_of_band: usize,
+    /// Chunks dropped as near-duplicates of a recent chunk.
+    pub dropped_near_duplicates: usize,
+    /// Chunk ends and overlap starts the strategy moved onto a char boundary (see
+    /// [`Chunker::boundary_adjustments`](crate::Chunker::boundary_adjustments)).
+    pub boundary_adjustments: usize,
+    /// Chunks shortened to end on a whole word (see
+    /// [`BytesChunker::with_snap_to_word`](crate::BytesChunker::with_snap_to_word)).
+    pub word_snap_adjustments: usize,
+    /// Bytes cut from those chunks, in total.
+    pub word_snap_bytes: usize,
+    /// Sentences cut for being longer than the hard cap of the strategy (see
+    /// [`SentencesChunker::with_max_bytes`](crate::SentencesChunker::with_max_bytes)).
+    pub hard_splits: usize,
+    /// Sources that ended in the middle of a UTF-8 character, e.g. truncated files (see
+    /// [`IncompleteUtf8`](crate::IncompleteUtf8)).
+    pub truncated_sources: usize,
+}
+
+impl ChunkStats {
+    /// Average bytes cut from a chunk shortened to end on a whole word, `0.0` if none was.
+    pub fn word_snap_mean_bytes(&self) -> f64 {
+        if self.word_snap_adjustments == 0 {
+            return 0.0;
+        }
+        self.word_snap_bytes as f64 / self.word_snap_adjustments as f64
+    }
+}
+
+/// Live counters behind [`ChunkStats`], shared by every iteration of a builder and its
+/// clones, including those running on other threads.
+#[derive(Debug, Default)]
+pub(crate) struct StatsCounters {
+    chunks: AtomicUsize,
+    dropped_out_of_band: AtomicUsize,
+    dropped_near_duplicates: AtomicUsize,
+    truncated_sources: AtomicUsize,
+}
+
+impl StatsCounters {
+    pub(crate) fn record_chunk(&self) {
+        self.chunks.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_out_of_band(&self) {
+        self.dropped_out_of_band.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_near_duplicate(&self) {
+        self.dropped_near_duplicates.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_truncated_source(&self) {
+        self.truncated_sources.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> ChunkStats {
+        ChunkStats {
+            chunks: self.chunks.load(Ordering::Relaxed),
+            dropped_out_of_band: self.dropped_out_of_band.load(Ordering::Relaxed),
+            dropped_near_duplicates: self.dropped_near_duplicates.load(Ordering::Relaxed),
+            boundary_adjustments: 0,
+            word_snap_adjustments: 0,
+            word_snap_bytes: 0,
+            hard_splits: 0,
+            truncated_sources: self.truncated_sources.load(Ordering::Relaxed),
+        }
+    }
+}
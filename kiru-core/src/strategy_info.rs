@@ -0,0 +1,199 @@
+/// How the overlap a strategy is configured with relates to the overlap it produces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlapSemantics {
+    /// Every chunk after the first repeats exactly the configured overlap.
+    Exact,
+    /// The overlap may grow past the configured one, e.g. to start on a char boundary.
+    AtLeast,
+    /// The overlap may shrink below the configured one, e.g. to hold whole segments.
+    AtMost,
+    /// The overlap only follows the configured one roughly, in either direction.
+    Approximate,
+}
+
+impl OverlapSemantics {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OverlapSemantics::Exact => "exact",
+            OverlapSemantics::AtLeast => "at_least",
+            OverlapSemantics::AtMost => "at_most",
+            OverlapSemantics::Approximate => "approximate",
+        }
+    }
+}
+
+/// Machine-readable semantics of a chunking strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrategyInfo {
+    /// Name of the strategy, as in its `ChunkerBuilder::by_*` constructor.
+    pub name: &'static str,
+    /// Unit of the chunk size.
+    pub unit: &'static str,
+    /// Unit of the overlap, when it differs from the chunk size's.
+    pub overlap_unit: &'static str,
+    pub overlap_semantics: OverlapSemantics,
+    /// Whether a cut may fall inside a grapheme cluster (e.g. between a letter and its
+    /// combining accent). Cuts never fall inside a UTF-8 char.
+    pub splits_graphemes: bool,
+    /// Whether the strategy chunks a stream without reading it whole.
+    pub streaming_supported: bool,
+}
+
+/// Semantics of every strategy of [`crate::ChunkerBuilder`], with their default options
+/// (e.g. sentence overlap changes the overlap of the bytes strategy).
+pub const STRATEGIES: [StrategyInfo; 17] = [
+    // The chunk end and the overlap start are snapped back to char boundaries
+    StrategyInfo {
+        name: "bytes",
+        unit: "bytes",
+        overlap_unit: "bytes",
+        overlap_semantics: OverlapSemantics::AtLeast,
+        splits_graphemes: true,
+        streaming_supported: true,
+    },
+    // A bytes budget estimated from the tokens, with chunk ends and overlap starts moved back
+    // onto word starts when one is near
+    StrategyInfo {
+        name: "approx_tokens",
+        unit: "tokens",
+        overlap_unit: "tokens",
+        overlap_semantics: OverlapSemantics::Approximate,
+        splits_graphemes: true,
+        streaming_supported: true,
+    },
+    StrategyInfo {
+        name: "characters",
+        unit: "characters",
+        overlap_unit: "characters",
+        overlap_semantics: OverlapSemantics::Exact,
+        splits_graphemes: true,
+        streaming_supported: true,
+    },
+    StrategyInfo {
+        name: "graphemes",
+        unit: "graphemes",
+        overlap_unit: "graphemes",
+        overlap_semantics: OverlapSemantics::Exact,
+        splits_graphemes: false,
+        streaming_supported: true,
+    },
+    // Overlap starts are snapped back to char boundaries, like cuts
+    StrategyInfo {
+        name: "boundaries",
+        unit: "bytes",
+        overlap_unit: "bytes",
+        overlap_semantics: OverlapSemantics::AtLeast,
+        splits_graphemes: true,
+        streaming_supported: true,
+    },
+    StrategyInfo {
+        name: "json_array",
+        unit: "elements",
+        overlap_unit: "elements",
+        overlap_semantics: OverlapSemantics::Exact,
+        splits_graphemes: false,
+        streaming_supported: true,
+    },
+    StrategyInfo {
+        name: "lines",
+        unit: "lines",
+        overlap_unit: "lines",
+        overlap_semantics: OverlapSemantics::Exact,
+        splits_graphemes: false,
+        streaming_supported: true,
+    },
+    // Fewer lines overlap when the next line would not fit otherwise
+    StrategyInfo {
+        name: "lines_packed",
+        unit: "bytes",
+        overlap_unit: "lines",
+        overlap_semantics: OverlapSemantics::AtMost,
+        splits_graphemes: false,
+        streaming_supported: true,
+    },
+    StrategyInfo {
+        name: "paragraphs",
+        unit: "paragraphs",
+        overlap_unit: "paragraphs",
+        overlap_semantics: OverlapSemantics::Exact,
+        splits_graphemes: false,
+        streaming_supported: true,
+    },
+    // The overlap is made of the whole trailing segments that fit, and segments longer than
+    // the chunk size are hard cut
+    StrategyInfo {
+        name: "predicate",
+        unit: "bytes",
+        overlap_unit: "bytes",
+        overlap_semantics: OverlapSemantics::AtMost,
+        splits_graphemes: true,
+        streaming_supported: true,
+    },
+    // The overlap is made of the whole trailing splits that fit, splits too long to pack do
+    // not overlap, and the empty separator splits between chars
+    StrategyInfo {
+        name: "recursive",
+        unit: "bytes",
+        overlap_unit: "bytes",
+        overlap_semantics: OverlapSemantics::AtMost,
+        splits_graphemes: true,
+        streaming_supported: false,
+    },
+    StrategyInfo {
+        name: "regex",
+        unit: "pieces",
+        overlap_unit: "pieces",
+        overlap_semantics: OverlapSemantics::Exact,
+        splits_graphemes: false,
+        streaming_supported: true,
+    },
+    // A chunk of no more units than the overlap repeats all but its first unit
+    StrategyInfo {
+        name: "semantic",
+        unit: "sentences",
+        overlap_unit: "sentences",
+        overlap_semantics: OverlapSemantics::AtMost,
+        splits_graphemes: false,
+        streaming_supported: true,
+    },
+    StrategyInfo {
+        name: "sentences",
+        unit: "sentences",
+        overlap_unit: "sentences",
+        overlap_semantics: OverlapSemantics::Exact,
+        splits_graphemes: false,
+        streaming_supported: true,
+    },
+    // With the `tokenizers` feature. A char encoded as several tokens is held whole by the
+    // chunks on both sides of a cut, and the source is encoded whole
+    StrategyInfo {
+        name: "tokens",
+        unit: "tokens",
+        overlap_unit: "tokens",
+        overlap_semantics: OverlapSemantics::Exact,
+        splits_graphemes: true,
+        streaming_supported: false,
+    },
+    // Windows start and end on the first timed lines at or after the configured times
+    StrategyInfo {
+        name: "transcript",
+        unit: "seconds",
+        overlap_unit: "seconds",
+        overlap_semantics: OverlapSemantics::Approximate,
+        splits_graphemes: false,
+        streaming_supported: true,
+    },
+    StrategyInfo {
+        name: "words",
+        unit: "words",
+        overlap_unit: "words",
+        overlap_semantics: OverlapSemantics::Exact,
+        splits_graphemes: false,
+        streaming_supported: true,
+    },
+];
+
+/// Semantics of the strategy called `name`, if there is one.
+pub fn strategy_info(name: &str) -> Option<&'static StrategyInfo> {
+    STRATEGIES.iter().find(|info| info.name == name)
+}
@@ -1,102 +1,207 @@
 use std::fs::File;
-use std::io::Read;
+use std::io::{self, Read};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
 
+use serde::{Deserialize, Serialize};
+
+use crate::stats::StatsCounters;
 use crate::ChunkingError;
 use crate::Source;
 
 use reqwest::blocking::Response;
 
-pub struct FileUtf8BlockReader {
-    reader: File,
-    block_size: usize,
-    leftover: Vec<u8>,
+/// Size of the blocks in which sources are read.
+pub const BLOCK_SIZE: usize = 8 * 1024;
+
+/// Handle to the error that cut a block reader's stream short. It is shared with the
+/// reader, so the error can still be inspected once the reader was handed to a chunker.
+#[derive(Clone, Default)]
+pub struct ReadErrorSlot(Arc<Mutex<Option<ChunkingError>>>);
+
+impl ReadErrorSlot {
+    fn set(&self, error: impl Into<ChunkingError>) {
+        *self.0.lock().unwrap() = Some(error.into());
+    }
+
+    /// Take the recorded error, if the reader stopped because of one.
+    pub fn take(&self) -> Option<ChunkingError> {
+        self.0.lock().unwrap().take()
+    }
+}
+
+/// A source read block by block, e.g. a database cursor. Chunk it with
+/// [`crate::ChunkerWithStrategy::chunk_blocks`], which all of kiru's own sources go through.
+pub trait SourceReader {
+    /// The next block of text, or `None` once the source is exhausted. Chunks run across
+    /// blocks, so blocks can be of any size. The reader is not called again after an error.
+    fn next_block(&mut self) -> Result<Option<String>, ChunkingError>;
+}
+
+/// The blocks of a [`SourceReader`] as the chunkers stream them: the stream ends at the
+/// first error, which is recorded in [`Self::read_error`].
+pub(crate) struct Blocks<R> {
+    reader: R,
+    error: ReadErrorSlot,
     done: bool,
 }
 
-impl FileUtf8BlockReader {
-    pub fn new(path: &str, block_size: usize) -> Result<Self, ChunkingError> {
-        let file = File::open(path)?;
-        Ok(Self {
-            reader: file,
-            block_size,
-            leftover: vec![],
+impl<R: SourceReader> Blocks<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader,
+            error: ReadErrorSlot::default(),
             done: false,
-        })
+        }
+    }
+
+    pub(crate) fn read_error(&self) -> ReadErrorSlot {
+        self.error.clone()
     }
 }
 
-impl Iterator for FileUtf8BlockReader {
+impl<R: SourceReader> Iterator for Blocks<R> {
     type Item = String;
 
-    fn next(&mut self) -> Option<Self::Item> {
+    fn next(&mut self) -> Option<String> {
         if self.done {
             return None;
         }
-
-        // Start with leftover bytes from previous iteration
-        let mut buffer = Vec::with_capacity(self.block_size + 4); // +4 for potential UTF-8 leftover
-        buffer.extend_from_slice(&self.leftover);
-        self.leftover.clear();
-
-        // Always try to read exactly block_size bytes
-        let mut temp = vec![0u8; self.block_size];
-        let n = match self.reader.read(&mut temp) {
-            Ok(0) => {
+        match self.reader.next_block() {
+            Ok(Some(block)) => Some(block),
+            Ok(None) => {
                 self.done = true;
-                0
+                None
             }
-            Ok(n) => n,
-            Err(_) => {
+            Err(e) => {
+                self.error.set(e);
                 self.done = true;
-                return None;
+                None
             }
-        };
-
-        // If we read nothing and have no leftover, we're done
-        if n == 0 && buffer.is_empty() {
-            return None;
         }
+    }
+}
 
-        buffer.extend_from_slice(&temp[..n]);
+/// What to do with the bytes of a character cut off by the end of a source, e.g. a file
+/// truncated mid-character. Such sources are counted in
+/// [`ChunkStats::truncated_sources`](crate::ChunkStats::truncated_sources) either way.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IncompleteUtf8 {
+    /// Leave them out of the text.
+    #[default]
+    Drop,
+    /// End the text with a single `U+FFFD` replacement character for them.
+    Replace,
+    /// End the stream with [`ChunkingError::IncompleteUtf8`], after the chunks of the rest.
+    Error,
+}
 
-        // Validate UTF-8
-        let valid_up_to = match std::str::from_utf8(&buffer) {
-            Ok(_) => buffer.len(),
-            Err(e) => {
-                let valid = e.valid_up_to();
-                // Save incomplete UTF-8 sequence for next iteration
-                // (At most 3 bytes for incomplete UTF-8 sequence)
-                self.leftover.extend_from_slice(&buffer[valid..]);
-                valid
-            }
-        };
+/// Reads any byte source in blocks of valid UTF-8, carrying incomplete sequences over to
+/// the next block. A read error ends the stream and is recorded in [`Self::read_error`].
+pub struct Utf8BlockReader<R: Read> {
+    reader: R,
+    block_size: usize,
+    leftover: Vec<u8>,
+    done: bool,
+    error: ReadErrorSlot,
+    ascii: bool,
+    incomplete_utf8: IncompleteUtf8,
+    stats: Option<Arc<StatsCounters>>,
+    /// Bytes of the source read and accounted for by the blocks so far.
+    consumed: usize,
+}
 
-        // If we have no valid UTF-8 at all, something is wrong
-        if valid_up_to == 0 {
-            if self.done {
-                return None;
-            }
-            // This shouldn't normally happen, but skip this byte and continue
-            eprintln!("Warning: No valid UTF-8 found in block");
-            return self.next();
+pub type FileUtf8BlockReader = Utf8BlockReader<RetryReader<File>>;
+pub type HttpUtf8BlockReader = Utf8BlockReader<Response>;
+
+impl<R: Read> Utf8BlockReader<R> {
+    pub fn from_reader(reader: R, block_size: usize) -> Self {
+        Self {
+            reader,
+            block_size,
+            leftover: vec![],
+            done: false,
+            error: ReadErrorSlot::default(),
+            ascii: false,
+            incomplete_utf8: IncompleteUtf8::Drop,
+            stats: None,
+            consumed: 0,
         }
+    }
 
-        let text = std::str::from_utf8(&buffer[..valid_up_to])
-            .expect("Already validated")
-            .to_string();
+    /// Trust that the source is pure ASCII: blocks are then taken as they are read, without
+    /// looking for sequences cut at their end or decoding them as UTF-8, only checked to be
+    /// ASCII. A wrong assertion ends the stream with [`ChunkingError::NonAscii`] at the
+    /// first other byte, after the blocks of the text before it.
+    pub fn with_ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
 
-        Some(text)
+    /// Handle the bytes of a character cut off by the end of the source as `policy` says.
+    pub fn with_incomplete_utf8(mut self, policy: IncompleteUtf8) -> Self {
+        self.incomplete_utf8 = policy;
+        self
+    }
+
+    /// Count a source cut off mid-character in `stats`.
+    pub(crate) fn with_stats(mut self, stats: Arc<StatsCounters>) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Handle to the error that interrupted the stream, if any.
+    pub fn read_error(&self) -> ReadErrorSlot {
+        self.error.clone()
+    }
+
+    /// Read the blocks along with where each one starts in the source and how many bytes
+    /// were carried over past its end, to check how blocks are cut.
+    pub fn debug_blocks(self) -> DebugBlocks<R> {
+        DebugBlocks { reader: self }
+    }
+
+    /// The last block of a source ending with the first `bytes` bytes of a character.
+    fn end_mid_character(&mut self, bytes: usize) -> Option<String> {
+        self.consumed += bytes;
+        if let Some(stats) = &self.stats {
+            stats.record_truncated_source();
+        }
+        match self.incomplete_utf8 {
+            IncompleteUtf8::Drop => None,
+            IncompleteUtf8::Replace => Some(char::REPLACEMENT_CHARACTER.to_string()),
+            IncompleteUtf8::Error => {
+                self.error.set(ChunkingError::IncompleteUtf8 { bytes });
+                None
+            }
+        }
     }
 }
 
-pub struct HttpUtf8BlockReader {
-    response: Response,
-    block_size: usize,
-    leftover: Vec<u8>,
-    done: bool,
+impl Utf8BlockReader<RetryReader<File>> {
+    pub fn new(path: &str, block_size: usize) -> Result<Self, ChunkingError> {
+        Self::with_retry(path, block_size, RetryPolicy::none())
+    }
+
+    /// Open the file at `path`, retrying the open and then every read that fails with a
+    /// transient error as `policy` says.
+    pub fn with_retry(
+        path: &str,
+        block_size: usize,
+        policy: RetryPolicy,
+    ) -> Result<Self, ChunkingError> {
+        let file = policy.run(|| File::open(path))?;
+        Ok(Self::from_reader(
+            RetryReader::new(file, policy),
+            block_size,
+        ))
+    }
 }
 
-impl HttpUtf8BlockReader {
+impl Utf8BlockReader<Response> {
     pub fn new(url: &str, block_size: usize) -> Result<Self, ChunkingError> {
         // Create a blocking HTTP client and send a GET request
         let client = reqwest::blocking::Client::builder()
@@ -117,16 +222,11 @@ impl HttpUtf8BlockReader {
             )));
         }
 
-        Ok(Self {
-            response,
-            block_size,
-            leftover: vec![],
-            done: false,
-        })
+        Ok(Self::from_reader(response, block_size))
     }
 }
 
-impl Iterator for HttpUtf8BlockReader {
+impl<R: Read> Iterator for Utf8BlockReader<R> {
     type Item = String;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -139,15 +239,23 @@ impl Iterator for HttpUtf8BlockReader {
         buffer.extend_from_slice(&self.leftover);
         self.leftover.clear();
 
-        // Try to read exactly block_size bytes from the HTTP response
+        // Always try to read exactly block_size bytes
         let mut temp = vec![0u8; self.block_size];
-        let n = match self.response.read(&mut temp) {
+        let read = loop {
+            match self.reader.read(&mut temp) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                read => break read,
+            }
+        };
+        let n = match read {
             Ok(0) => {
                 self.done = true;
                 0
             }
             Ok(n) => n,
-            Err(_) => {
+            Err(e) => {
+                // Keep the error for whoever consumes the chunks of what was read so far
+                self.error.set(e);
                 self.done = true;
                 return None;
             }
@@ -158,12 +266,41 @@ impl Iterator for HttpUtf8BlockReader {
             return None;
         }
 
+        // The source ended in the middle of a character
+        if n == 0 && !self.ascii {
+            if let Err(e) = std::str::from_utf8(&buffer) {
+                if e.valid_up_to() == 0 && e.error_len().is_none() {
+                    return self.end_mid_character(buffer.len());
+                }
+            }
+        }
+
+        if self.ascii {
+            temp.truncate(n);
+            if !temp.is_ascii() {
+                // End the stream after the text before the first other byte
+                let valid = temp.iter().position(|b| !b.is_ascii()).unwrap_or(n);
+                self.error.set(ChunkingError::NonAscii {
+                    offset: self.consumed + valid,
+                });
+                self.done = true;
+                temp.truncate(valid);
+            }
+            self.consumed += temp.len();
+            if temp.is_empty() {
+                return None;
+            }
+            return Some(String::from_utf8(temp).expect("ASCII is valid UTF-8"));
+        }
+
         buffer.extend_from_slice(&temp[..n]);
 
         // Validate UTF-8
+        let mut incomplete = false;
         let valid_up_to = match std::str::from_utf8(&buffer) {
             Ok(_) => buffer.len(),
             Err(e) => {
+                incomplete = e.error_len().is_none();
                 let valid = e.valid_up_to();
                 // Save incomplete UTF-8 sequence for next iteration
                 // (At most 3 bytes for incomplete UTF-8 sequence)
@@ -177,11 +314,15 @@ impl Iterator for HttpUtf8BlockReader {
             if self.done {
                 return None;
             }
-            // This shouldn't normally happen, but skip this byte and continue
-            eprintln!("Warning: No valid UTF-8 found in block");
+            // A read can end inside the first character, e.g. of a file still being written
+            if !incomplete {
+                // This shouldn't normally happen, but skip this byte and continue
+                eprintln!("Warning: No valid UTF-8 found in block");
+            }
             return self.next();
         }
 
+        self.consumed += valid_up_to;
         let text = std::str::from_utf8(&buffer[..valid_up_to])
             .expect("Already validated")
             .to_string();
@@ -190,6 +331,170 @@ impl Iterator for HttpUtf8BlockReader {
     }
 }
 
+impl<R: Read> SourceReader for Utf8BlockReader<R> {
+    fn next_block(&mut self) -> Result<Option<String>, ChunkingError> {
+        match self.next() {
+            Some(block) => Ok(Some(block)),
+            None => self.error.take().map_or(Ok(None), Err),
+        }
+    }
+}
+
+/// Reads a file that is still being written, like `tail -f`: at the end of the data written
+/// so far, it polls for more every `poll_interval` instead of ending, until `stop` is set.
+/// What was written before `stop` was set is still read.
+pub struct FollowReader<R> {
+    reader: R,
+    poll_interval: Duration,
+    stop: Arc<AtomicBool>,
+}
+
+impl<R: Read> FollowReader<R> {
+    pub fn new(reader: R, poll_interval: Duration, stop: Arc<AtomicBool>) -> Self {
+        Self {
+            reader,
+            poll_interval,
+            stop,
+        }
+    }
+}
+
+impl<R: Read> Read for FollowReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            // Checked before reading, so that the data written before the stop is read
+            let stopping = self.stop.load(Ordering::Relaxed);
+            let n = self.reader.read(buf)?;
+            if n > 0 || stopping || buf.is_empty() {
+                return Ok(n);
+            }
+            thread::sleep(self.poll_interval);
+        }
+    }
+}
+
+/// How many times an IO operation failing with a transient error is tried before its error
+/// is kept, and how long to wait after the first failure; the wait doubles after each one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub attempts: usize,
+    pub delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Try up to `attempts` times in all, at least once.
+    pub fn new(attempts: usize, delay: Duration) -> Self {
+        Self {
+            attempts: attempts.max(1),
+            delay,
+        }
+    }
+
+    /// Try once, keeping the first error.
+    pub fn none() -> Self {
+        Self::new(1, Duration::ZERO)
+    }
+
+    /// Run `operation` until it succeeds, fails with a permanent error (see
+    /// [`is_transient`]) or was tried `attempts` times.
+    pub fn run<T>(&self, mut operation: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+        let mut delay = self.delay;
+        let mut attempt = 1;
+        loop {
+            match operation() {
+                Err(e) if attempt < self.attempts && is_transient(&e) => {
+                    thread::sleep(delay);
+                    delay = delay.saturating_mul(2);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Whether trying again may get past `error`, e.g. a timeout or a busy network share, as
+/// opposed to a missing file, a denied permission or invalid data.
+pub fn is_transient(error: &io::Error) -> bool {
+    !matches!(
+        error.kind(),
+        io::ErrorKind::NotFound
+            | io::ErrorKind::PermissionDenied
+            | io::ErrorKind::InvalidInput
+            | io::ErrorKind::InvalidData
+            | io::ErrorKind::Unsupported
+            | io::ErrorKind::UnexpectedEof
+    )
+}
+
+/// Retries every read of `reader` that fails with a transient error as `policy` says; each
+/// read gets the full number of attempts.
+pub struct RetryReader<R> {
+    reader: R,
+    policy: RetryPolicy,
+}
+
+impl<R: Read> RetryReader<R> {
+    pub fn new(reader: R, policy: RetryPolicy) -> Self {
+        Self { reader, policy }
+    }
+}
+
+impl<R: Read> Read for RetryReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let reader = &mut self.reader;
+        self.policy.run(|| reader.read(buf))
+    }
+}
+
+/// A block read by [`Utf8BlockReader::debug_blocks`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DebugBlock {
+    pub text: String,
+    /// Byte offset in the source of the start of the block, i.e. of the bytes carried over
+    /// from the previous block.
+    pub file_offset: usize,
+    /// Bytes of the source the block stands for, the length of `text` unless a character cut
+    /// off by the end of the source was replaced or dropped.
+    pub valid_up_to: usize,
+    /// Bytes of a character cut by the end of the block, carried over to the next one.
+    pub leftover_len: usize,
+}
+
+/// The blocks of a [`Utf8BlockReader`], with how they were cut.
+pub struct DebugBlocks<R: Read> {
+    reader: Utf8BlockReader<R>,
+}
+
+impl<R: Read> DebugBlocks<R> {
+    /// Handle to the error that interrupted the stream, if any.
+    pub fn read_error(&self) -> ReadErrorSlot {
+        self.reader.read_error()
+    }
+}
+
+impl<R: Read> Iterator for DebugBlocks<R> {
+    type Item = DebugBlock;
+
+    fn next(&mut self) -> Option<DebugBlock> {
+        // A character cut off by the end of the source and dropped ends the blocks
+        let file_offset = self.reader.consumed;
+        let text = self.reader.next()?;
+        Some(DebugBlock {
+            text,
+            file_offset,
+            valid_up_to: self.reader.consumed - file_offset,
+            leftover_len: self.reader.leftover.len(),
+        })
+    }
+}
+
 pub enum StreamType {
     File(FileUtf8BlockReader),
     Text(std::vec::IntoIter<String>),
@@ -198,9 +503,17 @@ pub enum StreamType {
 
 impl StreamType {
     pub fn from_source(source: &Source) -> Result<Self, ChunkingError> {
+        Self::from_source_with_retry(source, RetryPolicy::none())
+    }
+
+    /// Open `source`, retrying the open and reads of a file as `file_retry` says.
+    pub fn from_source_with_retry(
+        source: &Source,
+        file_retry: RetryPolicy,
+    ) -> Result<Self, ChunkingError> {
         match source {
             Source::File(path) => {
-                let reader = FileUtf8BlockReader::new(path, 1024 * 8)?;
+                let reader = FileUtf8BlockReader::with_retry(path, BLOCK_SIZE, file_retry)?;
                 Ok(StreamType::File(reader))
             }
             Source::Text(text) => {
@@ -208,11 +521,48 @@ impl StreamType {
                 Ok(StreamType::Text(iterator))
             }
             Source::Http(url) => {
-                let reader = HttpUtf8BlockReader::new(url, 1024 * 8)?;
+                let reader = HttpUtf8BlockReader::new(url, BLOCK_SIZE)?;
                 Ok(StreamType::Http(reader))
             }
         }
     }
+
+    /// Read a file or HTTP source as pure ASCII, see [`Utf8BlockReader::with_ascii`].
+    pub fn with_ascii(self, ascii: bool) -> Self {
+        match self {
+            StreamType::File(r) => StreamType::File(r.with_ascii(ascii)),
+            StreamType::Text(r) => StreamType::Text(r),
+            StreamType::Http(r) => StreamType::Http(r.with_ascii(ascii)),
+        }
+    }
+
+    /// Handle the end of a file or HTTP source as `policy` says, see
+    /// [`Utf8BlockReader::with_incomplete_utf8`].
+    pub fn with_incomplete_utf8(self, policy: IncompleteUtf8) -> Self {
+        match self {
+            StreamType::File(r) => StreamType::File(r.with_incomplete_utf8(policy)),
+            StreamType::Text(r) => StreamType::Text(r),
+            StreamType::Http(r) => StreamType::Http(r.with_incomplete_utf8(policy)),
+        }
+    }
+
+    /// Count a file or HTTP source cut off mid-character in `stats`.
+    pub(crate) fn with_stats(self, stats: Arc<StatsCounters>) -> Self {
+        match self {
+            StreamType::File(r) => StreamType::File(r.with_stats(stats)),
+            StreamType::Text(r) => StreamType::Text(r),
+            StreamType::Http(r) => StreamType::Http(r.with_stats(stats)),
+        }
+    }
+
+    /// Handle to the IO error that interrupted the stream, if any.
+    pub fn read_error(&self) -> ReadErrorSlot {
+        match self {
+            StreamType::File(r) => r.read_error(),
+            StreamType::Text(_) => ReadErrorSlot::default(),
+            StreamType::Http(r) => r.read_error(),
+        }
+    }
 }
 
 impl Iterator for StreamType {
@@ -226,6 +576,16 @@ impl Iterator for StreamType {
     }
 }
 
+impl SourceReader for StreamType {
+    fn next_block(&mut self) -> Result<Option<String>, ChunkingError> {
+        match self {
+            StreamType::File(r) => r.next_block(),
+            StreamType::Text(r) => Ok(r.next()),
+            StreamType::Http(r) => r.next_block(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
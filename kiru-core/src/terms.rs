@@ -0,0 +1,16 @@
+use std::collections::HashMap;
+
+/// Lowercased word counts of `text`, for a bag-of-words view of a chunk.
+///
+/// Words are maximal runs of alphanumeric chars: whitespace, punctuation and apostrophes
+/// all separate words, so "Don't" counts as "don" and "t".
+pub fn term_counts(text: &str) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for word in text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        *counts.entry(word.to_lowercase()).or_insert(0) += 1;
+    }
+    counts
+}
@@ -0,0 +1,178 @@
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use tokenizers::Tokenizer;
+
+use crate::chunker::{
+    ceil_char_boundary, floor_char_boundary, Chunk, ChunkKind, Chunker, ChunkingError,
+};
+
+/// Chunks of at most `chunk_size` tokens of a HuggingFace tokenizer, each repeating the
+/// last `overlap` tokens of the previous one.
+///
+/// The text of a chunk is the source text its tokens were encoded from, from the start of
+/// its first token to the end of its last one, so chunks are slices of the source. A char
+/// encoded as several tokens (e.g. by byte-level BPE) is held whole by every chunk that
+/// holds one of its tokens. Text between tokens that no token covers (e.g. the whitespace
+/// a WordPiece tokenizer drops) is kept inside a chunk, but not at its ends.
+///
+/// Tokens depend on the text around them, so the source is read whole and encoded at once.
+/// The source is encoded without special tokens, and without the truncation or padding the
+/// tokenizer may be configured with.
+///
+/// The tokenizer is held in an [`Arc`], so that chunkers can share one tokenizer loaded once
+/// (see [`TokensChunker::load_tokenizer`]). It is only read while encoding, so chunkers
+/// sharing it can chunk sources on different threads at once.
+#[derive(Clone)]
+pub struct TokensChunker {
+    tokenizer: Arc<Tokenizer>,
+    chunk_size: usize,
+    overlap: usize,
+    /// Error of the tokenizer on the source, shared by clones so that the builder can report
+    /// it once the source yields no more chunks. Each source gets its own, see
+    /// [`Chunker::for_source`].
+    error: Arc<Mutex<Option<ChunkingError>>>,
+}
+
+impl TokensChunker {
+    /// Chunk with `tokenizer`, owned or shared. A shared tokenizer configured with truncation
+    /// or padding is copied to clear them.
+    pub fn new(
+        tokenizer: impl Into<Arc<Tokenizer>>,
+        chunk_size: usize,
+        overlap: usize,
+    ) -> Result<Self, ChunkingError> {
+        if chunk_size == 0 || overlap >= chunk_size {
+            return Err(ChunkingError::InvalidArguments {
+                chunk_size,
+                overlap,
+            });
+        }
+        let mut tokenizer = tokenizer.into();
+        if tokenizer.get_truncation().is_some() || tokenizer.get_padding().is_some() {
+            clear_truncation_and_padding(Arc::make_mut(&mut tokenizer))?;
+        }
+
+        Ok(Self {
+            tokenizer,
+            chunk_size,
+            overlap,
+            error: Arc::default(),
+        })
+    }
+
+    /// Load the tokenizer from a `tokenizer.json` file, as saved by the `tokenizers`
+    /// library.
+    pub fn from_file(
+        path: impl AsRef<Path>,
+        chunk_size: usize,
+        overlap: usize,
+    ) -> Result<Self, ChunkingError> {
+        Self::new(Self::load_tokenizer(path)?, chunk_size, overlap)
+    }
+
+    /// Load a tokenizer from a `tokenizer.json` file once, to share it between chunkers
+    /// created with [`TokensChunker::new`], which then neither reload nor copy it.
+    pub fn load_tokenizer(path: impl AsRef<Path>) -> Result<Arc<Tokenizer>, ChunkingError> {
+        let mut tokenizer =
+            Tokenizer::from_file(path).map_err(|e| ChunkingError::Tokenizer(e.to_string()))?;
+        clear_truncation_and_padding(&mut tokenizer)?;
+        Ok(Arc::new(tokenizer))
+    }
+
+    /// Byte ranges of `text` that the tokens of `text` were encoded from.
+    fn token_spans(&self, text: &str) -> Result<Vec<(usize, usize)>, ChunkingError> {
+        let encoding = self
+            .tokenizer
+            .encode(text, false)
+            .map_err(|e| ChunkingError::Tokenizer(e.to_string()))?;
+        Ok(encoding.get_offsets().to_vec())
+    }
+}
+
+/// Encode whole sources with `tokenizer`, whatever it was configured with.
+fn clear_truncation_and_padding(tokenizer: &mut Tokenizer) -> Result<(), ChunkingError> {
+    tokenizer
+        .with_truncation(None)
+        .map_err(|e| ChunkingError::Tokenizer(e.to_string()))?;
+    tokenizer.with_padding(None);
+    Ok(())
+}
+
+impl Chunker for TokensChunker {
+    fn chunk_size(&self) -> Option<usize> {
+        Some(self.chunk_size)
+    }
+
+    fn measure(&self, text: &str) -> usize {
+        self.token_spans(text).map_or(0, |spans| spans.len())
+    }
+
+    fn take_error(&self) -> Option<ChunkingError> {
+        self.error.lock().unwrap().take()
+    }
+
+    fn for_source(&self) -> Self {
+        Self {
+            error: Arc::default(),
+            ..self.clone()
+        }
+    }
+
+    /// Reads the source whole, into a string that may grow to twice its size, and holds its
+    /// encoding: some 64 bytes for each token of a few bytes.
+    fn estimate_memory(&self, source_len: usize) -> usize {
+        2 * source_len + 16 * source_len
+    }
+
+    fn chunk_string_with_offsets(self, input: String) -> impl Iterator<Item = Chunk> {
+        let spans = self.token_spans(&input).unwrap_or_else(|e| {
+            *self.error.lock().unwrap() = Some(e);
+            Vec::new()
+        });
+        let step = self.chunk_size - self.overlap;
+        let mut first_token = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || {
+            if first_token >= spans.len() {
+                return None;
+            }
+            let last_token = (first_token + self.chunk_size).min(spans.len());
+            let start = floor_char_boundary(&input, spans[first_token].0);
+            let end = ceil_char_boundary(&input, spans[last_token - 1].1).max(start);
+            // The last chunk has no chunk after it to overlap with
+            first_token = if last_token == spans.len() {
+                spans.len()
+            } else {
+                first_token + step
+            };
+
+            let chunk = Chunk {
+                text: input[start..end].to_string(),
+                start,
+                end,
+                index,
+                lines: None,
+                chars: None,
+                running_hash: None,
+                links: None,
+                hierarchy: None,
+                source_stamp: None,
+                header_len: 0,
+                prefix_len: 0,
+                suffix_len: 0,
+                kind: ChunkKind::Main,
+            };
+            index += 1;
+            Some(chunk)
+        })
+    }
+
+    fn chunk_stream_with_offsets(
+        self,
+        input: impl Iterator<Item = String>,
+    ) -> impl Iterator<Item = Chunk> {
+        self.chunk_string_with_offsets(input.collect())
+    }
+}
@@ -0,0 +1,211 @@
+use regex::Regex;
+
+use crate::chunker::{
+    peak_buffered, stream_memory, Chunk, ChunkKind, Chunker, ChunkingError, StringBuffer,
+};
+use crate::BLOCK_SIZE;
+
+/// Matches SRT/VTT cue times (`00:01:02,500 --> ...`) and `[01:02]`-style line prefixes.
+const DEFAULT_TIMESTAMP_PATTERN: &str = r"^\[?((?:\d+:)?\d{1,2}:\d{2}(?:[.,]\d+)?)";
+
+struct TranscriptChunkIndices {
+    start: usize,
+    end: usize,
+    next_start: usize,
+}
+
+/// Cuts time-stamped transcript lines into windows of `window_secs` seconds of content, each
+/// window after the first starting `overlap_secs` before the end of the previous one.
+///
+/// A timed line is one holding a timestamp (see [`Self::with_timestamp_pattern`]); lines
+/// without one attach to the timed line before them. A chunk starts at a timed line and
+/// takes every following line up to the first timed line at least `window_secs` later. The
+/// next chunk starts at the first timed line at least `window_secs - overlap_secs` after the
+/// start of the previous one.
+#[derive(Clone)]
+pub struct TranscriptChunker {
+    window_secs: f64,
+    overlap_secs: f64,
+    pattern: Regex,
+}
+
+impl TranscriptChunker {
+    pub fn new(window_secs: f64, overlap_secs: f64) -> Result<Self, ChunkingError> {
+        if !(window_secs > 0.0 && 0.0 <= overlap_secs && overlap_secs < window_secs) {
+            return Err(ChunkingError::InvalidTimeWindow {
+                window_secs,
+                overlap_secs,
+            });
+        }
+
+        Ok(Self {
+            window_secs,
+            overlap_secs,
+            pattern: Regex::new(DEFAULT_TIMESTAMP_PATTERN).expect("Bug: invalid default pattern"),
+        })
+    }
+
+    /// Find timestamps with `pattern` instead of the default, which matches SRT/VTT cue times
+    /// and `[mm:ss]` prefixes at the start of a line. The timestamp is taken from the first
+    /// capture group (or the whole match) and must read `[[hours:]minutes:]seconds`, with an
+    /// optional `.` or `,` fraction. Anchor the pattern with `^` to only match leading ones.
+    pub fn with_timestamp_pattern(mut self, pattern: &str) -> Result<Self, ChunkingError> {
+        self.pattern =
+            Regex::new(pattern).map_err(|e| ChunkingError::InvalidPattern(e.to_string()))?;
+        Ok(self)
+    }
+
+    /// Seconds of the timestamp of `line`, if it has a readable one.
+    fn timestamp(&self, line: &str) -> Option<f64> {
+        let captures = self.pattern.captures(line)?;
+        let timestamp = captures.get(1).or(captures.get(0))?.as_str();
+
+        let mut parts = timestamp.rsplit(':');
+        let mut seconds: f64 = parts.next()?.replace(',', ".").parse().ok()?;
+        for (part, scale) in parts.zip([60.0, 3600.0]) {
+            seconds += part.parse::<u32>().ok()? as f64 * scale;
+        }
+        Some(seconds)
+    }
+
+    /// Compute the chunk starting at `start`, or `None` once done or when more text is
+    /// needed (`done` tells whether `buffer` runs to the end of the source).
+    fn next_chunk_indices(
+        &self,
+        buffer: &str,
+        start: usize,
+        done: bool,
+    ) -> Option<TranscriptChunkIndices> {
+        if start >= buffer.len() {
+            return None;
+        }
+
+        let mut window_start = None;
+        let mut next_start = None;
+        let mut line_start = start;
+
+        while line_start < buffer.len() {
+            let line_end = match buffer[line_start..].find('\n') {
+                Some(i) => line_start + i + 1,
+                None if done => buffer.len(),
+                None => return None,
+            };
+
+            match (window_start, self.timestamp(&buffer[line_start..line_end])) {
+                (None, time) => window_start = time,
+                (Some(t0), Some(t)) => {
+                    if next_start.is_none() && t >= t0 + self.window_secs - self.overlap_secs {
+                        next_start = Some(line_start);
+                    }
+                    if t >= t0 + self.window_secs {
+                        return Some(TranscriptChunkIndices {
+                            start,
+                            end: line_start,
+                            next_start: next_start.unwrap_or(line_start),
+                        });
+                    }
+                }
+                (Some(_), None) => {}
+            }
+            line_start = line_end;
+        }
+
+        if !done {
+            return None;
+        }
+
+        // Last chunk
+        Some(TranscriptChunkIndices {
+            start,
+            end: buffer.len(),
+            next_start: buffer.len(),
+        })
+    }
+}
+
+impl Chunker for TranscriptChunker {
+    /// Assumes a window of the transcript fits in a block: a longer one is buffered whole.
+    fn estimate_memory(&self, source_len: usize) -> usize {
+        let buffered = peak_buffered(BLOCK_SIZE, source_len);
+
+        stream_memory(BLOCK_SIZE, buffered) + BLOCK_SIZE.min(source_len)
+    }
+
+    fn chunk_string_with_offsets(self, input: String) -> impl Iterator<Item = Chunk> {
+        let mut start = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || {
+            let next = self.next_chunk_indices(&input, start, true)?;
+            start = next.next_start;
+            let chunk = Chunk {
+                text: input[next.start..next.end].to_string(),
+                start: next.start,
+                end: next.end,
+                index,
+                lines: None,
+                chars: None,
+                running_hash: None,
+                links: None,
+                hierarchy: None,
+                source_stamp: None,
+                header_len: 0,
+                prefix_len: 0,
+                suffix_len: 0,
+                kind: ChunkKind::Main,
+            };
+            index += 1;
+            Some(chunk)
+        })
+    }
+
+    fn chunk_stream_with_offsets(
+        self,
+        input: impl Iterator<Item = String>,
+    ) -> impl Iterator<Item = Chunk> {
+        let mut string_buffer = StringBuffer::new(input, BLOCK_SIZE);
+        let mut start = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || loop {
+            let buffer = string_buffer.buffer();
+            let offset = string_buffer.offset;
+            let next = self.next_chunk_indices(buffer, start - offset, string_buffer.done);
+
+            match next {
+                // if the stream is done and no more chunks can be made, return None
+                None if string_buffer.done => return None,
+
+                // if no chunk can be made yet, fill more data and try again
+                None => {
+                    string_buffer.fill();
+                    continue;
+                }
+
+                // otherwise, return the chunk and keep the overlap in the buffer
+                Some(n) => {
+                    let chunk = Chunk {
+                        text: buffer[n.start..n.end].to_string(),
+                        start: offset + n.start,
+                        end: offset + n.end,
+                        index,
+                        lines: None,
+                        chars: None,
+                        running_hash: None,
+                        links: None,
+                        hierarchy: None,
+                        source_stamp: None,
+                        header_len: 0,
+                        prefix_len: 0,
+                        suffix_len: 0,
+                        kind: ChunkKind::Main,
+                    };
+                    index += 1;
+                    start = offset + n.next_start;
+                    string_buffer.set_position(n.next_start);
+                    return Some(chunk);
+                }
+            };
+        })
+    }
+}
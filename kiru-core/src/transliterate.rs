@@ -0,0 +1,53 @@
+use icu_normalizer::DecomposingNormalizerBorrowed;
+
+/// ASCII spelling of letters and punctuation that do not decompose into an ASCII base
+/// character, e.g. `ß` or typographic quotes.
+fn ascii_spelling(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ß' => "ss",
+        'ẞ' => "SS",
+        'æ' => "ae",
+        'Æ' => "AE",
+        'œ' => "oe",
+        'Œ' => "OE",
+        'ø' => "o",
+        'Ø' => "O",
+        'đ' | 'ð' => "d",
+        'Đ' | 'Ð' => "D",
+        'ł' => "l",
+        'Ł' => "L",
+        'þ' => "th",
+        'Þ' => "Th",
+        'ı' => "i",
+        '‘' | '’' | '‚' | '′' => "'",
+        '“' | '”' | '„' | '″' | '«' | '»' => "\"",
+        '‐' | '‑' | '‒' | '–' | '—' | '―' | '−' => "-",
+        '⁄' => "/",
+        '•' | '·' => "*",
+        _ => return None,
+    })
+}
+
+/// `text` with only ASCII characters, for systems that cannot handle any other.
+///
+/// Accented letters lose their accents and ligatures are split (`é` becomes `e` and `ﬁ`
+/// becomes `fi`, through their compatibility decomposition), common letters and punctuation
+/// without an ASCII base are spelled out (`ß` becomes `ss`, `—` becomes `-`), and every
+/// other character (CJK, emoji, ...) is dropped. This is lossy: the original text cannot be
+/// recovered, and the length of the text changes.
+pub fn transliterate(text: &str) -> String {
+    if text.is_ascii() {
+        return text.to_string();
+    }
+
+    let mut ascii = String::with_capacity(text.len());
+    for c in DecomposingNormalizerBorrowed::new_nfkd().normalize_iter(text.chars()) {
+        if c.is_ascii() {
+            ascii.push(c);
+        } else if let Some(spelling) = ascii_spelling(c) {
+            ascii.push_str(spelling);
+        }
+        // Combining marks and characters without an ASCII spelling are dropped
+    }
+    ascii
+}
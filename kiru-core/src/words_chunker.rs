@@ -0,0 +1,186 @@
+use crate::chunker::{
+    peak_buffered, stream_memory, Chunk, ChunkKind, Chunker, ChunkingError, StringBuffer,
+};
+use crate::BLOCK_SIZE;
+
+struct WordsChunkIndices {
+    start: usize,
+    end: usize,
+    next_start: usize,
+}
+
+/// Cuts chunks of `chunk_size` words, each chunk after the first starting on the last
+/// `overlap` words of the previous one. Words are runs of non-whitespace characters, Unicode
+/// whitespace (e.g. no-break and ideographic spaces) separating them.
+///
+/// A chunk runs up to the first word of the next chunk that does not overlap it, so it keeps
+/// the whitespace between its words as it is, ends with the whitespace after its last word,
+/// and the first chunk starts with the whitespace before its first word: chunks without
+/// overlap concatenate to the source. The last chunk holds the remaining words, however few.
+#[derive(Clone)]
+pub struct WordsChunker {
+    chunk_size: usize,
+    overlap: usize,
+}
+
+impl WordsChunker {
+    pub fn new(chunk_size: usize, overlap: usize) -> Result<Self, ChunkingError> {
+        if chunk_size == 0 || overlap >= chunk_size {
+            return Err(ChunkingError::InvalidArguments {
+                chunk_size,
+                overlap,
+            });
+        }
+
+        Ok(Self {
+            chunk_size,
+            overlap,
+        })
+    }
+
+    /// Compute the chunk starting at `start`, or `None` once done or when more text is
+    /// needed (`done` tells whether `buffer` runs to the end of the source).
+    fn next_chunk_indices(
+        &self,
+        buffer: &str,
+        start: usize,
+        done: bool,
+    ) -> Option<WordsChunkIndices> {
+        let step = self.chunk_size - self.overlap;
+        let mut words = 0;
+        let mut next_start = None;
+        let mut in_word = false;
+
+        for (i, c) in buffer[start..].char_indices() {
+            if c.is_whitespace() {
+                in_word = false;
+                continue;
+            }
+            if in_word {
+                continue;
+            }
+
+            // A word starts at `i`
+            let i = start + i;
+            if words == step {
+                next_start = Some(i);
+            }
+            // It is the first word after the chunk
+            if words == self.chunk_size {
+                return Some(WordsChunkIndices {
+                    start,
+                    end: i,
+                    next_start: next_start.unwrap_or(i),
+                });
+            }
+            in_word = true;
+            words += 1;
+        }
+
+        // Whether a word follows, or the last word goes on, is not known before the end
+        if !done || words == 0 {
+            return None;
+        }
+        Some(WordsChunkIndices {
+            start,
+            end: buffer.len(),
+            next_start: buffer.len(),
+        })
+    }
+}
+
+impl Chunker for WordsChunker {
+    fn chunk_size(&self) -> Option<usize> {
+        Some(self.chunk_size)
+    }
+
+    /// Words of `text`.
+    fn measure(&self, text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+
+    /// Assumes a chunk fits in a block: a longer one is buffered whole.
+    fn estimate_memory(&self, source_len: usize) -> usize {
+        let buffered = peak_buffered(BLOCK_SIZE, source_len);
+
+        stream_memory(BLOCK_SIZE, buffered) + BLOCK_SIZE.min(source_len)
+    }
+
+    fn chunk_string_with_offsets(self, input: String) -> impl Iterator<Item = Chunk> {
+        let mut start = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || {
+            let next = self.next_chunk_indices(&input, start, true)?;
+            start = next.next_start;
+            let chunk = Chunk {
+                text: input[next.start..next.end].to_string(),
+                start: next.start,
+                end: next.end,
+                index,
+                lines: None,
+                chars: None,
+                running_hash: None,
+                links: None,
+                hierarchy: None,
+                source_stamp: None,
+                header_len: 0,
+                prefix_len: 0,
+                suffix_len: 0,
+                kind: ChunkKind::Main,
+            };
+            index += 1;
+            Some(chunk)
+        })
+    }
+
+    fn chunk_stream_with_offsets(
+        self,
+        input: impl Iterator<Item = String>,
+    ) -> impl Iterator<Item = Chunk> {
+        let mut string_buffer = StringBuffer::new(input, BLOCK_SIZE);
+        let mut start = 0;
+        let mut index = 0;
+
+        std::iter::from_fn(move || loop {
+            let buffer = string_buffer.buffer();
+            let offset = string_buffer.offset;
+            let next = self.next_chunk_indices(buffer, start - offset, string_buffer.done);
+
+            match next {
+                // if the stream is done and no more chunks can be made, return None
+                None if string_buffer.done => return None,
+
+                // if no chunk can be made yet, fill more data and try again
+                None => {
+                    string_buffer.fill();
+                    continue;
+                }
+
+                // otherwise, return the chunk and keep the overlapping words in the buffer
+                Some(n) => {
+                    let chunk = Chunk {
+                        text: buffer[n.start..n.end].to_string(),
+                        start: offset + n.start,
+                        end: offset + n.end,
+                        index,
+                        lines: None,
+                        chars: None,
+                        running_hash: None,
+                        links: None,
+                        hierarchy: None,
+                        source_stamp: None,
+                        header_len: 0,
+                        prefix_len: 0,
+                        suffix_len: 0,
+                        kind: ChunkKind::Main,
+                    };
+                    index += 1;
+                    start = offset + n.next_start;
+                    string_buffer.set_position(n.next_start);
+                    return Some(chunk);
+                }
+            };
+        })
+    }
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{ChunkerBuilder, Source};
+
+#[test]
+fn prefix_and_suffix_do_not_count_towards_chunk_size() {
+    let text = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. ".repeat(100);
+    let (_dir, path) = create_temp_file(&text);
+    let plain = ChunkerBuilder::by_bytes(100, 20).unwrap();
+    let decorated = plain
+        .clone()
+        .with_prefix("# Lorem Ipsum\n\n")
+        .with_suffix("\n--");
+
+    let expected: Vec<_> = plain
+        .on_source_with_offsets(Source::File(path.clone()))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    let chunks: Vec<_> = decorated
+        .on_source_with_offsets(Source::File(path))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(chunks.len(), expected.len());
+    for (chunk, bare) in chunks.iter().zip(&expected) {
+        assert!(chunk.text.starts_with("# Lorem Ipsum\n\n"));
+        assert!(chunk.text.ends_with("\n--"));
+        assert_eq!(chunk.text, format!("# Lorem Ipsum\n\n{}\n--", bare.text));
+        assert_eq!((chunk.start, chunk.end), (bare.start, bare.end));
+    }
+}
+
+#[test]
+fn size_band_measures_content_only() {
+    let text = "a".repeat(250);
+
+    let chunks: Vec<_> = ChunkerBuilder::by_bytes(100, 0)
+        .unwrap()
+        .with_prefix("title: ")
+        .with_size_band(1.0, 1.0)
+        .unwrap()
+        .on_source(Source::Text(text))
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, vec![format!("title: {}", "a".repeat(100)); 2]);
+}
+
+#[test]
+fn preamble_goes_on_chunks_after_the_first() {
+    let header = "use std::io;\nuse std::fs;\n\n";
+    let text = format!("{header}{}", "fn f() {}\n".repeat(30));
+    let chunker = ChunkerBuilder::by_bytes(100, 0).unwrap();
+
+    let chunks: Vec<_> = chunker
+        .clone()
+        .with_prefix("// lib.rs\n")
+        .with_preamble(header, true)
+        .on_source_with_offsets(Source::Text(text.clone()))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    assert!(chunks.len() > 2);
+    assert_eq!(chunks[0].text, format!("// lib.rs\n{}", &text[..100]));
+    for chunk in &chunks[1..] {
+        let content = &text[chunk.start..chunk.end];
+        assert_eq!(chunk.text, format!("// lib.rs\n{header}{content}"));
+    }
+
+    let first = chunker
+        .with_preamble(header, false)
+        .first(Source::Text(text))
+        .unwrap()
+        .unwrap();
+    assert!(first.starts_with(&format!("{header}{header}")));
+}
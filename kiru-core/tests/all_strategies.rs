@@ -0,0 +1,313 @@
+mod common;
+
+use std::collections::HashSet;
+
+use kiru::{
+    BoundariesChunker, BytesChunker, CharactersChunker, Chunk, Chunker, GraphemesChunker,
+    JsonArrayChunker, LineEnding, LinesChunker, LinesPackedChunker, ParagraphsChunker,
+    PredicateChunker, RecursiveChunker, RegexChunker, SemanticChunker, SemanticUnit,
+    SentencesChunker, TranscriptChunker, WordsChunker, STRATEGIES,
+};
+use proptest::prelude::*;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Random parameters, turned into valid ones for each strategy.
+#[derive(Debug, Clone)]
+struct Params {
+    size: usize,
+    overlap_seed: usize,
+    /// Byte offsets for the boundaries strategy, also cutting chars.
+    boundaries: Vec<usize>,
+}
+
+/// Inputs built from the same random units: plain text, a JSON array and a transcript.
+struct Inputs {
+    text: String,
+    json: String,
+    transcript: String,
+}
+
+fn inputs(units: &[String], separators: &[&str]) -> Inputs {
+    let text = units
+        .iter()
+        .zip(separators.iter().cycle())
+        .map(|(unit, separator)| format!("{unit}{separator}"))
+        .collect();
+    let transcript = units
+        .iter()
+        .enumerate()
+        .map(|(i, unit)| format!("[{:02}:{:02}] {unit}\n", i * 3 / 60, i * 3 % 60))
+        .collect();
+
+    Inputs {
+        text,
+        json: serde_json::to_string(units).unwrap(),
+        transcript,
+    }
+}
+
+/// Split `text` into blocks at `cuts` (moved back to char boundaries), as a reader would.
+fn blocks(text: &str, cuts: &[usize]) -> Vec<String> {
+    let mut cuts: Vec<usize> = cuts
+        .iter()
+        .map(|&cut| {
+            let mut cut = cut % (text.len() + 1);
+            while !text.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            cut
+        })
+        .collect();
+    cuts.push(text.len());
+    cuts.sort_unstable();
+
+    let mut start = 0;
+    cuts.into_iter()
+        .map(|cut| {
+            let block = text[start..cut].to_string();
+            start = cut;
+            block
+        })
+        .collect()
+}
+
+/// Chunk `text` whole and in blocks, and check that both give the same chunks, that chunk
+/// starts never decrease, and that every chunk starts and ends on a char boundary of `text`
+/// and holds its text when `slices` tells the strategy cuts slices.
+fn assert_whole_chars<C: Chunker>(
+    name: &str,
+    chunker: C,
+    text: &str,
+    cuts: &[usize],
+    slices: bool,
+) -> Result<(), TestCaseError> {
+    let whole: Vec<Chunk> = chunker
+        .clone()
+        .chunk_string_with_offsets(text.to_string())
+        .collect();
+    let streamed: Vec<Chunk> = chunker
+        .chunk_stream_with_offsets(blocks(text, cuts).into_iter())
+        .collect();
+
+    prop_assert_eq!(&streamed, &whole, "{}: streamed chunks differ", name);
+    for pair in whole.windows(2) {
+        prop_assert!(
+            pair[0].start <= pair[1].start,
+            "{name}: chunk {} starts at {}, before chunk {} at {}",
+            pair[1].index,
+            pair[1].start,
+            pair[0].index,
+            pair[0].start
+        );
+    }
+    for chunk in &whole {
+        prop_assert!(
+            text.is_char_boundary(chunk.start) && text.is_char_boundary(chunk.end),
+            "{name}: chunk {} ({}..{}) splits a char",
+            chunk.index,
+            chunk.start,
+            chunk.end
+        );
+        if slices {
+            prop_assert_eq!(&text[chunk.start..chunk.end], &chunk.text, "{}", name);
+        }
+    }
+    Ok(())
+}
+
+/// Check the strategy called `name` with valid parameters derived from `params`.
+fn check_strategy(
+    name: &str,
+    params: &Params,
+    inputs: &Inputs,
+    cuts: &[usize],
+) -> Result<(), TestCaseError> {
+    let size = params.size;
+    let overlap = params.overlap_seed % size;
+    let text = inputs.text.as_str();
+
+    match name {
+        "bytes" => assert_whole_chars(
+            name,
+            BytesChunker::new(size, overlap).unwrap(),
+            text,
+            cuts,
+            true,
+        ),
+        "approx_tokens" => assert_whole_chars(
+            name,
+            BytesChunker::new(size, overlap)
+                .unwrap()
+                .with_snap_to_word(true)
+                .with_overlap_whole_words(true),
+            text,
+            cuts,
+            true,
+        ),
+        "characters" => assert_whole_chars(
+            name,
+            CharactersChunker::new(size, overlap).unwrap(),
+            text,
+            cuts,
+            true,
+        ),
+        "graphemes" => {
+            let chunker = GraphemesChunker::new(size, overlap).unwrap();
+            assert_whole_chars(name, chunker.clone(), text, cuts, true)?;
+            // Streamed chunks do not split a cluster cut by the end of a block either
+            let clusters: HashSet<usize> = text
+                .grapheme_indices(true)
+                .map(|(i, _)| i)
+                .chain([text.len()])
+                .collect();
+            for chunk in chunker.chunk_stream_with_offsets(blocks(text, cuts).into_iter()) {
+                prop_assert!(clusters.contains(&chunk.start) && clusters.contains(&chunk.end));
+            }
+            Ok(())
+        }
+        "boundaries" => {
+            let mut boundaries = params.boundaries.clone();
+            boundaries.sort_unstable();
+            boundaries.dedup();
+            let chunker = BoundariesChunker::new(boundaries, overlap).unwrap();
+            assert_whole_chars(name, chunker, text, cuts, true)
+        }
+        "json_array" => {
+            let chunker = JsonArrayChunker::new(size, overlap).unwrap();
+            let json = inputs.json.as_str();
+            // Elements are written back compactly, so texts are not slices of the source
+            assert_whole_chars(name, chunker.clone(), json, cuts, false)?;
+            for chunk in chunker.chunk_string_with_offsets(json.to_string()) {
+                prop_assert!(serde_json::from_str::<serde_json::Value>(&chunk.text).is_ok());
+            }
+            Ok(())
+        }
+        "lines" => {
+            let line_ending = if size.is_multiple_of(2) {
+                LineEnding::Lf
+            } else {
+                LineEnding::CrLf
+            };
+            let chunker = LinesChunker::new(size, overlap)
+                .unwrap()
+                .with_line_ending(line_ending);
+            assert_whole_chars(name, chunker, text, cuts, true)
+        }
+        "lines_packed" => assert_whole_chars(
+            name,
+            LinesPackedChunker::new(size, overlap % 3).unwrap(),
+            text,
+            cuts,
+            true,
+        ),
+        "paragraphs" => assert_whole_chars(
+            name,
+            ParagraphsChunker::new(size, overlap).unwrap(),
+            text,
+            cuts,
+            true,
+        ),
+        "predicate" => {
+            let at_word = |previous: char, next: char, _: usize| {
+                previous.is_whitespace() && !next.is_whitespace()
+            };
+            let chunker = PredicateChunker::new(at_word, size, overlap).unwrap();
+            assert_whole_chars(name, chunker, text, cuts, true)
+        }
+        "recursive" => {
+            // Odd sizes have no empty separator to fall back on
+            let mut chunker = RecursiveChunker::new(size, overlap).unwrap();
+            if !size.is_multiple_of(2) {
+                chunker = chunker.with_separators(vec!["\n".to_string(), ". ".to_string()]);
+            }
+            assert_whole_chars(name, chunker, text, cuts, true)
+        }
+        "regex" => {
+            // Odd sizes split on empty matches too, and drop the separators
+            let pattern = if size.is_multiple_of(2) {
+                r"\s+"
+            } else {
+                r"\b|\s+"
+            };
+            let chunker = RegexChunker::new(pattern, size, overlap)
+                .unwrap()
+                .with_keep_separator(size.is_multiple_of(2));
+            assert_whole_chars(name, chunker, text, cuts, true)
+        }
+        "semantic" => {
+            let full = move |chunk: &str, next: &str| chunk.len() + next.len() > size;
+            let unit = if size.is_multiple_of(2) {
+                SemanticUnit::Character
+            } else {
+                SemanticUnit::Sentence
+            };
+            let chunker = SemanticChunker::new(full, unit, overlap % 4);
+            assert_whole_chars(name, chunker, text, cuts, true)
+        }
+        "sentences" => {
+            // Odd sizes cap chunks, hard splitting long sentences
+            let mut chunker = SentencesChunker::new(size, overlap).unwrap();
+            if !size.is_multiple_of(2) {
+                chunker = chunker.with_max_bytes(size);
+            }
+            assert_whole_chars(name, chunker, text, cuts, true)
+        }
+        #[cfg(feature = "tokenizers")]
+        "tokens" => {
+            let tokenizer = common::helpers::byte_level_tokenizer();
+            let chunker = kiru::TokensChunker::new(tokenizer, size, overlap).unwrap();
+            assert_whole_chars(name, chunker, text, cuts, true)
+        }
+        // Built without the tokenizers feature
+        #[cfg(not(feature = "tokenizers"))]
+        "tokens" => Ok(()),
+        "transcript" => {
+            let chunker = TranscriptChunker::new(size as f64, overlap as f64).unwrap();
+            assert_whole_chars(name, chunker, &inputs.transcript, cuts, true)
+        }
+        "words" => assert_whole_chars(
+            name,
+            WordsChunker::new(size, overlap).unwrap(),
+            text,
+            cuts,
+            true,
+        ),
+        other => panic!("strategy '{other}' is not covered by this test, add it"),
+    }
+}
+
+fn separator() -> impl Strategy<Value = &'static str> {
+    prop_oneof![
+        Just(" "),
+        Just("\n"),
+        Just("\r\n"),
+        Just("\n\n"),
+        Just("\r\n \r\n"),
+        Just(". "),
+        Just("\u{3000}"),
+        Just("\u{301}\u{200d}"),
+        Just("\u{a0}\t"),
+        Just(""),
+    ]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(300))]
+
+    #[test]
+    fn every_strategy_streams_whole_chars_in_order(
+        units in prop::collection::vec("\\PC{0,10}", 0..60),
+        separators in prop::collection::vec(separator(), 1..6),
+        size in 1usize..48,
+        overlap_seed in any::<usize>(),
+        boundaries in prop::collection::vec(0usize..800, 0..12),
+        cuts in prop::collection::vec(any::<usize>(), 0..10),
+    ) {
+        let params = Params { size, overlap_seed, boundaries };
+        let inputs = inputs(&units, &separators);
+
+        for info in STRATEGIES.iter() {
+            check_strategy(info.name, &params, &inputs, &cuts)?;
+        }
+    }
+}
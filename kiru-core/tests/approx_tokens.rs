@@ -0,0 +1,62 @@
+use kiru::{ChunkerBuilder, ChunkingError, Source};
+
+fn chunk(text: &str, target_tokens: usize, overlap_tokens: usize, ratio: f64) -> Vec<String> {
+    ChunkerBuilder::by_approx_tokens(target_tokens, overlap_tokens, ratio)
+        .unwrap()
+        .on_source(Source::Text(text.to_string()))
+        .unwrap()
+        .collect()
+}
+
+#[test]
+fn chunk_lengths_are_near_the_token_budget() {
+    let text = "The quick brown fox jumps over the lazy dog, then naps in the sun. ".repeat(100);
+
+    for (target_tokens, ratio) in [(100, 4.0), (50, 3.5), (256, 4.0)] {
+        let budget = (target_tokens as f64 * ratio) as usize;
+        let chunks = chunk(&text, target_tokens, target_tokens / 10, ratio);
+
+        assert!(chunks.len() > 2);
+        for chunk in &chunks[..chunks.len() - 1] {
+            let chars = chunk.chars().count();
+            assert!(
+                chars <= budget && chars + 16 >= budget,
+                "{chars} chars for a budget of {budget}"
+            );
+        }
+    }
+}
+
+#[test]
+fn chunks_end_and_overlap_on_whole_words() {
+    let text = "Some words of uneven lengths, then a verylongwordindeed. ".repeat(40);
+    let chunks = chunk(&text, 20, 4, 4.0);
+
+    for pair in chunks.windows(2) {
+        assert!(pair[0].ends_with(' '), "{:?}", pair[0]);
+        assert!(!pair[1].starts_with(' '), "{:?}", pair[1]);
+        // The next chunk starts on words of the previous one
+        assert!(pair[0].contains(pair[1].split(' ').next().unwrap()));
+    }
+}
+
+#[test]
+fn invalid_budgets_are_rejected() {
+    for ratio in [0.0, -4.0, f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            ChunkerBuilder::by_approx_tokens(100, 10, ratio),
+            Err(ChunkingError::InvalidCharsPerToken(_))
+        ));
+    }
+    assert!(matches!(
+        ChunkerBuilder::by_approx_tokens(0, 0, 4.0),
+        Err(ChunkingError::InvalidArguments { .. })
+    ));
+    assert!(matches!(
+        ChunkerBuilder::by_approx_tokens(10, 10, 4.0),
+        Err(ChunkingError::InvalidArguments {
+            chunk_size: 10,
+            overlap: 10
+        })
+    ));
+}
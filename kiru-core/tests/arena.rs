@@ -0,0 +1,31 @@
+#![cfg(feature = "arena")]
+
+mod common;
+
+use bumpalo::Bump;
+use common::helpers::create_temp_file;
+use kiru::{chunk_into_arena, ChunkerBuilder, Source};
+
+#[test]
+fn arena_chunks_match_string_chunks() {
+    let text = "Größe über alles — naïve café 🚀 and plain ASCII too. ".repeat(400);
+    let (_dir, path) = create_temp_file(&text);
+    let chunker = ChunkerBuilder::by_characters(100, 20).unwrap();
+    let expected: Vec<String> = chunker
+        .on_source(Source::Text(text.clone()))
+        .unwrap()
+        .collect();
+
+    let mut arena = Bump::new();
+    for source in [Source::Text(text.clone()), Source::File(path)] {
+        let chunks: Vec<&str> = chunk_into_arena(&arena, source, &chunker)
+            .unwrap()
+            .collect();
+        assert!(chunks.len() > 10);
+        assert_eq!(chunks, expected);
+        assert!(arena.allocated_bytes() >= expected.iter().map(String::len).sum::<usize>());
+
+        drop(chunks);
+        arena.reset();
+    }
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "arrow")]
+
+use arrow_array::cast::AsArray;
+use arrow_array::ffi_stream::{ArrowArrayStreamReader, FFI_ArrowArrayStream};
+use arrow_array::types::UInt64Type;
+use kiru::{record_batches, ChunkerBuilder, ChunkingError, Source};
+
+#[test]
+fn text_column_matches_the_chunks() {
+    let text = "The quick brown fox jumps over the lazy dog. ".repeat(50);
+    let chunker = ChunkerBuilder::by_characters(100, 20).unwrap();
+    let expected: Vec<String> = chunker
+        .on_source(Source::Text(text.clone()))
+        .unwrap()
+        .collect();
+
+    let chunks = chunker.on_source_with_offsets(Source::Text(text)).unwrap();
+    let batches = record_batches(chunks, 10).unwrap();
+    // Read back through the C stream interface, as Python consumers do
+    let reader = ArrowArrayStreamReader::try_new(FFI_ArrowArrayStream::new(Box::new(batches)))
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert!(reader.iter().all(|batch| batch.num_rows() <= 10));
+    assert_eq!(reader.len(), expected.len().div_ceil(10));
+    let texts: Vec<&str> = reader
+        .iter()
+        .flat_map(|batch| batch.column(0).as_string::<i64>().iter().flatten())
+        .collect();
+    assert_eq!(texts, expected);
+    let indices: Vec<u64> = reader
+        .iter()
+        .flat_map(|batch| {
+            batch
+                .column(3)
+                .as_primitive::<UInt64Type>()
+                .values()
+                .to_vec()
+        })
+        .collect();
+    assert_eq!(indices, (0..expected.len() as u64).collect::<Vec<_>>());
+}
+
+#[test]
+fn batch_size_must_be_positive() {
+    let chunks = std::iter::empty();
+    assert!(matches!(
+        record_batches(chunks, 0),
+        Err(ChunkingError::InvalidBatchSize)
+    ));
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{
+    BytesChunker, CharactersChunker, Chunker, ChunkerWithStrategy, ChunkingError, OverlapDirection,
+    Source,
+};
+
+fn ascii_text() -> String {
+    "Plain ASCII words, numbers like 42 and punctuation! ".repeat(400)
+}
+
+/// Chunks of `chunker` from `text` as a string and as a file, which must agree.
+fn chunks<C: Chunker>(chunker: C, text: &str) -> Vec<String> {
+    let (_dir, path) = create_temp_file(text);
+    let chunker = ChunkerWithStrategy::new(chunker);
+
+    let from_string: Vec<_> = chunker
+        .on_source(Source::Text(text.to_string()))
+        .unwrap()
+        .collect();
+    let from_file: Vec<_> = chunker.on_source(Source::File(path)).unwrap().collect();
+    assert_eq!(from_file, from_string);
+    from_string
+}
+
+#[test]
+fn ascii_assertion_keeps_bytes_output() {
+    let text = ascii_text();
+
+    for (chunk_size, overlap) in [(100, 0), (100, 30), (7, 6), (5000, 500)] {
+        for direction in [OverlapDirection::Backward, OverlapDirection::Forward] {
+            let chunker = BytesChunker::new(chunk_size, overlap)
+                .unwrap()
+                .with_overlap_direction(direction);
+
+            assert_eq!(
+                chunks(chunker.clone().with_ascii(true), &text),
+                chunks(chunker, &text),
+                "{chunk_size}/{overlap} {direction:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn ascii_assertion_keeps_characters_output() {
+    let text = ascii_text();
+
+    for (chunk_size, overlap) in [(100, 0), (100, 30), (7, 6), (5000, 500)] {
+        let chunker = CharactersChunker::new(chunk_size, overlap).unwrap();
+
+        assert_eq!(
+            chunks(chunker.clone().with_ascii(true), &text),
+            chunks(chunker, &text),
+            "{chunk_size}/{overlap}"
+        );
+    }
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "ASCII assertion")]
+fn wrong_ascii_assertion_panics_in_debug_builds() {
+    let chunker = BytesChunker::new(100, 10).unwrap().with_ascii(true);
+
+    chunker
+        .chunk_string("Grüße aus Köln. ".repeat(20))
+        .for_each(drop);
+}
+
+#[test]
+fn wrong_ascii_assertion_ends_file_sources_with_an_error() {
+    let text = format!("{}Grüße aus Köln.", "Plain text. ".repeat(2_000));
+    let (_dir, path) = create_temp_file(&text);
+    let chunker =
+        ChunkerWithStrategy::new(CharactersChunker::new(100, 10).unwrap().with_ascii(true));
+
+    let chunks: Vec<_> = chunker
+        .on_source_with_offsets(Source::File(path))
+        .unwrap()
+        .collect();
+
+    let (last, chunks) = chunks.split_last().unwrap();
+    assert!(matches!(
+        last,
+        Err(ChunkingError::NonAscii { offset: 24_002 })
+    ));
+    // The text before the first non-ASCII byte is chunked
+    let chunks: Vec<_> = chunks.iter().map(|c| c.as_ref().unwrap()).collect();
+    assert_eq!(chunks.last().unwrap().end, 24_002);
+    assert!(chunks.iter().all(|chunk| chunk.text.is_ascii()));
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{BytesChunker, CharactersChunker, Chunker};
+use std::process::Command;
+
+fn run_benchmark(args: &[&str]) -> serde_json::Value {
+    let output = Command::new(env!("CARGO_BIN_EXE_benchmark"))
+        .args(args)
+        .output()
+        .expect("Failed to run benchmark binary");
+
+    assert!(
+        output.status.success(),
+        "benchmark failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("benchmark output is not JSON")
+}
+
+fn bucket_total(histogram: &serde_json::Value) -> u64 {
+    histogram["buckets"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|b| b["count"].as_u64().unwrap())
+        .sum()
+}
+
+#[test]
+fn hist_counts_match_chunks_bytes() {
+    let text = "Some words, then ünïcödé 🎉 and more words. ".repeat(500);
+    let (_dir, path) = create_temp_file(&text);
+
+    let histogram = run_benchmark(&["hist", "bytes", "file", &path, "256", "32"]);
+    let expected = BytesChunker::new(256, 32)
+        .unwrap()
+        .chunk_string(text)
+        .count() as u64;
+
+    assert_eq!(histogram["num_chunks"].as_u64().unwrap(), expected);
+    assert_eq!(bucket_total(&histogram), expected);
+    assert!(histogram["max_bytes"].as_u64().unwrap() <= 256);
+}
+
+#[test]
+fn hist_counts_match_chunks_chars() {
+    let text = "Some words, then ünïcödé 🎉 and more words. ".repeat(500);
+
+    let histogram = run_benchmark(&["hist", "chars", "string", &text, "100", "10"]);
+    let expected = CharactersChunker::new(100, 10)
+        .unwrap()
+        .chunk_string(text)
+        .count() as u64;
+
+    assert_eq!(histogram["num_chunks"].as_u64().unwrap(), expected);
+    assert_eq!(bucket_total(&histogram), expected);
+}
+
+#[test]
+fn benchmark_reports_throughput() {
+    let (_dir, path) = create_temp_file(&"abc ".repeat(1000));
+
+    let result = run_benchmark(&["bytes", "file", &path, "100", "10"]);
+
+    assert!(result["num_chunks"].as_u64().unwrap() > 0);
+    assert!(result["throughput_mb_s"].is_number());
+}
+
+#[test]
+fn benchmark_splits_io_and_cpu_time() {
+    let (_dir, path) = create_temp_file(&"Some words, then more words. ".repeat(100_000));
+
+    let result = run_benchmark(&["bytes", "file", &path, "1024", "128"]);
+
+    let io = result["io_secs"].as_f64().unwrap();
+    let cpu = result["cpu_secs"].as_f64().unwrap();
+    let total = result["elapsed_secs"].as_f64().unwrap();
+    assert!(io > 0.0 && cpu > 0.0);
+    assert!(io + cpu <= total, "{io} + {cpu} > {total}");
+    assert!(io + cpu >= total * 0.9, "{io} + {cpu} << {total}");
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{ChunkerBuilder, Source};
+
+const BIDI_CONTROLS: [char; 12] = [
+    '\u{200E}', '\u{200F}', '\u{061C}', '\u{202A}', '\u{202B}', '\u{202C}', '\u{202D}', '\u{202E}',
+    '\u{2066}', '\u{2067}', '\u{2068}', '\u{2069}',
+];
+
+/// Hebrew and Arabic phrases wrapped in isolates, embeddings and marks.
+fn rtl_text() -> String {
+    (0..200)
+        .map(|n| match n % 3 {
+            0 => format!("see \u{2067}שלום עולם\u{2069} #{n}\u{200F} "),
+            1 => format!("\u{202B}مرحبا بالعالم {n}\u{202C}\u{200E} "),
+            _ => format!("\u{202E}abc\u{202C}\u{061C}{n} "),
+        })
+        .collect()
+}
+
+fn assert_no_dangling_controls(chunks: &[String]) {
+    for chunk in &chunks[..chunks.len() - 1] {
+        assert!(
+            !chunk.ends_with(BIDI_CONTROLS),
+            "chunk ends with a bidi control: {chunk:?}"
+        );
+    }
+}
+
+#[test]
+fn chunks_never_end_with_a_bidi_control() {
+    let text = rtl_text();
+    let (_dir, path) = create_temp_file(&text);
+
+    for chunk_size in [7, 16, 33, 100] {
+        let bytes = ChunkerBuilder::by_bytes(chunk_size, chunk_size / 4).unwrap();
+        let chars = ChunkerBuilder::by_characters(chunk_size, chunk_size / 4).unwrap();
+
+        let chunks: Vec<_> = bytes
+            .on_source(Source::File(path.clone()))
+            .unwrap()
+            .collect();
+        assert_no_dangling_controls(&chunks);
+        let from_string: Vec<_> = bytes
+            .on_source(Source::Text(text.clone()))
+            .unwrap()
+            .collect();
+        assert_eq!(from_string, chunks);
+
+        let chunks: Vec<_> = chars
+            .on_source(Source::File(path.clone()))
+            .unwrap()
+            .collect();
+        assert_no_dangling_controls(&chunks);
+        let from_string: Vec<_> = chars
+            .on_source(Source::Text(text.clone()))
+            .unwrap()
+            .collect();
+        assert_eq!(from_string, chunks);
+    }
+}
+
+#[test]
+fn controls_move_to_the_next_chunk() {
+    let text = "abc\u{200F}\u{2067}def\u{2069}".to_string();
+
+    let chunks: Vec<_> = ChunkerBuilder::by_characters(5, 0)
+        .unwrap()
+        .on_source(Source::Text(text))
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, ["abc", "\u{200F}\u{2067}def", "\u{2069}"]);
+}
+
+#[test]
+fn bidi_balance_closes_and_drops_controls() {
+    let text = "\u{202B}abc def\u{202C} xyz".to_string();
+
+    let chunks: Vec<_> = ChunkerBuilder::by_characters(6, 0)
+        .unwrap()
+        .with_bidi_balance(true)
+        .on_source(Source::Text(text))
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, ["\u{202B}abc d\u{202C}", "ef xy", "z"]);
+}
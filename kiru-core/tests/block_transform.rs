@@ -0,0 +1,52 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use common::helpers::create_temp_file;
+use kiru::{ChunkerBuilder, Source, BLOCK_SIZE};
+
+#[test]
+fn transform_applies_block_by_block() {
+    let text = (0..5000)
+        .map(|i| format!(">quoted line {i}\n"))
+        .collect::<String>();
+    let (_dir, path) = create_temp_file(&text);
+    let calls = Arc::new(AtomicUsize::new(0));
+    let largest = Arc::new(AtomicUsize::new(0));
+
+    let chunker = ChunkerBuilder::by_bytes(1000, 100)
+        .unwrap()
+        .with_block_transform({
+            let (calls, largest) = (calls.clone(), largest.clone());
+            move |block| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                largest.fetch_max(block.len(), Ordering::Relaxed);
+                block.replace('>', "")
+            }
+        });
+    let chunks: Vec<String> = chunker.on_source(Source::File(path)).unwrap().collect();
+
+    // The file was read and transformed in blocks, not as a whole
+    assert!(calls.load(Ordering::Relaxed) > text.len() / BLOCK_SIZE);
+    assert!(largest.load(Ordering::Relaxed) <= BLOCK_SIZE);
+
+    let expected: Vec<String> = ChunkerBuilder::by_bytes(1000, 100)
+        .unwrap()
+        .on_source(Source::Text(text.replace('>', "")))
+        .unwrap()
+        .collect();
+    assert_eq!(chunks, expected);
+    assert!(chunks.iter().all(|chunk| !chunk.contains('>')));
+}
+
+#[test]
+fn count_file_chunks_the_transformed_text() {
+    let text = "a".repeat(10_000);
+    let (_dir, path) = create_temp_file(&text);
+    let chunker = ChunkerBuilder::by_bytes(100, 0)
+        .unwrap()
+        .with_block_transform(|block| block.repeat(2));
+
+    assert_eq!(chunker.count_file(&path, true).unwrap(), 200);
+}
@@ -0,0 +1,147 @@
+mod common;
+
+use common::helpers::{assert_all_valid_utf8, create_temp_file};
+use kiru::{BoundariesChunker, Chunker, ChunkingError, Source, StreamType};
+use proptest::prelude::*;
+
+// ============================================================================
+// PROPERTY-BASED TESTS
+// ============================================================================
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(200))]
+
+    #[test]
+    fn file_chunks_match_string_chunks(
+        text in "\\PC{100,5000}",
+        boundaries in prop::collection::btree_set(0usize..20_000, 0..50),
+        overlap in 0usize..50,
+    ) {
+        let chunker = BoundariesChunker::new(boundaries.into_iter().collect(), overlap)?;
+        let expected = chunker.clone().chunk_string(text.clone()).collect::<Vec<_>>();
+
+        let (_dir, path) = create_temp_file(&text);
+        let stream = StreamType::from_source(&Source::File(path))?;
+        let chunks = chunker.chunk_stream(stream).collect::<Vec<_>>();
+
+        assert_all_valid_utf8(&chunks);
+        prop_assert_eq!(chunks, expected);
+    }
+
+    #[test]
+    fn segments_reconstruct_input(
+        text in "\\PC{1,2000}",
+        boundaries in prop::collection::btree_set(0usize..8000, 0..30),
+    ) {
+        let chunker = BoundariesChunker::new(boundaries.into_iter().collect(), 0)?;
+        let chunks = chunker.chunk_string(text.clone()).collect::<Vec<_>>();
+
+        prop_assert!(chunks.iter().all(|c| !c.is_empty()));
+        prop_assert_eq!(chunks.concat(), text);
+    }
+}
+
+// ============================================================================
+// BEHAVIOR TESTS
+// ============================================================================
+
+#[test]
+fn explicit_boundaries_with_overlap() {
+    let text = "First clause, second clause. Third one!";
+    let chunker = BoundariesChunker::new(vec![13, 28], 7).unwrap();
+    let chunks: Vec<_> = chunker.chunk_string(text.to_string()).collect();
+
+    assert_eq!(
+        chunks,
+        vec![
+            "First clause,",
+            "clause, second clause.",
+            "clause. Third one!"
+        ]
+    );
+
+    // Every chunk after the first repeats the last `overlap` bytes before its cut
+    for (cut, chunk) in [13usize, 28].iter().zip(&chunks[1..]) {
+        assert!(chunk.starts_with(&text[cut - 7..*cut]));
+    }
+}
+
+#[test]
+fn explicit_boundaries_without_overlap() {
+    let text = "aaa|bbb|ccc";
+    let chunker = BoundariesChunker::new(vec![0, 4, 8, 11, 50], 0).unwrap();
+    let chunks: Vec<_> = chunker.chunk_string(text.to_string()).collect();
+
+    assert_eq!(chunks, vec!["aaa|", "bbb|", "ccc"]);
+}
+
+#[test]
+fn boundaries_snap_to_char_boundaries() {
+    // "é" is 2 bytes, "🎉" is 4 bytes
+    let text = "aé🎉b";
+    let chunker = BoundariesChunker::new(vec![2, 5], 1).unwrap();
+    let chunks: Vec<_> = chunker.chunk_string(text.to_string()).collect();
+
+    assert_all_valid_utf8(&chunks);
+    assert_eq!(chunks, vec!["a", "aé", "é🎉b"]);
+}
+
+#[test]
+fn boundaries_inside_same_char_are_skipped() {
+    let text = "🎉🎊";
+    let chunker = BoundariesChunker::new(vec![1, 2, 3, 4], 0).unwrap();
+    let chunks: Vec<_> = chunker.chunk_string(text.to_string()).collect();
+
+    assert_eq!(chunks, vec!["🎉", "🎊"]);
+}
+
+#[test]
+fn file_boundaries_across_blocks() {
+    let text = "0123456789".repeat(5_000);
+    let boundaries: Vec<usize> = (1..50).map(|i| i * 1_000 + 7).collect();
+    let (_dir, path) = create_temp_file(&text);
+
+    let chunker = BoundariesChunker::new(boundaries.clone(), 100).unwrap();
+    let stream = StreamType::from_source(&Source::File(path)).unwrap();
+    let chunks: Vec<_> = chunker.chunk_stream(stream).collect();
+
+    assert_eq!(chunks.len(), boundaries.len() + 1);
+    assert_eq!(chunks[1], text[907..2_007]);
+    assert_eq!(chunks.last().unwrap(), &text[49_007 - 100..]);
+}
+
+// ============================================================================
+// EDGE CASE TESTS
+// ============================================================================
+
+#[test]
+fn edge_case_empty_string() {
+    let chunker = BoundariesChunker::new(vec![5], 2).unwrap();
+    let chunks: Vec<_> = chunker.chunk_string("".to_string()).collect();
+
+    assert!(chunks.is_empty());
+}
+
+#[test]
+fn edge_case_no_boundaries() {
+    let chunker = BoundariesChunker::new(vec![], 2).unwrap();
+    let chunks: Vec<_> = chunker.chunk_string("whole".to_string()).collect();
+
+    assert_eq!(chunks, vec!["whole"]);
+}
+
+// ============================================================================
+// ERROR HANDLING TESTS
+// ============================================================================
+
+#[test]
+fn error_unsorted_boundaries() {
+    let result = BoundariesChunker::new(vec![10, 5], 0);
+    assert!(matches!(result, Err(ChunkingError::InvalidBoundaries)));
+}
+
+#[test]
+fn error_duplicate_boundaries() {
+    let result = BoundariesChunker::new(vec![5, 5], 0);
+    assert!(matches!(result, Err(ChunkingError::InvalidBoundaries)));
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{ChunkerBuilder, Source};
+
+#[test]
+fn ascii_input_needs_no_adjustment() {
+    let chunker = ChunkerBuilder::by_bytes(100, 30).unwrap();
+
+    let chunks = chunker
+        .on_source(Source::Text("plain ascii text ".repeat(500)))
+        .unwrap()
+        .count();
+
+    assert!(chunks > 1);
+    assert_eq!(chunker.stats().boundary_adjustments, 0);
+}
+
+#[test]
+fn multibyte_input_counts_snapped_ends_and_overlap_starts() {
+    // 2-byte chars: odd ends and overlap starts fall mid-char
+    let chunker = ChunkerBuilder::by_bytes(5, 0).unwrap();
+    let chunks: Vec<_> = chunker
+        .on_source(Source::Text("é".repeat(6)))
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, ["éé", "éé", "éé"]);
+    // Both non-final chunk ends, no overlap start
+    assert_eq!(chunker.stats().boundary_adjustments, 2);
+
+    let chunker = ChunkerBuilder::by_bytes(6, 3).unwrap();
+    chunker
+        .on_source(Source::Text("é".repeat(6)))
+        .unwrap()
+        .for_each(drop);
+
+    // Every overlap start, never a chunk end
+    assert_eq!(chunker.stats().boundary_adjustments, 3);
+}
+
+#[test]
+fn stream_counts_each_chunk_once() {
+    let text = "日本語のテキスト。".repeat(2_000);
+    let (_dir, path) = create_temp_file(&text);
+    let from_file = ChunkerBuilder::by_bytes(1000, 100).unwrap();
+    let from_string = ChunkerBuilder::by_bytes(1000, 100).unwrap();
+
+    from_file
+        .on_source(Source::File(path))
+        .unwrap()
+        .for_each(drop);
+    from_string
+        .on_source(Source::Text(text))
+        .unwrap()
+        .for_each(drop);
+
+    assert!(from_file.stats().boundary_adjustments > 0);
+    assert_eq!(from_file.stats(), from_string.stats());
+}
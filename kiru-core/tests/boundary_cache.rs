@@ -0,0 +1,51 @@
+mod common;
+
+use std::fs;
+
+use common::helpers::create_temp_file;
+use kiru::{from_cached_boundaries, precompute_boundaries, ChunkerBuilder, ChunkingError, Source};
+
+const PARAMS: &str = "bytes:100:20";
+
+#[test]
+fn cached_boundaries_give_identical_chunks() {
+    let text = "Grüße aus Köln, schön hier. ".repeat(500);
+    let (dir, path) = create_temp_file(&text);
+    let cache_path = dir.path().join("boundaries.json");
+    let chunker = ChunkerBuilder::by_bytes(100, 20).unwrap();
+
+    let count = precompute_boundaries(&chunker, &path, PARAMS, &cache_path).unwrap();
+
+    let chunked: Vec<_> = chunker
+        .on_source_with_offsets(Source::File(path.clone()))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    let cached: Vec<_> = from_cached_boundaries(&path, PARAMS, &cache_path)
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(count, chunked.len());
+    assert_eq!(cached, chunked);
+}
+
+#[test]
+fn cache_is_invalidated_by_other_params_or_a_changed_source() {
+    let (dir, path) = create_temp_file(&"Some words. ".repeat(100));
+    let cache_path = dir.path().join("boundaries.json");
+    let chunker = ChunkerBuilder::by_bytes(100, 20).unwrap();
+    precompute_boundaries(&chunker, &path, PARAMS, &cache_path).unwrap();
+
+    let other_params = from_cached_boundaries(&path, "bytes:100:10", &cache_path);
+    assert!(matches!(
+        other_params,
+        Err(ChunkingError::InvalidBoundaryCache(_))
+    ));
+
+    fs::write(&path, "Other words. ".repeat(100)).unwrap();
+    let changed = from_cached_boundaries(&path, PARAMS, &cache_path);
+    assert!(matches!(
+        changed,
+        Err(ChunkingError::InvalidBoundaryCache(_))
+    ));
+}
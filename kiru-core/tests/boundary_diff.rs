@@ -0,0 +1,74 @@
+use kiru::{diff_boundaries, ChunkerBuilder, Source};
+
+#[test]
+fn larger_chunks_shift_every_inner_cut() {
+    // 250 bytes in chunks of 100, then of 120
+    let a = [(0, 100), (100, 200), (200, 250)];
+    let b = [(0, 120), (120, 240), (240, 250)];
+
+    let diff = diff_boundaries(&a, &b);
+
+    assert_eq!(diff.shifted, [(100, 120), (200, 240)]);
+    assert!(diff.added.is_empty());
+    assert!(diff.removed.is_empty());
+    assert_eq!(diff.unchanged_chunks, 0);
+    assert_eq!(diff.unchanged_fraction, 0.0);
+}
+
+#[test]
+fn split_chunk_adds_a_cut_and_keeps_the_others() {
+    let a = [(0, 100), (100, 200), (200, 300)];
+    let b = [(0, 100), (100, 150), (150, 200), (200, 300)];
+
+    let diff = diff_boundaries(&a, &b);
+    assert_eq!(diff.added, [150]);
+    assert!(diff.removed.is_empty() && diff.shifted.is_empty());
+    // Only the split chunk changed
+    assert_eq!(diff.unchanged_chunks, 2);
+    assert_eq!(diff.unchanged_fraction, 0.5);
+
+    // And merging them back removes it
+    let diff = diff_boundaries(&b, &a);
+    assert_eq!(diff.removed, [150]);
+    assert!(diff.added.is_empty() && diff.shifted.is_empty());
+    assert_eq!(diff.unchanged_chunks, 2);
+    assert_eq!(diff.unchanged_fraction, 2.0 / 3.0);
+}
+
+#[test]
+fn identical_chunkings_are_unchanged() {
+    let text = "Some words, then more words. ".repeat(100);
+    let spans = |overlap| -> Vec<(usize, usize)> {
+        ChunkerBuilder::by_bytes(200, overlap)
+            .unwrap()
+            .on_source_with_offsets(Source::Text(text.clone()))
+            .unwrap()
+            .map(|chunk| chunk.map(|c| (c.start, c.end)).unwrap())
+            .collect()
+    };
+
+    let diff = diff_boundaries(&spans(20), &spans(20));
+    assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.shifted.is_empty());
+    assert_eq!(diff.unchanged_fraction, 1.0);
+
+    // The first chunk keeps its span when only the overlap changes
+    let diff = diff_boundaries(&spans(20), &spans(40));
+    assert!(diff.unchanged_chunks >= 1);
+    assert!(diff.unchanged_fraction < 1.0);
+
+    assert_eq!(diff_boundaries(&[], &[]).unchanged_fraction, 1.0);
+}
+
+#[test]
+fn extra_cuts_pair_with_the_nearest_one() {
+    let a = [(0, 100), (100, 200), (200, 300)];
+    let b = [(0, 100), (100, 150), (150, 220), (220, 300)];
+
+    let diff = diff_boundaries(&a, &b);
+    assert_eq!(diff.shifted, [(200, 220)]);
+    assert_eq!(diff.added, [150]);
+
+    let diff = diff_boundaries(&b, &a);
+    assert_eq!(diff.shifted, [(220, 200)]);
+    assert_eq!(diff.removed, [150]);
+}
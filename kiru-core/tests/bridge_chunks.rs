@@ -0,0 +1,75 @@
+use kiru::{Chunk, ChunkKind, ChunkerBuilder, Source};
+
+fn chunks_of(text: &str, overlap: usize, margin: usize) -> Vec<Chunk> {
+    ChunkerBuilder::by_bytes(100, overlap)
+        .unwrap()
+        .with_bridge_chunks(margin)
+        .on_source_with_offsets(Source::Text(text.to_string()))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+}
+
+#[test]
+fn bridges_straddle_the_boundaries_of_main_chunks() {
+    let text = "Größe über alles — naïve café 🚀 and plain ASCII too. ".repeat(40);
+    let chunks = chunks_of(&text, 20, 10);
+
+    let mains: Vec<&Chunk> = chunks
+        .iter()
+        .filter(|c| c.kind == ChunkKind::Main)
+        .collect();
+    let bridges: Vec<&Chunk> = chunks
+        .iter()
+        .filter(|c| c.kind == ChunkKind::Bridge)
+        .collect();
+    assert!(mains.len() > 10);
+    assert_eq!(bridges.len(), mains.len() - 1);
+
+    for (pair, bridge) in mains.windows(2).zip(&bridges) {
+        let (previous, next) = (pair[0], pair[1]);
+        assert_eq!(bridge.index, previous.index);
+        assert_eq!(bridge.text, text[bridge.start..bridge.end]);
+        // The overlap and up to 10 bytes around it, within the two chunks
+        assert!(previous.start <= bridge.start && bridge.start <= next.start);
+        assert!(previous.end <= bridge.end && bridge.end <= next.end);
+        assert!(next.start - bridge.start <= 10 && bridge.end - previous.end <= 10);
+        assert!(next.start - bridge.start >= 7 && bridge.end - previous.end >= 7);
+    }
+
+    // Each bridge comes right after the chunk before it
+    let kinds: Vec<ChunkKind> = chunks.iter().take(4).map(|c| c.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            ChunkKind::Main,
+            ChunkKind::Bridge,
+            ChunkKind::Main,
+            ChunkKind::Bridge
+        ]
+    );
+}
+
+#[test]
+fn bridges_center_on_the_cut_without_overlap() {
+    let text = "0123456789".repeat(30);
+    let bridges: Vec<(usize, usize)> = chunks_of(&text, 0, 5)
+        .into_iter()
+        .filter(|c| c.kind == ChunkKind::Bridge)
+        .map(|c| (c.start, c.end))
+        .collect();
+
+    assert_eq!(bridges, [(95, 105), (195, 205)]);
+}
+
+#[test]
+fn chunks_are_main_without_bridges() {
+    let chunks: Vec<Chunk> = ChunkerBuilder::by_bytes(10, 2)
+        .unwrap()
+        .on_source_with_offsets(Source::Text("x".repeat(100)))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    assert!(chunks.iter().all(|c| c.kind == ChunkKind::Main));
+}
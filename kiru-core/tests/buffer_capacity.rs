@@ -0,0 +1,73 @@
+mod common;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use common::helpers::create_temp_file;
+use kiru::{BytesChunker, CharactersChunker, Chunker, ChunkerWithStrategy, Source};
+
+/// Counts the reallocations of the whole test binary.
+struct CountingAllocator;
+
+static REALLOCS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        REALLOCS.fetch_add(1, Ordering::SeqCst);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Chunks of `path`, and the reallocations made while cutting them.
+fn chunk_counting<C: Chunker>(chunker: C, path: &str) -> (Vec<String>, usize) {
+    let strategy = ChunkerWithStrategy::new(chunker);
+    let mut chunks = Vec::with_capacity(100_000);
+
+    let before = REALLOCS.load(Ordering::SeqCst);
+    for chunk in strategy.on_source(Source::File(path.to_string())).unwrap() {
+        chunks.push(chunk);
+    }
+    let reallocs = REALLOCS.load(Ordering::SeqCst) - before;
+
+    (chunks, reallocs)
+}
+
+// A single test, so that no other test allocates concurrently
+#[test]
+fn presized_buffer_does_not_grow_and_keeps_chunks() {
+    let text = "Some words, then ünïcödé, and a few more words. ".repeat(20_000);
+    let (_dir, path) = create_temp_file(&text);
+
+    for chunk_size in [64, 1024] {
+        let bytes = BytesChunker::new(chunk_size, chunk_size / 8).unwrap();
+        let (tiny_chunks, tiny) = chunk_counting(bytes.clone().with_buffer_capacity(1), &path);
+        let (chunks, default) = chunk_counting(bytes.clone(), &path);
+        let (large_chunks, large) = chunk_counting(bytes.with_buffer_capacity(1 << 20), &path);
+
+        assert!(tiny > 0);
+        assert_eq!((default, large), (0, 0));
+        assert_eq!(tiny_chunks, chunks);
+        assert_eq!(large_chunks, chunks);
+
+        let chars = CharactersChunker::new(chunk_size, chunk_size / 8).unwrap();
+        let (tiny_chunks, tiny) = chunk_counting(chars.clone().with_buffer_capacity(1), &path);
+        let (chunks, default) = chunk_counting(chars.clone(), &path);
+        let (large_chunks, large) = chunk_counting(chars.with_buffer_capacity(1 << 20), &path);
+
+        // The character positions grow with the buffer, whatever its capacity
+        assert!(default < tiny && large <= default);
+        assert_eq!(tiny_chunks, chunks);
+        assert_eq!(large_chunks, chunks);
+    }
+}
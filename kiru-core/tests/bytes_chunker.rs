@@ -295,6 +295,243 @@ fn edge_case_file_exactly_chunk_size() {
     assert_eq!(chunks[0], text);
 }
 
+// ============================================================================
+// SENTENCE OVERLAP TESTS
+// ============================================================================
+
+fn sentence_text() -> String {
+    (0..40)
+        .map(|i| format!("Sentence number {i} is here. "))
+        .collect::<String>()
+}
+
+/// Each chunk after the first must begin at a sentence start and repeat exactly
+/// the last `n` sentences (starting inside) the previous chunk.
+fn assert_sentence_overlaps(chunks: &[String], n: usize) {
+    assert!(chunks.len() > 1);
+    for pair in chunks.windows(2) {
+        let (prev, next) = (&pair[0], &pair[1]);
+        assert!(
+            next.starts_with("Sentence"),
+            "chunk starts mid-sentence: {next:?}"
+        );
+
+        let overlap_start = prev
+            .rmatch_indices(". ")
+            .map(|(i, _)| i + 2)
+            .filter(|&start| start < prev.len())
+            .nth(n - 1)
+            .expect("previous chunk holds at least n sentence starts");
+        assert!(next.starts_with(&prev[overlap_start..]));
+    }
+}
+
+#[test]
+fn sentence_overlap_string() {
+    let text = sentence_text();
+    let chunker = BytesChunker::new(120, 10)
+        .unwrap()
+        .with_overlap_sentences(2);
+    let chunks: Vec<_> = chunker.chunk_string(text.clone()).collect();
+
+    assert_sentence_overlaps(&chunks, 2);
+    assert!(chunks
+        .last()
+        .unwrap()
+        .ends_with("Sentence number 39 is here. "));
+}
+
+#[test]
+fn sentence_overlap_file_matches_string() {
+    let text = sentence_text().repeat(50);
+    let (_dir, path) = create_temp_file(&text);
+
+    let chunker = BytesChunker::new(200, 10)
+        .unwrap()
+        .with_overlap_sentences(1);
+    let expected: Vec<_> = chunker.clone().chunk_string(text.clone()).collect();
+    let stream = StreamType::from_source(&Source::File(path)).unwrap();
+    let chunks: Vec<_> = chunker.chunk_stream(stream).collect();
+
+    assert_sentence_overlaps(&chunks, 1);
+    assert_eq!(chunks, expected);
+}
+
+#[test]
+fn sentence_overlap_falls_back_without_sentences() {
+    let text = "no terminators at all ".repeat(20);
+    let plain: Vec<_> = BytesChunker::new(50, 5)
+        .unwrap()
+        .chunk_string(text.clone())
+        .collect();
+    let with_sentences: Vec<_> = BytesChunker::new(50, 5)
+        .unwrap()
+        .with_overlap_sentences(2)
+        .chunk_string(text)
+        .collect();
+
+    assert_eq!(with_sentences, plain);
+}
+
+// ============================================================================
+// SOFT MAX TESTS
+// ============================================================================
+
+fn uneven_sentence_text() -> String {
+    (0..120)
+        .map(|i| format!("Sentence {i} says {}. ", "more ".repeat(i * 7 % 13)))
+        .collect()
+}
+
+#[test]
+fn soft_max_extends_to_sentence_end_within_budget() {
+    let text = uneven_sentence_text();
+    let (chunk_size, extra) = (100, 40);
+
+    let chunks: Vec<_> = BytesChunker::new(chunk_size, 10)
+        .unwrap()
+        .with_soft_max_extra(extra)
+        .chunk_string_with_offsets(text.clone())
+        .collect();
+
+    let mut extended = 0;
+    for chunk in &chunks[..chunks.len() - 1] {
+        let hard_end = chunk.start + chunk_size;
+        let sentence_end = text[hard_end - 2..]
+            .find(". ")
+            .map(|i| hard_end - 2 + i + 2)
+            .unwrap();
+
+        if sentence_end <= hard_end + extra {
+            assert_eq!(chunk.end, sentence_end, "chunk {}", chunk.index);
+            assert!(chunk.text.ends_with(". "));
+            extended += (sentence_end > hard_end) as usize;
+        } else {
+            assert_eq!(chunk.end, hard_end, "chunk {}", chunk.index);
+        }
+    }
+    assert!(extended > 0);
+}
+
+#[test]
+fn soft_max_file_matches_string() {
+    let text = uneven_sentence_text().repeat(10);
+    let (_dir, path) = create_temp_file(&text);
+
+    let chunker = BytesChunker::new(300, 20).unwrap().with_soft_max_extra(150);
+    let expected: Vec<_> = chunker.clone().chunk_string(text.clone()).collect();
+    let stream = StreamType::from_source(&Source::File(path)).unwrap();
+    let chunks: Vec<_> = chunker.chunk_stream(stream).collect();
+
+    assert_eq!(chunks, expected);
+}
+
+// ============================================================================
+// WORD OVERLAP TESTS
+// ============================================================================
+
+fn word_text() -> String {
+    "größere wörter ünïcödé 🎉🎉 naïve café 東京タワー résumé ".repeat(40)
+}
+
+#[test]
+fn overlap_chars_whole_words_string() {
+    let text = word_text();
+    let chunks: Vec<_> = BytesChunker::new(120, 10)
+        .unwrap()
+        .with_overlap_chars(12)
+        .with_overlap_whole_words(true)
+        .chunk_string_with_offsets(text.clone())
+        .collect();
+
+    assert!(chunks.len() > 2);
+    for pair in chunks.windows(2) {
+        let (prev, next) = (&pair[0], &pair[1]);
+        let overlap = &text[next.start..prev.end];
+        assert!(
+            overlap.chars().count() >= 12,
+            "overlap too short: {:?}",
+            overlap
+        );
+        assert!(
+            text[..next.start].ends_with(' ') && !overlap.starts_with(' '),
+            "overlap does not start at a word: {:?}",
+            overlap
+        );
+    }
+}
+
+#[test]
+fn overlap_chars_whole_words_file_matches_string() {
+    let text = word_text().repeat(20);
+    let (_dir, path) = create_temp_file(&text);
+
+    let chunker = BytesChunker::new(200, 10)
+        .unwrap()
+        .with_overlap_chars(20)
+        .with_overlap_whole_words(true);
+    let expected: Vec<_> = chunker.clone().chunk_string(text.clone()).collect();
+    let stream = StreamType::from_source(&Source::File(path)).unwrap();
+    let chunks: Vec<_> = chunker.chunk_stream(stream).collect();
+
+    assert_eq!(chunks, expected);
+}
+
+// ============================================================================
+// OFFSET TESTS
+// ============================================================================
+
+#[test]
+fn offsets_point_into_source() {
+    let text = "Some words, then ünïcödé 🎉 and more words. ".repeat(200);
+    let (_dir, path) = create_temp_file(&text);
+
+    let chunker = BytesChunker::new(300, 40).unwrap();
+    let expected: Vec<_> = chunker
+        .clone()
+        .chunk_string_with_offsets(text.clone())
+        .collect();
+    let stream = StreamType::from_source(&Source::File(path)).unwrap();
+    let chunks: Vec<_> = chunker.chunk_stream_with_offsets(stream).collect();
+
+    assert!(chunks.len() > 1);
+    for (i, chunk) in chunks.iter().enumerate() {
+        assert_eq!(chunk.index, i);
+        assert_eq!(&text[chunk.start..chunk.end], chunk.text);
+    }
+    assert_eq!(chunks, expected);
+}
+
+// ============================================================================
+// FORWARD PROGRESS TESTS
+// ============================================================================
+
+#[test]
+fn large_overlap_on_multibyte_text_slides_one_char() {
+    // A 1 byte step from each chunk start falls inside a 2 byte char
+    let chunks: Vec<_> = BytesChunker::new(4, 3)
+        .unwrap()
+        .chunk_string_with_offsets("ééééé".to_string())
+        .map(|c| (c.start, c.end))
+        .collect();
+
+    assert_eq!(chunks, vec![(0, 4), (2, 6), (4, 8), (6, 10)]);
+}
+
+#[test]
+fn chunk_size_smaller_than_char_holds_one_char() {
+    let text = "€€€".to_string();
+    let (_dir, path) = create_temp_file(&text);
+    let chunker = BytesChunker::new(2, 1).unwrap();
+
+    let from_string: Vec<_> = chunker.clone().chunk_string(text).collect();
+    let stream = StreamType::from_source(&Source::File(path)).unwrap();
+    let from_file: Vec<_> = chunker.chunk_stream(stream).collect();
+
+    assert_eq!(from_string, vec!["€", "€", "€"]);
+    assert_eq!(from_file, from_string);
+}
+
 // ============================================================================
 // ERROR HANDLING TESTS
 // ============================================================================
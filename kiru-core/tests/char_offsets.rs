@@ -0,0 +1,56 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{Chunker, ChunkerBuilder, ChunkerWithStrategy, Source};
+
+fn assert_slices_reproduce_chunks<C: Chunker>(chunker: ChunkerWithStrategy<C>, text: &str) {
+    let (_dir, path) = create_temp_file(text);
+    let chars: Vec<char> = text.chars().collect();
+
+    for source in [Source::Text(text.to_string()), Source::File(path)] {
+        let chunks: Vec<_> = chunker
+            .clone()
+            .with_char_offsets()
+            .on_source_with_offsets(source)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+
+        assert!(chunks.len() > 10);
+        for chunk in &chunks {
+            let range = chunk.chars.expect("char offsets requested");
+            assert_eq!(chunk.text, text[chunk.start..chunk.end]);
+            assert_eq!(
+                chunk.text,
+                chars[range.start..range.end].iter().collect::<String>(),
+                "chunk {}",
+                chunk.index
+            );
+        }
+    }
+}
+
+#[test]
+fn byte_and_char_slices_reproduce_byte_chunks() {
+    let text = "Größe über alles — naïve café 🚀 and plain ASCII too. ".repeat(400);
+    assert_slices_reproduce_chunks(ChunkerBuilder::by_bytes(100, 30).unwrap(), &text);
+}
+
+#[test]
+fn byte_and_char_slices_reproduce_character_chunks() {
+    let text = "日本語のテキスト, mixed with English 🚀. ".repeat(400);
+    assert_slices_reproduce_chunks(ChunkerBuilder::by_characters(60, 15).unwrap(), &text);
+}
+
+#[test]
+fn char_offsets_are_not_set_unless_requested() {
+    let chunk = ChunkerBuilder::by_bytes(10, 0)
+        .unwrap()
+        .on_source_with_offsets(Source::Text("abcdefghijkl".to_string()))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(chunk.chars, None);
+}
@@ -301,6 +301,109 @@ fn edge_case_mixed_multibyte() {
     assert_eq!(chunks[2].chars().count(), 3);
 }
 
+// ============================================================================
+// SENTENCE OVERLAP TESTS
+// ============================================================================
+
+fn sentence_text() -> String {
+    (0..40)
+        .map(|i| format!("Sentence number {i} is here. "))
+        .collect::<String>()
+}
+
+/// Each chunk after the first must begin at a sentence start and repeat exactly
+/// the last `n` sentences (starting inside) the previous chunk.
+fn assert_sentence_overlaps(chunks: &[String], n: usize) {
+    assert!(chunks.len() > 1);
+    for pair in chunks.windows(2) {
+        let (prev, next) = (&pair[0], &pair[1]);
+        assert!(
+            next.starts_with("Sentence"),
+            "chunk starts mid-sentence: {next:?}"
+        );
+
+        let overlap_start = prev
+            .rmatch_indices(". ")
+            .map(|(i, _)| i + 2)
+            .filter(|&start| start < prev.len())
+            .nth(n - 1)
+            .expect("previous chunk holds at least n sentence starts");
+        assert!(next.starts_with(&prev[overlap_start..]));
+    }
+}
+
+#[test]
+fn sentence_overlap_string() {
+    let text = sentence_text();
+    let chunker = CharactersChunker::new(120, 10)
+        .unwrap()
+        .with_overlap_sentences(2);
+    let chunks: Vec<_> = chunker.chunk_string(text.clone()).collect();
+
+    assert_sentence_overlaps(&chunks, 2);
+    assert!(chunks
+        .last()
+        .unwrap()
+        .ends_with("Sentence number 39 is here. "));
+}
+
+#[test]
+fn sentence_overlap_file_matches_string() {
+    let text = sentence_text().repeat(50);
+    let (_dir, path) = create_temp_file(&text);
+
+    let chunker = CharactersChunker::new(200, 10)
+        .unwrap()
+        .with_overlap_sentences(1);
+    let expected: Vec<_> = chunker.clone().chunk_string(text.clone()).collect();
+    let stream = StreamType::from_source(&Source::File(path)).unwrap();
+    let chunks: Vec<_> = chunker.chunk_stream(stream).collect();
+
+    assert_sentence_overlaps(&chunks, 1);
+    assert_eq!(chunks, expected);
+}
+
+#[test]
+fn sentence_overlap_falls_back_without_sentences() {
+    let text = "no terminators at all ".repeat(20);
+    let plain: Vec<_> = CharactersChunker::new(50, 5)
+        .unwrap()
+        .chunk_string(text.clone())
+        .collect();
+    let with_sentences: Vec<_> = CharactersChunker::new(50, 5)
+        .unwrap()
+        .with_overlap_sentences(2)
+        .chunk_string(text)
+        .collect();
+
+    assert_eq!(with_sentences, plain);
+}
+
+// ============================================================================
+// OFFSET TESTS
+// ============================================================================
+
+#[test]
+fn offsets_point_into_source() {
+    let text = "Some words, then ünïcödé 🎉 and more words. ".repeat(200);
+    let (_dir, path) = create_temp_file(&text);
+
+    let chunker = CharactersChunker::new(300, 40).unwrap();
+    let expected: Vec<_> = chunker
+        .clone()
+        .chunk_string_with_offsets(text.clone())
+        .collect();
+    let stream = StreamType::from_source(&Source::File(path)).unwrap();
+    let chunks: Vec<_> = chunker.chunk_stream_with_offsets(stream).collect();
+
+    assert!(chunks.len() > 1);
+    for (i, chunk) in chunks.iter().enumerate() {
+        assert_eq!(chunk.index, i);
+        assert_eq!(&text[chunk.start..chunk.end], chunk.text);
+    }
+    assert_eq!(chunks, expected);
+}
+
 // ============================================================================
 // ERROR HANDLING TESTS
 // ============================================================================
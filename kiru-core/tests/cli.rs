@@ -0,0 +1,112 @@
+mod common;
+
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+
+use common::helpers::create_temp_file;
+
+fn kiru(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_kiru"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn prints_chunks_of_a_file() {
+    let (_dir, path) = create_temp_file("héllo wörld, this is kiru");
+
+    let output = kiru(&[
+        "chunk",
+        "--strategy",
+        "characters",
+        "--chunk-size",
+        "10",
+        "--overlap",
+        "2",
+        &path,
+    ]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "héllo wörl\nrld, this \ns is kiru\n"
+    );
+}
+
+#[test]
+fn writes_jsonl_with_offsets() {
+    let text = "a".repeat(25);
+    let (_dir, path) = create_temp_file(&text);
+
+    let output = kiru(&["chunk", "--chunk-size", "10", "--format", "jsonl", &path]);
+
+    assert!(output.status.success());
+    let offsets: Vec<_> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| {
+            let chunk: serde_json::Value = serde_json::from_str(line).unwrap();
+            (
+                chunk["start"].as_u64().unwrap(),
+                chunk["end"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(offsets, [(0, 10), (10, 20), (20, 25)]);
+}
+
+#[test]
+fn rejects_invalid_arguments() {
+    let output = kiru(&["chunk", "--strategy", "words", "--source", "string", "text"]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("invalid strategy 'words'"));
+
+    let output = kiru(&["chunk", "--chunk-size", "10", "--overlap", "10", "-"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn lines_format_escapes_each_chunk_onto_one_line() {
+    let output = kiru(&[
+        "chunk",
+        "--chunk-size",
+        "6",
+        "--format",
+        "lines",
+        "--source",
+        "string",
+        "a\\b\ncd\r\nef",
+    ]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "a\\\\b\\ncd\n\\r\\nef\n"
+    );
+}
+
+#[test]
+fn stops_quietly_when_the_reader_closes_the_pipe() {
+    let (_dir, path) = create_temp_file(&"Some words and more words. ".repeat(400_000));
+    let mut child = Command::new(env!("CARGO_BIN_EXE_kiru"))
+        .args(["chunk", "--chunk-size", "100", "--format", "lines", &path])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // Read the first chunk, like `head -n 1`, then close the pipe
+    let mut first = String::new();
+    BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut first)
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert_eq!(first.len(), 101);
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+}
@@ -1,5 +1,7 @@
 use std::fs::File;
 use std::io::Write;
+
+use kiru::{Chunk, Chunker, ChunkerWithStrategy, Source};
 use tempfile::TempDir;
 
 /// Create a temporary file with given content
@@ -13,6 +15,35 @@ pub fn create_temp_file(content: &str) -> (TempDir, String) {
     (dir, path.to_string_lossy().to_string())
 }
 
+/// Texts of the chunks of `text`
+pub fn texts<C: Chunker>(chunker: &ChunkerWithStrategy<C>, text: &str) -> Vec<String> {
+    chunker
+        .on_source(Source::Text(text.to_string()))
+        .unwrap()
+        .collect()
+}
+
+/// Chunks of `text` read from a file, checked to be those of `text` read as a string
+pub fn file_chunks_matching_string_chunks<C: Chunker>(
+    chunker: &ChunkerWithStrategy<C>,
+    text: &str,
+) -> Vec<Chunk> {
+    let (_dir, path) = create_temp_file(text);
+    let from_file: Vec<_> = chunker
+        .on_source_with_offsets(Source::File(path))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let from_string: Vec<_> = chunker
+        .on_source_with_offsets(Source::Text(text.to_string()))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(from_file, from_string);
+    from_file
+}
+
 /// Verify all chunks are valid UTF-8 (universal for all chunkers)
 pub fn assert_all_valid_utf8(chunks: &[String]) {
     for (i, chunk) in chunks.iter().enumerate() {
@@ -23,3 +54,34 @@ pub fn assert_all_valid_utf8(chunks: &[String]) {
         );
     }
 }
+
+/// A byte-level BPE tokenizer without merges, making a token of every byte.
+#[cfg(feature = "tokenizers")]
+pub fn byte_level_tokenizer() -> tokenizers::Tokenizer {
+    let mut alphabet: Vec<char> = tokenizers::pre_tokenizers::byte_level::ByteLevel::alphabet()
+        .into_iter()
+        .collect();
+    alphabet.sort_unstable();
+    let vocab: serde_json::Map<_, _> = alphabet
+        .iter()
+        .enumerate()
+        .map(|(id, c)| (c.to_string(), serde_json::json!(id)))
+        .collect();
+    let config = serde_json::json!({
+        "version": "1.0",
+        "truncation": null,
+        "padding": null,
+        "added_tokens": [],
+        "normalizer": null,
+        "pre_tokenizer": {
+            "type": "ByteLevel",
+            "add_prefix_space": false,
+            "trim_offsets": false,
+            "use_regex": true
+        },
+        "post_processor": null,
+        "decoder": { "type": "ByteLevel", "add_prefix_space": false, "trim_offsets": false, "use_regex": true },
+        "model": { "type": "BPE", "vocab": vocab, "merges": [] }
+    });
+    config.to_string().parse().unwrap()
+}
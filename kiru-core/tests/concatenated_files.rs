@@ -0,0 +1,62 @@
+use std::fs;
+
+use kiru::{ChunkerBuilder, ConcatenatedFiles, FileSpan};
+
+#[test]
+fn chunks_map_back_to_their_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = dir.path().join("first.txt");
+    let second = dir.path().join("second.txt");
+    fs::write(&first, "Première ligne.\n".repeat(30)).unwrap();
+    fs::write(&second, "Second file line.\n".repeat(30)).unwrap();
+    let paths = vec![
+        first.to_string_lossy().into_owned(),
+        second.to_string_lossy().into_owned(),
+    ];
+    let contents = [
+        fs::read_to_string(&first).unwrap(),
+        fs::read_to_string(&second).unwrap(),
+    ];
+
+    let files = ConcatenatedFiles::open(paths.clone()).unwrap();
+    let offsets = files.offsets();
+    let chunks: Vec<_> = ChunkerBuilder::by_bytes(200, 50)
+        .unwrap()
+        .on_reader_with_offsets(files)
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert!(chunks.windows(2).all(|pair| pair[0].start < pair[1].start));
+    let mut straddling = 0;
+    for chunk in &chunks {
+        let spans = offsets.spans(chunk.start, chunk.end);
+        // The parts of the files make up the chunk, in order
+        let text: String = spans
+            .iter()
+            .map(|span| {
+                let file = paths.iter().position(|p| *p == span.path).unwrap();
+                &contents[file][span.start..span.end]
+            })
+            .collect();
+        assert_eq!(text, chunk.text);
+        straddling += usize::from(spans.len() > 1);
+    }
+    assert_eq!(straddling, 1);
+
+    let first_len = contents[0].len();
+    assert_eq!(
+        offsets.spans(first_len - 10, first_len + 5),
+        [
+            FileSpan {
+                path: paths[0].clone(),
+                start: first_len - 10,
+                end: first_len
+            },
+            FileSpan {
+                path: paths[1].clone(),
+                start: 0,
+                end: 5
+            }
+        ]
+    );
+}
@@ -0,0 +1,83 @@
+use kiru::{
+    BytesChunker, ChunkerBuilder, ChunkerConfig, ChunkerWithStrategy, ChunkingError,
+    ConfiguredChunker, OverlapDirection, Source,
+};
+
+fn chunks(chunker: &ConfiguredChunker, text: &str) -> Vec<String> {
+    let source = Source::Text(text.to_string());
+    match chunker {
+        ConfiguredChunker::Bytes(c) => c.on_source(source).unwrap().collect(),
+        ConfiguredChunker::Characters(c) => c.on_source(source).unwrap().collect(),
+    }
+}
+
+#[test]
+fn config_round_trips_through_json() {
+    let text = "Größe über alles. Naïve café 🚀 and plain ASCII too!\n".repeat(100);
+    let bytes = ChunkerWithStrategy::new(
+        BytesChunker::new(200, 20)
+            .unwrap()
+            .with_overlap_schedule(vec![(3, 50)])
+            .unwrap()
+            .with_snap_to_word(true)
+            .with_overlap_direction(OverlapDirection::Forward),
+    )
+    .with_prefix("doc: ")
+    .with_merge_small(50, 200)
+    .with_stride(2)
+    .unwrap();
+    let characters = ChunkerBuilder::by_characters(100, 10)
+        .unwrap()
+        .with_near_dedup(3, 64)
+        .with_size_band(0.5, 1.0)
+        .unwrap();
+
+    let bytes_chunks = bytes.on_source(Source::Text(text.clone())).unwrap();
+    let characters_chunks = characters.on_source(Source::Text(text.clone())).unwrap();
+
+    for (config, expected) in [
+        (bytes.config().unwrap(), bytes_chunks.collect::<Vec<_>>()),
+        (characters.config().unwrap(), characters_chunks.collect()),
+    ] {
+        let rebuilt = ChunkerConfig::from_json(&config.to_json()).unwrap();
+
+        assert_eq!(rebuilt, config);
+        assert!(expected.len() > 5);
+        assert_eq!(chunks(&rebuilt.build().unwrap(), &text), expected);
+    }
+}
+
+#[test]
+fn options_left_out_keep_their_default() {
+    let config =
+        ChunkerConfig::from_json(r#"{"strategy": "characters", "chunk_size": 4, "overlap": 1}"#)
+            .unwrap();
+
+    let chunker = config.build().unwrap();
+    assert_eq!(chunks(&chunker, "abcdefghij"), ["abcd", "defg", "ghij"]);
+}
+
+#[test]
+fn invalid_configs_are_rejected() {
+    for json in [
+        r#"{"strategy": "bytes", "chunk_size": 4, "overlap": 4}"#,
+        r#"{"strategy": "words", "chunk_size": 4, "overlap": 1}"#,
+        r#"{"strategy": "bytes", "overlap": 1}"#,
+    ] {
+        let built = ChunkerConfig::from_json(json).and_then(|config| config.build());
+        assert!(built.is_err(), "{json}");
+    }
+
+    let boundaries = ChunkerBuilder::by_boundaries(vec![10, 20], 0).unwrap();
+    assert!(matches!(
+        boundaries.config(),
+        Err(ChunkingError::InvalidConfig(_))
+    ));
+    let transformed = ChunkerBuilder::by_bytes(10, 0)
+        .unwrap()
+        .with_block_transform(|block| block.to_lowercase());
+    assert!(matches!(
+        transformed.config(),
+        Err(ChunkingError::InvalidConfig(_))
+    ));
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{BytesChunker, CharactersChunker, Chunk, Chunker, ChunkerWithStrategy, Source};
+
+fn chunks<C: Chunker>(chunker: C, source: Source) -> Vec<Chunk> {
+    ChunkerWithStrategy::new(chunker)
+        .on_source_with_offsets(source)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+#[test]
+fn bytes_chunks_include_context_on_both_sides() {
+    // Long enough for the stream buffer to be compacted many times
+    let text = "The quick brown fox jumps over the lazy dog. ".repeat(5000);
+    let (_dir, path) = create_temp_file(&text);
+    let core = chunks(
+        BytesChunker::new(100, 20).unwrap(),
+        Source::Text(text.clone()),
+    );
+
+    for source in [Source::Text(text.clone()), Source::File(path)] {
+        let chunker = BytesChunker::new(100, 20)
+            .unwrap()
+            .with_context_before_units(30)
+            .with_context_after_units(10);
+        let with_context = chunks(chunker, source);
+
+        // Same steps, so as many chunks, each widened by the context where there is some
+        assert_eq!(with_context.len(), core.len());
+        for (chunk, core) in with_context.iter().zip(&core) {
+            assert_eq!(chunk.start, core.start.saturating_sub(30));
+            assert_eq!(chunk.end, (core.end + 10).min(text.len()));
+            assert_eq!(chunk.text, text[chunk.start..chunk.end]);
+        }
+    }
+}
+
+#[test]
+fn characters_chunks_include_context_on_both_sides() {
+    let text = "Größe über alles, naïve café. ".repeat(1000);
+    let (_dir, path) = create_temp_file(&text);
+    let core = chunks(
+        CharactersChunker::new(50, 10).unwrap(),
+        Source::Text(text.clone()),
+    );
+    let char_at = |byte: usize| text[..byte].chars().count();
+    let byte_at = |char: usize| text.char_indices().nth(char).map_or(text.len(), |(i, _)| i);
+
+    for source in [Source::Text(text.clone()), Source::File(path)] {
+        let chunker = CharactersChunker::new(50, 10)
+            .unwrap()
+            .with_context_before_units(15)
+            .with_context_after_units(5);
+        let with_context = chunks(chunker, source);
+
+        assert_eq!(with_context.len(), core.len());
+        for (chunk, core) in with_context.iter().zip(&core) {
+            assert_eq!(chunk.start, byte_at(char_at(core.start).saturating_sub(15)));
+            assert_eq!(chunk.end, byte_at(char_at(core.end) + 5));
+            assert_eq!(chunk.text, text[chunk.start..chunk.end]);
+        }
+    }
+}
+
+#[test]
+fn context_does_not_split_chars() {
+    let text = "äöü".repeat(20);
+    let chunker = BytesChunker::new(10, 0)
+        .unwrap()
+        .with_context_before_units(3)
+        .with_context_after_units(3);
+
+    let with_context = chunks(chunker, Source::Text(text.clone()));
+
+    for chunk in &with_context {
+        assert!(chunk.text.len() <= 16);
+        assert_eq!(chunk.text, text[chunk.start..chunk.end]);
+    }
+    // 3 bytes before the second chunk fall inside an 'ä': the context starts after it
+    assert_eq!(with_context[1].text.chars().next(), Some('ö'));
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{Chunker, ChunkerBuilder, ChunkerWithStrategy, OverlapDirection, Source};
+
+fn streamed_count<C: Chunker>(chunker: &ChunkerWithStrategy<C>, path: &str) -> usize {
+    chunker
+        .on_source(Source::File(path.to_string()))
+        .unwrap()
+        .count()
+}
+
+#[test]
+fn fast_count_matches_streamed_count_on_ascii() {
+    for len in [0, 1, 99, 100, 101, 1000, 12_345] {
+        let text = "Some words. ".repeat(len / 12 + 1)[..len].to_string();
+        let (_dir, path) = create_temp_file(&text);
+
+        for (chunk_size, overlap) in [(100, 0), (100, 20), (64, 63), (7, 3)] {
+            for direction in [OverlapDirection::Backward, OverlapDirection::Forward] {
+                let bytes = ChunkerBuilder::by_bytes(chunk_size, overlap)
+                    .unwrap()
+                    .chunker()
+                    .clone()
+                    .with_overlap_direction(direction);
+                let bytes = ChunkerWithStrategy::new(bytes);
+                let expected = streamed_count(&bytes, &path);
+                assert_eq!(bytes.count_file(&path, true).unwrap(), expected);
+                assert_eq!(bytes.count_file(&path, false).unwrap(), expected);
+
+                let chars = ChunkerBuilder::by_characters(chunk_size, overlap)
+                    .unwrap()
+                    .chunker()
+                    .clone()
+                    .with_overlap_direction(direction);
+                let chars = ChunkerWithStrategy::new(chars).with_stride(3).unwrap();
+                let expected = streamed_count(&chars, &path);
+                assert_eq!(chars.count_file(&path, true).unwrap(), expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn non_ascii_files_are_streamed() {
+    let text = "Grüße aus Köln, schön hier. ".repeat(100);
+    let (_dir, path) = create_temp_file(&text);
+    let chunker = ChunkerBuilder::by_characters(100, 10).unwrap();
+
+    assert_eq!(
+        chunker.count_file(&path, false).unwrap(),
+        streamed_count(&chunker, &path)
+    );
+}
@@ -0,0 +1,48 @@
+use std::io::Cursor;
+
+use kiru::{IncompleteUtf8, Utf8BlockReader};
+
+#[test]
+fn debug_blocks_reassemble_the_source() {
+    let text = "Größe über alles, naïve café — 東京 🦀. ".repeat(50);
+    // Blocks of 7 bytes cut most characters of 2 to 4 bytes at some point
+    let blocks: Vec<_> = Utf8BlockReader::from_reader(Cursor::new(text.clone()), 7)
+        .debug_blocks()
+        .collect();
+
+    assert!(blocks.iter().any(|block| block.leftover_len > 0));
+    let mut offset = 0;
+    for block in &blocks {
+        assert_eq!(block.file_offset, offset);
+        assert_eq!(block.valid_up_to, block.text.len());
+        assert_eq!(&text[offset..offset + block.valid_up_to], block.text);
+        assert!(block.leftover_len < 4);
+        let end = offset + block.valid_up_to;
+        // Bytes carried over are the start of the character after the block
+        if block.leftover_len > 0 {
+            let next = text[end..].chars().next().unwrap();
+            assert!(block.leftover_len < next.len_utf8());
+        }
+        offset = end;
+    }
+    assert_eq!(offset, text.len());
+    assert_eq!(blocks.last().unwrap().leftover_len, 0);
+}
+
+#[test]
+fn debug_blocks_account_for_a_truncated_character() {
+    let mut bytes = "naïve café".as_bytes().to_vec();
+    bytes.extend_from_slice(&"€".as_bytes()[..2]);
+    let blocks: Vec<_> = Utf8BlockReader::from_reader(Cursor::new(bytes.clone()), 4)
+        .with_incomplete_utf8(IncompleteUtf8::Replace)
+        .debug_blocks()
+        .collect();
+
+    let last = blocks.last().unwrap();
+    assert_eq!(last.text, "\u{FFFD}");
+    assert_eq!(last.valid_up_to, 2);
+    assert_eq!(last.file_offset + last.valid_up_to, bytes.len());
+    assert!(blocks
+        .windows(2)
+        .all(|pair| pair[0].file_offset + pair[0].valid_up_to == pair[1].file_offset));
+}
@@ -0,0 +1,84 @@
+use kiru::{unique_chunks, ChunkerBuilder, NearDuplicateFilter, Source};
+
+const FOX: &str = "The quick brown fox jumps over the lazy dog while the farmer watches from \
+    the porch, sipping coffee and wondering whether the rain will hold off until the hay \
+    is safely stored in the barn before the evening storm rolls in from the west. ";
+const FOX_PERTURBED: &str = "The quick brown fox jumps over the lazy cat while the farmer watches \
+    from the porch, sipping coffee and wondering whether the rain will hold off until the hay \
+    is safely stored in the barn before the evening storm rolls in from the west! ";
+const HARBOR: &str = "Container ships queue outside the harbor at dawn, their hulls stacked \
+    with steel boxes bound for warehouses inland, while pilots in small boats weave between \
+    them to guide each vessel through the narrow channel past the old lighthouse. ";
+const LIBRARY: &str = "In the basement of the university library, archivists catalogue \
+    letters written by nineteenth century botanists, carefully unfolding brittle pages that \
+    describe expeditions, pressed flowers, quarrels with rivals and unpaid debts. ";
+
+/// Byte offsets separating consecutive parts, so each part becomes one chunk.
+fn cuts(parts: &[&str]) -> Vec<usize> {
+    parts
+        .iter()
+        .scan(0, |end, part| {
+            *end += part.len();
+            Some(*end)
+        })
+        .collect()
+}
+
+#[test]
+fn near_duplicates_are_suppressed() {
+    let parts = [FOX, FOX_PERTURBED, HARBOR, FOX_PERTURBED, LIBRARY, HARBOR];
+
+    let chunks: Vec<_> = ChunkerBuilder::by_boundaries(cuts(&parts), 0)
+        .unwrap()
+        .with_near_dedup(8, 16)
+        .on_source(Source::Text(parts.concat()))
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, vec![FOX, HARBOR, LIBRARY]);
+}
+
+#[test]
+fn distinct_chunks_pass_through() {
+    let parts = [FOX, HARBOR, LIBRARY];
+
+    let chunks: Vec<_> = ChunkerBuilder::by_boundaries(cuts(&parts), 0)
+        .unwrap()
+        .with_near_dedup(8, 16)
+        .on_sources(vec![Source::Text(parts.concat())])
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, parts);
+}
+
+#[test]
+fn least_recently_used_fingerprint_is_evicted() {
+    let mut filter = NearDuplicateFilter::new(8, 2);
+
+    assert!(!filter.is_near_duplicate(FOX));
+    assert!(!filter.is_near_duplicate(HARBOR));
+    // Refreshes FOX, so HARBOR is now the least recently used
+    assert!(filter.is_near_duplicate(FOX_PERTURBED));
+    assert!(!filter.is_near_duplicate(LIBRARY));
+
+    assert!(filter.is_near_duplicate(FOX));
+    assert!(!filter.is_near_duplicate(HARBOR));
+}
+
+#[test]
+fn unique_chunks_keeps_first_occurrences_in_order() {
+    let parts = [FOX, HARBOR, FOX, LIBRARY, HARBOR, FOX_PERTURBED];
+    let chunks = ChunkerBuilder::by_boundaries(cuts(&parts), 0)
+        .unwrap()
+        .on_source_with_offsets(Source::Text(parts.concat()))
+        .unwrap();
+
+    let unique: Vec<_> = unique_chunks(chunks).map(|chunk| chunk.unwrap()).collect();
+
+    let texts: Vec<&str> = unique.iter().map(|c| c.text.as_str()).collect();
+    // Near-duplicates are distinct content: only exact repeats go
+    assert_eq!(texts, [FOX, HARBOR, LIBRARY, FOX_PERTURBED]);
+    let indices: Vec<usize> = unique.iter().map(|c| c.index).collect();
+    assert_eq!(indices, [0, 1, 3, 5]);
+}
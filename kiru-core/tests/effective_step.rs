@@ -0,0 +1,37 @@
+use kiru::{BytesChunker, CharactersChunker, Chunker, ChunkerBuilder, Source};
+
+#[test]
+fn bytes_step_matches_chunk_starts() {
+    // 3-byte chars make most cuts snap back, shortening the step
+    let sample = "日本語のテキスト、".repeat(50);
+    let chunker = BytesChunker::new(100, 20).unwrap();
+
+    let starts: Vec<_> = ChunkerBuilder::by_bytes(100, 20)
+        .unwrap()
+        .on_source_with_offsets(Source::Text(sample.clone()))
+        .unwrap()
+        .map(|chunk| chunk.unwrap().start)
+        .collect();
+    let smallest = starts.windows(2).map(|w| w[1] - w[0]).min();
+
+    assert_eq!(chunker.effective_step(&sample), smallest);
+    assert!(smallest.unwrap() < 80);
+}
+
+#[test]
+fn characters_step_is_in_characters() {
+    let sample = "日本語のテキスト、".repeat(50);
+
+    let step = CharactersChunker::new(100, 20)
+        .unwrap()
+        .effective_step(&sample);
+
+    assert_eq!(step, Some(80));
+}
+
+#[test]
+fn short_sample_has_no_step() {
+    let chunker = BytesChunker::new(100, 20).unwrap();
+
+    assert_eq!(chunker.effective_step("short"), None);
+}
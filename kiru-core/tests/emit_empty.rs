@@ -0,0 +1,58 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{ChunkerBuilder, Source};
+
+#[test]
+fn empty_input_yields_one_empty_chunk() {
+    let (_dir, path) = create_temp_file("");
+    let chunker = ChunkerBuilder::by_bytes(10, 2)
+        .unwrap()
+        .with_emit_empty(true);
+
+    let chunks: Vec<_> = chunker
+        .on_source_with_offsets(Source::File(path))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(
+        (chunks[0].text.as_str(), chunks[0].start, chunks[0].end),
+        ("", 0, 0)
+    );
+
+    let chunks: Vec<_> = chunker
+        .on_sources(vec![
+            Source::Text(String::new()),
+            Source::Text("abc".to_string()),
+        ])
+        .unwrap()
+        .collect();
+    assert_eq!(chunks, ["", "abc"]);
+
+    let without: Vec<_> = ChunkerBuilder::by_bytes(10, 2)
+        .unwrap()
+        .on_source(Source::Text(String::new()))
+        .unwrap()
+        .collect();
+    assert!(without.is_empty());
+}
+
+#[test]
+fn non_empty_input_is_unaffected() {
+    let text = "The quick brown fox jumps over the lazy dog.".to_string();
+
+    let with: Vec<_> = ChunkerBuilder::by_characters(10, 3)
+        .unwrap()
+        .with_emit_empty(true)
+        .on_source(Source::Text(text.clone()))
+        .unwrap()
+        .collect();
+    let without: Vec<_> = ChunkerBuilder::by_characters(10, 3)
+        .unwrap()
+        .on_source(Source::Text(text))
+        .unwrap()
+        .collect();
+
+    assert_eq!(with, without);
+}
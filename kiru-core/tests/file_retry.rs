@@ -0,0 +1,168 @@
+use std::fs;
+use std::io::{self, Read};
+use std::time::{Duration, Instant};
+
+use kiru::{
+    is_transient, ChunkerBuilder, ChunkingError, HigherOrderSource, RetryPolicy, RetryReader,
+    Source, SourceGenerator,
+};
+use tempfile::TempDir;
+
+/// Fails its first `failures` reads with `kind`, then reads `data`.
+struct FlakyReader {
+    data: io::Cursor<Vec<u8>>,
+    failures: usize,
+    kind: io::ErrorKind,
+}
+
+impl FlakyReader {
+    fn new(data: &str, failures: usize, kind: io::ErrorKind) -> Self {
+        Self {
+            data: io::Cursor::new(data.as_bytes().to_vec()),
+            failures,
+            kind,
+        }
+    }
+}
+
+impl Read for FlakyReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.failures > 0 {
+            self.failures -= 1;
+            return Err(io::Error::new(self.kind, "flaky"));
+        }
+        self.data.read(buf)
+    }
+}
+
+fn retry() -> RetryPolicy {
+    RetryPolicy::new(3, Duration::from_millis(1))
+}
+
+#[test]
+fn read_failing_once_is_retried_and_chunked() {
+    let text = "a file on a share that times out once. ".repeat(10);
+    let chunker = ChunkerBuilder::by_bytes(32, 0).unwrap();
+    let reader = RetryReader::new(FlakyReader::new(&text, 1, io::ErrorKind::TimedOut), retry());
+
+    let chunks: Vec<_> = chunker
+        .on_reader_with_offsets(reader)
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    let expected: Vec<_> = chunker.on_source(Source::Text(text)).unwrap().collect();
+    assert_eq!(
+        chunks.iter().map(|c| c.text.clone()).collect::<Vec<_>>(),
+        expected
+    );
+}
+
+#[test]
+fn read_failing_once_without_retry_ends_with_the_error() {
+    let text = "a file on a share that times out once. ".repeat(10);
+    let reader = FlakyReader::new(&text, 1, io::ErrorKind::TimedOut);
+
+    let chunks: Vec<_> = ChunkerBuilder::by_bytes(32, 0)
+        .unwrap()
+        .on_reader_with_offsets(reader)
+        .collect();
+
+    assert!(matches!(chunks.as_slice(), [Err(ChunkingError::Io(_))]));
+}
+
+#[test]
+fn reads_failing_more_than_the_attempts_keep_the_error() {
+    let reader = RetryReader::new(
+        FlakyReader::new("never read", 3, io::ErrorKind::TimedOut),
+        retry(),
+    );
+
+    let chunks: Vec<_> = ChunkerBuilder::by_bytes(32, 0)
+        .unwrap()
+        .on_reader_with_offsets(reader)
+        .collect();
+
+    assert!(matches!(chunks.as_slice(), [Err(ChunkingError::Io(_))]));
+}
+
+#[test]
+fn permanent_errors_are_not_retried() {
+    let mut reader = RetryReader::new(
+        FlakyReader::new("data", 1, io::ErrorKind::PermissionDenied),
+        RetryPolicy::new(3, Duration::from_secs(10)),
+    );
+
+    let started = Instant::now();
+    let error = reader.read(&mut [0; 8]).unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+    assert!(started.elapsed() < Duration::from_secs(1));
+    assert!(!is_transient(&io::Error::from(io::ErrorKind::NotFound)));
+    assert!(is_transient(&io::Error::from(io::ErrorKind::TimedOut)));
+}
+
+#[test]
+fn missing_file_fails_without_waiting() {
+    let dir = TempDir::new().unwrap();
+    let missing = dir.path().join("missing.txt");
+    let chunker = ChunkerBuilder::by_bytes(32, 0)
+        .unwrap()
+        .with_file_retry(RetryPolicy::new(3, Duration::from_secs(10)));
+
+    let started = Instant::now();
+    let result =
+        chunker.on_source_with_offsets(Source::File(missing.to_string_lossy().to_string()));
+
+    assert!(matches!(result, Err(ChunkingError::Io(e)) if e.kind() == io::ErrorKind::NotFound));
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn glob_sources_are_chunked_with_retry() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("a.txt"), "alpha ".repeat(20)).unwrap();
+    fs::write(dir.path().join("b.txt"), "beta ".repeat(20)).unwrap();
+    let pattern = dir.path().join("*.txt").to_string_lossy().to_string();
+    let sources =
+        HigherOrderSource::into_flattened_sources(vec![HigherOrderSource::SourceGenerator(
+            SourceGenerator::Glob(pattern),
+        )])
+        .unwrap();
+    let chunker = ChunkerBuilder::by_bytes(32, 0).unwrap();
+
+    let retried: Vec<_> = chunker
+        .clone()
+        .with_file_retry(retry())
+        .on_sources(sources.clone())
+        .unwrap()
+        .collect();
+    let mut retried_par: Vec<_> = chunker
+        .clone()
+        .with_file_retry(retry())
+        .on_sources_par_stream(sources.clone(), 4)
+        .unwrap()
+        .collect();
+    let plain: Vec<_> = chunker.on_sources(sources).unwrap().collect();
+
+    assert_eq!(retried, plain);
+    assert!(!retried.is_empty());
+    retried_par.sort();
+    let mut sorted = plain;
+    sorted.sort();
+    assert_eq!(retried_par, sorted);
+}
+
+#[test]
+fn policy_tries_at_least_once() {
+    let policy = RetryPolicy::new(0, Duration::ZERO);
+    let mut calls = 0;
+
+    let result: io::Result<()> = policy.run(|| {
+        calls += 1;
+        Err(io::Error::from(io::ErrorKind::TimedOut))
+    });
+
+    assert!(result.is_err());
+    assert_eq!(calls, 1);
+    assert_eq!(policy, RetryPolicy::none());
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{ChunkerBuilder, Source, BLOCK_SIZE};
+
+/// Bytes read by this process so far. This file holds a single test, so no other test
+/// reads concurrently.
+#[cfg(target_os = "linux")]
+fn bytes_read() -> usize {
+    std::fs::read_to_string("/proc/self/io")
+        .unwrap()
+        .lines()
+        .find_map(|line| line.strip_prefix("rchar: "))
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn first_reads_only_the_first_buffer_fill() {
+    let text = "Some words and more words. ".repeat(400_000);
+    let (_dir, path) = create_temp_file(&text);
+    let chunker = ChunkerBuilder::by_bytes(1000, 100).unwrap();
+
+    let before = bytes_read();
+    let first = chunker.first(Source::File(path)).unwrap();
+    let read = bytes_read() - before;
+
+    assert_eq!(first.as_deref(), Some(&text[..1000]));
+    // One block fills the 5000 byte buffer; allow for reading /proc/self/io itself
+    assert!(
+        read <= BLOCK_SIZE + 1024,
+        "read {read} of {} bytes",
+        text.len()
+    );
+
+    assert_eq!(chunker.first(Source::Text(String::new())).unwrap(), None);
+}
@@ -0,0 +1,90 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use kiru::{ChunkerBuilder, ChunkingError};
+use tempfile::TempDir;
+
+#[test]
+fn follow_chunks_content_written_after_eof() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("growing.txt");
+    let first = "first part of the log. ".repeat(20);
+    fs::write(&path, &first).unwrap();
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let chunks = ChunkerBuilder::by_characters(16, 0)
+        .unwrap()
+        .on_file_follow(&path, Duration::from_millis(5), stop.clone())
+        .unwrap();
+
+    // "é" is written in two halves, so a read can end inside it
+    let later: Vec<Vec<u8>> = vec![
+        b"then a caf\xc3".to_vec(),
+        b"\xa9 opened. ".to_vec(),
+        "and later lines arrive, ".repeat(20).into_bytes(),
+    ];
+    let writer = {
+        let path = path.clone();
+        let stop = stop.clone();
+        let later = later.clone();
+        thread::spawn(move || {
+            let mut file = OpenOptions::new().append(true).open(path).unwrap();
+            for piece in later {
+                thread::sleep(Duration::from_millis(50));
+                file.write_all(&piece).unwrap();
+                file.flush().unwrap();
+            }
+            thread::sleep(Duration::from_millis(50));
+            stop.store(true, Ordering::Relaxed);
+        })
+    };
+
+    let mut stopped_at_first_chunk = None;
+    let mut text = String::new();
+    for chunk in chunks {
+        stopped_at_first_chunk.get_or_insert(stop.load(Ordering::Relaxed));
+        text.push_str(&chunk.unwrap().text);
+    }
+    writer.join().unwrap();
+
+    let expected = first + &String::from_utf8(later.concat()).unwrap();
+    assert_eq!(text, expected);
+    assert!(text.contains("café opened"));
+    // Chunks of the text already written are yielded while following
+    assert_eq!(stopped_at_first_chunk, Some(false));
+}
+
+#[test]
+fn stopped_follow_reads_to_the_end_like_a_file() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("done.txt");
+    fs::write(&path, "abcdefghij").unwrap();
+
+    let texts: Vec<String> = ChunkerBuilder::by_bytes(4, 0)
+        .unwrap()
+        .on_file_follow(
+            &path,
+            Duration::from_secs(60),
+            Arc::new(AtomicBool::new(true)),
+        )
+        .unwrap()
+        .map(|chunk| chunk.unwrap().text)
+        .collect();
+
+    assert_eq!(texts, ["abcd", "efgh", "ij"]);
+}
+
+#[test]
+fn follow_of_missing_file_is_an_io_error() {
+    let result = ChunkerBuilder::by_bytes(4, 0).unwrap().on_file_follow(
+        "/nonexistent/growing.txt",
+        Duration::from_millis(5),
+        Arc::new(AtomicBool::new(false)),
+    );
+
+    assert!(matches!(result, Err(ChunkingError::Io(_))));
+}
@@ -0,0 +1,76 @@
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::path::Path;
+use std::process::Command;
+
+use kiru::{ChunkerBuilder, HigherOrderSource, Source, SourceGenerator};
+
+fn glob(dir: &Path) -> Vec<HigherOrderSource> {
+    let pattern = format!("{}/**/*", dir.display());
+    vec![HigherOrderSource::SourceGenerator(SourceGenerator::Glob(
+        pattern,
+    ))]
+}
+
+fn file_names(sources: &[Source]) -> Vec<String> {
+    let mut names: Vec<String> = sources
+        .iter()
+        .map(|s| match s {
+            Source::File(path) => path.rsplit('/').next().unwrap().to_string(),
+            other => panic!("unexpected source {other:?}"),
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn broken_symlinks_and_fifos_are_skipped() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("doc.txt"), "some text to chunk").unwrap();
+    symlink(
+        dir.path().join("missing.txt"),
+        dir.path().join("broken.txt"),
+    )
+    .unwrap();
+    let fifo = dir.path().join("pipe");
+    assert!(Command::new("mkfifo")
+        .arg(&fifo)
+        .status()
+        .unwrap()
+        .success());
+
+    let expanded = HigherOrderSource::expand(glob(dir.path()), false).unwrap();
+
+    assert_eq!(file_names(&expanded.sources), ["doc.txt"]);
+    let mut skipped = expanded.skipped.clone();
+    skipped.sort();
+    assert_eq!(skipped, [dir.path().join("broken.txt"), fifo]);
+    // Chunking the expanded sources neither fails nor blocks on the FIFO
+    let chunks: Vec<String> = ChunkerBuilder::by_bytes(100, 0)
+        .unwrap()
+        .on_sources(expanded.sources)
+        .unwrap()
+        .collect();
+    assert_eq!(chunks, ["some text to chunk"]);
+}
+
+#[test]
+fn symlinks_are_followed_once_on_request() {
+    let dir = tempfile::tempdir().unwrap();
+    let docs = dir.path().join("docs");
+    fs::create_dir(&docs).unwrap();
+    fs::write(docs.join("doc.txt"), "text").unwrap();
+    symlink(docs.join("doc.txt"), dir.path().join("link.txt")).unwrap();
+    // A cycle: docs/loop leads back to docs
+    symlink(&docs, docs.join("loop")).unwrap();
+
+    let expanded = HigherOrderSource::expand(glob(dir.path()), false).unwrap();
+    assert_eq!(file_names(&expanded.sources), ["doc.txt"]);
+    assert!(expanded.skipped.contains(&dir.path().join("link.txt")));
+
+    let expanded = HigherOrderSource::expand(glob(dir.path()), true).unwrap();
+    assert_eq!(file_names(&expanded.sources), ["doc.txt"]);
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use common::helpers::{file_chunks_matching_string_chunks, texts};
+use kiru::{Chunker, ChunkerBuilder, ChunkingError, GraphemesChunker, Source};
+
+const FAMILY: &str = "👨\u{200d}👩\u{200d}👧\u{200d}👦";
+const FLAG: &str = "🇨🇭";
+const E_ACUTE: &str = "e\u{301}";
+
+#[test]
+fn clusters_are_never_cut() {
+    let text = format!("{FAMILY}{FLAG}{E_ACUTE}{FLAG}{FAMILY}");
+
+    assert_eq!(
+        texts(&ChunkerBuilder::by_graphemes(2, 0).unwrap(), &text),
+        [
+            format!("{FAMILY}{FLAG}"),
+            format!("{E_ACUTE}{FLAG}"),
+            FAMILY.to_string()
+        ]
+    );
+    // The characters strategy cuts inside them
+    let chars: Vec<_> = ChunkerBuilder::by_characters(2, 0)
+        .unwrap()
+        .on_source(Source::Text(text.clone()))
+        .unwrap()
+        .collect();
+    assert_ne!(chars[0], format!("{FAMILY}{FLAG}"));
+}
+
+#[test]
+fn overlap_is_counted_in_clusters() {
+    let text = format!("a{FLAG}b{E_ACUTE}c{FAMILY}");
+
+    assert_eq!(
+        texts(&ChunkerBuilder::by_graphemes(3, 1).unwrap(), &text),
+        [
+            format!("a{FLAG}b"),
+            format!("b{E_ACUTE}c"),
+            format!("c{FAMILY}")
+        ]
+    );
+}
+
+#[test]
+fn overlap_of_flags_starts_on_a_cluster() {
+    // A run of flags, each two regional indicators that only pair up counted from the
+    // start of the run
+    let flags = ["🇨🇭", "🇫🇷", "🇩🇪", "🇮🇹", "🇯🇵", "🇧🇷", "🇨🇦"];
+    let text = format!("a{}", flags.concat());
+    let chunks =
+        file_chunks_matching_string_chunks(&ChunkerBuilder::by_graphemes(3, 2).unwrap(), &text);
+
+    let texts: Vec<_> = chunks.iter().map(|chunk| chunk.text.as_str()).collect();
+    assert_eq!(
+        texts,
+        [
+            format!("a{}{}", flags[0], flags[1]),
+            flags[..3].concat(),
+            flags[1..4].concat(),
+            flags[2..5].concat(),
+            flags[3..6].concat(),
+            flags[4..7].concat(),
+        ]
+    );
+    // The effective overlap in bytes is whole flags
+    let overlaps: Vec<_> = chunks
+        .windows(2)
+        .map(|pair| pair[0].end - pair[1].start)
+        .collect();
+    assert_eq!(overlaps, [16; 5]);
+}
+
+#[test]
+fn crlf_is_one_cluster() {
+    assert_eq!(
+        texts(&ChunkerBuilder::by_graphemes(2, 0).unwrap(), "a\r\nb\r\n"),
+        ["a\r\n", "b\r\n"]
+    );
+}
+
+#[test]
+fn measure_counts_clusters() {
+    let chunker = GraphemesChunker::new(4, 0).unwrap();
+
+    assert_eq!(chunker.measure(&format!("{FAMILY}{FLAG}{E_ACUTE}")), 3);
+    assert_eq!(chunker.measure(""), 0);
+}
+
+#[test]
+fn clusters_cut_by_block_ends_are_not_split() {
+    let text = format!("{FAMILY}{FLAG}{FLAG}{E_ACUTE}").repeat(50);
+    let chunker = GraphemesChunker::new(3, 1).unwrap();
+    let whole: Vec<_> = chunker
+        .clone()
+        .chunk_string_with_offsets(text.clone())
+        .collect();
+
+    // Blocks of every size, so that every cluster is cut at some char
+    for block_chars in 1..12 {
+        let chars: Vec<char> = text.chars().collect();
+        let blocks = chars
+            .chunks(block_chars)
+            .map(|block| block.iter().collect::<String>());
+        let streamed: Vec<_> = chunker.clone().chunk_stream_with_offsets(blocks).collect();
+
+        assert_eq!(streamed, whole, "blocks of {block_chars} chars");
+    }
+}
+
+#[test]
+fn file_chunks_match_string_chunks_across_blocks() {
+    let text: String = (0..20_000)
+        .map(|n| [FAMILY, FLAG, E_ACUTE, "x", " "][n % 5])
+        .collect();
+    let from_file =
+        file_chunks_matching_string_chunks(&ChunkerBuilder::by_graphemes(500, 50).unwrap(), &text);
+
+    assert_eq!(from_file.len(), 45);
+}
+
+#[test]
+fn invalid_arguments_are_rejected() {
+    assert!(matches!(
+        ChunkerBuilder::by_graphemes(0, 0),
+        Err(ChunkingError::InvalidArguments { .. })
+    ));
+    assert!(matches!(
+        ChunkerBuilder::by_graphemes(3, 3),
+        Err(ChunkingError::InvalidArguments { .. })
+    ));
+}
@@ -0,0 +1,44 @@
+use kiru::{BytesChunker, Chunker, ChunkerWithStrategy, Source};
+
+#[test]
+fn chunk_ends_fall_on_the_grid() {
+    let text = "Grüße aus Köln, schön hier. ".repeat(400);
+    let chunker = BytesChunker::new(100, 20).unwrap().with_grid_align(16);
+
+    let chunks: Vec<_> = ChunkerWithStrategy::new(chunker)
+        .on_source_with_offsets(Source::Text(text.clone()))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    let (last, rest) = chunks.split_last().unwrap();
+    assert_eq!(last.end, text.len());
+    for chunk in rest {
+        assert_eq!(
+            chunk.end % 16,
+            0,
+            "chunk {} ends at {}",
+            chunk.index,
+            chunk.end
+        );
+        assert!(text.is_char_boundary(chunk.end));
+        assert!(chunk.end - chunk.start <= 100);
+    }
+}
+
+#[test]
+fn chunks_without_a_grid_line_end_as_usual() {
+    let text = "0123456789".repeat(10);
+    let chunker = BytesChunker::new(30, 0).unwrap();
+
+    let aligned: Vec<String> = chunker
+        .clone()
+        .with_grid_align(64)
+        .chunk_string(text.clone())
+        .collect();
+    let plain: Vec<String> = chunker.chunk_string(text).collect();
+
+    // No grid line within the first two chunks; the third one reaches the line at 64
+    assert_eq!(aligned[..2], plain[..2]);
+    assert_eq!(aligned[2], "0123");
+}
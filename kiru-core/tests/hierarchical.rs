@@ -0,0 +1,97 @@
+use kiru::{
+    hierarchical, BytesChunker, CharactersChunker, Chunk, ChunkHierarchy, ChunkingError, Source,
+};
+
+fn split(chunks: Vec<Chunk>) -> (Vec<Chunk>, Vec<Chunk>) {
+    chunks
+        .into_iter()
+        .partition(|chunk| chunk.hierarchy.unwrap().level == 0)
+}
+
+#[test]
+fn every_child_is_within_its_parent() {
+    let text = "Größe über alles, naïve café. ".repeat(40);
+    let chunks: Vec<Chunk> = hierarchical(
+        Source::Text(text.clone()),
+        200,
+        50,
+        10,
+        CharactersChunker::new,
+    )
+    .unwrap()
+    .map(Result::unwrap)
+    .collect();
+    let (parents, children) = split(chunks);
+
+    assert!(parents.len() > 1);
+    assert!(children.len() > parents.len());
+    for child in &children {
+        let parent = &parents[child.hierarchy.unwrap().parent_index.unwrap()];
+        assert!(parent.text.contains(&child.text));
+        assert!(parent.start <= child.start && child.end <= parent.end);
+        assert_eq!(text[child.start..child.end], child.text);
+    }
+    assert_eq!(
+        children.iter().map(|c| c.index).collect::<Vec<_>>(),
+        (0..children.len()).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn parents_come_before_their_children() {
+    let chunks: Vec<Chunk> = hierarchical(
+        Source::Text("abcdefghij".to_string()),
+        6,
+        3,
+        0,
+        BytesChunker::new,
+    )
+    .unwrap()
+    .map(Result::unwrap)
+    .collect();
+
+    let tagged: Vec<(&str, Option<ChunkHierarchy>)> = chunks
+        .iter()
+        .map(|chunk| (chunk.text.as_str(), chunk.hierarchy))
+        .collect();
+    let parent = Some(ChunkHierarchy {
+        level: 0,
+        parent_index: None,
+    });
+    let child_of = |index| {
+        Some(ChunkHierarchy {
+            level: 1,
+            parent_index: Some(index),
+        })
+    };
+    assert_eq!(
+        tagged,
+        [
+            ("abcdef", parent),
+            ("abc", child_of(0)),
+            ("def", child_of(0)),
+            ("ghij", parent),
+            ("ghi", child_of(1)),
+            ("j", child_of(1)),
+        ]
+    );
+}
+
+#[test]
+fn child_size_must_be_less_than_parent_size() {
+    let result = hierarchical(
+        Source::Text("abc".to_string()),
+        10,
+        10,
+        0,
+        BytesChunker::new,
+    );
+
+    assert!(matches!(
+        result.err(),
+        Some(ChunkingError::InvalidChildSize {
+            parent_size: 10,
+            child_size: 10
+        })
+    ));
+}
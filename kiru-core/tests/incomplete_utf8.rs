@@ -0,0 +1,87 @@
+use std::fs;
+
+use kiru::{ChunkerBuilder, ChunkingError, IncompleteUtf8, Source};
+use tempfile::TempDir;
+
+/// A file of `text` followed by the first two bytes of `€`, as if truncated mid-character.
+fn truncated_file(text: &str) -> (TempDir, String) {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("truncated.txt");
+    let mut bytes = text.as_bytes().to_vec();
+    bytes.extend_from_slice(&"€".as_bytes()[..2]);
+    fs::write(&path, bytes).unwrap();
+    (dir, path.to_string_lossy().into_owned())
+}
+
+fn chunk_with(path: &str, policy: IncompleteUtf8) -> (Vec<Result<String, ChunkingError>>, usize) {
+    let chunker = ChunkerBuilder::by_bytes(64, 0)
+        .unwrap()
+        .with_incomplete_utf8(policy);
+    let results = chunker
+        .on_source_with_offsets(Source::File(path.to_string()))
+        .unwrap()
+        .map(|chunk| chunk.map(|c| c.text))
+        .collect();
+    (results, chunker.stats().truncated_sources)
+}
+
+#[test]
+fn truncated_character_is_dropped_by_default() {
+    let text = "Größe über alles, naïve café. ".repeat(20);
+    let (_dir, path) = truncated_file(&text);
+
+    let chunks: Vec<String> = ChunkerBuilder::by_bytes(64, 0)
+        .unwrap()
+        .on_source(Source::File(path.clone()))
+        .unwrap()
+        .collect();
+    let (results, truncated) = chunk_with(&path, IncompleteUtf8::Drop);
+
+    assert_eq!(chunks.concat(), text);
+    assert_eq!(results.len(), chunks.len());
+    assert_eq!(truncated, 1);
+}
+
+#[test]
+fn truncated_character_is_replaced() {
+    let text = "Größe über alles, naïve café. ".repeat(20);
+    let (_dir, path) = truncated_file(&text);
+
+    let (results, truncated) = chunk_with(&path, IncompleteUtf8::Replace);
+
+    let text_read: String = results.into_iter().map(Result::unwrap).collect();
+    assert_eq!(text_read, format!("{text}\u{FFFD}"));
+    assert_eq!(truncated, 1);
+}
+
+#[test]
+fn truncated_character_is_an_error_after_the_chunks() {
+    let text = "Größe über alles, naïve café. ".repeat(20);
+    let (_dir, path) = truncated_file(&text);
+
+    let (results, truncated) = chunk_with(&path, IncompleteUtf8::Error);
+
+    let (error, chunks) = results.split_last().unwrap();
+    let text_read: String = chunks
+        .iter()
+        .map(|c| c.as_ref().unwrap().as_str())
+        .collect();
+    assert_eq!(text_read, text);
+    assert!(matches!(
+        error,
+        Err(ChunkingError::IncompleteUtf8 { bytes: 2 })
+    ));
+    assert_eq!(truncated, 1);
+}
+
+#[test]
+fn complete_file_is_not_counted() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("complete.txt");
+    fs::write(&path, "Größe über alles 🚀".repeat(20)).unwrap();
+
+    let (results, truncated) = chunk_with(&path.to_string_lossy(), IncompleteUtf8::Error);
+
+    assert!(results.iter().all(Result::is_ok));
+    assert_eq!(truncated, 0);
+}
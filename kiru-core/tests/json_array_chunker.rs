@@ -0,0 +1,108 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{ChunkerBuilder, ChunkingError, Source};
+use serde_json::{json, Value};
+
+/// A pretty-printed array of 10 elements of various nested shapes.
+fn fixture() -> (String, Vec<Value>) {
+    let elements: Vec<Value> = (0..10)
+        .map(|n| match n % 3 {
+            0 => json!(format!("item, [{n}] \"quoted\"")),
+            1 => json!({ "id": n, "tags": ["a", "b"], "nested": { "x": [n, { "y": null }] } }),
+            _ => json!([n, n + 1]),
+        })
+        .collect();
+    let text = serde_json::to_string_pretty(&elements).unwrap();
+    (format!("\n  {text}\n"), elements)
+}
+
+#[test]
+fn groups_elements_with_overlap() {
+    let (text, elements) = fixture();
+    let (_dir, path) = create_temp_file(&text);
+    let chunker = ChunkerBuilder::by_json_array(3, 1).unwrap();
+
+    let chunks: Vec<String> = chunker.on_source(Source::File(path)).unwrap().collect();
+
+    // Groups start every 2 elements: 0-2, 2-4, 4-6, 6-8, then 8-9 ends the array
+    assert_eq!(chunks.len(), 5);
+    for (k, chunk) in chunks.iter().enumerate() {
+        let group: Vec<Value> = serde_json::from_str(chunk).unwrap();
+        let first = 2 * k;
+        assert_eq!(group, elements[first..(first + 3).min(10)], "chunk {k}");
+    }
+
+    let from_string: Vec<String> = chunker.on_source(Source::Text(text)).unwrap().collect();
+    assert_eq!(from_string, chunks);
+}
+
+#[test]
+fn groups_of_three_without_overlap_reparse() {
+    let (text, elements) = fixture();
+    let chunker = ChunkerBuilder::by_json_array(3, 0).unwrap();
+
+    let chunks: Vec<_> = chunker
+        .on_source_with_offsets(Source::Text(text.clone()))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(chunks.len(), 4);
+    let mut reparsed = Vec::new();
+    for chunk in &chunks {
+        // Offsets span the elements in the source, and the text drops their whitespace
+        let source: Value =
+            serde_json::from_str(&format!("[{}]", &text[chunk.start..chunk.end])).unwrap();
+        let group: Value = serde_json::from_str(&chunk.text).unwrap();
+        assert_eq!(group, source);
+        assert!(!chunk.text.contains('\n'));
+        reparsed.extend(group.as_array().unwrap().iter().cloned());
+    }
+    assert_eq!(reparsed, elements);
+}
+
+#[test]
+fn arrays_larger_than_a_block_are_streamed() {
+    let elements: Vec<Value> = (0..5000)
+        .map(|n| json!({ "n": n, "text": "x".repeat(n % 50) }))
+        .collect();
+    let text = serde_json::to_string(&elements).unwrap();
+    let (_dir, path) = create_temp_file(&text);
+    let chunker = ChunkerBuilder::by_json_array(100, 10).unwrap();
+
+    let groups: Vec<Vec<Value>> = chunker
+        .on_source(Source::File(path))
+        .unwrap()
+        .map(|chunk| serde_json::from_str(&chunk).unwrap())
+        .collect();
+
+    assert_eq!(groups.len(), 56);
+    for (k, group) in groups.iter().enumerate() {
+        assert_eq!(group[..], elements[90 * k..(90 * k + 100).min(5000)]);
+    }
+}
+
+#[test]
+fn empty_array_has_no_chunks() {
+    let chunker = ChunkerBuilder::by_json_array(3, 1).unwrap();
+
+    for text in ["[]", " [ \n ] ", ""] {
+        let chunks: Vec<_> = chunker
+            .on_source(Source::Text(text.to_string()))
+            .unwrap()
+            .collect();
+        assert!(chunks.is_empty(), "{text:?}");
+    }
+}
+
+#[test]
+fn overlap_must_be_less_than_chunk_size() {
+    assert!(matches!(
+        ChunkerBuilder::by_json_array(3, 3),
+        Err(ChunkingError::InvalidArguments {
+            chunk_size: 3,
+            overlap: 3
+        })
+    ));
+}
@@ -0,0 +1,56 @@
+use std::fs;
+
+use kiru::{write_jsonl_sharded, ChunkerBuilder, ChunkingError, Source};
+
+#[test]
+fn chunks_are_split_across_shards() {
+    let dir = tempfile::tempdir().unwrap();
+    // 23 chunks of 10 bytes
+    let text = "0123456789".repeat(23);
+    let chunks = ChunkerBuilder::by_bytes(10, 0)
+        .unwrap()
+        .on_source_with_offsets(Source::Text(text))
+        .unwrap();
+
+    let paths = write_jsonl_sharded(chunks, dir.path(), 5).unwrap();
+
+    let names: Vec<_> = paths
+        .iter()
+        .map(|p| p.file_name().unwrap().to_str().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "shard_0000.jsonl",
+            "shard_0001.jsonl",
+            "shard_0002.jsonl",
+            "shard_0003.jsonl",
+            "shard_0004.jsonl"
+        ]
+    );
+
+    let counts: Vec<_> = paths
+        .iter()
+        .map(|p| fs::read_to_string(p).unwrap().lines().count())
+        .collect();
+    assert_eq!(counts, [5, 5, 5, 5, 3]);
+
+    let last = fs::read_to_string(&paths[4]).unwrap();
+    let chunk: serde_json::Value = serde_json::from_str(last.lines().last().unwrap()).unwrap();
+    assert_eq!(chunk["index"], 22);
+    assert_eq!(chunk["start"], 220);
+    assert_eq!(chunk["text"], "0123456789");
+}
+
+#[test]
+fn no_chunks_write_no_shards() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let paths = write_jsonl_sharded(std::iter::empty(), dir.path(), 5).unwrap();
+
+    assert!(paths.is_empty());
+    assert!(matches!(
+        write_jsonl_sharded(std::iter::empty(), dir.path(), 0),
+        Err(ChunkingError::InvalidShardSize)
+    ));
+}
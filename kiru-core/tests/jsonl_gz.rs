@@ -0,0 +1,55 @@
+#![cfg(feature = "gzip")]
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use flate2::read::GzDecoder;
+use kiru::{write_jsonl_gz, ChunkerBuilder, ChunkingError, Source};
+use serde_json::Value;
+use tempfile::TempDir;
+
+#[test]
+fn gzipped_jsonl_reads_back_as_the_chunks() {
+    let text = "Größe über alles, naïve café. ".repeat(500);
+    let chunker = ChunkerBuilder::by_bytes(64, 8).unwrap();
+    let expected: Vec<String> = chunker
+        .on_source(Source::Text(text.clone()))
+        .unwrap()
+        .collect();
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("chunks.jsonl.gz");
+
+    let chunks = chunker.on_source_with_offsets(Source::Text(text)).unwrap();
+    let written = write_jsonl_gz(chunks, &path, 9).unwrap();
+
+    let lines: Vec<Value> = BufReader::new(GzDecoder::new(File::open(&path).unwrap()))
+        .lines()
+        .map(|line| serde_json::from_str(&line.unwrap()).unwrap())
+        .collect();
+    assert_eq!(written, expected.len());
+    let texts: Vec<&str> = lines
+        .iter()
+        .map(|line| line["text"].as_str().unwrap())
+        .collect();
+    assert_eq!(texts, expected);
+    let indices: Vec<u64> = lines
+        .iter()
+        .map(|line| line["index"].as_u64().unwrap())
+        .collect();
+    assert_eq!(indices, (0..expected.len() as u64).collect::<Vec<_>>());
+    // The repeated text compresses well below its size
+    assert!(path.metadata().unwrap().len() < expected.concat().len() as u64 / 4);
+}
+
+#[test]
+fn compression_level_above_nine_is_rejected() {
+    let dir = TempDir::new().unwrap();
+    let chunks = std::iter::empty();
+
+    let result = write_jsonl_gz(chunks, dir.path().join("chunks.jsonl.gz"), 10);
+
+    assert!(matches!(
+        result,
+        Err(ChunkingError::InvalidCompressionLevel(10))
+    ));
+}
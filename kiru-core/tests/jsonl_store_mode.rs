@@ -0,0 +1,109 @@
+use std::fs;
+
+use kiru::{write_jsonl, ChunkerBuilder, Source, StoreMode};
+use serde_json::Value;
+use tempfile::TempDir;
+
+const TEXT: &str = "Größe über alles, naïve café. The quick brown fox jumps over the lazy dog.";
+
+/// The lines written for the chunks of `TEXT`, and the chunks themselves.
+fn stored(mode: StoreMode) -> (Vec<Value>, Vec<kiru::Chunk>) {
+    let chunker = ChunkerBuilder::by_bytes(16, 6).unwrap();
+    let chunks: Vec<_> = chunker
+        .on_source_with_offsets(Source::Text(TEXT.to_string()))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("chunks.jsonl");
+
+    let written = write_jsonl(chunks.clone().into_iter().map(Ok), &path, mode).unwrap();
+
+    let lines: Vec<Value> = fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(written, chunks.len());
+    (lines, chunks)
+}
+
+fn field<'a>(line: &'a Value, name: &str) -> &'a str {
+    line[name].as_str().unwrap()
+}
+
+#[test]
+fn full_mode_stores_the_overlapped_chunks() {
+    let (lines, chunks) = stored(StoreMode::Full);
+
+    for (line, chunk) in lines.iter().zip(&chunks) {
+        assert_eq!(field(line, "text"), chunk.text);
+        assert_eq!(line["start"], chunk.start);
+        assert!(line.get("overlap").is_none());
+    }
+}
+
+#[test]
+fn core_mode_texts_concatenate_to_the_input() {
+    let (lines, chunks) = stored(StoreMode::Core);
+
+    let texts: Vec<&str> = lines.iter().map(|line| field(line, "text")).collect();
+    assert_eq!(texts.concat(), TEXT);
+    for (line, chunk) in lines.iter().zip(&chunks) {
+        let start = line["start"].as_u64().unwrap() as usize;
+        assert_eq!(&TEXT[start..chunk.end], field(line, "text"));
+        assert!(line.get("overlap").is_none());
+    }
+    // The overlap is left out
+    let stored: usize = texts.iter().map(|text| text.len()).sum();
+    assert!(stored < chunks.iter().map(|chunk| chunk.text.len()).sum());
+}
+
+#[test]
+fn both_mode_rebuilds_the_overlapped_chunks() {
+    let (lines, chunks) = stored(StoreMode::Both);
+
+    let texts: Vec<&str> = lines.iter().map(|line| field(line, "text")).collect();
+    assert_eq!(texts.concat(), TEXT);
+    for (line, chunk) in lines.iter().zip(&chunks) {
+        let rebuilt = format!("{}{}", field(line, "overlap"), field(line, "text"));
+        assert_eq!(rebuilt, chunk.text);
+    }
+    assert_eq!(field(&lines[0], "overlap"), "");
+    assert!(!field(&lines[1], "overlap").is_empty());
+}
+
+#[test]
+fn bridges_are_stored_whole_and_main_chunks_tile_the_source() {
+    let text = "abcdefghijklmnopqrstuvwxyz";
+    let chunks: Vec<_> = ChunkerBuilder::by_bytes(10, 0)
+        .unwrap()
+        .with_bridge_chunks(2)
+        .on_source_with_offsets(Source::Text(text.to_string()))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("chunks.jsonl");
+
+    write_jsonl(chunks.clone().into_iter().map(Ok), &path, StoreMode::Both).unwrap();
+
+    let lines: Vec<Value> = fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let (bridges, main): (Vec<&Value>, Vec<&Value>) =
+        lines.iter().partition(|line| line["kind"] == "bridge");
+    let texts: Vec<&str> = main.iter().map(|line| field(line, "text")).collect();
+    assert_eq!(texts, ["abcdefghij", "klmnopqrst", "uvwxyz"]);
+    assert_eq!(main[1]["start"], 10);
+    assert_eq!(
+        bridges
+            .iter()
+            .map(|line| field(line, "text"))
+            .collect::<Vec<_>>(),
+        ["ijkl", "stuv"]
+    );
+    assert!(bridges.iter().all(|line| line.get("overlap").is_none()));
+}
@@ -0,0 +1,84 @@
+use kiru::{BytesChunker, ChunkerBuilder, ChunkerWithStrategy, LastChunk, Source};
+
+fn spans(chunker: &ChunkerWithStrategy<BytesChunker>, text: &str) -> Vec<(usize, usize, String)> {
+    chunker
+        .on_source_with_offsets(Source::Text(text.to_string()))
+        .unwrap()
+        .map(|chunk| {
+            let chunk = chunk.unwrap();
+            (chunk.start, chunk.end, chunk.text)
+        })
+        .collect()
+}
+
+/// Context after the chunk before the last one reaches the end of the text.
+fn with_context(policy: LastChunk) -> ChunkerWithStrategy<BytesChunker> {
+    let chunker = BytesChunker::new(4, 2).unwrap().with_context_after_units(2);
+    ChunkerWithStrategy::new(chunker).with_last_chunk(policy)
+}
+
+fn owned(chunks: &[(usize, usize, &str)]) -> Vec<(usize, usize, String)> {
+    chunks
+        .iter()
+        .map(|&(start, end, text)| (start, end, text.to_string()))
+        .collect()
+}
+
+#[test]
+fn redundant_last_chunk_is_kept_by_default() {
+    let chunks = spans(&with_context(LastChunk::Keep), "abcdefghij");
+
+    assert_eq!(
+        chunks,
+        owned(&[
+            (0, 6, "abcdef"),
+            (2, 8, "cdefgh"),
+            (4, 10, "efghij"),
+            (6, 10, "ghij"),
+        ])
+    );
+}
+
+#[test]
+fn redundant_last_chunk_is_dropped() {
+    let chunks = spans(&with_context(LastChunk::DropIfRedundant), "abcdefghij");
+
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks.last().unwrap(), &(4, 10, "efghij".to_string()));
+}
+
+#[test]
+fn redundant_last_chunk_is_merged() {
+    let chunks = spans(&with_context(LastChunk::Merge), "abcdefghij");
+
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks.last().unwrap(), &(4, 10, "efghij".to_string()));
+}
+
+#[test]
+fn mostly_repeated_last_chunk_is_only_merged() {
+    // The last chunk repeats 4 bytes and adds 2
+    let text = "abcdefghijkl";
+    let backward = |policy| {
+        ChunkerBuilder::by_bytes(10, 4)
+            .unwrap()
+            .with_last_chunk(policy)
+    };
+
+    let kept = owned(&[(0, 10, "abcdefghij"), (6, 12, "ghijkl")]);
+    assert_eq!(spans(&backward(LastChunk::Keep), text), kept);
+    assert_eq!(spans(&backward(LastChunk::DropIfRedundant), text), kept);
+    assert_eq!(
+        spans(&backward(LastChunk::Merge), text),
+        owned(&[(0, 12, "abcdefghijkl")])
+    );
+}
+
+#[test]
+fn only_the_last_chunk_is_affected() {
+    let text = "abcdefghij".repeat(5);
+    let keep = spans(&with_context(LastChunk::Keep), &text);
+    let drop = spans(&with_context(LastChunk::DropIfRedundant), &text);
+
+    assert_eq!(drop, keep[..keep.len() - 1]);
+}
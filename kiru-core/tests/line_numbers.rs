@@ -0,0 +1,67 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{ChunkerBuilder, LineRange, Source};
+
+fn log_fixture() -> String {
+    (1..=400)
+        .map(|n| format!("line {n}: request served in {}ms ✓\n", n * 7 % 300))
+        .collect()
+}
+
+/// Line range of `text[start..end]`, counted naively from the start of `text`.
+fn expected_lines(text: &str, start: usize, end: usize) -> LineRange {
+    let first = 1 + text[..start].matches('\n').count();
+    let last = first
+        + text[start..end]
+            .trim_end_matches('\n')
+            .matches('\n')
+            .count();
+    LineRange {
+        start: first,
+        end: last,
+    }
+}
+
+#[test]
+fn line_numbers_match_chunk_position_in_file() {
+    let text = log_fixture();
+    let (_dir, path) = create_temp_file(&text);
+
+    let chunks: Vec<_> = ChunkerBuilder::by_bytes(300, 80)
+        .unwrap()
+        .with_line_numbers()
+        .on_source_with_offsets(Source::File(path))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    assert!(chunks.len() > 10);
+    for chunk in &chunks {
+        assert_eq!(
+            chunk.lines,
+            Some(expected_lines(&text, chunk.start, chunk.end)),
+            "chunk {} at {}..{}",
+            chunk.index,
+            chunk.start,
+            chunk.end
+        );
+    }
+}
+
+#[test]
+fn line_numbers_for_string_source() {
+    let text = "first\nsecond\nthird\nfourth\n".to_string();
+
+    let lines: Vec<_> = ChunkerBuilder::by_characters(13, 6)
+        .unwrap()
+        .with_line_numbers()
+        .on_source_with_offsets(Source::Text(text))
+        .unwrap()
+        .map(|chunk| chunk.unwrap().lines.unwrap())
+        .collect();
+
+    // "first\nsecond\n", "econd\nthird\nf", "hird\nfourth\n"
+    let expected = [(1, 2), (2, 4), (3, 4)].map(|(start, end)| LineRange { start, end });
+    assert_eq!(lines, expected);
+}
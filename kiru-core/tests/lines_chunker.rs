@@ -0,0 +1,187 @@
+mod common;
+
+use std::io::{self, Write};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use common::helpers::{file_chunks_matching_string_chunks, texts};
+use kiru::{ChunkerBuilder, ChunkerWithStrategy, ChunkingError, LineEnding, LinesChunker, Source};
+
+fn with_line_ending(
+    chunk_size: usize,
+    overlap: usize,
+    line_ending: LineEnding,
+) -> ChunkerWithStrategy<LinesChunker> {
+    ChunkerWithStrategy::new(
+        LinesChunker::new(chunk_size, overlap)
+            .unwrap()
+            .with_line_ending(line_ending),
+    )
+}
+
+#[test]
+fn chunks_group_lines_with_their_terminators() {
+    let text = "a\nb\r\nc\n\nd\ne";
+
+    let chunks = texts(&with_line_ending(2, 0, LineEnding::Lf), text);
+
+    assert_eq!(chunks, ["a\nb\r\n", "c\n\n", "d\ne"]);
+    assert_eq!(chunks.concat(), text);
+}
+
+#[test]
+fn overlap_repeats_the_last_lines() {
+    assert_eq!(
+        texts(
+            &with_line_ending(3, 1, LineEnding::Lf),
+            "1\n2\n3\n4\n5\n6\n"
+        ),
+        ["1\n2\n3\n", "3\n4\n5\n", "5\n6\n"]
+    );
+    assert_eq!(
+        texts(&with_line_ending(3, 2, LineEnding::Lf), "1\n2\n3\n4\n5\n"),
+        ["1\n2\n3\n", "2\n3\n4\n", "3\n4\n5\n"]
+    );
+}
+
+#[test]
+fn crlf_lines_keep_lone_newlines() {
+    let text = "name,notes\r\n1,\"two\nlines\"\r\n2,x\r\n3,y";
+
+    let chunks = texts(&with_line_ending(2, 0, LineEnding::CrLf), text);
+
+    assert_eq!(chunks, ["name,notes\r\n1,\"two\nlines\"\r\n", "2,x\r\n3,y"]);
+}
+
+#[test]
+fn last_line_without_newline_is_its_own_line() {
+    assert_eq!(
+        texts(&with_line_ending(2, 0, LineEnding::Lf), "a\nb\nc"),
+        ["a\nb\n", "c"]
+    );
+    assert_eq!(
+        texts(&with_line_ending(2, 0, LineEnding::Lf), "a\nb\n"),
+        ["a\nb\n"]
+    );
+    assert_eq!(texts(&with_line_ending(3, 1, LineEnding::Lf), "a"), ["a"]);
+    assert!(texts(&with_line_ending(3, 1, LineEnding::Lf), "").is_empty());
+}
+
+#[test]
+fn file_chunks_match_string_chunks_across_blocks() {
+    // Lines of varying length, so that lines and "\r\n" pairs are cut at block ends
+    let text: String = (0..20_000)
+        .map(|n| format!("{n},{}\r\n", "é".repeat(n % 23)))
+        .collect::<String>()
+        + "no newline";
+
+    for line_ending in [LineEnding::Lf, LineEnding::CrLf] {
+        let from_file =
+            file_chunks_matching_string_chunks(&with_line_ending(100, 10, line_ending), &text);
+
+        assert_eq!(from_file.len(), 223);
+        assert_eq!(from_file.last().unwrap().text.lines().count(), 21);
+        assert!(from_file.last().unwrap().text.ends_with("no newline"));
+    }
+}
+
+#[test]
+fn collapsed_separators_leave_no_empty_lines() {
+    let text = "a\n\n\n\nb";
+    let chunker = |collapse| {
+        ChunkerWithStrategy::new(
+            LinesChunker::new(1, 0)
+                .unwrap()
+                .with_collapse_separators(collapse),
+        )
+    };
+
+    let kept: Vec<String> = chunker(false)
+        .on_source(Source::Text(text.to_string()))
+        .unwrap()
+        .collect();
+    let collapsed: Vec<_> = chunker(true)
+        .on_source_with_offsets(Source::Text(text.to_string()))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(kept, ["a\n", "\n", "\n", "\n", "b"]);
+    let texts: Vec<_> = collapsed.iter().map(|chunk| chunk.text.as_str()).collect();
+    assert_eq!(texts, ["a\n", "b"]);
+    // Offsets are positions in the collapsed text
+    assert_eq!((collapsed[1].start, collapsed[1].end), (2, 3));
+}
+
+#[test]
+fn collapsed_file_chunks_match_string_chunks() {
+    // Runs of blank lines, some of whitespace or "\r\n", cut at block ends
+    let text: String = (0..20_000)
+        .map(|n| format!("{n}\n{}", ["", "\n", "\n\n\n", " \r\n\t\n"][n % 4]))
+        .collect::<String>()
+        + "\n\n";
+    let chunker = ChunkerWithStrategy::new(
+        LinesChunker::new(100, 10)
+            .unwrap()
+            .with_collapse_separators(true),
+    );
+
+    let from_file = file_chunks_matching_string_chunks(&chunker, &text);
+
+    assert_eq!(from_file.len(), 223);
+    assert!(from_file
+        .iter()
+        .all(|chunk| chunk.text.lines().all(|line| !line.trim().is_empty())));
+}
+
+#[test]
+fn piped_lines_are_chunked_as_they_arrive() {
+    let (reader, mut writer) = io::pipe().unwrap();
+    let (chunk_read, wait_for_chunk) = mpsc::channel();
+    // Writes the lines slowly, and waits for the chunk of each pair before writing on
+    let feeder = thread::spawn(move || {
+        for pair in 0..3 {
+            for line in 0..2 {
+                writeln!(writer, "line {pair}.{line}").unwrap();
+                thread::sleep(Duration::from_millis(20));
+            }
+            wait_for_chunk
+                .recv_timeout(Duration::from_secs(5))
+                .expect("chunk yielded before more lines are written");
+        }
+    });
+
+    let mut chunks = Vec::new();
+    for chunk in ChunkerBuilder::by_lines(2, 1)
+        .unwrap()
+        .on_reader_with_offsets(reader)
+    {
+        chunks.push(chunk.unwrap().text);
+        let _ = chunk_read.send(());
+    }
+    feeder.join().unwrap();
+
+    assert_eq!(
+        chunks,
+        [
+            "line 0.0\nline 0.1\n",
+            "line 0.1\nline 1.0\n",
+            "line 1.0\nline 1.1\n",
+            "line 1.1\nline 2.0\n",
+            "line 2.0\nline 2.1\n"
+        ]
+    );
+}
+
+#[test]
+fn overlap_must_be_less_than_chunk_size() {
+    assert!(matches!(
+        ChunkerBuilder::by_lines(2, 2),
+        Err(ChunkingError::InvalidArguments {
+            chunk_size: 2,
+            overlap: 2
+        })
+    ));
+    assert!(ChunkerBuilder::by_lines(0, 0).is_err());
+}
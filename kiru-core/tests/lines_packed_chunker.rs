@@ -0,0 +1,76 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{ChunkerBuilder, Source};
+
+/// Log lines of 10 to roughly 300 bytes, with an occasional 500 byte stack dump.
+fn mixed_log() -> String {
+    (0..2_000)
+        .map(|n| {
+            let len = if n % 97 == 0 { 500 } else { 10 + n * 37 % 290 };
+            format!("{n:>5} {}\n", "é".repeat(len / 2))
+        })
+        .collect()
+}
+
+#[test]
+fn chunks_hold_whole_lines_within_budget() {
+    let text = mixed_log();
+    let (_dir, path) = create_temp_file(&text);
+    let chunker = ChunkerBuilder::by_lines_packed(400, 0).unwrap();
+
+    let chunks: Vec<_> = chunker
+        .on_source_with_offsets(Source::File(path))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    for chunk in &chunks {
+        assert!(chunk.start == 0 || text.as_bytes()[chunk.start - 1] == b'\n');
+        assert!(chunk.text.ends_with('\n'));
+        assert!(
+            chunk.text.len() <= 400 || chunk.text.lines().count() == 1,
+            "chunk {} of {} bytes spans several lines",
+            chunk.index,
+            chunk.text.len()
+        );
+    }
+    assert!(chunks.iter().any(|c| c.text.len() > 400));
+    assert_eq!(
+        chunks.iter().map(|c| c.text.as_str()).collect::<String>(),
+        text
+    );
+
+    let from_string: Vec<_> = chunker
+        .on_source_with_offsets(Source::Text(text))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(from_string, chunks);
+}
+
+#[test]
+fn overlap_carries_last_lines() {
+    let text = "aaa\nbbb\nccc\nddd\neee\n".to_string();
+
+    let chunks: Vec<_> = ChunkerBuilder::by_lines_packed(12, 1)
+        .unwrap()
+        .on_source(Source::Text(text))
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, ["aaa\nbbb\nccc\n", "ccc\nddd\neee\n"]);
+}
+
+#[test]
+fn oversized_line_is_emitted_alone() {
+    let text = "short\nthis line is far too long\nend".to_string();
+
+    let chunks: Vec<_> = ChunkerBuilder::by_lines_packed(10, 1)
+        .unwrap()
+        .on_source(Source::Text(text))
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, ["short\n", "this line is far too long\n", "end"]);
+}
@@ -0,0 +1,135 @@
+use kiru::{Chunk, ChunkKind, ChunkLinks, ChunkerBuilder, Source};
+
+fn links(chunks: &[Chunk]) -> Vec<(usize, Option<usize>, Option<usize>)> {
+    chunks
+        .iter()
+        .map(|chunk| {
+            let ChunkLinks {
+                prev_index,
+                next_index,
+            } = chunk.links.unwrap();
+            (chunk.index, prev_index, next_index)
+        })
+        .collect()
+}
+
+#[test]
+fn chunks_link_their_neighbours_and_none_at_the_ends() {
+    let chunks: Vec<Chunk> = ChunkerBuilder::by_bytes(4, 1)
+        .unwrap()
+        .with_links(true)
+        .on_source_with_offsets(Source::Text("abcdefghij".to_string()))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(
+        links(&chunks),
+        [
+            (0, None, Some(1)),
+            (1, Some(0), Some(2)),
+            (2, Some(1), None),
+        ]
+    );
+}
+
+#[test]
+fn links_follow_the_chunks_kept_by_the_stride() {
+    let chunks: Vec<Chunk> = ChunkerBuilder::by_bytes(2, 0)
+        .unwrap()
+        .with_stride(2)
+        .unwrap()
+        .with_links(true)
+        .on_source_with_offsets(Source::Text("abcdefghij".to_string()))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(
+        links(&chunks),
+        [
+            (0, None, Some(2)),
+            (2, Some(0), Some(4)),
+            (4, Some(2), None)
+        ]
+    );
+}
+
+#[test]
+fn bridges_link_the_chunks_they_straddle() {
+    let text = "Größe über alles — naïve café 🚀. ".repeat(10);
+    let chunks: Vec<Chunk> = ChunkerBuilder::by_bytes(100, 20)
+        .unwrap()
+        .with_bridge_chunks(10)
+        .with_links(true)
+        .on_source_with_offsets(Source::Text(text))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    let mains = chunks.iter().filter(|c| c.kind == ChunkKind::Main).count();
+    for (position, chunk) in chunks.iter().enumerate() {
+        let links = chunk.links.unwrap();
+        match chunk.kind {
+            ChunkKind::Main => {
+                assert_eq!(links.prev_index, chunk.index.checked_sub(1));
+                assert_eq!(
+                    links.next_index,
+                    Some(chunk.index + 1).filter(|&i| i < mains)
+                );
+            }
+            ChunkKind::Bridge => {
+                assert_eq!(links.prev_index, Some(chunks[position - 1].index));
+                assert_eq!(links.next_index, Some(chunks[position + 1].index));
+            }
+        }
+    }
+    assert!(chunks.iter().any(|c| c.kind == ChunkKind::Bridge));
+}
+
+#[test]
+fn links_start_over_with_each_source() {
+    let sources = vec![
+        Source::Text("abcdefgh".to_string()),
+        Source::Text("ijklmnop".to_string()),
+    ];
+    let chunks: Vec<Chunk> = ChunkerBuilder::by_bytes(4, 0)
+        .unwrap()
+        .with_links(true)
+        .on_sources_with_offsets(sources)
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(
+        links(&chunks),
+        [
+            (0, None, Some(1)),
+            (1, Some(0), None),
+            (0, None, Some(1)),
+            (1, Some(0), None),
+        ]
+    );
+}
+
+#[test]
+fn links_are_serialized_only_when_requested() {
+    let chunker = ChunkerBuilder::by_bytes(4, 0).unwrap();
+    let first = |linked: bool| {
+        let chunk = chunker
+            .clone()
+            .with_links(linked)
+            .on_source_with_offsets(Source::Text("abcdefgh".to_string()))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        serde_json::to_value(chunk).unwrap()
+    };
+
+    assert!(first(false).get("links").is_none());
+    assert_eq!(
+        first(true)["links"],
+        serde_json::json!({"prev_index": null, "next_index": 1})
+    );
+}
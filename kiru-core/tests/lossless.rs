@@ -0,0 +1,87 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{
+    verify_lossless, BytesChunker, CharactersChunker, Chunk, Chunker, LosslessMismatch, Source,
+};
+
+fn multibyte_text() -> String {
+    "Größe über alles — naïve café 🚀, 日本語のテキスト. ".repeat(2_000)
+}
+
+#[test]
+fn multibyte_file_is_chunked_losslessly() {
+    let (_dir, path) = create_temp_file(&multibyte_text());
+
+    for chunk_size in [7, 100, 5_000] {
+        let bytes = verify_lossless(Source::File(path.clone()), chunk_size, BytesChunker::new);
+        let chars = verify_lossless(
+            Source::File(path.clone()),
+            chunk_size,
+            CharactersChunker::new,
+        );
+
+        assert_eq!(bytes.unwrap(), None);
+        assert_eq!(chars.unwrap(), None);
+    }
+}
+
+#[test]
+fn broken_strategy_reports_first_mismatch() {
+    let text = multibyte_text();
+
+    // Context before each chunk repeats text, as an overlap would
+    let mismatch = verify_lossless(Source::Text(text.clone()), 100, |chunk_size, overlap| {
+        Ok(BytesChunker::new(chunk_size, overlap)?.with_context_before_units(4))
+    })
+    .unwrap()
+    .expect("repeated text is not lossless");
+
+    let second_start = mismatch.offset;
+    assert!(second_start > 90 && second_start <= 100);
+    assert_eq!(
+        mismatch.expected,
+        text[second_start..second_start + mismatch.expected.len()]
+    );
+    assert_ne!(mismatch.found, mismatch.expected);
+}
+
+/// Cuts like [`BytesChunker`], but only ever yields its first two chunks.
+#[derive(Clone)]
+struct FirstTwo(BytesChunker);
+
+impl Chunker for FirstTwo {
+    fn chunk_string_with_offsets(self, input: String) -> impl Iterator<Item = Chunk> {
+        self.0.chunk_string_with_offsets(input).take(2)
+    }
+
+    fn chunk_stream_with_offsets(
+        self,
+        input: impl Iterator<Item = String>,
+    ) -> impl Iterator<Item = Chunk> {
+        self.0.chunk_stream_with_offsets(input).take(2)
+    }
+
+    fn estimate_memory(&self, source_len: usize) -> usize {
+        self.0.estimate_memory(source_len)
+    }
+}
+
+#[test]
+fn missing_end_is_a_mismatch() {
+    let text = "0123456789".repeat(3);
+
+    let mismatch = verify_lossless(Source::Text(text), 10, |chunk_size, overlap| {
+        Ok(FirstTwo(BytesChunker::new(chunk_size, overlap)?))
+    })
+    .unwrap();
+
+    assert_eq!(
+        mismatch,
+        Some(LosslessMismatch {
+            offset: 20,
+            expected: "0123456789".to_string(),
+            found: String::new(),
+        })
+    );
+}
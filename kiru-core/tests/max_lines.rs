@@ -0,0 +1,53 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{BytesChunker, Chunker, ChunkerWithStrategy, Source};
+
+#[test]
+fn short_lines_are_cut_at_max_lines() {
+    let text = "short log line\n".repeat(100);
+    let chunks: Vec<String> = BytesChunker::new(1024, 0)
+        .unwrap()
+        .with_max_lines(10)
+        .chunk_string(text.clone())
+        .collect();
+
+    assert_eq!(chunks.len(), 10);
+    for chunk in &chunks {
+        assert_eq!(chunk.lines().count(), 10);
+        assert!(chunk.len() < 1024);
+        assert!(chunk.ends_with('\n'));
+    }
+    assert_eq!(chunks.concat(), text);
+}
+
+#[test]
+fn byte_size_still_applies_to_long_lines() {
+    let text = format!("{}\n", "x".repeat(25)).repeat(20);
+    let chunks: Vec<String> = BytesChunker::new(100, 0)
+        .unwrap()
+        .with_max_lines(10)
+        .chunk_string(text.clone())
+        .collect();
+
+    // Four lines of 26 bytes already exceed 100 bytes
+    assert!(chunks.iter().all(|chunk| chunk.len() <= 100));
+    assert_eq!(chunks[0].len(), 100);
+    assert_eq!(chunks.concat(), text);
+}
+
+#[test]
+fn streams_cut_at_the_same_lines() {
+    let text = (0..500).map(|i| format!("line {i}\n")).collect::<String>();
+    let (_dir, path) = create_temp_file(&text);
+    let chunker = ChunkerWithStrategy::new(BytesChunker::new(4096, 20).unwrap().with_max_lines(7));
+
+    let expected: Vec<String> = chunker
+        .on_source(Source::Text(text.clone()))
+        .unwrap()
+        .collect();
+    let streamed: Vec<String> = chunker.on_source(Source::File(path)).unwrap().collect();
+
+    assert_eq!(streamed, expected);
+    assert!(expected.iter().all(|chunk| chunk.lines().count() <= 7));
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use common::helpers::create_temp_file;
+use kiru::{ChunkerBuilder, Source};
+
+/// Tracks the live and peak heap bytes of the whole test binary.
+struct CountingAllocator;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let live = LIVE.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+        PEAK.fetch_max(live, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Peak heap bytes allocated while running `f`, above what was live before.
+fn observed_peak(f: impl FnOnce()) -> usize {
+    let baseline = LIVE.load(Ordering::SeqCst);
+    PEAK.store(baseline, Ordering::SeqCst);
+    f();
+    PEAK.load(Ordering::SeqCst) - baseline
+}
+
+fn assert_within_factor(estimate: usize, observed: usize) {
+    assert!(
+        observed <= estimate * 2 && estimate <= observed * 2,
+        "estimate {estimate} is off from observed peak {observed}"
+    );
+}
+
+// A single test, so that no other test allocates concurrently
+#[test]
+fn estimate_memory_matches_observed_peak() {
+    let text = "Some words, then more words, and a few more words. ".repeat(40_000);
+    let (_dir, path) = create_temp_file(&text);
+
+    for chunk_size in [256, 4096, 65_536] {
+        let bytes = ChunkerBuilder::by_bytes(chunk_size, chunk_size / 8).unwrap();
+        let observed = observed_peak(|| {
+            for chunk in bytes.on_source(Source::File(path.clone())).unwrap() {
+                drop(chunk);
+            }
+        });
+        eprintln!(
+            "bytes {chunk_size}: est {} obs {observed}",
+            bytes.estimate_memory(text.len())
+        );
+        assert_within_factor(bytes.estimate_memory(text.len()), observed);
+
+        let chars = ChunkerBuilder::by_characters(chunk_size, chunk_size / 8).unwrap();
+        let observed = observed_peak(|| {
+            for chunk in chars.on_source(Source::File(path.clone())).unwrap() {
+                drop(chunk);
+            }
+        });
+        eprintln!(
+            "chars {chunk_size}: est {} obs {observed}",
+            chars.estimate_memory(text.len())
+        );
+        assert_within_factor(chars.estimate_memory(text.len()), observed);
+    }
+}
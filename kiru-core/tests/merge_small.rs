@@ -0,0 +1,58 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{ChunkerBuilder, Source};
+
+/// Byte offsets at which the lines of `text` start.
+fn line_starts(text: &str) -> Vec<usize> {
+    text.match_indices('\n').map(|(i, _)| i + 1).collect()
+}
+
+#[test]
+fn short_lines_merge_into_larger_chunks() {
+    let text = format!("a\nbb\nccc\n{}\nd\ne\n", "x".repeat(30));
+
+    let chunks: Vec<_> = ChunkerBuilder::by_boundaries(line_starts(&text), 0)
+        .unwrap()
+        .with_merge_small(10, 20)
+        .on_source_with_offsets(Source::Text(text.clone()))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    let texts: Vec<_> = chunks.iter().map(|c| c.text.as_str()).collect();
+    let long_line = format!("{}\n", "x".repeat(30));
+    assert_eq!(texts, ["a\nbb\nccc\n", long_line.as_str(), "d\ne\n"]);
+    let indices: Vec<_> = chunks.iter().map(|c| c.index).collect();
+    assert_eq!(indices, [0, 1, 2]);
+}
+
+#[test]
+fn merged_chunks_keep_their_overlap() {
+    let text: String = (0..300).map(|n| format!("line {}\n", n % 7)).collect();
+    let (_dir, path) = create_temp_file(&text);
+    let chunker = ChunkerBuilder::by_boundaries(line_starts(&text), 3)
+        .unwrap()
+        .with_merge_small(20, 64);
+
+    let chunks: Vec<_> = chunker
+        .on_source_with_offsets(Source::File(path))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    assert!(chunks.len() < 300 / 4);
+    let mut previous_end = None;
+    let mut restored = String::new();
+    for chunk in &chunks {
+        assert!(chunk.text.len() <= 64);
+        assert_eq!(chunk.text, text[chunk.start..chunk.end]);
+        if let Some(end) = previous_end {
+            // Each chunk still starts 3 bytes before the end of the previous one
+            assert_eq!(chunk.start + 3, end);
+        }
+        restored.push_str(chunk.unique_text(previous_end));
+        previous_end = Some(chunk.end);
+    }
+    assert_eq!(restored, text);
+}
@@ -0,0 +1,66 @@
+use kiru::{Chunker, ChunkerBuilder, ChunkerWithStrategy, Source};
+
+/// Total bytes of the chunks of `text`, and the estimate made before chunking it.
+fn actual_and_estimate<C: Chunker>(chunker: &ChunkerWithStrategy<C>, text: &str) -> (usize, usize) {
+    let actual = chunker
+        .on_source(Source::Text(text.to_string()))
+        .unwrap()
+        .map(|chunk| chunk.len())
+        .sum();
+    (actual, chunker.output_size_estimate(text.len()).unwrap())
+}
+
+#[test]
+fn estimate_bounds_the_actual_output() {
+    let texts = [
+        "Some words and more words. ".repeat(200),
+        "Grüße aus Köln, schön hier! 日本語のテキスト。 ".repeat(150),
+        "short".to_string(),
+    ];
+
+    for text in &texts {
+        for (chunk_size, overlap) in [(100, 0), (100, 20), (64, 60), (10, 9)] {
+            let bytes = ChunkerBuilder::by_bytes(chunk_size, overlap).unwrap();
+            let (actual, estimate) = actual_and_estimate(&bytes, text);
+            assert!(
+                estimate >= actual,
+                "bytes {chunk_size}/{overlap}: {estimate} < {actual}"
+            );
+
+            let chars = ChunkerBuilder::by_characters(chunk_size, overlap).unwrap();
+            let (actual, estimate) = actual_and_estimate(&chars, text);
+            assert!(
+                estimate >= actual,
+                "chars {chunk_size}/{overlap}: {estimate} < {actual}"
+            );
+        }
+
+        let boundaries = ChunkerBuilder::by_boundaries(vec![7, 50, 51, 300], 20).unwrap();
+        let (actual, estimate) = actual_and_estimate(&boundaries, text);
+        assert!(estimate >= actual, "boundaries: {estimate} < {actual}");
+
+        let affixed = ChunkerBuilder::by_bytes(100, 20)
+            .unwrap()
+            .with_prefix("passage: ")
+            .with_suffix("\n");
+        let (actual, estimate) = actual_and_estimate(&affixed, text);
+        assert!(estimate >= actual, "affixed: {estimate} < {actual}");
+    }
+}
+
+#[test]
+fn estimate_is_close_for_plain_byte_chunks() {
+    let text = "Some words and more words. ".repeat(400);
+    let chunker = ChunkerBuilder::by_bytes(1000, 100).unwrap();
+
+    let (actual, estimate) = actual_and_estimate(&chunker, &text);
+
+    assert!(estimate <= actual + actual / 10, "{estimate} vs {actual}");
+}
+
+#[test]
+fn unbounded_strategies_have_no_estimate() {
+    let chunker = ChunkerBuilder::by_transcript(30.0, 5.0).unwrap();
+
+    assert_eq!(chunker.output_size_estimate(1000), None);
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{CharactersChunker, Chunk, Chunker, ChunkerWithStrategy, ChunkingError, Source};
+
+fn chunks(chunker: CharactersChunker, source: Source) -> Vec<Chunk> {
+    ChunkerWithStrategy::new(chunker)
+        .on_source_with_offsets(source)
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+}
+
+#[test]
+fn overlap_never_exceeds_overlap_bytes() {
+    let text = "abc é 日本語 🚀🎉 naïve ".repeat(300);
+    let (_dir, path) = create_temp_file(&text);
+    let chunker = CharactersChunker::new(20, 0)
+        .unwrap()
+        .with_overlap_bytes(10)
+        .unwrap();
+
+    for source in [Source::Text(text.clone()), Source::File(path)] {
+        let chunks = chunks(chunker.clone(), source);
+
+        assert!(chunks.len() > 100);
+        let mut widths = Vec::new();
+        for pair in chunks.windows(2) {
+            assert_eq!(pair[0].text.chars().count(), 20);
+            let overlap = pair[0].end - pair[1].start;
+            assert!(overlap <= 10, "{overlap} bytes of overlap");
+            // The overlap is as many trailing chars as fit
+            let next_char = pair[0].text[..pair[0].text.len() - overlap].chars().last();
+            assert!(overlap + next_char.unwrap().len_utf8() > 10);
+            widths.push(pair[1].text[..overlap].chars().count());
+        }
+        // The overlap in chars varies with the width of the chars it holds
+        assert!(widths.iter().min() < widths.iter().max());
+    }
+}
+
+#[test]
+fn ascii_overlap_is_overlap_bytes_chars() {
+    let text = "0123456789".repeat(5);
+    let chunker = CharactersChunker::new(10, 0)
+        .unwrap()
+        .with_overlap_bytes(3)
+        .unwrap();
+
+    let starts: Vec<usize> = chunker
+        .chunk_string_with_offsets(text)
+        .map(|chunk| chunk.start)
+        .collect();
+
+    assert_eq!(starts, [0, 7, 14, 21, 28, 35, 42]);
+}
+
+#[test]
+fn overlap_bytes_must_be_less_than_chunk_size() {
+    assert!(matches!(
+        CharactersChunker::new(10, 0)
+            .unwrap()
+            .with_overlap_bytes(10),
+        Err(ChunkingError::InvalidArguments {
+            chunk_size: 10,
+            overlap: 10
+        })
+    ));
+}
@@ -0,0 +1,56 @@
+use kiru::{BytesChunker, CharactersChunker, Chunker, OverlapDirection};
+
+const ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
+
+#[test]
+fn each_chunk_looks_ahead_into_the_next() {
+    let chunker = BytesChunker::new(10, 4)
+        .unwrap()
+        .with_overlap_direction(OverlapDirection::Forward);
+
+    let chunks: Vec<String> = chunker.chunk_string(ALPHABET.to_string()).collect();
+
+    // Own parts of 10 bytes followed by a 4 bytes lookahead; the last chunk has none
+    assert_eq!(chunks, ["abcdefghijklmn", "klmnopqrstuvwx", "uvwxyz"]);
+    for pair in chunks.windows(2) {
+        assert!(pair[0].ends_with(&pair[1][..4]), "{pair:?}");
+    }
+    let backward: Vec<String> = BytesChunker::new(10, 4)
+        .unwrap()
+        .chunk_string(ALPHABET.to_string())
+        .collect();
+    assert_ne!(chunks, backward);
+}
+
+#[test]
+fn backward_overlap_stops_at_the_end_of_the_text() {
+    let chunks: Vec<String> = BytesChunker::new(10, 4)
+        .unwrap()
+        .chunk_string(ALPHABET.to_string())
+        .collect();
+
+    assert_eq!(
+        chunks,
+        ["abcdefghij", "ghijklmnop", "mnopqrstuv", "stuvwxyz"]
+    );
+}
+
+#[test]
+fn characters_look_ahead_in_streams() {
+    let text = "äbcdéfghïjklmnöpq";
+    let blocks = text.chars().map(String::from);
+    let chunker = CharactersChunker::new(6, 2)
+        .unwrap()
+        .with_overlap_direction(OverlapDirection::Forward);
+
+    let streamed: Vec<String> = chunker.clone().chunk_stream(blocks).collect();
+    let chunks: Vec<String> = chunker.chunk_string(text.to_string()).collect();
+
+    assert_eq!(chunks, ["äbcdéfgh", "ghïjklmn", "mnöpq"]);
+    assert_eq!(streamed, chunks);
+    let backward: Vec<String> = CharactersChunker::new(6, 2)
+        .unwrap()
+        .chunk_string(text.to_string())
+        .collect();
+    assert_ne!(backward, chunks);
+}
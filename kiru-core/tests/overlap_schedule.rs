@@ -0,0 +1,68 @@
+use kiru::{BytesChunker, CharactersChunker, Chunker, ChunkingError};
+
+fn starts(chunker: impl Chunker, text: &str) -> Vec<usize> {
+    chunker
+        .chunk_string_with_offsets(text.to_string())
+        .map(|chunk| chunk.start)
+        .collect()
+}
+
+#[test]
+fn step_changes_at_the_scheduled_chunk() {
+    let text = "abcdefghij".repeat(10);
+    // No overlap up to chunk 3, which then overlaps chunk 2 by 4 units, and so on
+    let schedule = vec![(3, 4)];
+    let bytes = BytesChunker::new(10, 0)
+        .unwrap()
+        .with_overlap_schedule(schedule.clone())
+        .unwrap();
+    let chars = CharactersChunker::new(10, 0)
+        .unwrap()
+        .with_overlap_schedule(schedule)
+        .unwrap();
+
+    for starts in [starts(bytes, &text), starts(chars, &text)] {
+        let steps: Vec<usize> = starts.windows(2).map(|w| w[1] - w[0]).collect();
+        assert_eq!(steps[..2], [10, 10]);
+        assert!(steps[2..].iter().all(|&step| step == 6), "{steps:?}");
+        assert_eq!(starts[3], 26);
+    }
+}
+
+#[test]
+fn schedule_applies_in_index_order_in_streams() {
+    let text = "0123456789".repeat(200);
+    let chunker = BytesChunker::new(10, 2)
+        .unwrap()
+        .with_overlap_schedule(vec![(5, 0), (2, 5)])
+        .unwrap();
+
+    let blocks = text
+        .as_bytes()
+        .chunks(7)
+        .map(|b| String::from_utf8(b.to_vec()).unwrap());
+    let starts: Vec<usize> = chunker
+        .chunk_stream_with_offsets(blocks)
+        .map(|chunk| chunk.start)
+        .collect();
+
+    // Overlaps 2 (chunk 1), 5 (chunks 2 to 4), then 0
+    assert_eq!(starts[..7], [0, 8, 13, 18, 23, 33, 43]);
+}
+
+#[test]
+fn scheduled_overlap_must_be_less_than_chunk_size() {
+    assert!(matches!(
+        BytesChunker::new(10, 0)
+            .unwrap()
+            .with_overlap_schedule(vec![(1, 3), (4, 10)]),
+        Err(ChunkingError::InvalidArguments {
+            chunk_size: 10,
+            overlap: 10
+        })
+    ));
+    assert!(CharactersChunker::new(10, 0)
+        .unwrap()
+        .with_overlap_schedule(vec![(2, 12)])
+        .is_err());
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use common::helpers::{file_chunks_matching_string_chunks, texts};
+use kiru::{ChunkerBuilder, ChunkerWithStrategy, ChunkingError, ParagraphsChunker, Source};
+
+#[test]
+fn chunks_keep_the_separators_between_their_paragraphs() {
+    assert_eq!(
+        texts(
+            &ChunkerBuilder::by_paragraphs(2, 0).unwrap(),
+            "one\nstill one\n\ntwo\n\n\nthree\n\nfour"
+        ),
+        ["one\nstill one\n\ntwo\n\n\n", "three\n\nfour"]
+    );
+}
+
+#[test]
+fn overlap_repeats_the_last_paragraphs() {
+    assert_eq!(
+        texts(
+            &ChunkerBuilder::by_paragraphs(3, 1).unwrap(),
+            "a\n\nb\n\nc\n\nd\n\ne"
+        ),
+        ["a\n\nb\n\nc\n\n", "c\n\nd\n\ne"]
+    );
+    assert_eq!(
+        texts(
+            &ChunkerBuilder::by_paragraphs(2, 1).unwrap(),
+            "a\n\nb\n\nc\n\nd"
+        ),
+        ["a\n\nb\n\n", "b\n\nc\n\n", "c\n\nd"]
+    );
+}
+
+#[test]
+fn leading_and_trailing_blank_lines_are_left_out() {
+    let chunks: Vec<_> = ChunkerBuilder::by_paragraphs(5, 0)
+        .unwrap()
+        .on_source_with_offsets(Source::Text("\n\n  \nfirst\n\nlast\n\n\n".to_string()))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].text, "first\n\nlast\n");
+    assert_eq!((chunks[0].start, chunks[0].end), (5, 17));
+    assert!(texts(&ChunkerBuilder::by_paragraphs(2, 0).unwrap(), "\n \n\t\n").is_empty());
+}
+
+#[test]
+fn windows_line_endings_separate_paragraphs() {
+    assert_eq!(
+        texts(
+            &ChunkerBuilder::by_paragraphs(1, 0).unwrap(),
+            "one\r\nline two\r\n\r\ntwo\r\n\r\n\r\nthree\r\n\r\n"
+        ),
+        ["one\r\nline two\r\n\r\n", "two\r\n\r\n\r\n", "three\r\n"]
+    );
+}
+
+#[test]
+fn three_or_more_blank_lines_are_one_separator() {
+    assert_eq!(
+        texts(
+            &ChunkerBuilder::by_paragraphs(2, 0).unwrap(),
+            "section one\n\n\n\n\nsection two\n \t\n\nsection three"
+        ),
+        ["section one\n\n\n\n\nsection two\n \t\n\n", "section three"]
+    );
+}
+
+#[test]
+fn collapsed_separators_are_a_single_blank_line() {
+    let chunker = ParagraphsChunker::new(1, 0)
+        .unwrap()
+        .with_collapse_separators(true);
+
+    let chunks: Vec<String> = ChunkerWithStrategy::new(chunker)
+        .on_source(Source::Text("\n\none\n\n\n\ntwo\n \n\nthree".to_string()))
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, ["one\n\n", "two\n \n", "three"]);
+}
+
+#[test]
+fn indented_paragraphs_start_with_their_indentation() {
+    assert_eq!(
+        texts(
+            &ChunkerBuilder::by_paragraphs(1, 0).unwrap(),
+            "  first\n\n    second"
+        ),
+        ["  first\n\n", "    second"]
+    );
+}
+
+#[test]
+fn file_chunks_match_string_chunks_across_blocks() {
+    // Several blocks, with separators of both line endings cut at block ends
+    let text: String = (0..8_000)
+        .map(|n| {
+            format!(
+                "pärägraph {n}\nline{}",
+                ["\n\n", "\r\n\r\n", "\n\n\n\n", "\n \n"][n % 4]
+            )
+        })
+        .collect();
+    let from_file =
+        file_chunks_matching_string_chunks(&ChunkerBuilder::by_paragraphs(50, 5).unwrap(), &text);
+
+    assert_eq!(from_file.len(), 178);
+}
+
+#[test]
+fn invalid_arguments_are_rejected() {
+    assert!(matches!(
+        ChunkerBuilder::by_paragraphs(0, 0),
+        Err(ChunkingError::InvalidArguments { .. })
+    ));
+    assert!(matches!(
+        ChunkerBuilder::by_paragraphs(3, 3),
+        Err(ChunkingError::InvalidArguments { .. })
+    ));
+}
+
+#[test]
+fn lines_and_chars_count_the_skipped_blank_lines() {
+    let text = "\n\n\nfirst\n\nsecond";
+    let chunks: Vec<_> = ChunkerBuilder::by_paragraphs(1, 0)
+        .unwrap()
+        .with_line_numbers()
+        .with_char_offsets()
+        .on_source_with_offsets(Source::Text(text.to_string()))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    let spans: Vec<_> = chunks
+        .iter()
+        .map(|chunk| {
+            let (lines, chars) = (chunk.lines.unwrap(), chunk.chars.unwrap());
+            (chunk.start, lines.start, lines.end, chars.start, chars.end)
+        })
+        .collect();
+    assert_eq!(spans, [(3, 4, 5, 3, 10), (10, 6, 6, 10, 16)]);
+}
@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use kiru::{chunk_parallel_consume, ChunkerBuilder, ChunkingError, Source};
+
+#[test]
+fn every_chunk_is_processed_exactly_once() {
+    let text = "The quick brown fox jumps over the lazy dog. ".repeat(500);
+    let chunker = ChunkerBuilder::by_bytes(64, 16).unwrap();
+    let expected: Vec<String> = chunker
+        .on_source(Source::Text(text.clone()))
+        .unwrap()
+        .collect();
+    let seen = Mutex::new(HashMap::new());
+    let threads = Mutex::new(HashMap::new());
+
+    let results = chunk_parallel_consume(Source::Text(text), &chunker, 4, 2, false, |chunk| {
+        *seen.lock().unwrap().entry(chunk.index).or_insert(0) += 1;
+        *threads
+            .lock()
+            .unwrap()
+            .entry(thread::current().id())
+            .or_insert(0) += 1;
+        // Slow workers make the chunking wait on the bounded channel
+        thread::sleep(Duration::from_micros(50));
+        chunk.text.len()
+    })
+    .unwrap();
+
+    let seen = seen.into_inner().unwrap();
+    assert_eq!(seen.len(), expected.len());
+    assert!(seen.values().all(|&count| count == 1));
+    assert_eq!(results.len(), expected.len());
+    assert_eq!(
+        results.iter().sum::<usize>(),
+        expected.iter().map(String::len).sum::<usize>()
+    );
+    assert!(threads.into_inner().unwrap().len() > 1);
+}
+
+#[test]
+fn ordered_results_follow_the_chunks() {
+    let text = "0123456789".repeat(100);
+    let chunker = ChunkerBuilder::by_bytes(10, 0).unwrap();
+
+    let results = chunk_parallel_consume(Source::Text(text), &chunker, 3, 0, true, |chunk| {
+        // Later chunks take less time, so they tend to finish first
+        thread::sleep(Duration::from_micros(100 - chunk.index as u64));
+        chunk.index
+    })
+    .unwrap();
+
+    assert_eq!(results, (0..100).collect::<Vec<_>>());
+}
+
+#[test]
+fn chunking_error_is_returned() {
+    let chunker = ChunkerBuilder::by_bytes(10, 0).unwrap();
+    let source = Source::File("does/not/exist.txt".to_string());
+
+    assert!(matches!(
+        chunk_parallel_consume(source, &chunker, 2, 4, false, |chunk| chunk.index),
+        Err(ChunkingError::Io(_))
+    ));
+}
+
+#[test]
+fn worker_count_must_be_positive() {
+    let chunker = ChunkerBuilder::by_bytes(10, 0).unwrap();
+
+    assert!(matches!(
+        chunk_parallel_consume(Source::Text("text".into()), &chunker, 0, 4, false, |c| c),
+        Err(ChunkingError::InvalidWorkerCount)
+    ));
+}
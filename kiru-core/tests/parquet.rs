@@ -0,0 +1,90 @@
+#![cfg(feature = "parquet")]
+
+use std::fs::File;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::UInt64Type;
+use kiru::{write_parquet, ChunkerBuilder, ChunkingError, Source};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+#[test]
+fn columns_match_the_chunks() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("chunks.parquet");
+    let text = "Größe über alles, the quick brown fox. ".repeat(40);
+    let chunker = ChunkerBuilder::by_characters(100, 20).unwrap();
+    let expected: Vec<_> = chunker
+        .on_source_with_offsets(Source::Text(text.clone()))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    let chunks = chunker.on_source_with_offsets(Source::Text(text)).unwrap();
+    let written = write_parquet(chunks, Some("doc.txt"), &path, 4).unwrap();
+    assert_eq!(written, expected.len());
+
+    let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap()).unwrap();
+    let row_groups = builder.metadata().row_groups();
+    assert_eq!(row_groups.len(), expected.len().div_ceil(4));
+    assert!(row_groups.iter().all(|group| group.num_rows() <= 4));
+    let batches = builder
+        .build()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let texts: Vec<&str> = batches
+        .iter()
+        .flat_map(|batch| batch.column(0).as_string::<i64>().iter().flatten())
+        .collect();
+    let expected_texts: Vec<&str> = expected.iter().map(|c| c.text.as_str()).collect();
+    assert_eq!(texts, expected_texts);
+    let starts: Vec<u64> = batches
+        .iter()
+        .flat_map(|batch| {
+            batch
+                .column(1)
+                .as_primitive::<UInt64Type>()
+                .values()
+                .to_vec()
+        })
+        .collect();
+    let expected_starts: Vec<u64> = expected.iter().map(|c| c.start as u64).collect();
+    assert_eq!(starts, expected_starts);
+    assert!(batches.iter().all(|batch| batch
+        .column(4)
+        .as_string::<i32>()
+        .iter()
+        .all(|source| source == Some("doc.txt"))));
+}
+
+#[test]
+fn source_is_null_when_not_given() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("chunks.parquet");
+    let chunks = ChunkerBuilder::by_bytes(10, 0)
+        .unwrap()
+        .on_source_with_offsets(Source::Text("0123456789".repeat(3)))
+        .unwrap();
+
+    assert_eq!(write_parquet(chunks, None, &path, 100).unwrap(), 3);
+
+    let batches = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+        .unwrap()
+        .build()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].column(4).null_count(), 3);
+}
+
+#[test]
+fn row_group_size_must_be_positive() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("chunks.parquet");
+    assert!(matches!(
+        write_parquet(std::iter::empty(), None, &path, 0),
+        Err(ChunkingError::InvalidBatchSize)
+    ));
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{ChunkerBuilder, ChunkerWithStrategy, PredicateChunker, Source};
+
+fn before_uppercase(_prev: char, next: char, _pos: usize) -> bool {
+    next.is_uppercase()
+}
+
+#[test]
+fn cuts_on_uppercase_letters() {
+    let text = "OneTwoThreeFourFiveSixSeven".to_string();
+
+    let chunks: Vec<_> = ChunkerBuilder::by_predicate(before_uppercase, 8, 0)
+        .unwrap()
+        .on_source(Source::Text(text))
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, vec!["OneTwo", "Three", "FourFive", "SixSeven"]);
+}
+
+#[test]
+fn overlap_repeats_whole_segments() {
+    let text = "AaBbCcDdEeFf".to_string();
+
+    let chunks: Vec<_> = ChunkerBuilder::by_predicate(before_uppercase, 6, 4)
+        .unwrap()
+        .on_source(Source::Text(text))
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, vec!["AaBbCc", "BbCcDd", "CcDdEe", "DdEeFf"]);
+}
+
+fn after_pipe(prev: char, _next: char, _pos: usize) -> bool {
+    prev == '|'
+}
+
+fn pipe_chunks(text: &str, chunk_size: usize, overlap_segments: usize) -> Vec<String> {
+    let chunker = PredicateChunker::new(after_pipe, chunk_size, 0)
+        .unwrap()
+        .with_overlap_segments(overlap_segments);
+
+    ChunkerWithStrategy::new(chunker)
+        .on_source(Source::Text(text.to_string()))
+        .unwrap()
+        .collect()
+}
+
+#[test]
+fn overlap_segments_repeat_trailing_segments_with_their_delimiter() {
+    // Three one-byte segments and their delimiters per chunk
+    assert_eq!(pipe_chunks("a|b|c|d|e", 6, 1), ["a|b|c|", "c|d|e"]);
+    assert_eq!(
+        pipe_chunks("a|b|c|d|e|f|g", 6, 2),
+        ["a|b|c|", "b|c|d|", "c|d|e|", "d|e|f|", "e|f|g"]
+    );
+    // Whatever their length in bytes
+    assert_eq!(pipe_chunks("aaa|b|cc|d|e", 9, 1), ["aaa|b|cc|", "cc|d|e"]);
+}
+
+#[test]
+fn overlap_segments_leave_room_for_the_next_segment() {
+    // "bb|" and "cccc|" do not fit together, so no segment is repeated before "cccc|"
+    assert_eq!(pipe_chunks("a|bb|cccc|d", 6, 1), ["a|bb|", "cccc|d"]);
+}
+
+#[test]
+fn stream_matches_string() {
+    let text = "Lorem ipsum Dolor sit amet, Consectetur adipiscing élit. Sed do Eiusmod \
+        tempor Incididunt ut labore et Dolore magna aliqua. "
+        .repeat(300);
+    let (_dir, path) = create_temp_file(&text);
+    let chunker = ChunkerBuilder::by_predicate(before_uppercase, 100, 40).unwrap();
+
+    let from_string: Vec<_> = chunker
+        .on_source_with_offsets(Source::Text(text.clone()))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    let from_file: Vec<_> = chunker
+        .on_source_with_offsets(Source::File(path))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(from_string, from_file);
+    for chunk in &from_file {
+        assert!(chunk.text.len() <= 100);
+        assert_eq!(chunk.text, text[chunk.start..chunk.end]);
+    }
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use common::helpers::{file_chunks_matching_string_chunks, texts};
+use kiru::{
+    ChunkerBuilder, ChunkerWithStrategy, ChunkingError, RecursiveChunker,
+    DEFAULT_RECURSIVE_SEPARATORS,
+};
+
+fn with_separators(
+    text: &str,
+    separators: &[&str],
+    chunk_size: usize,
+    overlap: usize,
+) -> Vec<String> {
+    let chunker = ChunkerWithStrategy::new(
+        RecursiveChunker::new(chunk_size, overlap)
+            .unwrap()
+            .with_separators(separators.iter().map(|s| s.to_string()).collect()),
+    );
+    texts(&chunker, text)
+}
+
+#[test]
+fn small_paragraphs_are_packed_together() {
+    assert_eq!(
+        texts(
+            &ChunkerBuilder::by_recursive(12, 0).unwrap(),
+            "one\n\ntwo\n\nthree\n\nfour"
+        ),
+        ["one\n\ntwo\n\n", "three\n\nfour"]
+    );
+}
+
+#[test]
+fn long_paragraphs_fall_back_to_lines_then_words() {
+    let text = "short\n\na first line\na second line\n\nwords words words words words";
+
+    assert_eq!(
+        texts(&ChunkerBuilder::by_recursive(14, 0).unwrap(), text),
+        [
+            "short\n\n",
+            "a first line\n",
+            "a second line\n",
+            "\n",
+            "words words ",
+            "words words ",
+            "words"
+        ]
+    );
+}
+
+#[test]
+fn chunks_stay_under_the_size_whenever_the_separators_allow() {
+    let text = "Lorem ipsum dolor sit amet, consectetur adipiscing elit.\n\
+        Sed do eiusmod tempor incididunt ut labore et dolore magna aliqua.\n\n\
+        Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris.\n\
+        Averyveryverylongwordwithoutanyspaceatall and more words after it.\n\n"
+        .repeat(20);
+
+    for chunk_size in [8, 30, 64, 200] {
+        let chunks = texts(
+            &ChunkerBuilder::by_recursive(chunk_size, chunk_size / 4).unwrap(),
+            &text,
+        );
+
+        assert!(chunks.iter().all(|chunk| chunk.len() <= chunk_size));
+        assert!(chunks.iter().all(|chunk| !chunk.is_empty()));
+    }
+    // Without the empty separator, a long word is a chunk of its own
+    let chunks = with_separators(&text, &["\n\n", "\n", " "], 30, 0);
+    assert!(chunks
+        .iter()
+        .any(|chunk| chunk == "Averyveryverylongwordwithoutanyspaceatall "));
+    assert_eq!(chunks.concat(), text);
+}
+
+#[test]
+fn overlap_repeats_the_last_splits_that_fit() {
+    assert_eq!(
+        texts(
+            &ChunkerBuilder::by_recursive(9, 3).unwrap(),
+            "aa bb cc dd ee"
+        ),
+        ["aa bb cc ", "cc dd ee"]
+    );
+    // The overlap holds whole splits only: "bb " fits in 4 bytes, "aaaa bb " does not
+    assert_eq!(
+        texts(
+            &ChunkerBuilder::by_recursive(10, 4).unwrap(),
+            "aaaa bb cccc dd"
+        ),
+        ["aaaa bb ", "bb cccc dd"]
+    );
+}
+
+#[test]
+fn characters_are_never_split() {
+    let chunks = texts(&ChunkerBuilder::by_recursive(3, 0).unwrap(), "ééééé");
+
+    assert_eq!(chunks, ["é", "é", "é", "é", "é"]);
+}
+
+#[test]
+fn default_separators_go_from_paragraphs_to_characters() {
+    assert_eq!(DEFAULT_RECURSIVE_SEPARATORS, ["\n\n", "\n", " ", ""]);
+}
+
+#[test]
+fn file_chunks_match_string_chunks() {
+    let text = "A paragraph of a few words.\nAnd a second line.\n\n".repeat(5_000);
+    let from_file =
+        file_chunks_matching_string_chunks(&ChunkerBuilder::by_recursive(200, 40).unwrap(), &text);
+
+    for chunk in &from_file {
+        assert_eq!(&text[chunk.start..chunk.end], chunk.text);
+    }
+}
+
+#[test]
+fn invalid_arguments_are_rejected() {
+    assert!(matches!(
+        ChunkerBuilder::by_recursive(0, 0),
+        Err(ChunkingError::InvalidArguments { .. })
+    ));
+    assert!(matches!(
+        ChunkerBuilder::by_recursive(100, 100),
+        Err(ChunkingError::InvalidArguments { .. })
+    ));
+}
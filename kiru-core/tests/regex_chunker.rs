@@ -0,0 +1,160 @@
+mod common;
+
+use common::helpers::{file_chunks_matching_string_chunks, texts};
+use kiru::{Chunker, ChunkerBuilder, ChunkerWithStrategy, ChunkingError, RegexChunker, Source};
+
+fn dropped(text: &str, pattern: &str, chunk_size: usize, overlap: usize) -> Vec<(String, usize)> {
+    ChunkerWithStrategy::new(
+        RegexChunker::new(pattern, chunk_size, overlap)
+            .unwrap()
+            .with_keep_separator(false),
+    )
+    .on_source_with_offsets(Source::Text(text.to_string()))
+    .unwrap()
+    .map(|chunk| {
+        let chunk = chunk.unwrap();
+        (chunk.text, chunk.start)
+    })
+    .collect()
+}
+
+#[test]
+fn pieces_keep_the_separators_that_follow_them() {
+    assert_eq!(
+        texts(
+            &ChunkerBuilder::by_regex(r"\n---\n", 2, 0).unwrap(),
+            "one\n---\ntwo\n---\nthree\n---\nfour"
+        ),
+        ["one\n---\ntwo\n---\n", "three\n---\nfour"]
+    );
+}
+
+#[test]
+fn dropped_separators_are_left_out_around_chunks() {
+    assert_eq!(
+        dropped("one;two;;three;four;", ";+", 1, 0),
+        [
+            ("one".to_string(), 0),
+            ("two".to_string(), 4),
+            ("three".to_string(), 9),
+            ("four".to_string(), 15)
+        ]
+    );
+    // Separators between the pieces of a chunk stay
+    assert_eq!(
+        dropped(";a;b;c", ";", 2, 0),
+        [("a;b".to_string(), 1), ("c".to_string(), 5)]
+    );
+}
+
+#[test]
+fn overlap_repeats_the_last_pieces() {
+    assert_eq!(
+        texts(
+            &ChunkerBuilder::by_regex(", ", 3, 1).unwrap(),
+            "a, b, c, d, e"
+        ),
+        ["a, b, c, ", "c, d, e"]
+    );
+    assert_eq!(
+        dropped("a, b, c, d", ", ", 2, 1)
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect::<Vec<_>>(),
+        ["a, b", "b, c", "c, d"]
+    );
+}
+
+#[test]
+fn leading_and_consecutive_separators_make_no_piece() {
+    assert_eq!(
+        texts(&ChunkerBuilder::by_regex("-", 1, 0).unwrap(), "--a----b--"),
+        ["--a----", "b--"]
+    );
+    assert!(texts(&ChunkerBuilder::by_regex("-", 1, 0).unwrap(), "----").is_empty());
+}
+
+#[test]
+fn empty_matches_split_pieces() {
+    // Splits before each heading, keeping it in its piece
+    assert_eq!(
+        texts(
+            &ChunkerBuilder::by_regex(r"(?m)^", 1, 0).unwrap(),
+            "intro\n# one\ntext\n# two\n"
+        ),
+        ["intro\n", "# one\n", "text\n", "# two\n"]
+    );
+}
+
+#[test]
+fn measure_counts_pieces() {
+    let chunker = RegexChunker::new(r"\s*;\s*", 4, 0).unwrap();
+
+    assert_eq!(chunker.measure("a ; b;c ;"), 3);
+    assert_eq!(chunker.measure(" ; "), 0);
+    assert_eq!(chunker.measure(""), 0);
+}
+
+#[test]
+fn file_chunks_match_string_chunks_across_blocks() {
+    // Several blocks, with separators cut at block ends
+    let text: String = (0..8_000)
+        .map(|n| {
+            format!(
+                "récord {n}{}",
+                ["\n=====\n", "\n==\n", "\n========\n"][n % 3]
+            )
+        })
+        .collect();
+
+    for keep_separator in [true, false] {
+        let chunker = ChunkerWithStrategy::new(
+            RegexChunker::new(r"\n=+\n", 50, 5)
+                .unwrap()
+                .with_keep_separator(keep_separator),
+        );
+        let from_file = file_chunks_matching_string_chunks(&chunker, &text);
+
+        assert_eq!(from_file.len(), 178);
+    }
+}
+
+#[test]
+fn invalid_arguments_are_rejected() {
+    assert!(matches!(
+        ChunkerBuilder::by_regex("(unclosed", 3, 0),
+        Err(ChunkingError::InvalidPattern(_))
+    ));
+    assert!(matches!(
+        ChunkerBuilder::by_regex(",", 0, 0),
+        Err(ChunkingError::InvalidArguments { .. })
+    ));
+    assert!(matches!(
+        ChunkerBuilder::by_regex(",", 3, 3),
+        Err(ChunkingError::InvalidArguments { .. })
+    ));
+}
+
+#[test]
+fn lines_and_chars_count_dropped_separators() {
+    let chunks: Vec<_> = ChunkerWithStrategy::new(
+        RegexChunker::new("\n", 1, 0)
+            .unwrap()
+            .with_keep_separator(false),
+    )
+    .with_line_numbers()
+    .with_char_offsets()
+    .on_source_with_offsets(Source::Text("a\nb\nc".to_string()))
+    .unwrap()
+    .collect::<Result<_, _>>()
+    .unwrap();
+
+    let spans: Vec<_> = chunks
+        .iter()
+        .map(|chunk| {
+            let (lines, chars) = (chunk.lines.unwrap(), chunk.chars.unwrap());
+            (lines.start, lines.end, chars.start, chars.end)
+        })
+        .collect();
+    assert_eq!(spans, [(1, 1, 0, 1), (2, 2, 2, 3), (3, 3, 4, 5)]);
+}
@@ -0,0 +1,252 @@
+mod common;
+
+use std::fs;
+
+use common::helpers::create_temp_file;
+use kiru::{
+    write_jsonl, ChunkKind, ChunkerWithStrategy, LinesChunker, RegexChunker, Source, StoreMode,
+};
+use serde_json::Value;
+use tempfile::TempDir;
+
+const HEADER: &str = "id,name,score\n";
+
+fn csv(rows: usize) -> String {
+    let rows: String = (0..rows)
+        .map(|n| format!("{n},näme {n},{}\n", n * 7))
+        .collect();
+    format!("{HEADER}{rows}")
+}
+
+fn lines(chunk_size: usize, overlap: usize) -> ChunkerWithStrategy<LinesChunker> {
+    ChunkerWithStrategy::new(
+        LinesChunker::new(chunk_size, overlap)
+            .unwrap()
+            .with_repeat_header(true),
+    )
+}
+
+#[test]
+fn every_chunk_starts_with_the_header_once() {
+    let chunks: Vec<_> = lines(2, 0)
+        .on_source(Source::Text(csv(5)))
+        .unwrap()
+        .collect();
+
+    assert_eq!(
+        chunks,
+        [
+            format!("{HEADER}0,näme 0,0\n1,näme 1,7\n"),
+            format!("{HEADER}2,näme 2,14\n3,näme 3,21\n"),
+            format!("{HEADER}4,näme 4,28\n"),
+        ]
+    );
+    for chunk in &chunks {
+        assert_eq!(chunk.matches(HEADER).count(), 1);
+    }
+}
+
+#[test]
+fn overlap_repeats_rows_not_the_header() {
+    let chunks: Vec<_> = lines(3, 1)
+        .on_source(Source::Text(csv(5)))
+        .unwrap()
+        .collect();
+
+    assert_eq!(
+        chunks,
+        [
+            format!("{HEADER}0,näme 0,0\n1,näme 1,7\n2,näme 2,14\n"),
+            format!("{HEADER}2,näme 2,14\n3,näme 3,21\n4,näme 4,28\n"),
+        ]
+    );
+}
+
+#[test]
+fn offsets_and_line_numbers_span_the_rows_only() {
+    let text = csv(4);
+    let chunks: Vec<_> = lines(2, 0)
+        .with_line_numbers()
+        .on_source_with_offsets(Source::Text(text.clone()))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    let first = chunks[0].lines.unwrap();
+    let second = chunks[1].lines.unwrap();
+    assert_eq!((first.start, first.end), (1, 3));
+    assert_eq!((second.start, second.end), (4, 5));
+    assert_eq!(chunks[0].start, 0);
+    assert_eq!(
+        &text[chunks[1].start..chunks[1].end],
+        chunks[1].text.strip_prefix(HEADER).unwrap()
+    );
+}
+
+#[test]
+fn a_source_of_just_the_header_is_one_chunk() {
+    let chunks: Vec<_> = lines(2, 0)
+        .on_source(Source::Text(HEADER.to_string()))
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, [HEADER]);
+}
+
+#[test]
+fn bridges_neither_take_nor_get_the_header() {
+    let text = "id,name\n1,a\n2,b\n3,c\n4,d\n5,e\n6,f\n";
+    let chunks: Vec<_> =
+        ChunkerWithStrategy::new(LinesChunker::new(2, 0).unwrap().with_repeat_header(true))
+            .with_bridge_chunks(4)
+            .on_source_with_offsets(Source::Text(text.to_string()))
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+
+    let main: Vec<&str> = chunks
+        .iter()
+        .filter(|chunk| chunk.kind == ChunkKind::Main)
+        .map(|chunk| chunk.text.as_str())
+        .collect();
+    assert_eq!(
+        main,
+        [
+            "id,name\n1,a\n2,b\n",
+            "id,name\n3,c\n4,d\n",
+            "id,name\n5,e\n6,f\n"
+        ]
+    );
+    for bridge in chunks
+        .iter()
+        .filter(|chunk| chunk.kind == ChunkKind::Bridge)
+    {
+        assert_eq!(&text[bridge.start..bridge.end], bridge.text);
+    }
+}
+
+#[test]
+fn unique_text_leaves_out_the_header() {
+    // A non-ASCII header, with overlapping chunks
+    let text = "héé\n1\n2\n3\n4\n";
+    let chunks: Vec<_> =
+        ChunkerWithStrategy::new(LinesChunker::new(2, 1).unwrap().with_repeat_header(true))
+            .on_source_with_offsets(Source::Text(text.to_string()))
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+
+    let mut previous_end = None;
+    let mut restored = String::new();
+    for chunk in &chunks {
+        restored.push_str(chunk.unique_text(previous_end));
+        previous_end = Some(chunk.end);
+    }
+    assert_eq!(chunks[1].text, "héé\n2\n3\n");
+    assert_eq!(chunks[1].header_len, "héé\n".len());
+    assert_eq!(restored, text);
+
+    // Core records keep the header and drop the overlap after it
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("chunks.jsonl");
+    write_jsonl(chunks.clone().into_iter().map(Ok), &path, StoreMode::Core).unwrap();
+    let lines: Vec<Value> = fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines[1]["text"], "héé\n3\n");
+    assert_eq!(lines[1]["start"], chunks[0].end);
+}
+
+#[test]
+fn unique_text_leaves_out_the_affixes_and_the_header() {
+    let text = "héé\n1\n2\n3\n4\n";
+    let chunks: Vec<_> = lines(2, 1)
+        .with_prefix("P:")
+        .with_preamble("[doc]\n", false)
+        .with_suffix("!!")
+        .on_source_with_offsets(Source::Text(text.to_string()))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(chunks[1].text, "P:[doc]\nhéé\n2\n3\n!!");
+    assert_eq!(chunks[1].content(), "héé\n2\n3\n");
+    let mut previous_end = None;
+    let mut restored = String::new();
+    for chunk in &chunks {
+        restored.push_str(chunk.unique_text(previous_end));
+        previous_end = Some(chunk.end);
+    }
+    assert_eq!(restored, text);
+
+    // Core records keep the affixes and the header, and drop the overlap between them
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("chunks.jsonl");
+    write_jsonl(chunks.clone().into_iter().map(Ok), &path, StoreMode::Core).unwrap();
+    let lines: Vec<Value> = fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines[1]["text"], "P:[doc]\nhéé\n3\n!!");
+    assert_eq!(lines[1]["start"], chunks[0].end);
+}
+
+#[test]
+fn regex_pieces_repeat_the_first_piece() {
+    let text = "title: Notes\n---\nfirst\n---\nsecond\n---\nthird";
+    for (keep_separator, expected) in [
+        (
+            true,
+            [
+                "title: Notes\n---\nfirst\n---\nsecond\n---\n",
+                "title: Notes\n---\nthird",
+            ],
+        ),
+        (
+            false,
+            [
+                "title: Notes\n---\nfirst\n---\nsecond",
+                "title: Notes\n---\nthird",
+            ],
+        ),
+    ] {
+        let chunks: Vec<_> = ChunkerWithStrategy::new(
+            RegexChunker::new(r"\n---\n", 2, 0)
+                .unwrap()
+                .with_keep_separator(keep_separator)
+                .with_repeat_header(true),
+        )
+        .on_source(Source::Text(text.to_string()))
+        .unwrap()
+        .collect();
+
+        assert_eq!(chunks, expected);
+    }
+}
+
+#[test]
+fn file_chunks_match_string_chunks_across_blocks() {
+    let text = csv(20_000);
+    let (_dir, path) = create_temp_file(&text);
+    let chunker = lines(100, 10);
+
+    let from_file: Vec<_> = chunker
+        .on_source_with_offsets(Source::File(path))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let from_string: Vec<_> = chunker
+        .on_source_with_offsets(Source::Text(text))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(from_file, from_string);
+    assert_eq!(from_file.len(), 223);
+    assert!(from_file
+        .iter()
+        .all(|chunk| chunk.text.starts_with(HEADER) && chunk.text.matches(HEADER).count() == 1));
+}
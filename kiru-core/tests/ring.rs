@@ -0,0 +1,106 @@
+use std::thread;
+use std::time::Duration;
+
+use kiru::{ChunkerBuilder, ChunkingError, RingChunker, RingOverflow, Source};
+
+#[test]
+fn blocking_ring_yields_every_chunk_in_order() {
+    let text = "The quick brown fox jumps over the lazy dog. ".repeat(200);
+    let chunker = ChunkerBuilder::by_bytes(64, 16).unwrap();
+    let expected: Vec<String> = chunker
+        .on_source(Source::Text(text.clone()))
+        .unwrap()
+        .collect();
+
+    let mut ring = RingChunker::new(Source::Text(text), &chunker, 2, RingOverflow::Block).unwrap();
+    let mut chunks = Vec::new();
+    for chunk in ring.by_ref() {
+        // A slow consumer makes the chunking wait on the full buffer
+        thread::sleep(Duration::from_micros(50));
+        chunks.push(chunk.text);
+    }
+
+    assert_eq!(chunks, expected);
+    assert_eq!(ring.finish().unwrap(), 0);
+}
+
+#[test]
+fn slow_consumer_misses_the_oldest_chunks() {
+    let text = "The quick brown fox jumps over the lazy dog. ".repeat(2000);
+    let chunker = ChunkerBuilder::by_bytes(64, 16).unwrap();
+    let total = chunker
+        .on_source(Source::Text(text.clone()))
+        .unwrap()
+        .count();
+
+    let mut ring =
+        RingChunker::new(Source::Text(text), &chunker, 4, RingOverflow::DropOldest).unwrap();
+    ring.recv().unwrap();
+    // Let the chunking run to the end while nothing is consumed
+    thread::sleep(Duration::from_millis(200));
+    let rest: Vec<usize> = ring.by_ref().map(|chunk| chunk.index).collect();
+
+    let dropped = ring.finish().unwrap();
+    assert!(dropped > 0);
+    assert_eq!(1 + rest.len() + dropped, total);
+    // Only the most recent chunks are left, still in order
+    assert!(rest.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(rest.last(), Some(&(total - 1)));
+    assert!(rest.len() <= 4);
+}
+
+#[test]
+fn fast_consumer_drops_nothing() {
+    let text = "The quick brown fox jumps over the lazy dog. ".repeat(50);
+    let chunker = ChunkerBuilder::by_bytes(64, 16).unwrap();
+    let total = chunker
+        .on_source(Source::Text(text.clone()))
+        .unwrap()
+        .count();
+
+    let mut ring = RingChunker::new(
+        Source::Text(text),
+        &chunker,
+        total,
+        RingOverflow::DropOldest,
+    )
+    .unwrap();
+    let indices: Vec<usize> = ring.by_ref().map(|chunk| chunk.index).collect();
+
+    assert_eq!(indices, (0..total).collect::<Vec<_>>());
+    assert_eq!(ring.finish().unwrap(), 0);
+}
+
+#[test]
+fn finishing_early_stops_a_blocked_chunking() {
+    let text = "The quick brown fox jumps over the lazy dog. ".repeat(2000);
+    let chunker = ChunkerBuilder::by_bytes(64, 16).unwrap();
+
+    let ring = RingChunker::new(Source::Text(text), &chunker, 1, RingOverflow::Block).unwrap();
+    assert_eq!(ring.recv().unwrap().index, 0);
+    assert_eq!(ring.finish().unwrap(), 0);
+}
+
+#[test]
+fn ring_rejects_zero_capacity_and_unreadable_sources() {
+    let chunker = ChunkerBuilder::by_bytes(64, 16).unwrap();
+
+    assert!(matches!(
+        RingChunker::new(
+            Source::Text("text".into()),
+            &chunker,
+            0,
+            RingOverflow::Block
+        ),
+        Err(ChunkingError::InvalidRingCapacity)
+    ));
+    assert!(matches!(
+        RingChunker::new(
+            Source::File("/nonexistent/ring.txt".into()),
+            &chunker,
+            4,
+            RingOverflow::Block
+        ),
+        Err(ChunkingError::Io(_))
+    ));
+}
@@ -0,0 +1,47 @@
+use kiru::{ChunkerBuilder, Source};
+
+#[test]
+fn round_robin_interleaves_sources() {
+    let sources = vec![
+        Source::Text("a0a0a1a1a2a2".to_string()),
+        Source::Text("b0b0b1b1".to_string()),
+        Source::Text("c0c0".to_string()),
+    ];
+
+    let chunks: Vec<_> = ChunkerBuilder::by_bytes(4, 0)
+        .unwrap()
+        .on_sources_round_robin(sources)
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, vec!["a0a0", "b0b0", "c0c0", "a1a1", "b1b1", "a2a2"]);
+}
+
+#[test]
+fn round_robin_keeps_per_source_offsets() {
+    let sources = vec![
+        Source::Text("0123456789".to_string()),
+        Source::Text("abcdefghij".to_string()),
+    ];
+
+    let chunks: Vec<_> = ChunkerBuilder::by_characters(5, 1)
+        .unwrap()
+        .on_sources_round_robin_with_offsets(sources)
+        .unwrap()
+        .map(|chunk| {
+            let chunk = chunk.unwrap();
+            (chunk.text, chunk.index, chunk.start)
+        })
+        .collect();
+
+    let expected = [
+        ("01234", 0, 0),
+        ("abcde", 0, 0),
+        ("45678", 1, 4),
+        ("efghi", 1, 4),
+        ("89", 2, 8),
+        ("ij", 2, 8),
+    ]
+    .map(|(text, index, start)| (text.to_string(), index, start));
+    assert_eq!(chunks, expected);
+}
@@ -0,0 +1,59 @@
+use kiru::{content_hash, Chunk, ChunkerBuilder, Source};
+
+fn chunks_of(text: &str) -> Vec<Chunk> {
+    ChunkerBuilder::by_bytes(100, 20)
+        .unwrap()
+        .with_running_hash(true)
+        .on_source_with_offsets(Source::Text(text.to_string()))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+}
+
+#[test]
+fn final_hash_is_the_hash_of_all_chunks() {
+    let text = "Größe über alles — naïve café 🚀 and plain ASCII too. ".repeat(40);
+    let chunks = chunks_of(&text);
+
+    assert!(chunks.len() > 10);
+    for (i, chunk) in chunks.iter().enumerate() {
+        let seen: String = chunks[..=i].iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(chunk.running_hash, Some(content_hash(&seen)));
+    }
+}
+
+#[test]
+fn hash_runs_across_sources_and_covers_affixes() {
+    let chunker = ChunkerBuilder::by_bytes(10, 0)
+        .unwrap()
+        .with_prefix("> ")
+        .with_running_hash(true);
+
+    let chunks: Vec<Chunk> = chunker
+        .on_sources_with_offsets(vec![
+            Source::Text("a".repeat(25)),
+            Source::Text("b".repeat(15)),
+        ])
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    let all: String = chunks.iter().map(|c| c.text.as_str()).collect();
+    assert!(all.starts_with("> aaaaaaaaaa"));
+    assert_eq!(
+        chunks.last().unwrap().running_hash,
+        Some(content_hash(&all))
+    );
+}
+
+#[test]
+fn runs_diverge_at_the_first_different_chunk() {
+    let a = chunks_of(&format!("{}{}", "a".repeat(300), "b".repeat(100)));
+    let b = chunks_of(&format!("{}{}", "a".repeat(300), "c".repeat(100)));
+
+    let hashes =
+        |chunks: &[Chunk]| -> Vec<u64> { chunks.iter().map(|c| c.running_hash.unwrap()).collect() };
+    let (a, b) = (hashes(&a), hashes(&b));
+    assert_eq!(a[..3], b[..3]);
+    assert_ne!(a[3], b[3]);
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{ChunkerBuilder, SemanticUnit, Source};
+
+/// Break once the chunk holds three sentences.
+fn three_sentences(current: &str, _next: &str) -> bool {
+    current.matches(". ").count() == 3
+}
+
+#[test]
+fn breaks_after_every_three_sentences() {
+    let text = "One. Two. Three. Four. Five. Six. Seven. ".to_string();
+
+    let chunks: Vec<_> = ChunkerBuilder::by_semantic(three_sentences, SemanticUnit::Sentence, 0)
+        .on_source(Source::Text(text))
+        .unwrap()
+        .collect();
+
+    assert_eq!(
+        chunks,
+        vec!["One. Two. Three. ", "Four. Five. Six. ", "Seven. "]
+    );
+}
+
+#[test]
+fn overlap_repeats_trailing_units() {
+    let text = "abcdefgh".to_string();
+    let four_chars = |current: &str, _next: &str| current.len() == 4;
+
+    let chunks: Vec<_> = ChunkerBuilder::by_semantic(four_chars, SemanticUnit::Character, 1)
+        .on_source(Source::Text(text))
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, vec!["abcd", "defg", "gh"]);
+}
+
+#[test]
+fn stream_matches_string() {
+    let text = "Lorem ipsum dolor sit amet. Consectetur adipiscing élit! Sed do eiusmod? \
+        Tempor incididunt ut labore. "
+        .repeat(2000);
+    let (_dir, path) = create_temp_file(&text);
+    let long_enough = |current: &str, _next: &str| current.len() >= 200;
+    let chunker = ChunkerBuilder::by_semantic(long_enough, SemanticUnit::Sentence, 2);
+
+    let from_string: Vec<_> = chunker
+        .on_source_with_offsets(Source::Text(text.clone()))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    let from_file: Vec<_> = chunker
+        .on_source_with_offsets(Source::File(path))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(from_string, from_file);
+    assert_eq!(from_file.last().unwrap().end, text.len());
+    for chunk in &from_file {
+        assert_eq!(chunk.text, text[chunk.start..chunk.end]);
+    }
+}
@@ -0,0 +1,178 @@
+mod common;
+
+use common::helpers::{create_temp_file, file_chunks_matching_string_chunks, texts};
+use kiru::{
+    Chunker, ChunkerBuilder, ChunkerWithStrategy, ChunkingError, SentenceTerminators,
+    SentencesChunker, Source,
+};
+
+fn capped(
+    chunk_size: usize,
+    overlap: usize,
+    max_bytes: usize,
+) -> ChunkerWithStrategy<SentencesChunker> {
+    ChunkerWithStrategy::new(
+        SentencesChunker::new(chunk_size, overlap)
+            .unwrap()
+            .with_max_bytes(max_bytes),
+    )
+}
+
+fn one_per_sentence(terminators: SentenceTerminators) -> ChunkerWithStrategy<SentencesChunker> {
+    ChunkerWithStrategy::new(
+        SentencesChunker::new(1, 0)
+            .unwrap()
+            .with_terminators(terminators),
+    )
+}
+
+#[test]
+fn chunks_group_sentences_with_their_whitespace() {
+    let text = "One. Two!  \"Three?\" Four 3.14 e.g. five";
+
+    let chunks = texts(&ChunkerBuilder::by_sentences(2, 0).unwrap(), text);
+
+    assert_eq!(
+        chunks,
+        ["One. Two!  ", "\"Three?\" Four 3.14 e.g. ", "five"]
+    );
+    assert_eq!(chunks.concat(), text);
+}
+
+#[test]
+fn overlap_repeats_the_last_sentences() {
+    assert_eq!(
+        texts(
+            &ChunkerBuilder::by_sentences(3, 1).unwrap(),
+            "A. B. C. D. E. F."
+        ),
+        ["A. B. C. ", "C. D. E. ", "E. F."]
+    );
+}
+
+#[test]
+fn measure_counts_sentences() {
+    let chunker = SentencesChunker::new(4, 0).unwrap();
+
+    assert_eq!(chunker.measure("One. Two? Three"), 3);
+    assert_eq!(chunker.measure("No end"), 1);
+    assert_eq!(chunker.measure(""), 0);
+}
+
+#[test]
+fn file_chunks_match_string_chunks_across_blocks() {
+    let text = "Lorem ipsum dolor sit amet. Consectetur adipiscing élit! Sed do eiusmod? \
+        Tempor incididunt ut labore. "
+        .repeat(2000);
+
+    let from_file =
+        file_chunks_matching_string_chunks(&ChunkerBuilder::by_sentences(10, 2).unwrap(), &text);
+
+    assert_eq!(from_file.len(), 1000);
+    assert_eq!(from_file.last().unwrap().end, text.len());
+}
+
+#[test]
+fn cjk_sentences_end_without_whitespace() {
+    let text = "今日は晴れです。明日は雨でしょう！本当ですか？ はい。".to_string();
+    let cjk = SentenceTerminators::default().with_cjk(true);
+
+    let chunks: Vec<_> = one_per_sentence(cjk)
+        .on_source(Source::Text(text.clone()))
+        .unwrap()
+        .collect();
+    let whole: Vec<_> = one_per_sentence(SentenceTerminators::default())
+        .on_source(Source::Text(text.clone()))
+        .unwrap()
+        .collect();
+
+    assert_eq!(
+        chunks,
+        vec![
+            "今日は晴れです。",
+            "明日は雨でしょう！",
+            "本当ですか？ ",
+            "はい。"
+        ]
+    );
+    assert_eq!(whole, vec![text]);
+}
+
+#[test]
+fn custom_terminators_end_sentences() {
+    let text = "First clause; second clause… third. ".to_string();
+
+    let chunks: Vec<_> = one_per_sentence(SentenceTerminators::new([';', '…']))
+        .on_source(Source::Text(text))
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, vec!["First clause; ", "second clause… ", "third. "]);
+}
+
+#[test]
+fn max_bytes_hard_splits_a_huge_sentence() {
+    // One megabyte of minified code without any sentence end, then a short sentence
+    let sentence = "var a=1;".repeat(128 * 1024);
+    let text = format!("{sentence}. The end. ");
+    let (_dir, path) = create_temp_file(&text);
+
+    for source in [Source::Text(text.clone()), Source::File(path)] {
+        let chunker = capped(10, 0, 8192);
+        let chunks: Vec<_> = chunker
+            .on_source_with_offsets(source)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+
+        assert!(chunks.iter().all(|chunk| chunk.text.len() <= 8192));
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|chunk| chunk.text.as_str())
+                .collect::<String>(),
+            text
+        );
+        // The sentence falls back to full chunks of the cap, and its end joins what follows
+        assert_eq!(chunks.len(), 129);
+        assert_eq!(chunker.stats().hard_splits, 128);
+    }
+}
+
+#[test]
+fn max_bytes_ends_a_group_of_sentences_that_fit() {
+    let chunker = capped(3, 0, 10);
+
+    let chunks: Vec<_> = chunker
+        .on_source(Source::Text("One. Two. Three. Four. ".to_string()))
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, ["One. Two. ", "Three. ", "Four. "]);
+    assert_eq!(chunker.stats().hard_splits, 0);
+}
+
+#[test]
+fn max_bytes_never_splits_a_char() {
+    let chunker = capped(2, 0, 5);
+
+    let chunks: Vec<_> = chunker
+        .on_source(Source::Text("ééééé".to_string()))
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, ["éé", "éé", "é"]);
+    assert_eq!(chunker.stats().hard_splits, 2);
+}
+
+#[test]
+fn overlap_must_be_less_than_chunk_size() {
+    assert!(matches!(
+        ChunkerBuilder::by_sentences(2, 2),
+        Err(ChunkingError::InvalidArguments {
+            chunk_size: 2,
+            overlap: 2
+        })
+    ));
+    assert!(ChunkerBuilder::by_sentences(0, 0).is_err());
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::helpers::create_temp_file;
+use kiru::{chunk_shared, ChunkerBuilder, ChunkerWithStrategy, RegexChunker, SharedChunk, Source};
+
+/// Bytes held by the distinct buffers behind `chunks`.
+fn buffered_bytes(chunks: &[SharedChunk]) -> usize {
+    let mut buffers: Vec<&Arc<str>> = Vec::new();
+    for chunk in chunks {
+        if !buffers.iter().any(|buf| Arc::ptr_eq(buf, &chunk.buf)) {
+            buffers.push(&chunk.buf);
+        }
+    }
+    buffers.iter().map(|buf| buf.len()).sum()
+}
+
+#[test]
+fn shared_chunks_match_string_chunks() {
+    let text = "Größe über alles — naïve café 🚀 and plain ASCII too. ".repeat(400);
+    let (_dir, path) = create_temp_file(&text);
+    let chunker = ChunkerBuilder::by_characters(100, 20).unwrap();
+    let expected: Vec<String> = chunker
+        .on_source(Source::Text(text.clone()))
+        .unwrap()
+        .collect();
+
+    for source in [Source::Text(text.clone()), Source::File(path)] {
+        let chunks = chunk_shared(source, &chunker).unwrap();
+        let texts: Vec<&str> = chunks.iter().map(SharedChunk::text).collect();
+
+        assert!(chunks.len() > 10);
+        assert_eq!(texts, expected);
+    }
+}
+
+#[test]
+fn high_overlap_is_held_once() {
+    let text = "A sentence of plain text, repeated to fill the source. ".repeat(2_000);
+    let chunker = ChunkerBuilder::by_bytes(1_000, 900).unwrap();
+
+    let strings: Vec<String> = chunker
+        .on_source(Source::Text(text.clone()))
+        .unwrap()
+        .collect();
+    let shared = chunk_shared(Source::Text(text.clone()), &chunker).unwrap();
+    let string_bytes: usize = strings.iter().map(String::len).sum();
+
+    assert_eq!(shared.len(), strings.len());
+    // One buffer holding the source, against ten copies of it
+    assert_eq!(buffered_bytes(&shared), text.len());
+    assert!(string_bytes > 9 * text.len());
+}
+
+#[test]
+fn chunks_that_are_not_slices_get_their_own_buffer() {
+    let chunker = ChunkerBuilder::by_bytes(8, 2).unwrap().with_prefix("doc: ");
+
+    let chunks = chunk_shared(Source::Text("some text to share".to_string()), &chunker).unwrap();
+
+    assert!(chunks.len() > 1);
+    for chunk in &chunks {
+        assert!(chunk.text().starts_with("doc: "));
+        assert_eq!((chunk.start, chunk.end), (0, chunk.buf.len()));
+    }
+    assert_eq!(
+        buffered_bytes(&chunks),
+        chunks.iter().map(|c| c.text().len()).sum::<usize>()
+    );
+}
+
+#[test]
+fn gaps_between_chunks_start_a_new_buffer() {
+    // Without their separators, the chunks of the regex strategy leave gaps
+    let chunker = ChunkerWithStrategy::new(
+        RegexChunker::new(";+", 2, 0)
+            .unwrap()
+            .with_keep_separator(false),
+    );
+
+    let chunks = chunk_shared(Source::Text("a;b;;c;d;;e".to_string()), &chunker).unwrap();
+    let texts: Vec<&str> = chunks.iter().map(SharedChunk::text).collect();
+
+    assert_eq!(texts, ["a;b", "c;d", "e"]);
+    assert_eq!(buffered_bytes(&chunks), 7);
+    assert!(!Arc::ptr_eq(&chunks[0].buf, &chunks[1].buf));
+}
+
+#[test]
+fn overlapping_chunks_share_their_buffer() {
+    let chunker = ChunkerBuilder::by_regex(";", 2, 1).unwrap();
+
+    let chunks = chunk_shared(Source::Text("a;b;c;d".to_string()), &chunker).unwrap();
+    let texts: Vec<&str> = chunks.iter().map(SharedChunk::text).collect();
+
+    assert_eq!(texts, ["a;b;", "b;c;", "c;d"]);
+    assert!(chunks.iter().all(|c| Arc::ptr_eq(&c.buf, &chunks[0].buf)));
+    assert_eq!(&*chunks[0].buf, "a;b;c;d");
+    assert_eq!((chunks[1].start, chunks[1].end), (2, 6));
+}
@@ -0,0 +1,59 @@
+use kiru::{ChunkStats, ChunkerBuilder, ChunkingError, Source};
+
+#[test]
+fn out_of_band_chunks_are_dropped_and_counted() {
+    // 25 full chunks of 40 bytes, then a 10 byte tail
+    let text = "abcdefghij".repeat(101);
+
+    let chunker = ChunkerBuilder::by_bytes(40, 0)
+        .unwrap()
+        .with_size_band(0.5, 1.0)
+        .unwrap();
+    let chunks: Vec<_> = chunker.on_source(Source::Text(text)).unwrap().collect();
+
+    assert_eq!(chunks.len(), 25);
+    assert!(chunks.iter().all(|c| c.len() == 40));
+    assert_eq!(
+        chunker.stats(),
+        ChunkStats {
+            chunks: 25,
+            dropped_out_of_band: 1,
+            dropped_near_duplicates: 0,
+            boundary_adjustments: 0,
+            word_snap_adjustments: 0,
+            word_snap_bytes: 0,
+            hard_splits: 0,
+            truncated_sources: 0,
+        }
+    );
+}
+
+#[test]
+fn characters_band_is_measured_in_characters() {
+    // 3 chunks of 10 characters (20 bytes each), then a 4 character tail
+    let text = "é".repeat(34);
+
+    let chunker = ChunkerBuilder::by_characters(10, 0)
+        .unwrap()
+        .with_size_band(0.5, 1.0)
+        .unwrap();
+    let chunks: Vec<_> = chunker.on_source(Source::Text(text)).unwrap().collect();
+
+    assert_eq!(chunks, vec!["é".repeat(10); 3]);
+    assert_eq!(chunker.stats().dropped_out_of_band, 1);
+}
+
+#[test]
+fn invalid_size_band_is_rejected() {
+    let bytes = ChunkerBuilder::by_bytes(40, 0).unwrap();
+    assert!(matches!(
+        bytes.with_size_band(1.5, 0.5),
+        Err(ChunkingError::InvalidSizeBand { .. })
+    ));
+
+    let boundaries = ChunkerBuilder::by_boundaries(vec![10], 0).unwrap();
+    assert!(matches!(
+        boundaries.with_size_band(0.5, 1.0),
+        Err(ChunkingError::NoChunkSize)
+    ));
+}
@@ -0,0 +1,63 @@
+use std::collections::VecDeque;
+
+use kiru::{BytesChunker, Chunker, ChunkerBuilder, ChunkingError, SourceReader};
+
+/// Serves rows as a database cursor would, then fails if `error` is set.
+struct Rows {
+    rows: VecDeque<String>,
+    error: Option<&'static str>,
+}
+
+impl SourceReader for Rows {
+    fn next_block(&mut self) -> Result<Option<String>, ChunkingError> {
+        match self.rows.pop_front() {
+            Some(row) => Ok(Some(row + "\n")),
+            None => match self.error.take() {
+                Some(message) => Err(ChunkingError::Http(message.to_string())),
+                None => Ok(None),
+            },
+        }
+    }
+}
+
+fn rows(error: Option<&'static str>) -> Rows {
+    Rows {
+        rows: (0..200).map(|i| format!("row {i}: naïve café")).collect(),
+        error,
+    }
+}
+
+#[test]
+fn custom_reader_is_chunked_like_its_text() {
+    let text: String = rows(None)
+        .rows
+        .iter()
+        .map(|row| format!("{row}\n"))
+        .collect();
+
+    let chunks: Vec<_> = ChunkerBuilder::by_bytes(100, 20)
+        .unwrap()
+        .chunk_blocks(rows(None))
+        .map(|chunk| chunk.unwrap().text)
+        .collect();
+    let expected: Vec<_> = BytesChunker::new(100, 20)
+        .unwrap()
+        .chunk_string(text)
+        .collect();
+
+    assert!(chunks.len() > 10);
+    assert_eq!(chunks, expected);
+}
+
+#[test]
+fn reader_error_surfaces_after_chunks_read_so_far() {
+    let results: Vec<_> = ChunkerBuilder::by_bytes(100, 20)
+        .unwrap()
+        .chunk_blocks(rows(Some("cursor closed")))
+        .collect();
+
+    let (error, chunks) = results.split_last().unwrap();
+    assert!(chunks.len() > 10);
+    assert!(chunks.iter().all(Result::is_ok));
+    assert!(matches!(error, Err(ChunkingError::Http(message)) if message == "cursor closed"));
+}
@@ -0,0 +1,135 @@
+use std::fs;
+
+use kiru::{ChunkerBuilder, HigherOrderSource, Source, SourceGenerator, SourceStamp};
+use tempfile::TempDir;
+
+#[test]
+fn chunks_carry_the_mtime_and_size_of_their_file() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("doc.txt");
+    fs::write(&path, "a document to index incrementally. ".repeat(10)).unwrap();
+    let metadata = fs::metadata(&path).unwrap();
+
+    let chunks: Vec<_> = ChunkerBuilder::by_bytes(64, 8)
+        .unwrap()
+        .with_source_stamps(true)
+        .on_source_with_offsets(Source::File(path.to_string_lossy().to_string()))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert!(chunks.len() > 1);
+    for chunk in &chunks {
+        let stamp = chunk.source_stamp.unwrap();
+        assert_eq!(stamp.mtime, metadata.modified().unwrap());
+        assert_eq!(stamp.size, metadata.len());
+    }
+}
+
+#[test]
+fn glob_chunks_carry_the_stamp_of_their_own_file() {
+    let dir = TempDir::new().unwrap();
+    let short = dir.path().join("a.txt");
+    let long = dir.path().join("b.txt");
+    fs::write(&short, "short file. ".repeat(4)).unwrap();
+    fs::write(&long, "a longer file. ".repeat(20)).unwrap();
+    let pattern = dir.path().join("*.txt").to_string_lossy().to_string();
+    let sources =
+        HigherOrderSource::into_flattened_sources(vec![HigherOrderSource::SourceGenerator(
+            SourceGenerator::Glob(pattern),
+        )])
+        .unwrap();
+
+    let chunks: Vec<_> = ChunkerBuilder::by_bytes(32, 0)
+        .unwrap()
+        .with_source_stamps(true)
+        .on_sources_with_offsets(sources)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    let sizes: Vec<_> = chunks
+        .iter()
+        .map(|c| c.source_stamp.unwrap().size)
+        .collect();
+    let short_chunks = sizes.iter().filter(|&&s| s == 48).count();
+    assert_eq!(short_chunks, 2);
+    assert_eq!(sizes.len() - short_chunks, 10);
+    assert_eq!(
+        chunks[0].source_stamp,
+        Some(SourceStamp::of(&short).unwrap())
+    );
+    assert_eq!(
+        chunks.last().unwrap().source_stamp,
+        Some(SourceStamp::of(&long).unwrap())
+    );
+}
+
+#[test]
+fn parallel_stream_chunks_carry_the_stamp_of_their_own_file() {
+    let dir = TempDir::new().unwrap();
+    let short = dir.path().join("a.txt");
+    let long = dir.path().join("b.txt");
+    fs::write(&short, "short file. ".repeat(4)).unwrap();
+    fs::write(&long, "a longer file. ".repeat(20)).unwrap();
+    let sources = [&short, &long]
+        .map(|path| Source::File(path.to_string_lossy().to_string()))
+        .to_vec();
+
+    let chunks: Vec<_> = ChunkerBuilder::by_bytes(32, 0)
+        .unwrap()
+        .with_source_stamps(true)
+        .on_sources_par_stream_with_offsets(sources, 4)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    let (short_stamp, long_stamp) = (
+        SourceStamp::of(&short).unwrap(),
+        SourceStamp::of(&long).unwrap(),
+    );
+    let stamps: Vec<_> = chunks.iter().map(|c| c.source_stamp.unwrap()).collect();
+    assert_eq!(stamps.iter().filter(|&&s| s == short_stamp).count(), 2);
+    assert_eq!(stamps.iter().filter(|&&s| s == long_stamp).count(), 10);
+    assert_eq!(stamps.len(), 12);
+}
+
+#[test]
+fn stamps_are_only_set_when_requested_and_for_files() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("doc.txt");
+    fs::write(&path, "some text").unwrap();
+    let chunker = ChunkerBuilder::by_bytes(4, 0).unwrap();
+
+    let unstamped = chunker
+        .on_source_with_offsets(Source::File(path.to_string_lossy().to_string()))
+        .unwrap()
+        .map(Result::unwrap);
+    let text = chunker
+        .clone()
+        .with_source_stamps(true)
+        .on_source_with_offsets(Source::Text("some text".to_string()))
+        .unwrap()
+        .map(Result::unwrap);
+
+    assert!(unstamped.chain(text).all(|c| c.source_stamp.is_none()));
+}
+
+#[test]
+fn merged_chunks_keep_the_stamp() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("doc.txt");
+    fs::write(&path, "tiny pieces of text").unwrap();
+    let stamp = SourceStamp::of(&path).unwrap();
+
+    let chunks: Vec<_> = ChunkerBuilder::by_bytes(4, 0)
+        .unwrap()
+        .with_merge_small(8, 16)
+        .with_source_stamps(true)
+        .on_source_with_offsets(Source::File(path.to_string_lossy().to_string()))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert!(chunks.iter().all(|c| c.source_stamp == Some(stamp)));
+}
@@ -0,0 +1,47 @@
+use std::fs;
+
+use kiru::{source_stats, ChunkingError, SourceStats, BLOCK_SIZE};
+use tempfile::TempDir;
+
+fn stats_of(bytes: &[u8]) -> SourceStats {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("source.txt");
+    fs::write(&path, bytes).unwrap();
+    source_stats(&path).unwrap()
+}
+
+#[test]
+fn multibyte_file_counts_match_manual_counts() {
+    // Spans several blocks, so characters are cut at block ends
+    let text = "Größe über alles\nnaïve café — 東京 🦀\n".repeat(BLOCK_SIZE / 10) + "no newline";
+
+    let stats = stats_of(text.as_bytes());
+
+    assert_eq!(
+        stats,
+        SourceStats {
+            bytes: text.len(),
+            chars: text.chars().count(),
+            lines: text.lines().count(),
+        }
+    );
+    assert!(stats.chars < stats.bytes);
+}
+
+#[test]
+fn lines_are_counted_like_str_lines() {
+    for text in ["", "\n", "one", "one\n", "one\ntwo", "one\n\ntwo\n"] {
+        let stats = stats_of(text.as_bytes());
+
+        assert_eq!(stats.lines, text.lines().count(), "{text:?}");
+        assert_eq!(stats.chars, text.len());
+    }
+}
+
+#[test]
+fn missing_file_is_an_io_error() {
+    assert!(matches!(
+        source_stats("/nonexistent/source.txt"),
+        Err(ChunkingError::Io(_))
+    ));
+}
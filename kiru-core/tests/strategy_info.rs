@@ -0,0 +1,43 @@
+use kiru::{strategy_info, OverlapSemantics, STRATEGIES};
+
+#[test]
+fn registry_covers_every_strategy() {
+    // One entry per `ChunkerBuilder::by_*` constructor
+    let names: Vec<&str> = STRATEGIES.iter().map(|info| info.name).collect();
+    assert_eq!(
+        names,
+        [
+            "bytes",
+            "approx_tokens",
+            "characters",
+            "graphemes",
+            "boundaries",
+            "json_array",
+            "lines",
+            "lines_packed",
+            "paragraphs",
+            "predicate",
+            "recursive",
+            "regex",
+            "semantic",
+            "sentences",
+            "tokens",
+            "transcript",
+            "words"
+        ]
+    );
+
+    for name in names {
+        assert_eq!(strategy_info(name).unwrap().name, name);
+    }
+    assert!(strategy_info("sentence").is_none());
+}
+
+#[test]
+fn bytes_overlap_is_at_least_the_configured_one() {
+    let info = strategy_info("bytes").unwrap();
+
+    assert_eq!(info.overlap_semantics, OverlapSemantics::AtLeast);
+    assert_eq!(info.overlap_semantics.as_str(), "at_least");
+    assert!(info.splits_graphemes);
+}
@@ -0,0 +1,65 @@
+use std::io::{self, Read};
+
+use kiru::{BytesChunker, Chunker, ChunkerBuilder, ChunkingError, Utf8BlockReader};
+
+/// Serves `data` in small reads, then fails as a dropped network mount would.
+struct FailingReader {
+    data: Vec<u8>,
+    position: usize,
+}
+
+impl Read for FailingReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position >= self.data.len() {
+            return Err(io::Error::new(io::ErrorKind::BrokenPipe, "mount dropped"));
+        }
+        let n = buf.len().min(100).min(self.data.len() - self.position);
+        buf[..n].copy_from_slice(&self.data[self.position..self.position + n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+#[test]
+fn read_error_surfaces_after_chunks_read_so_far() {
+    let readable = "Some words, then ünïcödé 🎉 and more words. ".repeat(100);
+    let reader = FailingReader {
+        data: readable.clone().into_bytes(),
+        position: 0,
+    };
+
+    let results: Vec<_> = ChunkerBuilder::by_bytes(256, 32)
+        .unwrap()
+        .on_reader_with_offsets(reader)
+        .collect();
+    let expected: Vec<_> = BytesChunker::new(256, 32)
+        .unwrap()
+        .chunk_string(readable)
+        .collect();
+
+    let (error, chunks) = results.split_last().unwrap();
+    let chunks: Vec<_> = chunks
+        .iter()
+        .map(|c| c.as_ref().unwrap().text.clone())
+        .collect();
+    assert_eq!(chunks, expected);
+    assert!(matches!(error, Err(ChunkingError::Io(e)) if e.kind() == io::ErrorKind::BrokenPipe));
+}
+
+#[test]
+fn read_error_is_recorded_by_block_reader() {
+    let reader = Utf8BlockReader::from_reader(
+        FailingReader {
+            data: b"abc".to_vec(),
+            position: 0,
+        },
+        8,
+    );
+    let read_error = reader.read_error();
+
+    let blocks: Vec<_> = reader.collect();
+
+    assert_eq!(blocks, vec!["abc".to_string()]);
+    assert!(read_error.take().is_some());
+    assert!(read_error.take().is_none());
+}
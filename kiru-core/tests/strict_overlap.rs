@@ -0,0 +1,76 @@
+use std::fs;
+
+use kiru::{CharactersChunker, Chunker, ChunkerWithStrategy, OverlapDirection, Source};
+use tempfile::TempDir;
+
+type Spans = Vec<(usize, usize)>;
+
+fn spans(chunks: impl Iterator<Item = kiru::Chunk>) -> Spans {
+    chunks.map(|chunk| (chunk.start, chunk.end)).collect()
+}
+
+/// Chunks of a file, read block by block through a buffer that is compacted many times,
+/// and of the same text in memory.
+fn file_and_memory_spans(chunker: CharactersChunker, text: &str) -> (Spans, Spans) {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("text.txt");
+    fs::write(&path, text).unwrap();
+
+    let file = ChunkerWithStrategy::new(chunker.clone())
+        .on_source_with_offsets(Source::File(path.to_string_lossy().into_owned()))
+        .unwrap()
+        .map(Result::unwrap);
+    let memory = chunker.chunk_string_with_offsets(text.to_string());
+    (spans(file), spans(memory))
+}
+
+#[test]
+fn file_overlap_matches_memory_across_compactions() {
+    // Multi-byte chars make char and byte positions differ after every compaction
+    let text = "Größe über alles, naïve café — 東京 🦀. ".repeat(3000);
+
+    for (chunk_size, overlap) in [(100, 30), (500, 499), (3000, 1500)] {
+        let chunker = CharactersChunker::new(chunk_size, overlap)
+            .unwrap()
+            .with_strict_overlap(true);
+        let (file, memory) = file_and_memory_spans(chunker, &text);
+
+        assert_eq!(file, memory);
+        // Far more chunks than fit in the buffer of five chunks
+        assert!(file.len() > 20);
+        for pair in file.windows(2) {
+            let overlap_text = &text[pair[1].0..pair[0].1];
+            assert_eq!(overlap_text.chars().count(), overlap);
+        }
+    }
+}
+
+#[test]
+fn strict_overlap_holds_with_a_schedule_and_context() {
+    let text = "東京🦀é abc ".repeat(5000);
+    let chunker = CharactersChunker::new(200, 50)
+        .unwrap()
+        .with_overlap_schedule(vec![(3, 10), (40, 150)])
+        .unwrap()
+        .with_context_before_units(120)
+        .with_context_after_units(80)
+        .with_overlap_direction(OverlapDirection::Forward)
+        .with_strict_overlap(true);
+
+    let (file, memory) = file_and_memory_spans(chunker, &text);
+
+    assert_eq!(file, memory);
+}
+
+#[test]
+fn strict_overlap_is_part_of_the_config() {
+    let chunker = ChunkerWithStrategy::new(
+        CharactersChunker::new(100, 10)
+            .unwrap()
+            .with_strict_overlap(true),
+    );
+
+    let json = chunker.config().unwrap().to_json();
+
+    assert!(json.contains("\"strict_overlap\":true"));
+}
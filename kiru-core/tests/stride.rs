@@ -0,0 +1,83 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use kiru::{ChunkerBuilder, ChunkingError, Source};
+
+/// Counts the bytes allocated by the current thread.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATED: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.with(|allocated| allocated.set(allocated.get() + layout.size()));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocated() -> usize {
+    ALLOCATED.with(Cell::get)
+}
+
+#[test]
+fn every_nth_chunk_is_kept() {
+    let text = "0123456789".repeat(100);
+    let chunker = ChunkerBuilder::by_characters(10, 2)
+        .unwrap()
+        .with_stride(25)
+        .unwrap();
+
+    let chunks: Vec<_> = chunker
+        .on_source_with_offsets(Source::Text(text))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    let indices: Vec<usize> = chunks.iter().map(|chunk| chunk.index).collect();
+    assert_eq!(indices, [0, 25, 50, 75, 100]);
+    assert_eq!(chunks[1].start, 25 * 8);
+}
+
+#[test]
+fn skipped_chunks_are_not_allocated() {
+    let text = "The quick brown fox jumps over the lazy dog. ".repeat(20_000);
+    let chunker = ChunkerBuilder::by_bytes(1000, 100).unwrap();
+    let count = |chunker: &kiru::ChunkerWithStrategy<kiru::BytesChunker>| {
+        let before = allocated();
+        let kept = chunker
+            .on_source(Source::Text(text.clone()))
+            .unwrap()
+            .count();
+        (kept, allocated() - before)
+    };
+
+    let (all, all_bytes) = count(&chunker);
+    let (sampled, sampled_bytes) = count(&chunker.clone().with_stride(100).unwrap());
+
+    assert_eq!(sampled, all.div_ceil(100));
+    // Without the text of the chunks in between, only the source copy and the buffers remain
+    let chunk_bytes = all * 1000;
+    assert!(
+        sampled_bytes + chunk_bytes * 8 / 10 < all_bytes,
+        "sampled {sampled_bytes}, all {all_bytes}"
+    );
+}
+
+#[test]
+fn stride_must_be_positive() {
+    let chunker = ChunkerBuilder::by_bytes(10, 0).unwrap();
+
+    assert!(matches!(
+        chunker.with_stride(0),
+        Err(ChunkingError::InvalidStride)
+    ));
+}
@@ -0,0 +1,48 @@
+use kiru::{suggest_chunk_size, ChunkerBuilder, ChunkingError, Source};
+
+fn count_chunks(text: &str, chunk_size: usize, overlap: usize) -> usize {
+    ChunkerBuilder::by_bytes(chunk_size, overlap)
+        .unwrap()
+        .on_source(Source::Text(text.to_string()))
+        .unwrap()
+        .count()
+}
+
+#[test]
+fn suggested_size_yields_about_the_target_count() {
+    let text = "The quick brown fox jumps over the lazy dog. ".repeat(300);
+
+    for target in [1, 2, 7, 10, 33, 100, 250] {
+        for overlap in [0, 5, 40] {
+            let chunk_size = suggest_chunk_size(text.len(), target, overlap).unwrap();
+            let count = count_chunks(&text, chunk_size, overlap);
+            assert!(
+                count.abs_diff(target) <= 1,
+                "{count} chunks of {chunk_size} bytes for a target of {target}, overlap {overlap}"
+            );
+        }
+    }
+}
+
+#[test]
+fn multibyte_text_stays_close_to_the_target() {
+    let text = "Größe über alles — naïve café 🚀, 日本語. ".repeat(200);
+
+    let chunk_size = suggest_chunk_size(text.len(), 20, 16).unwrap();
+
+    assert!(count_chunks(&text, chunk_size, 16).abs_diff(20) <= 1);
+}
+
+#[test]
+fn size_stays_above_the_overlap() {
+    assert_eq!(suggest_chunk_size(10, 4, 50).unwrap(), 51);
+    assert_eq!(suggest_chunk_size(0, 3, 0).unwrap(), 1);
+}
+
+#[test]
+fn zero_target_is_rejected() {
+    assert!(matches!(
+        suggest_chunk_size(1000, 0, 10),
+        Err(ChunkingError::InvalidTargetChunks)
+    ));
+}
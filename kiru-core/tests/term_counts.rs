@@ -0,0 +1,31 @@
+#![cfg(feature = "term-counts")]
+
+use std::collections::HashMap;
+
+use kiru::{term_counts, ChunkerBuilder, Source};
+
+#[test]
+fn counts_lowercased_words_of_a_chunk() {
+    let text = "Ünïcode wörds: Wörds, WÖRDS and 42 numbers-42.\nNew line".to_string();
+
+    let chunk = ChunkerBuilder::by_characters(40, 0)
+        .unwrap()
+        .on_source_with_offsets(Source::Text(text))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(chunk.text, "Ünïcode wörds: Wörds, WÖRDS and 42 numbe");
+    let expected: HashMap<String, usize> = [
+        ("ünïcode", 1),
+        ("wörds", 3),
+        ("and", 1),
+        ("42", 1),
+        ("numbe", 1),
+    ]
+    .into_iter()
+    .map(|(word, count)| (word.to_string(), count))
+    .collect();
+    assert_eq!(term_counts(&chunk.text), expected);
+}
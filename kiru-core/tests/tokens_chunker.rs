@@ -0,0 +1,248 @@
+#![cfg(feature = "tokenizers")]
+
+mod common;
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use common::helpers::{byte_level_tokenizer, create_temp_file};
+use kiru::{Chunker, ChunkerBuilder, ChunkerWithStrategy, ChunkingError, Source, TokensChunker};
+use serde_json::json;
+use tokenizers::Tokenizer;
+
+/// A tokenizer making a token of each word and of each run of punctuation, mapping words
+/// out of `vocab` to `[UNK]` if `unk` is set, and failing on them otherwise.
+fn word_level(vocab: &[&str], unk: bool) -> Tokenizer {
+    let mut ids = serde_json::Map::new();
+    if unk {
+        ids.insert("[UNK]".to_string(), json!(0));
+    }
+    for word in vocab {
+        ids.insert(word.to_string(), json!(ids.len()));
+    }
+    let config = json!({
+        "version": "1.0",
+        "truncation": null,
+        "padding": null,
+        "added_tokens": [],
+        "normalizer": null,
+        "pre_tokenizer": { "type": "Whitespace" },
+        "post_processor": null,
+        "decoder": null,
+        "model": { "type": "WordLevel", "vocab": ids, "unk_token": "[UNK]" }
+    });
+    Tokenizer::from_str(&config.to_string()).unwrap()
+}
+
+fn texts(tokenizer: Tokenizer, text: &str, chunk_size: usize, overlap: usize) -> Vec<String> {
+    ChunkerWithStrategy::new(TokensChunker::new(tokenizer, chunk_size, overlap).unwrap())
+        .on_source(Source::Text(text.to_string()))
+        .unwrap()
+        .collect()
+}
+
+#[test]
+fn chunks_hold_at_most_chunk_size_tokens() {
+    let tokenizer = word_level(&[], true);
+    let text = "one two three four five six seven eight";
+
+    assert_eq!(
+        texts(tokenizer.clone(), text, 3, 0),
+        ["one two three", "four five six", "seven eight"]
+    );
+    // The last chunk holds the tokens left, however few
+    assert_eq!(
+        texts(tokenizer, text, 3, 1),
+        [
+            "one two three",
+            "three four five",
+            "five six seven",
+            "seven eight"
+        ]
+    );
+}
+
+#[test]
+fn chunks_are_the_text_of_their_tokens() {
+    // Punctuation makes tokens of its own, and whitespace none
+    let chunks = texts(
+        word_level(&[], true),
+        "  Hello, world!  How are you?\n",
+        3,
+        0,
+    );
+
+    assert_eq!(chunks, ["Hello, world", "!  How are", "you?"]);
+}
+
+#[test]
+fn chars_split_across_tokens_are_held_whole() {
+    let text = "añb 🚀 é";
+    let tokenizer = byte_level_tokenizer();
+    assert_eq!(tokenizer.encode(text, false).unwrap().len(), text.len());
+
+    for chunk_size in 1..6 {
+        for overlap in 0..chunk_size {
+            let chunks = ChunkerWithStrategy::new(
+                TokensChunker::new(tokenizer.clone(), chunk_size, overlap).unwrap(),
+            )
+            .on_source_with_offsets(Source::Text(text.to_string()))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+            for chunk in &chunks {
+                assert_eq!(&text[chunk.start..chunk.end], chunk.text);
+            }
+            assert_eq!(chunks.last().unwrap().end, text.len());
+        }
+    }
+}
+
+#[test]
+fn file_chunks_match_string_chunks() {
+    let text = "A sentence of plain words, repeated to fill a few blocks.\n".repeat(5_000);
+    let (_dir, path) = create_temp_file(&text);
+    let chunker =
+        ChunkerWithStrategy::new(TokensChunker::new(word_level(&[], true), 100, 20).unwrap());
+
+    let from_file: Vec<_> = chunker
+        .on_source_with_offsets(Source::File(path))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let from_string: Vec<_> = chunker
+        .on_source_with_offsets(Source::Text(text.clone()))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(from_file, from_string);
+    assert_eq!(from_file.last().unwrap().end, text.len() - 1);
+}
+
+#[test]
+fn tokenizer_is_loaded_from_a_file() {
+    let (_dir, path) = create_temp_file(&word_level(&["hello"], true).to_string(false).unwrap());
+
+    let chunks: Vec<String> = ChunkerBuilder::by_tokens(&path, 2, 0)
+        .unwrap()
+        .on_source(Source::Text("hello there, world".to_string()))
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, ["hello there", ", world"]);
+}
+
+#[test]
+fn chunkers_share_a_tokenizer_loaded_once() {
+    let (_dir, path) = create_temp_file(&word_level(&["hello"], true).to_string(false).unwrap());
+    let tokenizer = TokensChunker::load_tokenizer(&path).unwrap();
+    let text = "hello there, world. ".repeat(1_000);
+
+    let chunkers: Vec<_> = (0..2)
+        .map(|_| ChunkerWithStrategy::new(TokensChunker::new(tokenizer.clone(), 50, 10).unwrap()))
+        .collect();
+    // Neither chunker copied the tokenizer
+    assert_eq!(Arc::strong_count(&tokenizer), 3);
+
+    let chunks: Vec<Vec<String>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = chunkers
+            .iter()
+            .map(|chunker| {
+                scope.spawn(|| {
+                    chunker
+                        .on_source(Source::Text(text.clone()))
+                        .unwrap()
+                        .collect()
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    assert!(chunks[0].len() > 10);
+    assert_eq!(chunks[0], chunks[1]);
+    assert_eq!(
+        chunks[0],
+        texts(word_level(&["hello"], true), &text, 50, 10)
+    );
+}
+
+#[test]
+fn invalid_tokenizers_and_arguments_are_rejected() {
+    let (_dir, path) = create_temp_file("not a tokenizer");
+
+    assert!(matches!(
+        ChunkerBuilder::by_tokens(&path, 10, 0),
+        Err(ChunkingError::Tokenizer(_))
+    ));
+    assert!(matches!(
+        ChunkerBuilder::by_tokens("/no/such/tokenizer.json", 10, 0),
+        Err(ChunkingError::Tokenizer(_))
+    ));
+    assert!(matches!(
+        TokensChunker::new(word_level(&[], true), 4, 4),
+        Err(ChunkingError::InvalidArguments { .. })
+    ));
+}
+
+#[test]
+fn encoding_errors_end_the_iteration() {
+    // Without an unknown token, the tokenizer fails on words out of its vocabulary
+    let chunker =
+        ChunkerWithStrategy::new(TokensChunker::new(word_level(&["known"], false), 4, 0).unwrap());
+
+    let known: Vec<_> = chunker
+        .on_source(Source::Text("known known".to_string()))
+        .unwrap()
+        .collect();
+    let unknown: Vec<_> = chunker
+        .on_source_with_offsets(Source::Text("known unknown".to_string()))
+        .unwrap()
+        .collect();
+
+    assert_eq!(known, ["known known"]);
+    assert!(matches!(
+        unknown.as_slice(),
+        [Err(ChunkingError::Tokenizer(_))]
+    ));
+}
+
+#[test]
+fn encoding_errors_stay_with_their_source() {
+    let tokenizer = word_level(&["known"], false);
+    let chunker = TokensChunker::new(tokenizer, 4, 0).unwrap();
+    // An error of the strategy used on its own is never reported by the builder
+    assert_eq!(
+        chunker.clone().chunk_string("unknown".to_string()).count(),
+        0
+    );
+    let chunker = ChunkerWithStrategy::new(chunker);
+
+    let (known, unknown) = std::thread::scope(|scope| {
+        let chunks = |text: &'static str| {
+            let chunker = &chunker;
+            scope.spawn(move || {
+                (0..200)
+                    .flat_map(|_| {
+                        chunker
+                            .on_source_with_offsets(Source::Text(text.to_string()))
+                            .unwrap()
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+        let known = chunks("known known");
+        let unknown = chunks("known unknown");
+        (known.join().unwrap(), unknown.join().unwrap())
+    });
+
+    assert_eq!(known.len(), 200);
+    assert!(known.iter().all(Result::is_ok));
+    assert_eq!(unknown.len(), 200);
+    assert!(unknown
+        .iter()
+        .all(|chunk| matches!(chunk, Err(ChunkingError::Tokenizer(_)))));
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{Chunk, Chunker, ChunkerBuilder, ChunkerWithStrategy, Source};
+
+/// Byte offsets at which the lines of `text` start.
+fn line_starts(text: &str) -> Vec<usize> {
+    text.match_indices('\n').map(|(i, _)| i + 1).collect()
+}
+
+fn chunks<C: Chunker>(chunker: &ChunkerWithStrategy<C>, source: Source) -> Vec<Chunk> {
+    chunker
+        .clone()
+        .with_attach_trailing_whitespace(true)
+        .on_source_with_offsets(source)
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+}
+
+#[test]
+fn blank_lines_attach_to_last_content_chunk() {
+    let text = "First paragraph.\nSecond paragraph.\n\n\n  \n";
+    let chunker = ChunkerBuilder::by_boundaries(line_starts(text), 0).unwrap();
+
+    let chunks = chunks(&chunker, Source::Text(text.to_string()));
+
+    let texts: Vec<_> = chunks.iter().map(|c| c.text.as_str()).collect();
+    assert_eq!(texts, ["First paragraph.\n", "Second paragraph.\n\n\n  \n"]);
+    assert_eq!(chunks[1].end, text.len());
+    let indices: Vec<_> = chunks.iter().map(|c| c.index).collect();
+    assert_eq!(indices, [0, 1]);
+}
+
+#[test]
+fn leading_whitespace_moves_to_previous_chunk() {
+    let text = "First.\n\n\nSecond.\n";
+    let chunker = ChunkerBuilder::by_boundaries(vec![7], 0).unwrap();
+
+    let chunks = chunks(&chunker, Source::Text(text.to_string()));
+
+    let texts: Vec<_> = chunks.iter().map(|c| c.text.as_str()).collect();
+    assert_eq!(texts, ["First.\n\n\n", "Second.\n"]);
+    for chunk in &chunks {
+        assert_eq!(chunk.text, text[chunk.start..chunk.end]);
+    }
+}
+
+#[test]
+fn overlapping_chunks_still_restore_the_source() {
+    let text = "Some words and then a lot of blank space.".to_string() + &"\n".repeat(50);
+    let (_dir, path) = create_temp_file(&text);
+    let chunker = ChunkerBuilder::by_bytes(20, 5).unwrap();
+
+    let chunks = chunks(&chunker, Source::File(path));
+
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks.last().unwrap().end, text.len());
+    let mut previous_end = None;
+    let mut restored = String::new();
+    for chunk in &chunks {
+        assert_eq!(chunk.text, text[chunk.start..chunk.end]);
+        // Every chunk brings new content besides whitespace
+        assert!(!chunk.unique_text(previous_end).trim().is_empty());
+        restored.push_str(chunk.unique_text(previous_end));
+        previous_end = Some(chunk.end);
+    }
+    assert_eq!(restored, text);
+}
+
+#[test]
+fn moved_starts_never_pass_the_next_chunk() {
+    let text = "\n  b é";
+    let chunker = ChunkerBuilder::by_recursive(5, 3)
+        .unwrap()
+        .with_line_numbers();
+
+    let chunks = chunks(&chunker, Source::Text(text.to_string()));
+
+    assert!(chunks.windows(2).all(|pair| pair[0].start <= pair[1].start));
+    for chunk in &chunks {
+        assert_eq!(chunk.text, text[chunk.start..chunk.end]);
+        let lines = chunk.lines.unwrap();
+        assert_eq!(lines.start, text[..chunk.start].matches('\n').count() + 1);
+    }
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{ChunkerBuilder, ChunkerWithStrategy, ChunkingError, Source, TranscriptChunker};
+
+/// SRT timestamp of `ms` milliseconds.
+fn time(ms: usize) -> String {
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        ms % 1000
+    )
+}
+
+/// SRT cues of 2.5 seconds each, cue `n` saying "line n".
+fn srt(cues: usize) -> String {
+    (0..cues)
+        .map(|n| {
+            format!(
+                "{}\n{} --> {}\nline {n}\n\n",
+                n + 1,
+                time(n * 2500),
+                time(n * 2500 + 2500)
+            )
+        })
+        .collect()
+}
+
+/// Numbers of the cues spoken in `chunk`.
+fn cues(chunk: &str) -> Vec<usize> {
+    chunk
+        .lines()
+        .filter_map(|line| line.strip_prefix("line "))
+        .map(|n| n.parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn chunks_cover_time_windows_with_overlap() {
+    let text = srt(100);
+    let (_dir, path) = create_temp_file(&text);
+    let chunker = ChunkerBuilder::by_transcript(30.0, 10.0).unwrap();
+
+    let chunks: Vec<_> = chunker.on_source(Source::File(path)).unwrap().collect();
+
+    // Windows of 30s (12 cues) starting every 20s (8 cues)
+    assert_eq!(chunks.len(), 12);
+    for (k, chunk) in chunks.iter().enumerate() {
+        let first = 8 * k;
+        let expected: Vec<_> = (first..(first + 12).min(100)).collect();
+        assert_eq!(cues(chunk), expected, "chunk {k}");
+        assert!(k == 0 || chunk.starts_with(&time(first * 2500)));
+    }
+
+    let from_string: Vec<_> = chunker.on_source(Source::Text(text)).unwrap().collect();
+    assert_eq!(from_string, chunks);
+}
+
+#[test]
+fn untimed_lines_attach_to_previous_timed_line() {
+    let text = "intro\n[00:00] a\nmore a\n[00:04] b\n[00:09] c\nmore c\n[00:12] d\n".to_string();
+
+    let chunks: Vec<_> = ChunkerBuilder::by_transcript(8.0, 4.0)
+        .unwrap()
+        .on_source(Source::Text(text))
+        .unwrap()
+        .collect();
+
+    assert_eq!(
+        chunks,
+        [
+            "intro\n[00:00] a\nmore a\n[00:04] b\n",
+            "[00:04] b\n[00:09] c\nmore c\n",
+            "[00:09] c\nmore c\n[00:12] d\n",
+        ]
+    );
+}
+
+#[test]
+fn custom_timestamp_pattern() {
+    let text = "t=0.5 a\nt=1.5 b\nt=2.5 c\n".to_string();
+    let chunker = TranscriptChunker::new(1.0, 0.0)
+        .unwrap()
+        .with_timestamp_pattern(r"^t=(\d+\.\d+)")
+        .unwrap();
+
+    let chunks: Vec<_> = ChunkerWithStrategy::new(chunker)
+        .on_source(Source::Text(text))
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks, ["t=0.5 a\n", "t=1.5 b\n", "t=2.5 c\n"]);
+}
+
+#[test]
+fn rejects_invalid_windows_and_patterns() {
+    assert!(matches!(
+        TranscriptChunker::new(10.0, 10.0),
+        Err(ChunkingError::InvalidTimeWindow { .. })
+    ));
+    assert!(matches!(
+        TranscriptChunker::new(0.0, 0.0),
+        Err(ChunkingError::InvalidTimeWindow { .. })
+    ));
+    assert!(matches!(
+        TranscriptChunker::new(10.0, 1.0)
+            .unwrap()
+            .with_timestamp_pattern("("),
+        Err(ChunkingError::InvalidPattern(_))
+    ));
+}
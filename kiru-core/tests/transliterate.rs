@@ -0,0 +1,49 @@
+#![cfg(feature = "transliterate")]
+
+use kiru::{transliterate, ChunkerBuilder, ChunkerConfig, Source};
+
+#[test]
+fn accented_text_is_spelled_in_ascii() {
+    assert_eq!(
+        transliterate("Größe über alles — naïve café, ﬁn «Łódź» 🚀 日本"),
+        "Grosse uber alles - naive cafe, fin \"Lodz\"  "
+    );
+    assert_eq!(transliterate("plain ASCII"), "plain ASCII");
+}
+
+#[test]
+fn chunks_are_ascii_and_sized_after_transliteration() {
+    let text = "Größe über alles — naïve café 🚀 Ærøskøbing. ".repeat(100);
+    let chunker = ChunkerBuilder::by_bytes(64, 8)
+        .unwrap()
+        .with_transliteration(true);
+
+    let chunks: Vec<String> = chunker
+        .on_source(Source::Text(text.clone()))
+        .unwrap()
+        .collect();
+
+    assert!(chunks.len() > 10);
+    assert!(chunks.iter().all(|chunk| chunk.is_ascii()));
+    // Every chunk but the last is full, as if the transliterated text was chunked
+    assert!(chunks[..chunks.len() - 1].iter().all(|c| c.len() == 64));
+    let expected: Vec<String> = ChunkerBuilder::by_bytes(64, 8)
+        .unwrap()
+        .on_source(Source::Text(transliterate(&text)))
+        .unwrap()
+        .collect();
+    assert_eq!(chunks, expected);
+}
+
+#[test]
+fn transliteration_is_part_of_the_config() {
+    let chunker = ChunkerBuilder::by_bytes(64, 8)
+        .unwrap()
+        .with_transliteration(true);
+
+    let config = chunker.config().unwrap();
+    assert!(config.options.transliterate);
+    let rebuilt = ChunkerConfig::from_json(&config.to_json()).unwrap();
+    assert_eq!(rebuilt, config);
+    assert!(rebuilt.build().is_ok());
+}
@@ -0,0 +1,98 @@
+use kiru::{
+    BoundariesChunker, BytesChunker, CharactersChunker, Chunk, Chunker, ChunkerBuilder, Source,
+};
+use proptest::prelude::*;
+
+fn concat_unique(chunks: &[Chunk]) -> String {
+    let mut previous_end = None;
+    let mut restored = String::new();
+    for chunk in chunks {
+        restored.push_str(chunk.unique_text(previous_end));
+        previous_end = Some(chunk.end);
+    }
+    restored
+}
+
+fn sample_text() -> String {
+    "First sentence here. Second one, ünïcödé 🎉! Third? Fourth line\nand fifth. ".repeat(30)
+}
+
+#[test]
+fn unique_text_round_trips_bytes() {
+    let text = sample_text();
+    let chunkers = [
+        BytesChunker::new(64, 16).unwrap(),
+        BytesChunker::new(64, 16).unwrap().with_overlap_sentences(1),
+        BytesChunker::new(64, 16)
+            .unwrap()
+            .with_overlap_chars(10)
+            .with_overlap_whole_words(true),
+        BytesChunker::new(64, 16).unwrap().with_soft_max_extra(30),
+    ];
+
+    for chunker in chunkers {
+        let chunks: Vec<_> = chunker.chunk_string_with_offsets(text.clone()).collect();
+        assert_eq!(concat_unique(&chunks), text);
+    }
+}
+
+#[test]
+fn unique_text_leaves_out_prefix_and_suffix() {
+    let text = "abcdefghijklmnopqrstuvwxyz";
+    let chunks: Vec<_> = ChunkerBuilder::by_bytes(10, 3)
+        .unwrap()
+        .with_prefix("P:")
+        .with_suffix("!!")
+        .on_source_with_offsets(Source::Text(text.to_string()))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(chunks[1].unique_text(Some(chunks[0].end)), "klmnopq");
+    assert_eq!(concat_unique(&chunks), text);
+}
+
+#[test]
+fn unique_text_round_trips_characters() {
+    let text = sample_text();
+    for chunker in [
+        CharactersChunker::new(50, 20).unwrap(),
+        CharactersChunker::new(50, 20)
+            .unwrap()
+            .with_overlap_sentences(2),
+    ] {
+        let chunks: Vec<_> = chunker.chunk_string_with_offsets(text.clone()).collect();
+        assert_eq!(concat_unique(&chunks), text);
+    }
+}
+
+#[test]
+fn unique_text_round_trips_boundaries() {
+    let text = sample_text();
+    let cuts = (1..text.len()).step_by(37).collect();
+
+    let chunks: Vec<_> = BoundariesChunker::new(cuts, 12)
+        .unwrap()
+        .chunk_string_with_offsets(text.clone())
+        .collect();
+
+    assert_eq!(concat_unique(&chunks), text);
+}
+
+proptest! {
+    #[test]
+    fn unique_text_round_trips_random(
+        text in "\\PC{0,400}",
+        (chunk_size, overlap) in (1usize..80).prop_flat_map(|size| (Just(size), 0..size)),
+    ) {
+        let chunks: Vec<_> = BytesChunker::new(chunk_size, overlap)?
+            .chunk_string_with_offsets(text.clone())
+            .collect();
+        prop_assert_eq!(concat_unique(&chunks), text.clone());
+
+        let chunks: Vec<_> = CharactersChunker::new(chunk_size, overlap)?
+            .chunk_string_with_offsets(text.clone())
+            .collect();
+        prop_assert_eq!(concat_unique(&chunks), text);
+    }
+}
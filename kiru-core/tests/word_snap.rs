@@ -0,0 +1,77 @@
+mod common;
+
+use common::helpers::create_temp_file;
+use kiru::{BytesChunker, ChunkerWithStrategy, Source};
+
+fn snapping(chunk_size: usize, overlap: usize) -> ChunkerWithStrategy<BytesChunker> {
+    ChunkerWithStrategy::new(
+        BytesChunker::new(chunk_size, overlap)
+            .unwrap()
+            .with_snap_to_word(true),
+    )
+}
+
+#[test]
+fn chunks_end_with_whole_words() {
+    let text = "Some words of uneven lengths, then ünïcödé ones too. ".repeat(50);
+    let chunker = snapping(100, 20);
+
+    let chunks: Vec<_> = chunker
+        .on_source_with_offsets(Source::Text(text.clone()))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    let mut cut = 0;
+    for chunk in &chunks[..chunks.len() - 1] {
+        let around = &text.as_bytes()[chunk.end - 1..=chunk.end];
+        assert!(around.contains(&b' '), "{:?}", chunk.text);
+        cut += 100 - chunk.text.len();
+    }
+    let stats = chunker.stats();
+    assert!(stats.word_snap_adjustments > 0);
+    assert!(stats.word_snap_adjustments < chunks.len());
+    assert_eq!(stats.word_snap_bytes, cut);
+    assert_eq!(
+        stats.word_snap_mean_bytes(),
+        cut as f64 / stats.word_snap_adjustments as f64
+    );
+}
+
+#[test]
+fn cuts_between_words_are_not_counted() {
+    // Chunks start and end every 5 bytes, i.e. right after a space
+    let chunker = snapping(40, 10);
+
+    let chunks = chunker
+        .on_source(Source::Text("word ".repeat(500)))
+        .unwrap()
+        .count();
+
+    assert!(chunks > 1);
+    let stats = chunker.stats();
+    assert_eq!(stats.word_snap_adjustments, 0);
+    assert_eq!(stats.word_snap_bytes, 0);
+    assert_eq!(stats.word_snap_mean_bytes(), 0.0);
+}
+
+#[test]
+fn file_matches_string() {
+    let text = "Some words of uneven lengths, then ünïcödé ones too. ".repeat(500);
+    let (_dir, path) = create_temp_file(&text);
+    let chunker = snapping(300, 40);
+
+    let expected: Vec<_> = chunker.on_source(Source::Text(text)).unwrap().collect();
+    let from_string = chunker.stats();
+    let chunks: Vec<_> = chunker.on_source(Source::File(path)).unwrap().collect();
+
+    assert_eq!(chunks, expected);
+    // A stream counts each chunk once, even when computed again after reading more text
+    let stats = chunker.stats();
+    assert!(from_string.word_snap_adjustments > 0);
+    assert_eq!(
+        stats.word_snap_adjustments,
+        2 * from_string.word_snap_adjustments
+    );
+    assert_eq!(stats.word_snap_bytes, 2 * from_string.word_snap_bytes);
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use common::helpers::{file_chunks_matching_string_chunks, texts};
+use kiru::{ChunkerBuilder, ChunkingError};
+
+#[test]
+fn chunks_keep_the_whitespace_between_their_words() {
+    assert_eq!(
+        texts(
+            &ChunkerBuilder::by_words(3, 0).unwrap(),
+            "one  two\tthree\n\nfour five six seven"
+        ),
+        ["one  two\tthree\n\n", "four five six ", "seven"]
+    );
+}
+
+#[test]
+fn overlap_repeats_the_last_words() {
+    assert_eq!(
+        texts(&ChunkerBuilder::by_words(3, 1).unwrap(), "a b c d e f g"),
+        ["a b c ", "c d e ", "e f g"]
+    );
+    assert_eq!(
+        texts(&ChunkerBuilder::by_words(3, 2).unwrap(), "a b c d e f"),
+        ["a b c ", "b c d ", "c d e ", "d e f"]
+    );
+}
+
+#[test]
+fn trailing_chunk_of_fewer_words_is_emitted() {
+    assert_eq!(
+        texts(&ChunkerBuilder::by_words(2, 0).unwrap(), "a b c d e"),
+        ["a b ", "c d ", "e"]
+    );
+    assert_eq!(
+        texts(&ChunkerBuilder::by_words(2, 0).unwrap(), "a b c d e  "),
+        ["a b ", "c d ", "e  "]
+    );
+    assert_eq!(texts(&ChunkerBuilder::by_words(5, 2).unwrap(), "a"), ["a"]);
+    assert!(texts(&ChunkerBuilder::by_words(2, 0).unwrap(), " \n\t").is_empty());
+}
+
+#[test]
+fn unicode_whitespace_separates_words() {
+    // No-break space, ideographic space, em space and line separator
+    let text = "東京\u{a0}大阪\u{3000}京都\u{2003}奈良\u{2028}神戸";
+
+    assert_eq!(
+        texts(&ChunkerBuilder::by_words(2, 0).unwrap(), text),
+        ["東京\u{a0}大阪\u{3000}", "京都\u{2003}奈良\u{2028}", "神戸"]
+    );
+}
+
+#[test]
+fn file_chunks_match_string_chunks_across_blocks() {
+    // Several blocks, with words and whitespace runs cut at block ends
+    let text: String = (0..60_000)
+        .map(|n| format!("wörd{n}{}", [" ", "\n", "\u{3000}", "  \t"][n % 4]))
+        .collect();
+    let from_file =
+        file_chunks_matching_string_chunks(&ChunkerBuilder::by_words(100, 10).unwrap(), &text);
+
+    assert_eq!(from_file.len(), 667);
+    for chunk in &from_file {
+        assert_eq!(text[chunk.start..chunk.end], chunk.text);
+        let words = chunk.text.split_whitespace().count();
+        assert!(words == 100 || chunk.end == text.len(), "{words} words");
+    }
+}
+
+#[test]
+fn overlap_must_be_less_than_chunk_size() {
+    assert!(matches!(
+        ChunkerBuilder::by_words(3, 3),
+        Err(ChunkingError::InvalidArguments {
+            chunk_size: 3,
+            overlap: 3
+        })
+    ));
+    assert!(ChunkerBuilder::by_words(0, 0).is_err());
+}
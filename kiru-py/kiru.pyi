@@ -1,6 +1,17 @@
 """Kiru text chunking library."""
 
-from typing import Iterator, List, Optional
+from typing import (
+    Any,
+    Callable,
+    Dict,
+    Iterator,
+    List,
+    Literal,
+    NamedTuple,
+    Optional,
+    Tuple,
+    Union,
+)
 
 __version__: str
 
@@ -8,48 +19,1103 @@ class Chunker:
     """A factory for creating chunkers with specific strategies (bytes or characters)."""
 
     @staticmethod
-    def by_bytes(chunk_size: int, overlap: int) -> "ChunkerBuilder":
+    def by_bytes(
+        chunk_size: int,
+        overlap: int,
+        *,
+        overlap_sentences: Optional[int] = None,
+        overlap_chars: Optional[int] = None,
+        overlap_whole_words: bool = False,
+        snap_to_word: bool = False,
+        soft_max_extra: Optional[int] = None,
+        grid_align: Optional[int] = None,
+        overlap_direction: Literal["backward", "forward"] = "backward",
+        ascii: bool = False,
+        context_before_units: int = 0,
+        context_after_units: int = 0,
+        overlap_schedule: Optional[List[Tuple[int, int]]] = None,
+        max_lines: Optional[int] = None,
+        buffer_capacity: Optional[int] = None,
+    ) -> "ChunkerBuilder":
         """
         Create a byte-based chunker.
 
         Args:
             chunk_size: Size of each chunk in bytes.
             overlap: Number of overlapping bytes between chunks (must be less than chunk_size).
+            overlap_sentences: Start each chunk at the N-th last sentence of the previous chunk
+                instead of overlapping by bytes (falls back to `overlap` when fewer sentences fit).
+            overlap_chars: Overlap by at least the last N characters of the previous chunk instead
+                of `overlap` bytes (sentence overlap takes precedence when it applies).
+            overlap_whole_words: Move the overlap start back to the beginning of its word
+                (searching at most 64 characters back).
+            snap_to_word: Move a chunk end that falls inside a word back to the beginning of that
+                word (searching at most 64 characters back), so chunks end with complete words.
+                Shortened chunks are counted in `stats()`.
+            soft_max_extra: Let a chunk grow up to this many bytes past chunk_size to finish its
+                current sentence (hard cut at chunk_size when no sentence ends in time).
+            grid_align: End chunks on multiples of this many bytes of the source (the last grid
+                line each chunk reaches that is a character boundary), so chunks of documents
+                sharing a prefix line up. Chunks reaching no grid line end as usual.
+            overlap_direction: "backward" to repeat the end of the previous chunk at the start of
+                each chunk, or "forward" to end each chunk with a lookahead into the next one.
+                With "forward", chunks hold their own chunk_size units plus the overlap and start
+                every chunk_size units; the last chunk has no lookahead.
+            ascii: Trust that the input is pure ASCII, skipping UTF-8 decoding of files and URLs
+                (only checked to be ASCII) and character boundary checks. Iterating a file or
+                URL with another byte raises ValueError; on strings, a wrong assertion panics in
+                debug builds and may fail otherwise.
+            context_before_units: Extend each chunk with up to this many bytes of the text before
+                it. Only the emitted text and offsets grow, not the step between chunks.
+            context_after_units: Extend each chunk with up to this many bytes of the text after
+                it, likewise.
+            overlap_schedule: `(from_index, overlap)` steps setting the overlap in bytes of chunk
+                `from_index` and the chunks after it with the chunk before them, until the next
+                step (`overlap` before the first step).
+            max_lines: End a chunk right after its N-th line when it reaches N lines before
+                chunk_size bytes (whichever limit is hit first ends the chunk).
+            buffer_capacity: Bytes to allocate up front for the buffer that files and URLs are
+                streamed through (defaults to 10 * chunk_size bytes, or at least its usual
+                peak).
 
         Returns:
             ChunkerBuilder: A builder for chunking sources.
 
         Raises:
-            ValueError: If chunk_size is 0 or overlap >= chunk_size.
+            ValueError: If chunk_size is 0, overlap or a scheduled overlap >= chunk_size or the
+                overlap direction is unknown.
         """
         ...
 
     @staticmethod
-    def by_characters(chunk_size: int, overlap: int) -> "ChunkerBuilder":
+    def by_approx_tokens(
+        target_tokens: int, overlap_tokens: int, chars_per_token: float = 4.0
+    ) -> "ChunkerBuilder":
+        """
+        Create a chunker that sizes chunks by an estimated token budget, without loading a
+        tokenizer.
+
+        Tokens are estimated at chars_per_token characters each (about 4 for English text and
+        GPT tokenizers). The budget is converted to bytes, one per character of ASCII text and
+        erring on the short side for other text, and chunks end and overlap on whole words.
+
+        Args:
+            target_tokens: Estimated number of tokens in each chunk.
+            overlap_tokens: Estimated number of tokens repeated at the start of the next chunk.
+            chars_per_token: Characters per token.
+
+        Returns:
+            ChunkerBuilder: A builder for chunking sources.
+
+        Raises:
+            ValueError: If target_tokens is 0, overlap_tokens is not less than target_tokens or
+                chars_per_token is not a positive number.
+        """
+        ...
+
+    @staticmethod
+    def by_characters(
+        chunk_size: int,
+        overlap: int,
+        *,
+        overlap_sentences: Optional[int] = None,
+        overlap_direction: Literal["backward", "forward"] = "backward",
+        ascii: bool = False,
+        context_before_units: int = 0,
+        context_after_units: int = 0,
+        overlap_schedule: Optional[List[Tuple[int, int]]] = None,
+        overlap_bytes: Optional[int] = None,
+        buffer_capacity: Optional[int] = None,
+    ) -> "ChunkerBuilder":
         """
         Create a character-based chunker.
 
         Args:
-            chunk_size: Size of each chunk in characters.
-            overlap: Number of overlapping characters between chunks (must be less than chunk_size).
+            chunk_size: Size of each chunk in characters.
+            overlap: Number of overlapping characters between chunks (must be less than chunk_size).
+            overlap_sentences: Start each chunk at the N-th last sentence of the previous chunk
+                instead of overlapping by characters (falls back to `overlap` when fewer sentences fit).
+            overlap_direction: "backward" to repeat the end of the previous chunk at the start of
+                each chunk, or "forward" to end each chunk with a lookahead into the next one.
+                With "forward", chunks hold their own chunk_size units plus the overlap and start
+                every chunk_size units; the last chunk has no lookahead.
+            ascii: Trust that the input is pure ASCII, skipping UTF-8 decoding of files and URLs
+                (only checked to be ASCII) and character boundary checks. Iterating a file or
+                URL with another byte raises ValueError; on strings, a wrong assertion panics in
+                debug builds and may fail otherwise.
+            context_before_units: Extend each chunk with up to this many characters of the text
+                before it. Only the emitted text and offsets grow, not the step between chunks.
+            context_after_units: Extend each chunk with up to this many characters of the text
+                after it, likewise.
+            overlap_schedule: `(from_index, overlap)` steps setting the overlap in characters of
+                chunk `from_index` and the chunks after it with the chunk before them, until the
+                next step (`overlap` before the first step).
+            overlap_bytes: Overlap by as many of the last characters of the previous chunk as
+                fit in this many bytes, instead of `overlap` characters and the schedule
+                (sentence overlap takes precedence when it applies).
+            buffer_capacity: Bytes to allocate up front for the buffer that files and URLs are
+                streamed through (defaults to 10 * chunk_size bytes, or at least its usual
+                peak).
+
+        Returns:
+            ChunkerBuilder: A builder for chunking sources.
+
+        Raises:
+            ValueError: If chunk_size is 0, overlap, a scheduled overlap or overlap_bytes >=
+                chunk_size or the overlap direction is unknown.
+        """
+        ...
+
+    @staticmethod
+    def from_config(config: Dict[str, Any]) -> "ChunkerBuilder":
+        """
+        Rebuild a chunker from the dict returned by `config()` of a builder, e.g. one logged
+        with an experiment. Options left out keep their default.
+
+        Args:
+            config: The strategy ("bytes" or "characters"), `chunk_size`, `overlap` and any
+                other option, named after the argument or method that sets it.
+
+        Returns:
+            ChunkerBuilder: A builder chunking like the one the config describes.
+
+        Raises:
+            ValueError: If the config is invalid or describes invalid parameters.
+        """
+        ...
+
+    @staticmethod
+    def by_boundaries(boundaries: List[int], overlap: int) -> "ChunkerBuilder":
+        """
+        Create a chunker that cuts at externally computed byte offsets.
+
+        Each chunk spans from one boundary to the next; boundaries inside a multi-byte
+        character are snapped back to the previous character boundary.
+
+        Args:
+            boundaries: Strictly increasing byte offsets at which to cut.
+            overlap: Number of bytes preceding each cut to repeat at the start of the next chunk.
+
+        Returns:
+            ChunkerBuilder: A builder for chunking sources.
+
+        Raises:
+            ValueError: If the boundaries are not strictly increasing.
+        """
+        ...
+
+    @staticmethod
+    def by_lines(
+        chunk_size: int,
+        overlap: int,
+        *,
+        line_ending: Literal["\n", "\r\n"] = "\n",
+        repeat_header: bool = False,
+        collapse_separators: bool = False,
+    ) -> "ChunkerBuilder":
+        """
+        Create a chunker that counts chunk_size and overlap in lines.
+
+        Lines keep their terminator, so chunks without overlap concatenate to the source. The
+        last chunk holds the remaining lines, however few, the last of which may have no
+        terminator.
+
+        Args:
+            chunk_size: Number of lines in each chunk.
+            overlap: Number of last lines of a chunk repeated at the start of the next one.
+            line_ending: "\\n" (also ending "\\r\\n" lines, their "\\r" kept in the line) or
+                "\\r\\n" (a lone "\\n" is then part of the line, e.g. in a quoted CSV field).
+            repeat_header: Take the first line as a header (e.g. CSV column names), held by the
+                first chunk besides its chunk_size lines and repeated at the top of every later
+                chunk.
+            collapse_separators: Drop blank lines, so that a run of line endings ends a single
+                line and no line is blank. Offsets are then positions in the collapsed text, not
+                in the source.
+
+        Returns:
+            ChunkerBuilder: A builder for chunking sources.
+
+        Raises:
+            ValueError: If chunk_size is 0, overlap is not less than chunk_size or the line
+                ending is neither "\\n" nor "\\r\\n".
+        """
+        ...
+
+    @staticmethod
+    def by_lines_packed(target_bytes: int, overlap_lines: int) -> "ChunkerBuilder":
+        """
+        Create a chunker that packs whole lines into chunks of a byte budget.
+
+        Lines are accumulated until the next one would exceed target_bytes, so chunks never
+        end mid-line; a single line longer than target_bytes becomes a chunk of its own.
+
+        Args:
+            target_bytes: Maximum size of each chunk in bytes.
+            overlap_lines: Number of last lines of a chunk to repeat at the start of the next
+                one (fewer when the next line would not fit otherwise).
+
+        Returns:
+            ChunkerBuilder: A builder for chunking sources.
+
+        Raises:
+            ValueError: If target_bytes is 0.
+        """
+        ...
+
+    @staticmethod
+    def by_graphemes(chunk_size: int, overlap: int) -> "ChunkerBuilder":
+        """
+        Create a chunker that counts chunk_size and overlap in extended grapheme clusters,
+        what a reader sees as one character, so that chunks never cut an accented letter
+        written with a combining mark, a flag or an emoji joined by ZWJs.
+
+        Chunks without overlap concatenate to the source. When chunking a file, a cluster cut
+        by the end of a block is never split.
+
+        Args:
+            chunk_size: Number of grapheme clusters in each chunk.
+            overlap: Number of last clusters of a chunk repeated at the start of the next one.
+
+        Returns:
+            ChunkerBuilder: A builder for chunking sources.
+
+        Raises:
+            ValueError: If chunk_size is 0 or overlap is not less than chunk_size.
+        """
+        ...
+
+    @staticmethod
+    def by_words(chunk_size: int, overlap: int) -> "ChunkerBuilder":
+        """
+        Create a chunker that counts chunk_size and overlap in words, so that chunks never
+        cut a word in half.
+
+        Words are runs of non-whitespace characters, separated by any Unicode whitespace.
+        Chunks keep the whitespace between their words as it is and end with the whitespace
+        after their last word, so that chunks without overlap concatenate to the source. The
+        last chunk holds the remaining words, however few.
+
+        Args:
+            chunk_size: Number of words in each chunk.
+            overlap: Number of last words of a chunk repeated at the start of the next one.
+
+        Returns:
+            ChunkerBuilder: A builder for chunking sources.
+
+        Raises:
+            ValueError: If chunk_size is 0 or overlap is not less than chunk_size.
+        """
+        ...
+
+    @staticmethod
+    def by_paragraphs(
+        chunk_size: int, overlap: int, *, collapse_separators: bool = False
+    ) -> "ChunkerBuilder":
+        """
+        Create a chunker that counts chunk_size and overlap in paragraphs.
+
+        Paragraphs are separated by one or more blank lines, i.e. by whitespace holding two or
+        more newlines, so "\\r\\n" line endings and lines of spaces separate them too. Chunks
+        keep the separators between and after their paragraphs as they are; only the blank
+        lines before the first paragraph and after the last one are left out. The last chunk
+        holds the remaining paragraphs, however few.
+
+        Args:
+            chunk_size: Number of paragraphs in each chunk.
+            overlap: Number of last paragraphs of a chunk repeated at the start of the next
+                one.
+            collapse_separators: Collapse each run of blank lines into its first one, so that
+                paragraphs are separated by a single blank line. Offsets are then positions in
+                the collapsed text, not in the source.
+
+        Returns:
+            ChunkerBuilder: A builder for chunking sources.
+
+        Raises:
+            ValueError: If chunk_size is 0 or overlap is not less than chunk_size.
+        """
+        ...
+
+    @staticmethod
+    def by_sentences(
+        chunk_size: int,
+        overlap: int,
+        *,
+        max_bytes: Optional[int] = None,
+        terminators: Optional[set[str]] = None,
+        cjk: bool = False,
+    ) -> "ChunkerBuilder":
+        """
+        Create a chunker that counts chunk_size and overlap in sentences.
+
+        A sentence ends after a terminator, '.', '!' or '?' by default (optionally closed by
+        quotes or brackets), followed by whitespace, which it keeps, so chunks without overlap
+        concatenate to the source. The last chunk holds the remaining sentences, however few.
+
+        `max_bytes` is a hard cap on the size of a chunk: a chunk ends before the first
+        sentence that would take it past the cap, and a sentence longer than the cap (e.g.
+        minified code) falls back to chunks of at most `max_bytes` bytes, counted as
+        `hard_splits` in `stats()`.
+
+        Args:
+            chunk_size: Number of sentences in each chunk.
+            overlap: Number of last sentences of a chunk repeated at the start of the next
+                one.
+            max_bytes: A hard cap on the size of a chunk, in bytes.
+            terminators: The chars that end a sentence when whitespace follows them, '.',
+                '!' and '?' by default (e.g. {".", "…", ";"}).
+            cjk: Whether '。', '！', '？' and '｡' also end a sentence, whitespace following
+                them or not, as in CJK text.
+
+        Returns:
+            ChunkerBuilder: A builder for chunking sources.
+
+        Raises:
+            ValueError: If chunk_size is 0, overlap is not less than chunk_size, or a
+                terminator is not a single character.
+        """
+        ...
+
+    @staticmethod
+    def by_regex(
+        pattern: str,
+        chunk_size: int,
+        overlap: int,
+        *,
+        keep_separator: bool = True,
+        repeat_header: bool = False,
+    ) -> "ChunkerBuilder":
+        """
+        Create a chunker that splits the text on the matches of a regex and counts chunk_size
+        and overlap in the resulting pieces, e.g. sections between `---` rules or records
+        between delimiter lines.
+
+        Pieces are the non-empty texts between matches, so consecutive separators make one.
+        With keep_separator, a piece keeps the separators that follow it, so chunks without
+        overlap concatenate to the source; otherwise a chunk runs from the text of its first
+        piece to the end of the text of its last one, dropping the separators around it.
+
+        Args:
+            pattern: Regex matching separators (Rust regex syntax).
+            chunk_size: Number of pieces in each chunk.
+            overlap: Number of last pieces of a chunk repeated at the start of the next one.
+            keep_separator: Whether pieces keep the separators that follow them.
+            repeat_header: Take the first piece and its separator as a header, held by the first
+                chunk besides its chunk_size pieces and repeated at the top of every later chunk.
+
+        Returns:
+            ChunkerBuilder: A builder for chunking sources.
+
+        Raises:
+            ValueError: If the pattern is not a valid regex, chunk_size is 0 or overlap is
+                not less than chunk_size.
+        """
+        ...
+
+    @staticmethod
+    def by_recursive(
+        chunk_size: int, overlap: int, *, separators: Optional[List[str]] = None
+    ) -> "ChunkerBuilder":
+        """
+        Create a chunker that splits the text on the first of a list of separators found in
+        it, packs the splits back into chunks of at most chunk_size bytes, and splits the
+        splits still too long on the next separators, like LangChain's
+        RecursiveCharacterTextSplitter.
+
+        Splits keep the separator that ends them, so chunks are slices of the source. The
+        empty separator splits between characters; without it, a split holding none of the
+        separators is a chunk however long. The source is read whole before chunking.
+
+        Args:
+            chunk_size: Maximum size of each chunk in bytes.
+            overlap: Maximum number of bytes of whole trailing splits of a chunk repeated at
+                the start of the next one.
+            separators: Separators to try in order (default: ["\\n\\n", "\\n", " ", ""],
+                i.e. paragraphs, lines, words, then characters).
+
+        Returns:
+            ChunkerBuilder: A builder for chunking sources.
+
+        Raises:
+            ValueError: If chunk_size is 0 or overlap is not less than chunk_size.
+        """
+        ...
+
+    @staticmethod
+    def by_tokens(
+        tokenizer: Union["Tokenizer", str], chunk_size: int, overlap: int
+    ) -> "ChunkerBuilder":
+        """
+        Create a chunker that measures chunks in tokens of a HuggingFace tokenizer, e.g. to
+        fill the context window of a model.
+
+        Each chunk holds at most chunk_size tokens (the last one the tokens left, however
+        few) and starts with the last `overlap` tokens of the previous one. Its text is the
+        source text its tokens were encoded from, so chunks are slices of the source, and a
+        character encoded as several tokens is held whole. The source is read whole and
+        encoded without special tokens, truncation or padding.
+
+        Args:
+            tokenizer: A tokenizer loaded with `Tokenizer.from_file`, which builders share
+                without reloading it, or the path to a `tokenizer.json` file, as saved by the
+                `tokenizers` library, to load for this builder alone.
+            chunk_size: Maximum number of tokens in each chunk.
+            overlap: Number of tokens to repeat between chunks.
+
+        Returns:
+            ChunkerBuilder: A builder for chunking sources.
+
+        Raises:
+            ValueError: If the tokenizer cannot be loaded, or chunk_size is 0 or overlap is
+                not less than chunk_size. The iterators raise it if the tokenizer fails to
+                encode a source.
+        """
+        ...
+
+    @staticmethod
+    def by_json_array(chunk_size: int, overlap: int) -> "ChunkerBuilder":
+        """
+        Create a chunker that groups the elements of a JSON array, e.g. a file holding one
+        large array of records, without loading the whole array.
+
+        Each chunk is a JSON array of chunk_size elements (fewer for the last one),
+        re-serialized without whitespace, so that it parses on its own. Elements may be any
+        JSON values, nested ones included.
+
+        Args:
+            chunk_size: Number of elements in each chunk.
+            overlap: Number of last elements of a chunk to repeat at the start of the next
+                one (must be less than chunk_size).
+
+        Returns:
+            ChunkerBuilder: A builder for chunking sources.
+
+        Raises:
+            ValueError: If overlap >= chunk_size.
+        """
+        ...
+
+    @staticmethod
+    def by_transcript(
+        window_secs: float,
+        overlap_secs: float,
+        *,
+        timestamp_pattern: Optional[str] = None,
+    ) -> "ChunkerBuilder":
+        """
+        Create a chunker that cuts time-stamped transcript lines (e.g. SRT or VTT subtitles)
+        into windows of seconds of content.
+
+        A chunk starts at a timed line and takes every following line up to the first timed
+        line at least window_secs later; lines without a timestamp attach to the timed line
+        before them. The next chunk starts overlap_secs before the end of the window.
+
+        Args:
+            window_secs: Duration of content in each chunk, in seconds.
+            overlap_secs: Duration shared with the previous chunk (less than window_secs).
+            timestamp_pattern: Regex finding the timestamp of a line, taken from its first
+                capture group and read as `[[hours:]minutes:]seconds[.fraction]` (default:
+                SRT/VTT cue times and `[mm:ss]` prefixes at the start of a line).
+
+        Returns:
+            ChunkerBuilder: A builder for chunking sources.
+
+        Raises:
+            ValueError: If the window is not positive, the overlap is not in [0, window_secs),
+                or the pattern is not a valid regex.
+        """
+        ...
+
+    @staticmethod
+    def by_predicate(
+        is_boundary: Callable[[str, str, int], bool],
+        chunk_size: int,
+        overlap: int,
+        *,
+        overlap_segments: Optional[int] = None,
+    ) -> "ChunkerBuilder":
+        """
+        Create a chunker that cuts wherever a callback says so, then packs the resulting
+        segments into chunks.
+
+        `is_boundary(prev_char, next_char, pos)` is called for every pair of adjacent
+        characters, `pos` being the byte offset of `next_char`; returning True cuts between
+        them. Consecutive segments are packed into chunks of at most chunk_size bytes (longer
+        segments are hard cut), and each chunk repeats the trailing segments of the previous
+        one that fit within `overlap` bytes, or its last `overlap_segments` segments.
+
+        The callback is called in batches, taking the GIL once per block of text. An
+        exception it raises ends the iteration and is re-raised by the iterator.
+
+        Args:
+            is_boundary: The boundary predicate.
+            chunk_size: Maximum size of each chunk in bytes.
+            overlap: Maximum number of bytes of whole segments to repeat between chunks.
+            overlap_segments: Repeat this many trailing segments of the previous chunk instead,
+                each with the delimiter cut after (fewer when the previous chunk holds fewer,
+                or to leave room for the next segment).
+
+        Returns:
+            ChunkerBuilder: A builder for chunking sources.
+
+        Raises:
+            TypeError: If is_boundary is not callable.
+            ValueError: If chunk_size is 0 or overlap is not less than chunk_size.
+        """
+        ...
+
+    @staticmethod
+    def by_semantic(
+        should_break: Callable[[str, str], bool],
+        overlap_unit: Literal["sentence", "character"] = "sentence",
+        overlap: int = 0,
+    ) -> "ChunkerBuilder":
+        """
+        Create a chunker that grows each chunk unit by unit until a callback says to stop.
+
+        `should_break(current_chunk, candidate_next)` is called with the chunk so far and the
+        unit (a sentence or a character) that would join it next; returning True finalizes
+        the chunk without it. The first unit always joins a chunk. Each chunk after the
+        first repeats the last `overlap` units of the previous one (all but the first unit
+        of a chunk of `overlap` units or less).
+
+        The callback is called in batches, taking the GIL once per block of text. An
+        exception it raises ends the iteration and is re-raised by the iterator. A chunk is
+        held whole in memory until the callback breaks it.
+
+        Args:
+            should_break: The break callback.
+            overlap_unit: The unit chunks grow and overlap by.
+            overlap: Number of units to repeat between chunks.
+
+        Returns:
+            ChunkerBuilder: A builder for chunking sources.
+
+        Raises:
+            TypeError: If should_break is not callable.
+            ValueError: If overlap_unit is not "sentence" or "character".
+        """
+        ...
+
+class ChunkTuple(NamedTuple):
+    """A chunk as a plain namedtuple, yielded with `with_metadata(output="namedtuple")`."""
+
+    text: str
+    start: int
+    end: int
+    index: int
+
+class Chunk:
+    """A chunk of text with its position in the source and the metadata of that source."""
+
+    text: str
+    unique_text: str
+    """The part of `text` not covered by the previous chunk of the same source, without the
+    affixes or a header repeated by `repeat_header` (empty for a bridge chunk): joining the unique text of
+    every chunk of a source restores it (chunks of a source must arrive in order, which `on_sources_par` and
+    `round_robin` do not guarantee)."""
+    start: int
+    """Byte offset of the first byte of the chunk within its source."""
+    end: int
+    """Byte offset one past the last byte of the chunk within its source."""
+    start_byte: int
+    """Same as `start`."""
+    end_byte: int
+    """Same as `end`."""
+    index: int
+    """Position of the chunk among the chunks of its source, starting at 0."""
+    start_line: Optional[int]
+    """1-based line the chunk starts on, when requested with `line_numbers=True`."""
+    end_line: Optional[int]
+    """1-based line the chunk ends on (inclusive), when requested with `line_numbers=True`."""
+    start_char: Optional[int]
+    """Character offset of the first character of the chunk within its source, when requested
+    with `char_offsets=True`."""
+    end_char: Optional[int]
+    """Character offset one past the last character of the chunk within its source, when
+    requested with `char_offsets=True`."""
+    running_hash: Optional[int]
+    """`content_hash` of the text of every chunk yielded so far, this one included, when
+    requested with `running_hash=True`."""
+    prev_index: Optional[int]
+    """Index of the chunk before this one in its source, when requested with `links=True`;
+    None for the first chunk."""
+    next_index: Optional[int]
+    """Index of the chunk after this one in its source, when requested with `links=True`;
+    None for the last chunk."""
+    level: Optional[int]
+    """0 for a parent chunk and 1 for a child chunk, for chunks from `hierarchical`."""
+    parent_index: Optional[int]
+    """Index of the parent a child chunk was cut from, for chunks from `hierarchical`."""
+    source_mtime: Optional[float]
+    """Modification time of the file the chunk was cut from, in seconds since the Unix epoch
+    like `os.stat().st_mtime`, when requested with `source_stamps=True`."""
+    source_size: Optional[int]
+    """Size in bytes of the file the chunk was cut from, when requested with
+    `source_stamps=True`."""
+    term_counts: Optional[Dict[str, int]]
+    """Lowercased word counts of the chunk's content, when requested with `term_counts=True`."""
+    files: Optional[List[Tuple[str, int, int]]]
+    """`(path, start, end)` of the part of each file the chunk spans, with offsets within that
+    file, when chunking with `on_files_concatenated`."""
+    kind: Literal["main", "bridge"]
+    """"bridge" for a bridge chunk added by `with_bridge_chunks` around the boundary of two
+    main chunks, else "main"."""
+    metadata: Optional[Dict[str, Any]]
+    """The metadata passed for the source, shared by all of its chunks."""
+
+class ArrowChunks:
+    """Chunks as Arrow record batches, exported through the Arrow PyCapsule stream interface."""
+
+    def __arrow_c_stream__(self, requested_schema: Optional[object] = None) -> object:
+        """Export the record batches as an `ArrowArrayStream` PyCapsule. A requested schema is
+        ignored: batches always have the `text`, `start`, `end` and `index` columns.
+
+        Raises:
+            ValueError: If the stream was already exported.
+        """
+        ...
+
+class ChunkerBuilder:
+    """A builder for chunking various sources using a specified strategy."""
+
+    def with_metadata(
+        self,
+        *,
+        line_numbers: bool = False,
+        term_counts: bool = False,
+        char_offsets: bool = False,
+        running_hash: bool = False,
+        links: bool = False,
+        source_stamps: bool = False,
+        output: Literal["chunk", "namedtuple"] = "chunk",
+    ) -> "ChunkerBuilder":
+        """
+        Yield `Chunk` objects (text, byte offsets, index and source metadata) instead of strings.
+
+        Args:
+            line_numbers: Also report the 1-based range of lines each chunk spans
+                (`start_line` and `end_line`).
+            term_counts: Also report the lowercased word counts of each chunk's content
+                (`term_counts`), computed in Rust. Words are runs of alphanumeric characters.
+            char_offsets: Also report the offsets of each chunk in characters (`start_char` and
+                `end_char`), to slice a `str` where `start_byte` and `end_byte` slice its UTF-8
+                bytes.
+            running_hash: Also report the 64-bit FNV-1a hash of the text of every chunk yielded
+                so far, this one included (`running_hash`), i.e. `content_hash` of their
+                concatenation, to check that two runs yield the same chunks up to a point.
+            links: Also report the indices of the chunks before and after each chunk in its
+                source (`prev_index` and `next_index`, None at the ends), e.g. to build a graph
+                of chunks. Each chunk is held back until the next one is cut.
+            source_stamps: Also report the modification time and size of the file each chunk
+                was cut from (`source_mtime` and `source_size`, None for other sources), read
+                once per file, e.g. to skip files unchanged since the last run.
+            output: "chunk" to yield `Chunk` objects, or "namedtuple" to yield plain
+                `ChunkTuple(text, start, end, index)` namedtuples, which unpack and pickle like
+                tuples but carry no other metadata.
+
+        Returns:
+            ChunkerBuilder: A copy of this builder with metadata output enabled.
+
+        Raises:
+            ValueError: If the output is unknown.
+        """
+        ...
+
+    def with_memory_warning(self, fraction: float = 0.5) -> "ChunkerBuilder":
+        """
+        Set when `on_file` warns about memory: when the estimated memory use of chunking the
+        file (see `estimate_memory`) exceeds this fraction of the available memory. The
+        available memory is only known on Linux.
+
+        Args:
+            fraction: Fraction of the available memory.
+
+        Returns:
+            ChunkerBuilder: A copy of this builder with the threshold set.
+
+        Raises:
+            ValueError: If the fraction is not positive.
+        """
+        ...
+
+    def with_near_dedup(
+        self, max_distance: int = 3, capacity: int = 1024
+    ) -> "ChunkerBuilder":
+        """
+        Drop chunks that are near-duplicates of a recently seen chunk.
+
+        Chunks are compared through 64-bit SimHash fingerprints of their rolling hashes, so
+        chunks differing by a few edits are caught, not only exact copies.
+
+        Args:
+            max_distance: Maximum number of differing fingerprint bits (out of 64) for a
+                chunk to count as a near-duplicate.
+            capacity: Number of recent fingerprints to remember, least recently used
+                evicted first.
+
+        Returns:
+            ChunkerBuilder: A copy of this builder with near-duplicate suppression.
+        """
+        ...
+
+    def with_emit_empty(self, emit_empty: bool = True) -> "ChunkerBuilder":
+        """
+        Yield a single empty string for a source that produces no chunks (an empty input),
+        instead of nothing. Non-empty inputs are unaffected.
+
+        Args:
+            emit_empty: Whether to yield the empty chunk.
+
+        Returns:
+            ChunkerBuilder: A copy of this builder with the option set.
+        """
+        ...
+
+    def with_block_transform(self, transform: Callable[[str], str]) -> "ChunkerBuilder":
+        """
+        Apply a callable to every block of text read from a source, before it is chunked.
+
+        Files and URLs are transformed as they stream, a block (about 8 KiB) at a time,
+        instead of as a whole. Blocks are cut at arbitrary character boundaries, so the
+        transform should work character by character (e.g. lowercasing or unescaping).
+
+        Offsets are those of the transformed text: a transform that changes the length of the
+        text shifts them off the source.
+
+        Args:
+            transform: Called with each block, returns its replacement.
+
+        Returns:
+            ChunkerBuilder: A copy of this builder with the transform set.
+        """
+        ...
+
+    def with_last_chunk(
+        self, policy: Literal["keep", "drop_if_redundant", "merge"]
+    ) -> "ChunkerBuilder":
+        """
+        Choose what happens to the last chunk of a source when it holds nothing beyond the
+        previous chunk, e.g. when context after the chunk before it reaches the end of the
+        text.
+
+        Only consecutive chunks are compared, so a stride leaves the last chunk alone.
+
+        Args:
+            policy: "keep" to yield it anyway (the default), "drop_if_redundant" to leave it
+                out, or "merge" to fold it into the previous chunk, which also folds in a last
+                chunk adding less new text than it repeats (the previous chunk then grows past
+                the chunk size by less than the overlap).
+
+        Returns:
+            ChunkerBuilder: A copy of this builder with the policy set.
+
+        Raises:
+            ValueError: If the policy is unknown.
+        """
+        ...
+
+    def with_incomplete_utf8(
+        self, policy: Literal["drop", "replace", "error"]
+    ) -> "ChunkerBuilder":
+        """
+        Choose what happens to the bytes of a character cut off by the end of a file or URL,
+        e.g. a file truncated mid-character.
+
+        Such sources are counted in `stats()["truncated_sources"]` whatever the policy.
+
+        Args:
+            policy: "drop" to leave the bytes out (the default), "replace" to end the text
+                with a U+FFFD replacement character, or "error" to raise ValueError after the
+                chunks of the rest of the source.
+
+        Returns:
+            ChunkerBuilder: A copy of this builder with the policy set.
+
+        Raises:
+            ValueError: If the policy is unknown.
+        """
+        ...
+
+    def with_file_retry(self, attempts: int, delay: float = 0.1) -> "ChunkerBuilder":
+        """
+        Retry opening and reading files, e.g. those matched by a glob, when they fail with
+        a transient error such as a timeout on a network share. A missing file or a denied
+        permission fails at once.
+
+        Args:
+            attempts: Times an open or read is tried in all, at least 1.
+            delay: Seconds to wait after the first failure; the wait doubles after each one.
+
+        Returns:
+            ChunkerBuilder: A copy of this builder with the retries set.
+
+        Raises:
+            ValueError: If attempts is 0 or delay is negative.
+        """
+        ...
+
+    def with_transliteration(self, enabled: bool = True) -> "ChunkerBuilder":
+        """
+        Transliterate text to ASCII as it streams, so that chunks only hold ASCII.
+
+        This is lossy: accents are removed (`é` becomes `e`), common letters and punctuation
+        are spelled in ASCII (`ß` becomes `ss`, `—` becomes `-`), and other characters (CJK,
+        emoji, ...) are dropped. Chunks are sized, and offsets given, in the transliterated
+        text, so offsets no longer line up with the source. Runs after the block transform.
+
+        Args:
+            enabled: Whether to transliterate.
+
+        Returns:
+            ChunkerBuilder: A copy of this builder with the option set.
+        """
+        ...
+
+    def with_bridge_chunks(self, margin: int = 32) -> "ChunkerBuilder":
+        """
+        Add a bridge chunk between every two consecutive chunks that overlap or touch, so that
+        queries spanning their boundary match one chunk.
+
+        A bridge spans the overlap of the two chunks (or the point where they meet) and up to
+        `margin` bytes on each side, without leaving them. It comes right after the chunk
+        before it, with its index; with metadata output, its `kind` is "bridge" instead of
+        "main".
+
+        Args:
+            margin: Bytes the bridge extends past the overlap on each side.
+
+        Returns:
+            ChunkerBuilder: A copy of this builder adding bridge chunks.
+        """
+        ...
+
+    def with_stride(self, stride: int) -> "ChunkerBuilder":
+        """
+        Only keep every N-th chunk of each source, e.g. for a quick look at a huge file.
+
+        Chunks at indices 0, stride, 2 * stride, ... are kept, with their index. The whole
+        source is still read, but the bytes and characters strategies do not build the text
+        of the chunks in between. Sampling runs before all the other options, and line
+        numbers are not reported.
+
+        Args:
+            stride: Keep one chunk out of this many.
+
+        Returns:
+            ChunkerBuilder: A copy of this builder sampling chunks.
+
+        Raises:
+            ValueError: If stride is 0.
+        """
+        ...
+
+    def with_merge_small(
+        self, min_chunk_size: int, max_chunk_size: int
+    ) -> "ChunkerBuilder":
+        """
+        Merge runs of consecutive small chunks into larger ones.
+
+        Every run of consecutive chunks that are each smaller than min_chunk_size is merged
+        into chunks of at most max_chunk_size, measured in the unit of the strategy. A merged
+        chunk spans from the start of the first chunk of its run to the end of the last one,
+        so its overlap with the chunks around it is unchanged. Chunks are renumbered.
+
+        Args:
+            min_chunk_size: Size below which a chunk is merged with its small neighbours.
+            max_chunk_size: Maximum size of a merged chunk, typically the chunk size.
+
+        Returns:
+            ChunkerBuilder: A copy of this builder with small chunks merged.
+        """
+        ...
+
+    def with_attach_trailing_whitespace(self, enabled: bool = True) -> "ChunkerBuilder":
+        """
+        Attach the whitespace after the text of a chunk, e.g. trailing blank lines, to that
+        chunk instead of starting the next chunk with it.
+
+        A chunk adding only whitespace to the previous one is merged into it, so blank lines
+        never make a chunk of their own. Chunks after a merged one are renumbered.
+
+        Args:
+            enabled: Whether to attach trailing whitespace.
+
+        Returns:
+            ChunkerBuilder: A copy of this builder with the option set.
+        """
+        ...
+
+    def with_prefix(self, prefix: str) -> "ChunkerBuilder":
+        """
+        Prepend a string (e.g. the document title) to the text of every chunk.
+
+        The prefix does not count towards the chunk size, the size band or near-duplicate
+        detection, and chunk offsets still span the content only.
+
+        Args:
+            prefix: The text to prepend.
+
+        Returns:
+            ChunkerBuilder: A copy of this builder with the prefix set.
+        """
+        ...
+
+    def with_preamble(self, preamble: str, skip_first: bool = False) -> "ChunkerBuilder":
+        """
+        Prepend a preamble (e.g. the import block of a code file, as context) to the text of
+        every chunk, after the prefix.
+
+        Like the prefix, the preamble does not count towards the chunk size, and chunk offsets
+        still span the content only.
+
+        Args:
+            preamble: The text to prepend, typically several lines.
+            skip_first: Leave it out of chunks starting at the start of their source, which
+                already hold it when it is the head of the source.
+
+        Returns:
+            ChunkerBuilder: A copy of this builder with the preamble set.
+        """
+        ...
+
+    def with_suffix(self, suffix: str) -> "ChunkerBuilder":
+        """
+        Append a string to the text of every chunk, like `with_prefix`.
+
+        Args:
+            suffix: The text to append.
+
+        Returns:
+            ChunkerBuilder: A copy of this builder with the suffix set.
+        """
+        ...
+
+    def with_bidi_balance(self, enabled: bool = True) -> "ChunkerBuilder":
+        """
+        Balance the bidi embedding, override and isolate controls within each chunk, so that
+        right-to-left text renders the same on its own: closers whose opener is in an earlier
+        chunk are removed, and openers still open at the end of a chunk are closed there.
+
+        Args:
+            enabled: Whether to balance the controls.
+
+        Returns:
+            ChunkerBuilder: A copy of this builder with the option set.
+        """
+        ...
+
+    def with_size_band(self, min_ratio: float, max_ratio: float) -> "ChunkerBuilder":
+        """
+        Only keep chunks whose size lies within a band around the chunk size.
+
+        Sizes are measured in the unit of the strategy (bytes or characters). Dropped chunks
+        are counted in `stats()`.
+
+        Args:
+            min_ratio: Smallest size kept, as a fraction of chunk_size.
+            max_ratio: Largest size kept, as a fraction of chunk_size.
+
+        Returns:
+            ChunkerBuilder: A copy of this builder filtering chunks by size.
+
+        Raises:
+            ValueError: If the ratios are negative or out of order, or the strategy has no
+                chunk size (boundaries).
+        """
+        ...
+
+    def stats(self) -> Dict[str, Union[int, float]]:
+        """
+        Counts of the chunks produced and dropped so far by iterators of this builder.
+
+        Returns:
+            dict: `chunks` (yielded), `dropped_out_of_band` (size band),
+                `dropped_near_duplicates` (near-duplicate suppression), `boundary_adjustments`
+                (chunk ends and overlap starts moved onto a char boundary by the bytes strategy),
+                `word_snap_adjustments` (chunks shortened by `snap_to_word`), `word_snap_bytes`
+                (bytes cut from them in total), `word_snap_mean_bytes` (per shortened chunk),
+                `hard_splits` (sentences cut at the `max_bytes` cap of the sentences strategy)
+                and `truncated_sources` (files and URLs ending mid-character).
+        """
+        ...
+
+    def config(self) -> Dict[str, Any]:
+        """
+        Parameters of this builder as a JSON-serializable dict, e.g. to log with an
+        experiment, which `Chunker.from_config` rebuilds the builder from.
+
+        The dict holds the strategy ("bytes" or "characters"), `chunk_size`, `overlap` and
+        every other option, named after the argument or method that sets it (`0` or None
+        when unset). The size band is given as the smallest and largest chunk size.
+
+        Returns:
+            dict: The parameters of the builder.
+
+        Raises:
+            ValueError: If the strategy is not bytes or characters, or a block transform is
+                set, since callables cannot be described.
+        """
+        ...
+
+    def estimate_memory(self, source_len: int) -> int:
+        """
+        Estimate the peak memory used to stream a file or URL of the given size.
+
+        The estimate follows the streaming buffer sizes of the strategy (plus its character
+        positions for the characters strategy) and excludes chunks kept by the caller. Chunking
+        a string additionally holds the string itself.
+
+        Args:
+            source_len: Size of the source in bytes.
 
         Returns:
-            ChunkerBuilder: A builder for chunking sources.
+            int: Approximate peak heap usage in bytes.
+        """
+        ...
 
-        Raises:
-            ValueError: If chunk_size is 0 or overlap >= chunk_size.
+    def output_size_estimate(self, source_len: int) -> Optional[int]:
+        """
+        Worst-case total bytes of the chunks of a source of the given size, for capacity
+        planning: the source itself, every repeated overlap and the prefix and suffix of every
+        chunk.
+
+        Args:
+            source_len: Size of the source in bytes.
+
+        Returns:
+            Optional[int]: Upper bound of the output in bytes, or None if the strategy does not
+                bound chunk sizes (json_array, lines_packed, semantic and transcript).
         """
         ...
 
-class ChunkerBuilder:
-    """A builder for chunking various sources using a specified strategy."""
+    def count_file(self, path: str, ascii: bool = False) -> int:
+        """
+        Number of chunks of a file. For an ASCII file, the count follows from the file's size
+        alone, without reading it; other files are chunked to count their chunks.
+
+        Args:
+            path: The path of the file.
+            ascii: Trust that the file is ASCII instead of checking it, which would read it
+                once.
+
+        Returns:
+            int: The number of chunks `on_file` yields for the file.
+
+        Raises:
+            IOError: If reading the file failed.
+        """
+        ...
 
-    def on_string(self, text: str) -> "ChunkerIterator":
+    def on_string(
+        self, text: str, metadata: Optional[Dict[str, Any]] = None
+    ) -> "ChunkerIterator":
         """
         Chunk a single string input.
 
         Args:
             text: The input text to chunk.
+            metadata: Key/values attached to every chunk when metadata output is enabled.
 
         Returns:
             ChunkerIterator: An iterator over the chunks.
@@ -59,27 +1125,148 @@ class ChunkerBuilder:
         """
         ...
 
-    def on_file(self, path: str) -> "ChunkerIterator":
+    def chunks(
+        self, text: str, metadata: Optional[Dict[str, Any]] = None
+    ) -> "ChunkSequence":
+        """
+        Chunk a string into a sequence that can be indexed, counted and iterated over any
+        number of times, e.g. to look at chunks in a notebook.
+
+        Chunks are cut as they are first indexed, up to the chunk indexed, and kept: counting
+        them or a negative index cuts them all.
+
+        Args:
+            text: The input text to chunk.
+            metadata: Key/values attached to every chunk when metadata output is enabled.
+
+        Returns:
+            ChunkSequence: The chunks of the text.
+
+        Raises:
+            ValueError: If the input cannot be processed.
+        """
+        ...
+
+    def on_file(
+        self,
+        path: str,
+        metadata: Optional[Dict[str, Any]] = None,
+        *,
+        follow: bool = False,
+        poll_interval: float = 0.1,
+        stop: Optional["StopSignal"] = None,
+    ) -> "ChunkerIterator":
         """
         Chunk a single file from a local path.
 
+        With `follow=True`, the file is followed as it is written, like `tail -f`: at the end
+        of the data written so far, it is polled for more every `poll_interval` seconds until
+        `stop.stop()` is called, after which the rest of the file is chunked and iteration
+        ends. A chunk is yielded once the text after it fills the chunker's buffer, a few
+        chunks.
+
         Args:
             path: The path to the file (e.g., "path/to/file.txt").
+            metadata: Key/values attached to every chunk when metadata output is enabled.
+            follow: Wait for more data at the end of the file. Defaults to False.
+            poll_interval: Seconds between polls for more data when following. Defaults to 0.1.
+            stop: Ends following once stopped; required with `follow=True`.
+
+        Returns:
+            ChunkerIterator: An iterator over the chunks.
+
+        Raises:
+            ValueError: If the file cannot be read (e.g., does not exist), or `follow=True`
+                is given without `stop` or with a negative `poll_interval`.
+
+        Warns:
+            UserWarning: If a chunk size in bytes (of `by_bytes` or `by_lines_packed`)
+                exceeds the size of the file, so that it is buffered whole instead of streamed, or the estimated memory use exceeds the fraction of
+                the available memory set by `with_memory_warning`.
+        """
+        ...
+
+    def precompute_boundaries(self, path: str, params: str, cache_path: str) -> int:
+        """
+        Chunk a file and cache the boundaries of its chunks to disk, so that later runs can
+        slice the file with `from_cached_boundaries` instead of chunking it again.
+
+        The cache records a hash of `params` along with the size and modification time of
+        the file, which `from_cached_boundaries` checks before using it.
+
+        Args:
+            path: The path of the file to chunk.
+            params: Any description of the chunking parameters, e.g. "bytes:1024:128".
+            cache_path: The path of the cache file to write.
+
+        Returns:
+            int: The number of chunks.
+
+        Raises:
+            IOError: If reading the file or writing the cache failed.
+        """
+        ...
+
+    def from_cached_boundaries(
+        self,
+        path: str,
+        params: str,
+        cache_path: str,
+        metadata: Optional[Dict[str, Any]] = None,
+    ) -> "ChunkerIterator":
+        """
+        Chunk a file by slicing it at the boundaries cached by `precompute_boundaries`,
+        without chunking it again.
+
+        Chunks carry the offsets and indices they were cached with, and their text is read
+        straight from the file, so prefixes and suffixes of this builder are not applied.
+
+        Args:
+            path: The path of the file.
+            params: The description of the chunking parameters the cache was made with.
+            cache_path: The path of the cache file.
+            metadata: Key/values attached to every chunk when metadata output is enabled.
+
+        Returns:
+            ChunkerIterator: An iterator over the chunks.
+
+        Raises:
+            ValueError: If the cache was made with other params, or the file changed since.
+            IOError: If reading the file or the cache failed.
+        """
+        ...
+
+    def on_files_concatenated(
+        self, paths: List[str], metadata: Optional[Dict[str, Any]] = None
+    ) -> "ChunkerIterator":
+        """
+        Chunk several files as one source, their contents concatenated in order.
+
+        Offsets (`start` and `end`) run across the concatenation. With metadata output, each
+        chunk also reports `files`: the part of every file it spans, so a chunk straddling two
+        files lists both parts.
+
+        Args:
+            paths: The paths of the files, in order.
+            metadata: Key/values attached to every chunk when metadata output is enabled.
 
         Returns:
             ChunkerIterator: An iterator over the chunks.
 
         Raises:
-            ValueError: If the file cannot be read (e.g., does not exist).
+            IOError: If a file cannot be opened, or reading one failed midway.
         """
         ...
 
-    def on_http(self, url: str) -> "ChunkerIterator":
+    def on_http(
+        self, url: str, metadata: Optional[Dict[str, Any]] = None
+    ) -> "ChunkerIterator":
         """
         Chunk content from an HTTP/HTTPS URL.
 
         Args:
             url: The URL to fetch content from (e.g., "http://example.com/text").
+            metadata: Key/values attached to every chunk when metadata output is enabled.
 
         Returns:
             ChunkerIterator: An iterator over the chunks.
@@ -89,7 +1276,9 @@ class ChunkerBuilder:
         """
         ...
 
-    def on_sources(self, source_strings: List[str]) -> "ChunkerIterator":
+    def on_sources(
+        self, source_strings: List[str], *, follow_symlinks: bool = False
+    ) -> "ChunkerIterator":
         """
         Chunk multiple sources specified as strings with prefixes.
 
@@ -102,6 +1291,8 @@ class ChunkerBuilder:
 
         Args:
             source_strings: A list of source strings with optional prefixes.
+            follow_symlinks: Chunk the files behind symlinks matched by a glob, each file
+                once. Otherwise symlinks and what lies under symlinked directories are skipped.
 
         Returns:
             ChunkerIterator: An iterator over the chunks from all sources.
@@ -112,8 +1303,61 @@ class ChunkerBuilder:
         """
         ...
 
+    def first(self, source: str) -> Optional[str]:
+        """
+        Get only the first chunk of a source, e.g. to preview a document.
+
+        A file or URL is only read as far as needed to cut the first chunk. The source string
+        takes the same prefixes as `on_sources`; a glob yields the first chunk of its first file.
+
+        Args:
+            source: A source string with an optional prefix.
+
+        Returns:
+            The first chunk, or None if the source produces no chunks.
+
+        Raises:
+            ValueError: If the source has an invalid prefix or cannot be opened.
+            IOError: If reading the source failed before the first chunk.
+        """
+        ...
+
+    def round_robin(
+        self, source_strings: List[str], *, follow_symlinks: bool = False
+    ) -> "ChunkerIterator":
+        """
+        Chunk multiple sources in turn, one chunk from each source at a time.
+
+        Sources are advanced round-robin, skipping exhausted ones, until all are done. Chunks
+        are streamed: a source is only read as its chunks are pulled.
+
+        Supported prefixes:
+        - `file://` for local files (e.g., "file://path/to/file.txt").
+        - `http://` or `https://` for URLs (e.g., "http://example.com/text").
+        - `text://` for raw text (e.g., "text://Hello world").
+        - `glob://` for glob patterns (e.g., "glob://*.txt").
+        - No prefix assumes a text string.
+
+        Args:
+            source_strings: A list of source strings with optional prefixes.
+            follow_symlinks: Chunk the files behind symlinks matched by a glob, each file
+                once. Otherwise symlinks and what lies under symlinked directories are skipped.
+
+        Returns:
+            ChunkerIterator: An iterator over the interleaved chunks of all sources.
+
+        Raises:
+            ValueError: If any source has an invalid prefix, uses an unsupported type (e.g., "sitemap://"),
+                        or cannot be processed (e.g., file not found, invalid glob).
+        """
+        ...
+
     def on_sources_par(
-        self, source_strings: List[str], channel_size: Optional[int] = None
+        self,
+        source_strings: List[str],
+        channel_size: Optional[int] = None,
+        *,
+        follow_symlinks: bool = False,
     ) -> "ChunkerIterator":
         """
         Chunk multiple sources in parallel, specified as strings with prefixes.
@@ -128,6 +1372,8 @@ class ChunkerBuilder:
         Args:
             source_strings: A list of source strings with optional prefixes.
             channel_size: Number of chunks to buffer in the channel (default: 100).
+            follow_symlinks: Chunk the files behind symlinks matched by a glob, each file
+                once. Otherwise symlinks and what lies under symlinked directories are skipped.
 
         Returns:
             ChunkerIterator: An iterator over the chunks from all sources.
@@ -138,18 +1384,220 @@ class ChunkerBuilder:
         """
         ...
 
+class StopSignal:
+    """Tells `on_file(..., follow=True)` to stop waiting for more data once it reaches the end
+    of the file, e.g. from the thread writing it."""
+
+    def __init__(self) -> None: ...
+    def stop(self) -> None:
+        """Stop following: the data written so far is still chunked, then iteration ends."""
+        ...
+
+    @property
+    def is_stopped(self) -> bool:
+        """Whether `stop` was called."""
+        ...
+
+class Tokenizer:
+    """A HuggingFace tokenizer loaded once, to share between `by_tokens` chunkers.
+
+    Chunkers only read it while encoding, so builders sharing it can chunk sources on
+    different threads at once."""
+
+    @staticmethod
+    def from_file(path: str) -> "Tokenizer":
+        """
+        Load a tokenizer from a `tokenizer.json` file, as saved by the `tokenizers` library.
+
+        Raises:
+            ValueError: If the tokenizer cannot be loaded.
+        """
+        ...
+
+class ChunkSequence:
+    """The chunks of a string, pulled as they are first indexed and kept, so that they can be
+    indexed, counted and iterated over any number of times."""
+
+    def __len__(self) -> int:
+        """Number of chunks, cutting them all.
+
+        Raises:
+            Exception: Whatever a boundary callback raised.
+        """
+        ...
+
+    def __getitem__(self, index: int) -> Union[str, Chunk, Tuple[int, Union[str, Chunk]]]:
+        """Get a chunk by index, negative indices counting from the end.
+
+        Raises:
+            IndexError: If there is no chunk at the index.
+            Exception: Whatever a boundary callback raised.
+        """
+        ...
+
+    def __iter__(self) -> Iterator[Union[str, Chunk, Tuple[int, Union[str, Chunk]]]]:
+        """Return an iterator over the chunks, from the first one."""
+        ...
+
 class ChunkerIterator:
     """An iterator over chunks produced from one or more sources."""
 
-    def all(self) -> List[str]:
+    def all(self) -> List[Union[str, Chunk, Tuple[int, Union[str, Chunk]]]]:
         """Collect all chunks into a list.
 
         Returns:
             A list of all chunks.
+
+        Raises:
+            IOError: If reading a source failed midway.
+            Exception: Whatever a boundary callback raised.
+        """
+        ...
+
+    def enumerated(self) -> "ChunkerIterator":
+        """Yield `(position, chunk)` tuples from now on, `position` counting every chunk of
+        this iterator from 0 (including those already consumed).
+
+        Returns:
+            The iterator itself.
+        """
+        ...
+
+    def unique(self) -> "ChunkerIterator":
+        """Yield only the chunks whose text was not yielded before from now on, in order.
+
+        Texts are remembered by a 64-bit hash, so memory grows by 8 bytes per distinct chunk
+        rather than by its size. Chunks are compared across all the sources of the iterator.
+
+        Returns:
+            The iterator itself.
+        """
+        ...
+
+    def to_jsonl_sharded(self, dir: str, shard_size: int) -> List[str]:
+        """Write the remaining chunks as JSON lines into rolling files `shard_0000.jsonl`,
+        `shard_0001.jsonl`, ... of a directory, without going through Python objects.
+
+        Each line holds a chunk's `text`, `start`, `end` and `index` (plus `lines` when line
+        numbers are enabled); source metadata is not written.
+
+        Args:
+            dir: Output directory, created if missing.
+            shard_size: Maximum number of chunks per file; the last file holds the rest.
+
+        Returns:
+            The paths written, in order (empty when there are no chunks).
+
+        Raises:
+            ValueError: If shard_size is 0.
+            IOError: If reading a source or writing a file failed.
+        """
+        ...
+
+    def to_jsonl(
+        self, path: str, store_mode: Literal["full", "core", "both"] = "full"
+    ) -> int:
+        """Write the remaining chunks as JSON lines to a file, without going through Python
+        objects, storing the overlap of each chunk as `store_mode` says, e.g. to keep it out
+        of a vector index.
+
+        Lines are those of `to_jsonl_sharded()`. With "core", `text` is only the part of the
+        chunk not covered by the previous chunk of its source, and `start` where that part
+        starts, so the texts of the main chunks of a source concatenate to it; bridge chunks
+        are stored whole. "both" adds the covered text in an `overlap` field, the chunk being
+        `overlap` followed by `text`. The overlap assumes no prefix, preamble or suffix.
+
+        Args:
+            path: Path of the file, overwritten if it exists.
+            store_mode: "full" (the whole chunk), "core" or "both".
+
+        Returns:
+            The number of chunks written.
+
+        Raises:
+            ValueError: If the store mode is unknown.
+            IOError: If reading a source or writing the file failed.
+        """
+        ...
+
+    def to_jsonl_gz(self, path: str, level: int = 6) -> int:
+        """Write the remaining chunks as JSON lines to a gzip file, compressed as they are
+        written, without going through Python objects.
+
+        Lines are those of `to_jsonl_sharded()`; source metadata is not written.
+
+        Args:
+            path: Path of the file, e.g. `chunks.jsonl.gz`, overwritten if it exists.
+            level: Compression level, from 0 (none) to 9 (smallest output).
+
+        Returns:
+            The number of chunks written.
+
+        Raises:
+            ValueError: If level is more than 9.
+            IOError: If reading a source or writing the file failed.
+        """
+        ...
+
+    def to_parquet(self, path: str, row_group_size: int = 65536) -> int:
+        """Write the remaining chunks to a Parquet file, with columns `text` (string), `start`,
+        `end` and `index` (uint64) and `source`, without going through Python objects.
+
+        `source` holds the path or URL chunked by `on_file()` or `on_http()`, and is null for
+        text and for several sources. Chunks are cut one row group at a time, so the whole
+        table is never held in memory. Source metadata is not written.
+
+        Args:
+            path: Path of the Parquet file, overwritten if it exists.
+            row_group_size: Maximum number of chunks per row group.
+
+        Returns:
+            The number of chunks written.
+
+        Raises:
+            ValueError: If row_group_size is 0.
+            IOError: If reading a source or writing the file failed.
+        """
+        ...
+
+    def to_arrow(self, batch_size: int = 65536) -> "ArrowChunks":
+        """Gather the remaining chunks into Arrow record batches, with columns `text`, `start`,
+        `end` and `index`, without going through Python objects.
+
+        The result implements the Arrow PyCapsule stream interface, so it can be read by
+        `pyarrow.table()`, `pyarrow.RecordBatchReader.from_stream()` or `polars.DataFrame()`.
+        Chunks are cut as the batches are read. Source metadata is not included.
+
+        Args:
+            batch_size: Maximum number of chunks per record batch.
+
+        Returns:
+            The record batches, to be read once.
+
+        Raises:
+            ValueError: If batch_size is 0.
         """
         ...
 
-    def __iter__(self) -> Iterator[str]:
+    def skipped(self) -> List[str]:
+        """Paths matched by a glob source but not chunked: directories, FIFOs, devices and
+        sockets, unreadable paths, symlinks that are broken or not followed, and files
+        already reached through another symlink.
+
+        Returns:
+            The skipped paths, in the order the globs matched them.
+        """
+        ...
+
+    def close(self) -> None:
+        """Stop the iteration and release what it holds (open files, connections, worker
+        threads) right away instead of when the iterator is garbage collected.
+
+        Further iteration yields no chunks; closing twice is a no-op.
+        """
+        ...
+
+    def __iter__(self) -> Iterator[Union[str, Chunk, Tuple[int, Union[str, Chunk]]]]:
         """Return an iterator over the chunks.
 
         Returns:
@@ -157,7 +1605,7 @@ class ChunkerIterator:
         """
         ...
 
-    def __next__(self) -> str:
+    def __next__(self) -> Union[str, Chunk, Tuple[int, Union[str, Chunk]]]:
         """Get the next chunk.
 
         Returns:
@@ -165,7 +1613,217 @@ class ChunkerIterator:
 
         Raises:
             StopIteration: If no more chunks are available.
+            IOError: If reading a source failed midway, once the chunks read before the failure
+                have been yielded.
+            Exception: Whatever a boundary callback raised.
         """
         ...
 
-__all__ = ["Chunker", "ChunkerBuilder", "ChunkerIterator"]
+def effective_step(
+    text_sample: str, chunk_size: int, overlap: int, strategy: str = "bytes"
+) -> Optional[int]:
+    """
+    Report the step a strategy actually takes between consecutive chunks of a sample.
+
+    Snapping to char boundaries can make the step differ from `chunk_size - overlap`, so
+    chunking a representative sample before a big run shows the step to expect.
+
+    Args:
+        text_sample: Representative text to chunk.
+        chunk_size: The size of each chunk.
+        overlap: The overlap between chunks (must be less than chunk_size).
+        strategy: "bytes" or "characters" (also "chars").
+
+    Returns:
+        The smallest step between the start offsets of consecutive chunks, in bytes or
+        characters, or None if the sample makes fewer than two chunks.
+
+    Raises:
+        ValueError: If the arguments are invalid or the strategy is unknown.
+    """
+    ...
+
+def suggest_chunk_size(source_len: int, target_chunks: int, overlap: int) -> int:
+    """
+    Suggest the chunk size that cuts a source into about `target_chunks` chunks.
+
+    Inverts the chunk count of the bytes and characters strategies, accounting for the
+    overlap. The size is rounded up, so ASCII text never makes more than `target_chunks`
+    chunks; snapping to char boundaries can add one on other text.
+
+    Args:
+        source_len: Length of the source, in bytes or characters.
+        target_chunks: The number of chunks wanted.
+        overlap: The overlap the chunks will have, in the same unit.
+
+    Returns:
+        The chunk size to chunk with, always greater than the overlap.
+
+    Raises:
+        ValueError: If `target_chunks` is 0.
+    """
+    ...
+
+def source_stats(path: str) -> Dict[str, int]:
+    """
+    Count the bytes, characters and lines of a file in one streaming pass, e.g. to gauge from
+    `chars / bytes` how much multi-byte text it holds before choosing a strategy.
+
+    Characters are counted as the bytes starting a UTF-8 sequence, without validating the
+    file. Lines are those ending with a newline, plus a last line without one.
+
+    Args:
+        path: The path of the file.
+
+    Returns:
+        `bytes`, `chars` and `lines`.
+
+    Raises:
+        IOError: If reading the file failed.
+    """
+    ...
+
+def content_hash(text: str) -> int:
+    """
+    Hash a text the way `running_hash` hashes chunks.
+
+    Args:
+        text: The text to hash.
+
+    Returns:
+        The 64-bit FNV-1a hash of the UTF-8 bytes of `text`, stable across runs and
+        platforms. The running hash of chunks is the hash of their concatenation.
+    """
+    ...
+
+def diff_boundaries(
+    boundaries_a: List[Tuple[int, int]], boundaries_b: List[Tuple[int, int]]
+) -> Dict[str, Any]:
+    """
+    Compare two chunkings, e.g. before and after tuning chunk_size or overlap, to tell which
+    cached chunks stay valid.
+
+    The boundaries of a chunking are the starts and ends of its chunks. Between two offsets
+    that both chunkings cut at, the changed boundaries of each are paired in order as shifted
+    ones, the side with more of them leaving out those farther from their counterpart, which
+    are added or removed.
+
+    Args:
+        boundaries_a: `(start, end)` of every chunk of the first chunking.
+        boundaries_b: `(start, end)` of every chunk of the second one.
+
+    Returns:
+        `added` and `removed` offsets, `shifted` `(old, new)` offset pairs, `unchanged_chunks`
+        (chunks of the second chunking with the same span as one of the first) and
+        `unchanged_fraction` (their share of the second chunking, 1.0 if empty).
+    """
+    ...
+
+def strategy_info(name: str) -> Dict[str, Any]:
+    """
+    Describe how a strategy sizes and overlaps chunks.
+
+    Args:
+        name: Strategy name, as in the `Chunker.by_*` constructors (e.g. "bytes",
+            "lines_packed").
+
+    Returns:
+        `unit` and `overlap_unit` (e.g. "bytes", "characters", "lines", "seconds"),
+        `overlap_semantics` ("exact", "at_least", "at_most" or "approximate", relating the
+        produced overlap to the configured one), `splits_graphemes` (whether a cut may fall
+        inside a grapheme cluster) and `streaming_supported`.
+
+    Raises:
+        ValueError: If the strategy is unknown.
+    """
+    ...
+
+def verify_lossless(
+    source: str,
+    chunk_size: int,
+    strategy: str = "bytes",
+    source_type: Literal["file", "http", "string"] = "file",
+) -> Dict[str, Any]:
+    """
+    Check that chunking a source without overlap loses nothing, i.e. that its chunks
+    concatenate to the source.
+
+    The chunks are compared as they are cut with a second read of the source, so neither is
+    held whole from a file or URL; a URL is fetched twice.
+
+    Args:
+        source: The path, URL or text to chunk, as told by `source_type`.
+        chunk_size: The size of each chunk.
+        strategy: "bytes" or "characters" (also "chars").
+        source_type: "file", "http" or "string".
+
+    Returns:
+        `lossless`, and unless it is True `offset` (byte offset in the source of the first
+        character that differs), `expected` and `found` (up to 32 bytes of the source and of
+        the concatenated chunks from there).
+
+    Raises:
+        ValueError: If the arguments are invalid or the strategy or source type is unknown.
+        IOError: If the source cannot be read.
+    """
+    ...
+
+def hierarchical(
+    source: str,
+    parent_size: int,
+    child_size: int,
+    overlap: int,
+    strategy: str = "bytes",
+    source_type: Literal["file", "http", "string"] = "file",
+    metadata: Optional[Dict[str, Any]] = None,
+) -> ChunkerIterator:
+    """
+    Chunk a source into parent chunks and each parent into smaller child chunks, reading it
+    once, e.g. to retrieve small chunks and hand their parent to a model.
+
+    Each parent is yielded followed by its children, which are cut from its text. Parents
+    have `level` 0, children `level` 1 and the `parent_index` of their parent, and children
+    are indexed among the children of the source. Offsets of children are within the source.
+
+    Args:
+        source: The path, URL or text to chunk, as told by `source_type`.
+        parent_size: The size of each parent chunk.
+        child_size: The size of each child chunk, less than `parent_size`.
+        overlap: The overlap between consecutive parents, and between consecutive children
+            of a parent.
+        strategy: "bytes" or "characters" (also "chars").
+        source_type: "file", "http" or "string".
+        metadata: Key/values attached to every chunk.
+
+    Returns:
+        ChunkerIterator: An iterator over `Chunk` objects.
+
+    Raises:
+        ValueError: If the arguments are invalid or the strategy or source type is unknown.
+        IOError: If the source cannot be read.
+    """
+    ...
+
+def main() -> None:
+    """
+    Run the `kiru` command line with the arguments of `sys.argv`.
+
+    This is the entry point of the `kiru` console script, e.g.
+    `kiru chunk --strategy characters --chunk-size 1000 --overlap 100 file.txt`.
+
+    Raises:
+        SystemExit: With code 2 for invalid arguments and 1 when chunking fails.
+    """
+    ...
+
+__all__ = [
+    "Chunk",
+    "ChunkTuple",
+    "Chunker",
+    "ChunkerBuilder",
+    "ChunkerIterator",
+    "diff_boundaries",
+    "effective_step",
+    "main",
+    "verify_lossless",
+]
@@ -1,7 +1,26 @@
+import gzip
+import json
+import os
+import threading
+import time
+import warnings
+
 import pytest
 from hypothesis import assume, given, settings
 from hypothesis import strategies as st
-from kiru import Chunker
+from kiru import (
+    Chunker,
+    StopSignal,
+    Tokenizer,
+    content_hash,
+    diff_boundaries,
+    effective_step,
+    hierarchical,
+    source_stats,
+    strategy_info,
+    suggest_chunk_size,
+    verify_lossless,
+)
 from langchain.text_splitter import CharacterTextSplitter
 
 
@@ -166,6 +185,1800 @@ class TestKiruLangChainComparison:
         assert is_identical, error
 
 
+class TestKiruMetadata:
+    """Test suite for chunk metadata output."""
+
+    def test_every_chunk_carries_doc_id(self, tmp_path):
+        """Every chunk of a source carries the metadata passed for it."""
+        text = "Hello 世界! This is a test string. " * 20
+        path = tmp_path / "doc.txt"
+        path.write_text(text, encoding="utf-8")
+        chunker = Chunker.by_bytes(chunk_size=50, overlap=10).with_metadata()
+
+        chunks = chunker.on_file(str(path), metadata={"doc_id": "doc-1"}).all()
+
+        assert len(chunks) > 1
+        assert all(chunk.metadata["doc_id"] == "doc-1" for chunk in chunks)
+        assert [chunk.index for chunk in chunks] == list(range(len(chunks)))
+        encoded = text.encode("utf-8")
+        assert all(
+            encoded[chunk.start : chunk.end].decode("utf-8") == chunk.text
+            for chunk in chunks
+        )
+
+    def test_line_numbers(self, tmp_path):
+        """A chunk's line range matches the lines its text sits on."""
+        text = "".join(f"line {n}: naïve café\n" for n in range(1, 101))
+        path = tmp_path / "log.txt"
+        path.write_text(text, encoding="utf-8")
+        chunker = Chunker.by_bytes(chunk_size=120, overlap=30).with_metadata(
+            line_numbers=True
+        )
+
+        encoded = text.encode("utf-8")
+        for chunk in chunker.on_file(str(path)):
+            before = encoded[: chunk.start].decode("utf-8")
+            assert chunk.start_line == before.count("\n") + 1
+            assert chunk.end_line == chunk.start_line + chunk.text.rstrip("\n").count("\n")
+
+    def test_term_counts(self):
+        """Term counts match a manual count of the lowercased words of the chunk."""
+        text = "The cat saw the dog. The DOG ran; the cat didn't!"
+
+        (chunk,) = Chunker.by_bytes(chunk_size=100, overlap=0).with_metadata(
+            term_counts=True
+        ).on_string(text)
+
+        assert chunk.term_counts == {
+            "the": 4,
+            "cat": 2,
+            "saw": 1,
+            "dog": 2,
+            "ran": 1,
+            "didn": 1,
+            "t": 1,
+        }
+
+    def test_unique_text_round_trip(self):
+        """Joining the unique text of every chunk restores the input."""
+        text = "Première phrase. Deuxième phrase, ünïcödé 🎉! Troisième? " * 20
+        for chunker in [
+            Chunker.by_bytes(chunk_size=64, overlap=16),
+            Chunker.by_bytes(chunk_size=64, overlap=16, overlap_sentences=1),
+            Chunker.by_characters(chunk_size=40, overlap=15),
+        ]:
+            chunks = chunker.with_metadata().on_string(text).all()
+            assert "".join(chunk.unique_text for chunk in chunks) == text
+
+    def test_metadata_ignored_without_metadata_output(self):
+        """Plain strings are produced unless metadata output is enabled."""
+        chunks = Chunker.by_characters(chunk_size=10, overlap=2).on_string(
+            "abcdefghijklmnop", metadata={"doc_id": "doc-1"}
+        )
+
+        assert all(isinstance(chunk, str) for chunk in chunks)
+
+
+class TestKiruPredicate:
+    """Test suite for chunking with a boundary callback."""
+
+    def test_cuts_on_uppercase_letters(self, tmp_path):
+        """Chunks are packed from segments starting at uppercase letters."""
+        text = "OneTwoThreeFourFiveSixSeven"
+        path = tmp_path / "camel.txt"
+        path.write_text(text, encoding="utf-8")
+        chunker = Chunker.by_predicate(
+            lambda prev, next, pos: next.isupper(), chunk_size=8, overlap=0
+        )
+
+        expected = ["OneTwo", "Three", "FourFive", "SixSeven"]
+        assert chunker.on_string(text).all() == expected
+        assert chunker.on_file(str(path)).all() == expected
+
+    def test_callback_exception_is_raised(self):
+        """An exception raised by the callback surfaces from the iterator."""
+
+        def is_boundary(prev, next, pos):
+            raise RuntimeError("boom")
+
+        chunker = Chunker.by_predicate(is_boundary, chunk_size=8, overlap=0)
+
+        with pytest.raises(RuntimeError, match="boom"):
+            chunker.on_string("OneTwoThree").all()
+
+    def test_overlap_segments_repeat_delimited_segments(self):
+        """The overlap is the last segment of the previous chunk, with its delimiter."""
+        chunker = Chunker.by_predicate(
+            lambda prev, next, pos: prev == "|", chunk_size=6, overlap=0, overlap_segments=1
+        )
+
+        assert chunker.on_string("a|b|c|d|e").all() == ["a|b|c|", "c|d|e"]
+
+
+class TestKiruSemantic:
+    """Test suite for growing chunks until a callback breaks them."""
+
+    def test_breaks_after_every_three_sentences(self, tmp_path):
+        """A callback breaking after three sentences makes three-sentence chunks."""
+        text = "One. Two. Three. Four. Five. Six. Seven. "
+        path = tmp_path / "sentences.txt"
+        path.write_text(text, encoding="utf-8")
+        chunker = Chunker.by_semantic(
+            lambda chunk, next: chunk.count(". ") == 3, overlap_unit="sentence"
+        )
+
+        expected = ["One. Two. Three. ", "Four. Five. Six. ", "Seven. "]
+        assert chunker.on_string(text).all() == expected
+        assert chunker.on_file(str(path)).all() == expected
+
+    def test_overlap_repeats_trailing_characters(self):
+        """Character units overlap by whole characters."""
+        chunker = Chunker.by_semantic(
+            lambda chunk, next: len(chunk) == 4, overlap_unit="character", overlap=1
+        )
+
+        assert chunker.on_string("abcdefgh").all() == ["abcd", "defg", "gh"]
+
+    def test_callback_exception_is_raised(self):
+        """An exception raised by the callback surfaces from the iterator."""
+
+        def is_boundary(prev, next, pos):
+            raise RuntimeError("boom")
+
+        chunker = Chunker.by_predicate(is_boundary, chunk_size=8, overlap=0)
+
+        with pytest.raises(RuntimeError, match="boom"):
+            chunker.on_string("OneTwoThree").all()
+
+    def test_overlap_segments_repeat_delimited_segments(self):
+        """The overlap is the last segment of the previous chunk, with its delimiter."""
+        chunker = Chunker.by_predicate(
+            lambda prev, next, pos: prev == "|", chunk_size=6, overlap=0, overlap_segments=1
+        )
+
+        assert chunker.on_string("a|b|c|d|e").all() == ["a|b|c|", "c|d|e"]
+
+
+class TestKiruSemantic:
+    """Test suite for growing chunks until a callback breaks them."""
+
+    def test_breaks_after_every_three_sentences(self, tmp_path):
+        """A callback breaking after three sentences makes three-sentence chunks."""
+        text = "One. Two. Three. Four. Five. Six. Seven. "
+        path = tmp_path / "sentences.txt"
+        path.write_text(text, encoding="utf-8")
+        chunker = Chunker.by_semantic(
+            lambda chunk, next: chunk.count(". ") == 3, overlap_unit="sentence"
+        )
+
+        expected = ["One. Two. Three. ", "Four. Five. Six. ", "Seven. "]
+        assert chunker.on_string(text).all() == expected
+        assert chunker.on_file(str(path)).all() == expected
+
+    def test_overlap_repeats_trailing_characters(self):
+        """Character units overlap by whole characters."""
+        chunker = Chunker.by_semantic(
+            lambda chunk, next: len(chunk) == 4, overlap_unit="character", overlap=1
+        )
+
+        assert chunker.on_string("abcdefgh").all() == ["abcd", "defg", "gh"]
+
+    def test_cjk_sentences_end_without_whitespace(self):
+        """With cjk=True, 。 ends a sentence even when no space follows it."""
+        text = "今日は晴れです。明日は雨でしょう。 はい。"
+        one_sentence = lambda chunk, next: chunk != ""
+
+        assert Chunker.by_semantic(one_sentence, cjk=True).on_string(text).all() == [
+            "今日は晴れです。",
+            "明日は雨でしょう。 ",
+            "はい。",
+        ]
+        assert Chunker.by_semantic(one_sentence).on_string(text).all() == [text]
+
+    def test_custom_terminators(self):
+        """Sentences end on the given terminators only."""
+        chunker = Chunker.by_semantic(
+            lambda chunk, next: chunk != "", terminators={";", "…"}
+        )
+
+        assert chunker.on_string("One; two… three. four").all() == [
+            "One; ",
+            "two… ",
+            "three. four",
+        ]
+        with pytest.raises(ValueError, match="single character"):
+            Chunker.by_semantic(lambda chunk, next: False, terminators={"..."})
+
+    def test_callback_exception_is_raised(self):
+        """An exception raised by the callback surfaces from the iterator."""
+
+        def should_break(chunk, next):
+            raise RuntimeError("boom")
+
+        with pytest.raises(RuntimeError, match="boom"):
+            Chunker.by_semantic(should_break).on_string("One. Two. Three.").all()
+
+
+def open_paths() -> set[str]:
+    """Paths of the files currently open by this process."""
+    fd_dir = "/proc/self/fd"
+    paths = set()
+    for fd in os.listdir(fd_dir):
+        try:
+            paths.add(os.readlink(os.path.join(fd_dir, fd)))
+        except OSError:
+            pass
+    return paths
+
+
+@pytest.mark.skipif(not os.path.isdir("/proc/self/fd"), reason="needs /proc")
+class TestKiruClose:
+    """Test suite for stopping an iteration early."""
+
+    def test_close_frees_file_descriptor(self, tmp_path):
+        """Closing a partially consumed iterator closes its file right away."""
+        path = tmp_path / "big.txt"
+        path.write_text("Some words and more words. " * 50_000, encoding="utf-8")
+        chunks = Chunker.by_bytes(chunk_size=100, overlap=10).on_file(str(path))
+
+        for _ in range(3):
+            next(chunks)
+        assert str(path) in open_paths()
+
+        chunks.close()
+
+        assert str(path) not in open_paths()
+        assert chunks.all() == []
+
+
+class TestKiruJsonArray:
+    """Test suite for chunking the elements of a JSON array."""
+
+    def test_groups_reparse(self, tmp_path):
+        """Groups of elements re-parse to the elements of the array, nested ones included."""
+        elements = [{"id": n, "tags": ["a", "b"], "text": f"item, [{n}]"} for n in range(10)]
+        path = tmp_path / "records.json"
+        path.write_text(json.dumps(elements, indent=2), encoding="utf-8")
+        chunker = Chunker.by_json_array(chunk_size=3, overlap=0)
+
+        groups = [json.loads(chunk) for chunk in chunker.on_file(str(path))]
+
+        assert groups == [elements[0:3], elements[3:6], elements[6:9], elements[9:10]]
+
+    def test_overlap_must_be_less_than_chunk_size(self):
+        """An overlap of the whole chunk is rejected."""
+        with pytest.raises(ValueError):
+            Chunker.by_json_array(chunk_size=3, overlap=3)
+
+
+class TestKiruLines:
+    """Test suite for chunking by lines."""
+
+    def test_lines_keep_their_terminators(self, tmp_path):
+        """Chunks concatenate to the file, a last line without newline included."""
+        text = "".join(f"{n},é\r\n" for n in range(5000)) + "last"
+        path = tmp_path / "rows.csv"
+        path.write_bytes(text.encode("utf-8"))
+        chunker = Chunker.by_lines(chunk_size=100, overlap=0)
+
+        chunks = chunker.on_file(str(path)).all()
+
+        assert "".join(chunks) == text
+        assert chunks == chunker.on_string(text).all()
+        assert chunks[-1] == "last"
+
+    def test_crlf_line_ending_keeps_lone_newlines(self):
+        """With "\\r\\n" lines, a lone "\\n" stays inside its line."""
+        text = 'a,"x\ny"\r\nb,z\r\nc,w'
+        chunker = Chunker.by_lines(chunk_size=2, overlap=1, line_ending="\r\n")
+
+        assert chunker.on_string(text).all() == ['a,"x\ny"\r\nb,z\r\n', "b,z\r\nc,w"]
+
+    def test_collapsed_separators_leave_no_empty_lines(self):
+        """Runs of newlines end a single line when collapsing separators."""
+        text = "a\n\n\n\nb"
+
+        kept = Chunker.by_lines(chunk_size=1, overlap=0).on_string(text).all()
+        collapsed = (
+            Chunker.by_lines(chunk_size=1, overlap=0, collapse_separators=True)
+            .with_metadata()
+            .on_string(text)
+            .all()
+        )
+
+        assert kept == ["a\n", "\n", "\n", "\n", "b"]
+        assert [chunk.text for chunk in collapsed] == ["a\n", "b"]
+        assert (collapsed[1].start, collapsed[1].end) == (2, 3)
+
+    def test_unknown_line_ending_is_rejected(self):
+        """Line endings other than "\\n" and "\\r\\n" are rejected."""
+        with pytest.raises(ValueError):
+            Chunker.by_lines(chunk_size=2, overlap=0, line_ending="\r")
+
+
+class TestKiruApproxTokens:
+    """Test suite for chunking by an estimated token budget."""
+
+    def test_chunk_lengths_are_near_the_budget(self):
+        """Chunks hold about target_tokens * chars_per_token characters, ending on words."""
+        text = "The quick brown fox jumps over the lazy dog, then naps in the sun. " * 100
+
+        chunks = Chunker.by_approx_tokens(100, 10, chars_per_token=4.0).on_string(text).all()
+
+        assert len(chunks) > 2
+        for chunk in chunks[:-1]:
+            assert 400 - 16 <= len(chunk) <= 400
+            assert chunk.endswith(" ")
+
+    def test_invalid_ratio_raises(self):
+        """A ratio that is not a positive number is rejected."""
+        with pytest.raises(ValueError, match="characters per token"):
+            Chunker.by_approx_tokens(100, 10, chars_per_token=0.0)
+        with pytest.raises(ValueError):
+            Chunker.by_approx_tokens(10, 10)
+
+
+class TestKiruGraphemes:
+    """Test suite for chunking by extended grapheme clusters."""
+
+    family = "\U0001f468\u200d\U0001f469\u200d\U0001f467\u200d\U0001f466"
+    flag = "\U0001f1e8\U0001f1ed"
+
+    def test_clusters_are_never_cut(self):
+        """A ZWJ emoji, a flag and a combining accent each count as one."""
+        text = f"{self.family}{self.flag}e\u0301{self.flag}"
+
+        chunks = Chunker.by_graphemes(chunk_size=2, overlap=1).on_string(text).all()
+
+        assert chunks == [
+            f"{self.family}{self.flag}",
+            f"{self.flag}e\u0301",
+            f"e\u0301{self.flag}",
+        ]
+
+    def test_file_matches_string(self, tmp_path):
+        """A file spanning several blocks is chunked like the same string."""
+        text = "".join([self.family, self.flag, "e\u0301", "x "][n % 4] for n in range(20_000))
+        path = tmp_path / "graphemes.txt"
+        path.write_text(text, encoding="utf-8")
+        chunker = Chunker.by_graphemes(chunk_size=300, overlap=30)
+
+        assert chunker.on_file(str(path)).all() == chunker.on_string(text).all()
+
+    def test_overlap_must_be_less_than_chunk_size(self):
+        """An overlap of all the clusters of a chunk is rejected."""
+        with pytest.raises(ValueError):
+            Chunker.by_graphemes(chunk_size=3, overlap=3)
+
+
+class TestKiruWords:
+    """Test suite for chunking by whitespace-delimited words."""
+
+    def test_chunks_keep_whitespace_and_trailing_chunk(self):
+        """Chunks keep their original whitespace, and the last one holds the rest."""
+        text = "one  two\tthree\u3000four five"
+
+        chunks = Chunker.by_words(chunk_size=2, overlap=0).on_string(text).all()
+
+        assert chunks == ["one  two\t", "three\u3000four ", "five"]
+        assert "".join(chunks) == text
+
+    def test_file_matches_string(self, tmp_path):
+        """A file spanning several blocks is chunked like the same string."""
+        text = "".join(f"wörd{n}{' ' if n % 3 else chr(10)}" for n in range(50_000))
+        path = tmp_path / "words.txt"
+        path.write_text(text, encoding="utf-8")
+        chunker = Chunker.by_words(chunk_size=64, overlap=8)
+
+        assert chunker.on_file(str(path)).all() == chunker.on_string(text).all()
+
+    def test_overlap_must_be_less_than_chunk_size(self):
+        """An overlap of all the words of a chunk is rejected."""
+        with pytest.raises(ValueError):
+            Chunker.by_words(chunk_size=3, overlap=3)
+
+
+class TestKiruParagraphs:
+    """Test suite for chunking by blank-line separated paragraphs."""
+
+    def test_separators_are_kept_and_outer_blank_lines_left_out(self):
+        """Separators stay inside chunks, blank lines around the document do not."""
+        text = "\n\nfirst\nline\n\n\n\nsecond\r\n\r\nthird\n\n"
+
+        chunks = Chunker.by_paragraphs(chunk_size=2, overlap=0).on_string(text).all()
+
+        assert chunks == ["first\nline\n\n\n\nsecond\r\n\r\n", "third\n"]
+
+    def test_file_matches_string(self, tmp_path):
+        """A file spanning several blocks is chunked like the same string."""
+        separators = ["\r\n\r\n", "\n\n", "\n\n\n\n"]
+        text = "".join(f"pärägraph {n}" + separators[n % 3] for n in range(20_000))
+        path = tmp_path / "paragraphs.txt"
+        path.write_bytes(text.encode("utf-8"))
+        chunker = Chunker.by_paragraphs(chunk_size=20, overlap=2)
+
+        assert chunker.on_file(str(path)).all() == chunker.on_string(text).all()
+
+    def test_collapsed_separators_are_a_single_blank_line(self):
+        """Runs of blank lines are collapsed into their first one."""
+        chunker = Chunker.by_paragraphs(chunk_size=1, overlap=0, collapse_separators=True)
+
+        chunks = chunker.on_string("one\n\n\n\ntwo\n \n\nthree").all()
+
+        assert chunks == ["one\n\n", "two\n \n", "three"]
+
+    def test_overlap_must_be_less_than_chunk_size(self):
+        """An overlap of all the paragraphs of a chunk is rejected."""
+        with pytest.raises(ValueError):
+            Chunker.by_paragraphs(chunk_size=3, overlap=3)
+
+
+class TestKiruSentences:
+    """Test suite for chunking by sentences."""
+
+    def test_sentences_keep_their_whitespace(self):
+        """Chunks hold chunk_size sentences and concatenate to the text."""
+        text = "One. Two! Three? Four."
+
+        chunks = Chunker.by_sentences(chunk_size=2, overlap=0).on_string(text).all()
+
+        assert chunks == ["One. Two! ", "Three? Four."]
+
+    def test_max_bytes_hard_splits_a_huge_sentence(self, tmp_path):
+        """A sentence longer than max_bytes is cut instead of emitted whole."""
+        text = "var a=1;" * (128 * 1024) + ". The end. "
+        path = tmp_path / "minified.txt"
+        path.write_text(text, encoding="utf-8")
+        chunker = Chunker.by_sentences(chunk_size=10, overlap=0, max_bytes=8192)
+
+        chunks = chunker.on_file(str(path)).all()
+
+        assert all(len(chunk.encode()) <= 8192 for chunk in chunks)
+        assert "".join(chunks) == text
+        assert chunker.stats()["hard_splits"] == 128
+
+    def test_cjk_sentences_end_without_whitespace(self):
+        """With cjk=True, 。 ends a sentence even when no space follows it."""
+        text = "今日は晴れです。明日は雨でしょう。 はい。"
+
+        assert Chunker.by_sentences(1, 0, cjk=True).on_string(text).all() == [
+            "今日は晴れです。",
+            "明日は雨でしょう。 ",
+            "はい。",
+        ]
+        assert Chunker.by_sentences(1, 0).on_string(text).all() == [text]
+
+    def test_custom_terminators(self):
+        """Sentences end on the given terminators only."""
+        chunker = Chunker.by_sentences(1, 0, terminators={";", "…"})
+
+        assert chunker.on_string("One; two… three. four").all() == [
+            "One; ",
+            "two… ",
+            "three. four",
+        ]
+        with pytest.raises(ValueError, match="single character"):
+            Chunker.by_sentences(1, 0, terminators={"..."})
+
+    def test_overlap_must_be_less_than_chunk_size(self):
+        """An overlap of all the sentences of a chunk is rejected."""
+        with pytest.raises(ValueError):
+            Chunker.by_sentences(chunk_size=3, overlap=3)
+
+
+class TestKiruRecursive:
+    """Test suite for the recursive separators strategy."""
+
+    def test_splits_are_packed_and_fall_back_to_finer_separators(self):
+        """Short paragraphs are packed, long ones are cut on lines and words."""
+        text = "one\n\ntwo\n\na longer line here\nand words words words"
+
+        chunks = Chunker.by_recursive(chunk_size=16, overlap=0).on_string(text).all()
+
+        assert chunks == ["one\n\ntwo\n\n", "a longer line ", "here\n", "and words words ", "words"]
+        assert "".join(chunks) == text
+        assert all(len(chunk.encode("utf-8")) <= 16 for chunk in chunks)
+
+    def test_custom_separators_and_overlap(self):
+        """The separators are tried in the given order, and the overlap holds whole splits."""
+        chunker = Chunker.by_recursive(9, 4, separators=[", "])
+
+        assert chunker.on_string("aa, bb, cc, dd").all() == ["aa, bb, ", "bb, cc, ", "cc, dd"]
+
+    def test_overlap_must_be_less_than_chunk_size(self):
+        """An overlap of the whole chunk size is rejected."""
+        with pytest.raises(ValueError):
+            Chunker.by_recursive(chunk_size=10, overlap=10)
+
+
+def word_level_tokenizer(path, vocab, unk=True):
+    """Save a tokenizer making a token of each word and each run of punctuation to path."""
+    ids = {"[UNK]": 0} if unk else {}
+    ids.update({word: len(ids) + i for i, word in enumerate(vocab)})
+    config = {
+        "version": "1.0",
+        "truncation": None,
+        "padding": None,
+        "added_tokens": [],
+        "normalizer": None,
+        "pre_tokenizer": {"type": "Whitespace"},
+        "post_processor": None,
+        "decoder": None,
+        "model": {"type": "WordLevel", "vocab": ids, "unk_token": "[UNK]"},
+    }
+    path.write_text(json.dumps(config), encoding="utf-8")
+    return str(path)
+
+
+class TestKiruTokens:
+    """Test suite for chunks measured in tokens of a HuggingFace tokenizer."""
+
+    def test_chunks_hold_at_most_chunk_size_tokens(self, tmp_path):
+        """Chunks are the text of their tokens, and the last one holds the tokens left."""
+        tokenizer = word_level_tokenizer(tmp_path / "tokenizer.json", [])
+        chunker = Chunker.by_tokens(tokenizer, 3, 1)
+
+        chunks = chunker.on_string("one two three four five six seven eight").all()
+
+        assert chunks == ["one two three", "three four five", "five six seven", "seven eight"]
+
+    def test_file_chunks_match_string_chunks(self, tmp_path):
+        """Files are chunked like strings."""
+        tokenizer = word_level_tokenizer(tmp_path / "tokenizer.json", ["plain"])
+        text = "Plain words, and naïve ones too.\n" * 5_000
+        path = tmp_path / "words.txt"
+        path.write_text(text, encoding="utf-8")
+        chunker = Chunker.by_tokens(tokenizer, 100, 10)
+
+        chunks = chunker.on_file(str(path)).all()
+
+        assert len(chunks) > 100
+        assert chunks == chunker.on_string(text).all()
+
+    def test_builders_share_a_tokenizer(self, tmp_path):
+        """Two builders of one shared tokenizer chunk like a builder loading its own."""
+        path = word_level_tokenizer(tmp_path / "tokenizer.json", ["hello"])
+        tokenizer = Tokenizer.from_file(path)
+        text = "hello there, world. " * 1_000
+
+        first = Chunker.by_tokens(tokenizer, 50, 10).on_string(text).all()
+        second = Chunker.by_tokens(tokenizer, 50, 10).on_string(text).all()
+
+        assert len(first) > 10
+        assert first == second
+        assert first == Chunker.by_tokens(path, 50, 10).on_string(text).all()
+
+    def test_invalid_tokenizer_is_rejected(self, tmp_path):
+        """A file that is not a tokenizer raises ValueError."""
+        path = tmp_path / "tokenizer.json"
+        path.write_text("not a tokenizer", encoding="utf-8")
+
+        with pytest.raises(ValueError, match="tokenizer"):
+            Chunker.by_tokens(str(path), 10, 0)
+        with pytest.raises(ValueError, match="tokenizer"):
+            Tokenizer.from_file(str(path))
+
+    def test_encoding_errors_raise_value_error(self, tmp_path):
+        """A source the tokenizer fails to encode raises ValueError from the iterator."""
+        tokenizer = word_level_tokenizer(tmp_path / "tokenizer.json", ["known"], unk=False)
+        chunker = Chunker.by_tokens(tokenizer, 4, 0)
+
+        assert chunker.on_string("known known").all() == ["known known"]
+        with pytest.raises(ValueError, match="tokenizer"):
+            chunker.on_string("known unknown").all()
+
+
+class TestKiruRepeatHeader:
+    """Test suite for repeating the header line of headered data in every chunk."""
+
+    def test_header_appears_once_per_chunk(self, tmp_path):
+        """Every chunk starts with the header, which the first one does not hold twice."""
+        header = "id,name\n"
+        text = header + "".join(f"{n},näme {n}\n" for n in range(10_000))
+        path = tmp_path / "records.csv"
+        path.write_bytes(text.encode("utf-8"))
+        chunker = Chunker.by_lines(chunk_size=100, overlap=0, repeat_header=True)
+
+        chunks = chunker.on_file(str(path)).all()
+
+        assert len(chunks) == 100
+        assert chunks == chunker.on_string(text).all()
+        for chunk in chunks:
+            assert chunk.startswith(header)
+            assert chunk.count(header) == 1
+            assert chunk.count("\n") == 101
+
+    def test_regex_pieces_repeat_the_first_piece(self):
+        """The first piece and its separator head every chunk."""
+        chunker = Chunker.by_regex(";", chunk_size=2, overlap=0, repeat_header=True)
+
+        assert chunker.on_string("h;a;b;c").all() == ["h;a;b;", "h;c"]
+
+    def test_unique_text_leaves_out_the_header(self):
+        """Joining the unique text restores the source, with a non-ASCII header and overlap."""
+        text = "héé\n1\n2\n3\n4\n"
+        chunker = Chunker.by_lines(chunk_size=2, overlap=1, repeat_header=True)
+
+        chunks = chunker.with_metadata().on_string(text).all()
+
+        assert chunks[1].text == "héé\n2\n3\n"
+        assert "".join(chunk.unique_text for chunk in chunks) == text
+
+    def test_unique_text_leaves_out_affixes_and_the_header(self):
+        """Neither a prefix, a preamble, a suffix nor the header is unique text."""
+        text = "héé\n1\n2\n3\n4\n"
+        chunker = (
+            Chunker.by_lines(chunk_size=2, overlap=1, repeat_header=True)
+            .with_prefix("P:")
+            .with_preamble("[doc]\n")
+            .with_suffix("!!")
+        )
+
+        chunks = chunker.with_metadata().on_string(text).all()
+
+        assert chunks[1].text == "P:[doc]\nhéé\n2\n3\n!!"
+        assert chunks[1].unique_text == "3\n"
+        assert "".join(chunk.unique_text for chunk in chunks) == text
+
+
+class TestKiruRegex:
+    """Test suite for chunking on the matches of a regex."""
+
+    def test_pieces_keep_their_separators(self):
+        """By default a piece keeps the separator after it."""
+        text = "one\n---\ntwo\n---\nthree"
+
+        chunks = Chunker.by_regex(r"\n---\n", chunk_size=2, overlap=0).on_string(text).all()
+
+        assert chunks == ["one\n---\ntwo\n---\n", "three"]
+
+    def test_separators_can_be_dropped(self):
+        """Without keep_separator, chunks leave out the separators around them."""
+        chunker = Chunker.by_regex(";+", chunk_size=1, overlap=0, keep_separator=False)
+
+        assert chunker.on_string("a;;b;c;").all() == ["a", "b", "c"]
+
+    def test_file_matches_string(self, tmp_path):
+        """A file spanning several blocks is chunked like the same string."""
+        text = "".join(f"récord {n}\n" + "=" * (n % 7 + 1) + "\n" for n in range(20_000))
+        path = tmp_path / "records.txt"
+        path.write_bytes(text.encode("utf-8"))
+        chunker = Chunker.by_regex(r"\n=+\n", chunk_size=20, overlap=2)
+
+        assert chunker.on_file(str(path)).all() == chunker.on_string(text).all()
+
+    def test_invalid_pattern_is_rejected(self):
+        """A pattern that does not compile raises a ValueError."""
+        with pytest.raises(ValueError):
+            Chunker.by_regex("(unclosed", chunk_size=3, overlap=0)
+
+
+class TestKiruTranscript:
+    """Test suite for chunking time-stamped transcript lines."""
+
+    def test_windows_overlap_by_seconds(self):
+        """Chunks hold 8 seconds of lines and share the last 4 with the next one."""
+        text = "intro\n[00:00] a\n[00:04] b\n[00:09] c\nmore c\n[00:12] d\n"
+
+        chunks = Chunker.by_transcript(8, 4).on_string(text).all()
+
+        assert chunks == [
+            "intro\n[00:00] a\n[00:04] b\n",
+            "[00:04] b\n[00:09] c\nmore c\n",
+            "[00:09] c\nmore c\n[00:12] d\n",
+        ]
+
+
+class TestKiruAffixes:
+    """Test suite for prepending and appending text to every chunk."""
+
+    def test_prefix_is_not_sized(self):
+        """Every chunk starts with the prefix, and the content is cut as without it."""
+        text = "The quick brown fox jumps over the lazy dog. " * 20
+        plain = Chunker.by_characters(chunk_size=50, overlap=10)
+
+        chunks = plain.with_prefix("Title: Fox\n").with_metadata().on_string(text).all()
+        expected = plain.on_string(text).all()
+
+        assert [c.text for c in chunks] == ["Title: Fox\n" + e for e in expected]
+        assert "".join(c.unique_text for c in chunks) == text
+
+    def test_preamble_on_chunks_after_the_first(self):
+        """The preamble is prepended to every chunk but the one already holding it."""
+        header = "import os\nimport sys\n\n"
+        text = header + "def f():\n    return 1\n" * 20
+        plain = Chunker.by_bytes(chunk_size=100, overlap=0)
+
+        chunks = plain.with_preamble(header, skip_first=True).with_metadata().on_string(text).all()
+        expected = plain.on_string(text).all()
+
+        assert chunks[0].text == expected[0]
+        assert [c.text for c in chunks[1:]] == [header + e for e in expected[1:]]
+        assert "".join(c.unique_text for c in chunks) == text
+
+
+class TestKiruMergeSmall:
+    """Test suite for merging runs of small chunks."""
+
+    def test_short_lines_are_merged(self):
+        """Consecutive short lines merge up to the maximum, long ones stay alone."""
+        text = "a\nbb\nccc\n" + "x" * 30 + "\nd\ne\n"
+        boundaries = [i + 1 for i, c in enumerate(text) if c == "\n"]
+        builder = Chunker.by_boundaries(boundaries, 0).with_merge_small(10, 20)
+
+        chunks = builder.with_metadata().on_string(text).all()
+
+        assert [c.text for c in chunks] == ["a\nbb\nccc\n", "x" * 30 + "\n", "d\ne\n"]
+        assert [c.index for c in chunks] == [0, 1, 2]
+
+
+class TestKiruAttachTrailingWhitespace:
+    """Test suite for attaching trailing whitespace to the previous chunk."""
+
+    def test_blank_lines_attach_to_last_content_chunk(self):
+        """Blank lines after the content end the last content chunk instead of their own."""
+        text = "First paragraph.\nSecond paragraph.\n\n\n  \n"
+        boundaries = [i + 1 for i, c in enumerate(text) if c == "\n"]
+        builder = Chunker.by_boundaries(boundaries, 0).with_attach_trailing_whitespace()
+
+        chunks = builder.with_metadata().on_string(text).all()
+
+        assert [c.text for c in chunks] == ["First paragraph.\n", "Second paragraph.\n\n\n  \n"]
+        assert [c.index for c in chunks] == [0, 1]
+
+
+class TestKiruNamedTupleOutput:
+    """Test suite for yielding chunks as namedtuples."""
+
+    def test_chunks_unpack_like_tuples(self):
+        """Namedtuple chunks unpack into the text and offsets of the Chunk objects."""
+        text = "The quick brown fox jumps over the lazy dog. " * 20
+        builder = Chunker.by_bytes(chunk_size=100, overlap=10)
+
+        tuples = builder.with_metadata(output="namedtuple").on_string(text).all()
+        chunks = builder.with_metadata().on_string(text).all()
+
+        assert len(tuples) == len(chunks)
+        for chunk, expected in zip(tuples, chunks):
+            text, start, end, index = chunk
+            assert (text, start, end, index) == (expected.text, expected.start, expected.end, expected.index)
+            assert chunk.text == text
+
+    def test_chunks_pickle(self):
+        """Namedtuple chunks survive pickling."""
+        import pickle
+
+        chunk = Chunker.by_bytes(10, 0).with_metadata(output="namedtuple").on_string("abcdefghijkl").all()[1]
+
+        assert pickle.loads(pickle.dumps(chunk)) == ("kl", 10, 12, 1)
+
+    def test_invalid_output_raises(self):
+        """An unknown output raises ValueError."""
+        with pytest.raises(ValueError):
+            Chunker.by_bytes(10, 0).with_metadata(output="dict")
+
+
+class TestKiruArrow:
+    """Test suite for the Arrow output."""
+
+    def test_text_column_equals_all(self):
+        """Reading the batches back gives the chunks of `.all()`, in batches of batch_size."""
+        pa = pytest.importorskip("pyarrow")
+        text = "The quick brown fox jumps over the lazy dog. " * 50
+        builder = Chunker.by_characters(chunk_size=100, overlap=20)
+
+        reader = pa.RecordBatchReader.from_stream(builder.on_string(text).to_arrow(batch_size=10))
+        batches = list(reader)
+
+        assert all(batch.num_rows <= 10 for batch in batches)
+        table = pa.Table.from_batches(batches)
+        assert table.column_names == ["text", "start", "end", "index"]
+        assert table.column("text").to_pylist() == builder.on_string(text).all()
+
+    def test_stream_is_exported_once(self):
+        """A second export of the same batches raises."""
+        batches = Chunker.by_bytes(chunk_size=10, overlap=0).on_string("x" * 100).to_arrow()
+        batches.__arrow_c_stream__()
+
+        with pytest.raises(ValueError):
+            batches.__arrow_c_stream__()
+
+
+class TestKiruParquet:
+    """Test suite for the Parquet output."""
+
+    def test_text_column_equals_all(self, tmp_path):
+        """Reading the file back gives the chunks of `.all()`, in row groups of row_group_size."""
+        pq = pytest.importorskip("pyarrow.parquet")
+        path = tmp_path / "doc.txt"
+        path.write_text("The quick brown fox jumps over the lazy dog. " * 50, encoding="utf-8")
+        builder = Chunker.by_characters(chunk_size=100, overlap=20)
+        out = tmp_path / "chunks.parquet"
+
+        written = builder.on_file(str(path)).to_parquet(str(out), row_group_size=10)
+
+        expected = builder.on_file(str(path)).all()
+        assert written == len(expected)
+        parquet_file = pq.ParquetFile(out)
+        assert parquet_file.num_row_groups == -(-len(expected) // 10)
+        table = parquet_file.read()
+        assert table.column_names == ["text", "start", "end", "index", "source"]
+        assert table.column("text").to_pylist() == expected
+        assert set(table.column("source").to_pylist()) == {str(path)}
+
+    def test_row_group_size_must_be_positive(self, tmp_path):
+        """A row group size of 0 raises ValueError."""
+        chunks = Chunker.by_bytes(chunk_size=10, overlap=0).on_string("x" * 100)
+
+        with pytest.raises(ValueError):
+            chunks.to_parquet(str(tmp_path / "chunks.parquet"), row_group_size=0)
+
+
+@pytest.mark.skipif(os.name != "posix", reason="needs symlinks and FIFOs")
+class TestKiruGlobSkipped:
+    """Test suite for the files a glob source skips."""
+
+    def test_broken_symlink_and_fifo_are_skipped(self, tmp_path):
+        """Special files are listed by skipped() instead of failing the chunking."""
+        (tmp_path / "doc.txt").write_text("some text to chunk", encoding="utf-8")
+        (tmp_path / "broken.txt").symlink_to(tmp_path / "missing.txt")
+        os.mkfifo(tmp_path / "pipe")
+        builder = Chunker.by_bytes(chunk_size=100, overlap=0)
+
+        chunks = builder.on_sources([f"glob://{tmp_path}/*"])
+
+        assert chunks.all() == ["some text to chunk"]
+        assert sorted(chunks.skipped()) == [str(tmp_path / "broken.txt"), str(tmp_path / "pipe")]
+
+    def test_follow_symlinks(self, tmp_path):
+        """A symlinked file is only chunked when following symlinks."""
+        (tmp_path / "docs").mkdir()
+        (tmp_path / "docs" / "doc.txt").write_text("text", encoding="utf-8")
+        (tmp_path / "link.txt").symlink_to(tmp_path / "docs" / "doc.txt")
+        builder = Chunker.by_bytes(chunk_size=100, overlap=0)
+
+        assert builder.on_sources([f"glob://{tmp_path}/*.txt"]).all() == []
+        followed = builder.on_sources([f"glob://{tmp_path}/*.txt"], follow_symlinks=True)
+        assert followed.all() == ["text"]
+        assert followed.skipped() == []
+
+
+class TestKiruStrategyInfo:
+    """Test suite for the strategy semantics registry."""
+
+    def test_registry_covers_every_strategy(self):
+        """Every `Chunker.by_*` constructor has an entry."""
+        names = [name[3:] for name in dir(Chunker) if name.startswith("by_")]
+
+        assert names
+        for name in names:
+            info = strategy_info(name)
+            assert set(info) == {
+                "unit",
+                "overlap_unit",
+                "overlap_semantics",
+                "splits_graphemes",
+                "streaming_supported",
+            }
+            assert info["overlap_semantics"] in {"exact", "at_least", "at_most", "approximate"}
+
+    def test_characters_overlap_is_exact(self):
+        """The characters strategy overlaps by exactly the configured characters."""
+        assert strategy_info("characters")["overlap_semantics"] == "exact"
+        with pytest.raises(ValueError):
+            strategy_info("sentence")
+
+
+class TestKiruOverlapDirection:
+    """Test suite for forward (lookahead) overlap."""
+
+    def test_tail_overlaps_next_head(self):
+        """Each chunk ends with the head of the next one, and the last one has no lookahead."""
+        text = "abcdefghijklmnopqrstuvwxyz"
+        chunks = Chunker.by_bytes(chunk_size=10, overlap=4, overlap_direction="forward")
+
+        result = chunks.on_string(text).all()
+
+        assert result == ["abcdefghijklmn", "klmnopqrstuvwx", "uvwxyz"]
+        for current, following in zip(result, result[1:]):
+            assert current.endswith(following[:4])
+        assert result != Chunker.by_bytes(chunk_size=10, overlap=4).on_string(text).all()
+
+    def test_invalid_direction(self):
+        """An unknown direction is rejected."""
+        with pytest.raises(ValueError, match="overlap direction"):
+            Chunker.by_characters(chunk_size=10, overlap=4, overlap_direction="sideways")
+
+
+class TestKiruStride:
+    """Test suite for sampling every N-th chunk."""
+
+    def test_sampled_indices(self):
+        """Only the chunks at multiples of the stride are yielded, with their index."""
+        text = "0123456789" * 100
+        builder = Chunker.by_characters(chunk_size=10, overlap=2)
+
+        sampled = builder.with_stride(25).with_metadata().on_string(text).all()
+        everything = builder.on_string(text).all()
+
+        assert [c.index for c in sampled] == [0, 25, 50, 75, 100]
+        assert [c.text for c in sampled] == everything[::25]
+
+    def test_zero_stride(self):
+        """A stride of 0 is rejected."""
+        with pytest.raises(ValueError, match="stride"):
+            Chunker.by_bytes(chunk_size=10, overlap=0).with_stride(0)
+
+
+class TestKiruGridAlign:
+    """Test suite for aligning chunk ends on a byte grid."""
+
+    def test_chunk_ends_on_grid(self):
+        """Every chunk but the last ends on a multiple of the grid."""
+        text = "Grüße aus Köln, schön hier. " * 100
+        builder = Chunker.by_bytes(chunk_size=100, overlap=20, grid_align=16).with_metadata()
+
+        chunks = builder.on_string(text).all()
+
+        assert all(c.end % 16 == 0 for c in chunks[:-1])
+        assert chunks[-1].end == len(text.encode())
+
+
+class TestKiruSnapToWord:
+    """Test suite for ending chunks on whole words."""
+
+    def test_shortened_chunks_are_reported(self):
+        """Chunks end with whole words, and the stats count the shortened ones."""
+        text = "Some words of uneven lengths, then more of them. " * 50
+        builder = Chunker.by_bytes(
+            chunk_size=100, overlap=20, snap_to_word=True
+        ).with_metadata()
+
+        chunks = builder.on_string(text).all()
+
+        assert all(" " in text[c.end - 1 : c.end + 1] for c in chunks[:-1])
+        stats = builder.stats()
+        assert stats["word_snap_adjustments"] > 0
+        assert stats["word_snap_mean_bytes"] == (
+            stats["word_snap_bytes"] / stats["word_snap_adjustments"]
+        )
+
+    def test_no_mid_word_cut_reports_zero(self):
+        """Cuts that all fall between words shorten nothing."""
+        builder = Chunker.by_bytes(chunk_size=40, overlap=10, snap_to_word=True)
+
+        builder.on_string("word " * 500).all()
+
+        stats = builder.stats()
+        assert stats["word_snap_adjustments"] == 0
+        assert stats["word_snap_bytes"] == 0
+        assert stats["word_snap_mean_bytes"] == 0.0
+
+
+class TestKiruAscii:
+    """Test suite for the ASCII assertion."""
+
+    def test_same_chunks_as_safe_path(self, tmp_path):
+        """Asserting ASCII input changes nothing for ASCII input."""
+        text = "Plain ASCII words and numbers like 42. " * 300
+        path = tmp_path / "ascii.txt"
+        path.write_text(text, encoding="ascii")
+        for by in [Chunker.by_bytes, Chunker.by_characters]:
+            expected = by(chunk_size=100, overlap=30).on_file(str(path)).all()
+
+            assert by(chunk_size=100, overlap=30, ascii=True).on_file(str(path)).all() == expected
+
+    def test_non_ascii_file_raises(self, tmp_path):
+        """A file with a non-ASCII byte raises ValueError instead of being altered."""
+        path = tmp_path / "utf8.txt"
+        path.write_text("Plain text. " * 2_000 + "Grüße aus Köln.", encoding="utf-8")
+
+        with pytest.raises(ValueError, match="ASCII"):
+            Chunker.by_bytes(chunk_size=100, overlap=30, ascii=True).on_file(str(path)).all()
+
+
+class TestKiruContextUnits:
+    """Test suite for the context added around chunks."""
+
+    def test_chunks_include_context_on_both_sides(self):
+        """Each chunk is its core span widened by the context where the text has some."""
+        text = "The quick brown fox jumps over the lazy dog. " * 40
+        for by in [Chunker.by_bytes, Chunker.by_characters]:
+            core = by(chunk_size=50, overlap=10).with_metadata().on_string(text).all()
+
+            chunks = (
+                by(chunk_size=50, overlap=10, context_before_units=8, context_after_units=5)
+                .with_metadata()
+                .on_string(text)
+                .all()
+            )
+
+            assert len(chunks) == len(core)
+            for chunk, inner in zip(chunks, core):
+                start, end = max(inner.start - 8, 0), min(inner.end + 5, len(text))
+                assert (chunk.start, chunk.end) == (start, end)
+                assert chunk.text == text[start:end]
+
+
+class TestKiruOverlapSchedule:
+    """Test suite for overlap varying with the chunk index."""
+
+    def test_step_changes_at_scheduled_chunk(self):
+        """Chunks overlap from the scheduled index on, shortening the step."""
+        text = "abcdefghij" * 10
+        for by in [Chunker.by_bytes, Chunker.by_characters]:
+            chunks = (
+                by(chunk_size=10, overlap=0, overlap_schedule=[(3, 4)])
+                .with_metadata()
+                .on_string(text)
+                .all()
+            )
+
+            starts = [chunk.start for chunk in chunks]
+            assert starts[:5] == [0, 10, 20, 26, 32]
+
+    def test_scheduled_overlap_must_be_less_than_chunk_size(self):
+        """A scheduled overlap as large as the chunk size raises ValueError."""
+        with pytest.raises(ValueError):
+            Chunker.by_bytes(chunk_size=10, overlap=0, overlap_schedule=[(2, 10)])
+
+
+class TestKiruUnique:
+    """Test suite for exact deduplication of chunks."""
+
+    def test_only_first_occurrences_in_order(self):
+        """Repeated chunks are dropped, across sources, and the rest keep their order."""
+        builder = Chunker.by_bytes(chunk_size=4, overlap=0)
+
+        chunks = builder.on_sources(["aaaabbbbaaaacccc", "bbbbdddd"]).unique().all()
+
+        assert chunks == ["aaaa", "bbbb", "cccc", "dddd"]
+
+
+class TestKiruCharOffsets:
+    """Test suite for byte and character offsets side by side."""
+
+    def test_byte_and_char_slices_reproduce_chunks(self):
+        """Slicing the bytes by byte offsets and the str by char offsets gives each chunk."""
+        text = "Größe über alles — naïve café 🚀 and plain ASCII. " * 50
+        data = text.encode("utf-8")
+        for by in [Chunker.by_bytes, Chunker.by_characters]:
+            chunks = (
+                by(chunk_size=60, overlap=15)
+                .with_metadata(char_offsets=True)
+                .on_string(text)
+                .all()
+            )
+
+            assert len(chunks) > 10
+            for chunk in chunks:
+                assert data[chunk.start_byte : chunk.end_byte].decode("utf-8") == chunk.text
+                assert text[chunk.start_char : chunk.end_char] == chunk.text
+
+    def test_char_offsets_are_none_unless_requested(self):
+        """Without char_offsets=True, only the byte offsets are set."""
+        chunk = Chunker.by_bytes(chunk_size=4, overlap=0).with_metadata().on_string("abcdef").all()[1]
+
+        assert (chunk.start_byte, chunk.end_byte) == (4, 6)
+        assert chunk.start_char is None and chunk.end_char is None
+
+
+class TestKiruMaxLines:
+    """Test suite for the line cap of the bytes strategy."""
+
+    def test_short_lines_are_cut_at_max_lines(self):
+        """Chunks end after max_lines lines, well before chunk_size bytes."""
+        text = "short log line\n" * 100
+        chunks = Chunker.by_bytes(chunk_size=1024, overlap=0, max_lines=10).on_string(text).all()
+
+        assert len(chunks) == 10
+        assert all(chunk.count("\n") == 10 for chunk in chunks)
+        assert "".join(chunks) == text
+
+
+class TestKiruBufferCapacity:
+    """Test suite for pre-sizing the stream buffer."""
+
+    def test_capacity_does_not_change_chunks(self, tmp_path):
+        """Any buffer capacity yields the chunks of the default one."""
+        path = tmp_path / "doc.txt"
+        path.write_text("Some words, then ünïcödé, and more words. " * 2000, encoding="utf-8")
+
+        for by in [Chunker.by_bytes, Chunker.by_characters]:
+            expected = by(chunk_size=100, overlap=10).on_file(str(path)).all()
+            for capacity in [1, 1 << 20]:
+                chunker = by(chunk_size=100, overlap=10, buffer_capacity=capacity)
+                assert chunker.on_file(str(path)).all() == expected
+
+
+class TestKiruOverlapBytes:
+    def test_overlap_fits_in_overlap_bytes(self):
+        text = "日本語のテキスト, mixed with English 🚀. " * 50
+        chunks = (
+            Chunker.by_characters(40, 0, overlap_bytes=12)
+            .with_metadata()
+            .on_string(text)
+            .all()
+        )
+        assert len(chunks) > 10
+        for prev, chunk in zip(chunks, chunks[1:]):
+            overlap = text.encode()[chunk.start : prev.end]
+            assert 0 < len(overlap) <= 12
+
+    def test_overlap_bytes_must_be_less_than_chunk_size(self):
+        with pytest.raises(ValueError):
+            Chunker.by_characters(10, 0, overlap_bytes=10)
+
+
+class TestKiruMemoryWarning:
+    def test_warns_when_chunk_size_exceeds_file_size(self, tmp_path):
+        path = tmp_path / "doc.txt"
+        path.write_text("some text\n" * 10_000)
+        with pytest.warns(UserWarning, match="buffered in memory"):
+            chunks = Chunker.by_bytes(1_000_000, 0).on_file(str(path)).all()
+        assert len(chunks) == 1
+
+    def test_no_warning_when_streaming(self, tmp_path):
+        path = tmp_path / "doc.txt"
+        path.write_text("some text\n" * 10_000)
+        with warnings.catch_warnings():
+            warnings.simplefilter("error")
+            Chunker.by_bytes(1024, 0).on_file(str(path)).all()
+
+    def test_chunk_sizes_in_other_units_are_not_compared_with_the_file(self, tmp_path):
+        path = tmp_path / "doc.txt"
+        path.write_text("some text\n" * 10_000)
+        with warnings.catch_warnings():
+            warnings.simplefilter("error")
+            # chunk_size is above the 100 000 bytes of the file, but counts other units
+            Chunker.by_characters(200_000, 0).on_file(str(path)).all()
+            Chunker.by_words(200_000, 0).on_file(str(path)).all()
+
+    def test_warns_above_memory_fraction(self, tmp_path):
+        if not os.path.exists("/proc/meminfo"):
+            pytest.skip("available memory is only known on Linux")
+        path = tmp_path / "doc.txt"
+        path.write_text("some text\n" * 10_000)
+        chunker = Chunker.by_bytes(1024, 0).with_memory_warning(1e-12)
+        with pytest.warns(UserWarning, match="available memory"):
+            chunker.on_file(str(path)).all()
+
+    def test_fraction_must_be_positive(self):
+        with pytest.raises(ValueError):
+            Chunker.by_bytes(1024, 0).with_memory_warning(0)
+
+
+class TestKiruBlockTransform:
+    def test_transform_applies_block_by_block(self, tmp_path):
+        text = "".join(f">quoted line {i}\n" for i in range(5000))
+        path = tmp_path / "quoted.txt"
+        path.write_text(text)
+        blocks = []
+
+        def strip_quotes(block):
+            blocks.append(len(block))
+            return block.replace(">", "")
+
+        chunks = (
+            Chunker.by_bytes(1000, 100)
+            .with_block_transform(strip_quotes)
+            .on_file(str(path))
+            .all()
+        )
+        assert len(blocks) > 1
+        assert max(blocks) < len(text)
+        assert chunks == Chunker.by_bytes(1000, 100).on_string(text.replace(">", "")).all()
+
+    def test_exception_in_transform_is_raised(self):
+        def fail(block):
+            raise RuntimeError("bad block")
+
+        with pytest.raises(RuntimeError, match="bad block"):
+            Chunker.by_bytes(10, 0).with_block_transform(fail).on_string("abc" * 10).all()
+
+
+class TestKiruLastChunk:
+    def with_context(self):
+        return Chunker.by_bytes(4, 2, context_after_units=2)
+
+    def test_redundant_last_chunk_is_kept_by_default(self):
+        chunks = self.with_context().on_string("abcdefghij").all()
+        assert chunks == ["abcdef", "cdefgh", "efghij", "ghij"]
+        assert chunks == self.with_context().with_last_chunk("keep").on_string("abcdefghij").all()
+
+    def test_redundant_last_chunk_is_dropped(self):
+        chunks = self.with_context().with_last_chunk("drop_if_redundant").on_string("abcdefghij").all()
+        assert chunks == ["abcdef", "cdefgh", "efghij"]
+
+    def test_redundant_last_chunk_is_merged(self):
+        chunks = self.with_context().with_last_chunk("merge").on_string("abcdefghij").all()
+        assert chunks == ["abcdef", "cdefgh", "efghij"]
+
+    def test_mostly_repeated_last_chunk_is_only_merged(self):
+        text = "abcdefghijkl"
+        for policy, expected in [
+            ("drop_if_redundant", ["abcdefghij", "ghijkl"]),
+            ("merge", ["abcdefghijkl"]),
+        ]:
+            assert Chunker.by_bytes(10, 4).with_last_chunk(policy).on_string(text).all() == expected
+
+    def test_invalid_policy_raises(self):
+        with pytest.raises(ValueError):
+            self.with_context().with_last_chunk("trim")
+
+
+class TestKiruRunningHash:
+    def test_final_hash_is_hash_of_all_chunks(self):
+        text = "Größe über alles — naïve café 🚀 and plain ASCII too. " * 40
+        chunks = Chunker.by_bytes(100, 20).with_metadata(running_hash=True).on_string(text).all()
+        assert len(chunks) > 10
+        assert chunks[-1].running_hash == content_hash("".join(c.text for c in chunks))
+        assert chunks[3].running_hash == content_hash("".join(c.text for c in chunks[:4]))
+
+    def test_runs_diverge_where_chunks_differ(self):
+        chunker = Chunker.by_bytes(10, 0).with_metadata(running_hash=True)
+        a = [c.running_hash for c in chunker.on_string("a" * 30 + "b" * 10)]
+        b = [c.running_hash for c in chunker.on_string("a" * 30 + "c" * 10)]
+        assert a[:3] == b[:3]
+        assert a[3] != b[3]
+
+    def test_no_hash_by_default(self):
+        assert Chunker.by_bytes(10, 0).with_metadata().on_string("abc").all()[0].running_hash is None
+
+
+class TestKiruSourceStamps:
+    def test_mtime_and_size_match_the_file(self, tmp_path):
+        (tmp_path / "a.txt").write_text("first file. " * 10)
+        (tmp_path / "b.txt").write_text("second file. " * 30)
+        chunks = (
+            Chunker.by_bytes(64, 0)
+            .with_metadata(source_stamps=True)
+            .on_sources([f"glob://{tmp_path}/*.txt"])
+            .all()
+        )
+        stats = [os.stat(tmp_path / name) for name in ["a.txt", "b.txt"]]
+        expected = {(s.st_mtime, s.st_size) for s in stats}
+        assert {(c.source_mtime, c.source_size) for c in chunks} == expected
+
+    def test_stamps_are_none_unless_requested(self, tmp_path):
+        path = tmp_path / "a.txt"
+        path.write_text("some text")
+        chunks = Chunker.by_bytes(4, 0).with_metadata().on_file(str(path)).all()
+        assert all(c.source_mtime is None and c.source_size is None for c in chunks)
+
+
+class TestKiruLinks:
+    def test_links_name_neighbours_and_none_at_the_ends(self):
+        chunks = Chunker.by_bytes(4, 1).with_metadata(links=True).on_string("abcdefghij").all()
+        assert [(c.prev_index, c.index, c.next_index) for c in chunks] == [
+            (None, 0, 1),
+            (0, 1, 2),
+            (1, 2, None),
+        ]
+
+    def test_links_start_over_with_each_source(self):
+        chunks = (
+            Chunker.by_bytes(4, 0)
+            .with_metadata(links=True)
+            .on_sources(["text://abcdefgh", "text://ijkl"])
+            .all()
+        )
+        assert [(c.prev_index, c.next_index) for c in chunks] == [(None, 1), (0, None), (None, None)]
+
+    def test_no_links_by_default(self):
+        chunk = Chunker.by_bytes(10, 0).with_metadata().on_string("abc").all()[0]
+        assert chunk.prev_index is None and chunk.next_index is None
+
+
+class TestKiruIncompleteUtf8:
+    @pytest.fixture
+    def truncated_path(self, tmp_path):
+        path = tmp_path / "truncated.txt"
+        path.write_bytes(("Größe über alles. " * 20).encode() + "€".encode()[:2])
+        return str(path)
+
+    def test_truncated_character_is_dropped_and_counted(self, truncated_path):
+        chunker = Chunker.by_bytes(64, 0)
+        assert "".join(chunker.on_file(truncated_path)) == "Größe über alles. " * 20
+        assert chunker.stats()["truncated_sources"] == 1
+
+    def test_truncated_character_is_replaced(self, truncated_path):
+        chunks = Chunker.by_bytes(64, 0).with_incomplete_utf8("replace").on_file(truncated_path)
+        assert "".join(chunks).endswith("alles. \ufffd")
+
+    def test_truncated_character_raises(self, truncated_path):
+        chunks = Chunker.by_bytes(64, 0).with_incomplete_utf8("error").on_file(truncated_path)
+        with pytest.raises(ValueError, match="incomplete UTF-8"):
+            list(chunks)
+
+    def test_invalid_policy_raises(self):
+        with pytest.raises(ValueError):
+            Chunker.by_bytes(64, 0).with_incomplete_utf8("ignore")
+
+
+class TestKiruFileRetry:
+    def test_glob_files_are_chunked_with_retry(self, tmp_path):
+        (tmp_path / "a.txt").write_text("alpha " * 20)
+        (tmp_path / "b.txt").write_text("beta " * 20)
+        sources = [f"glob://{tmp_path}/*.txt"]
+        plain = Chunker.by_bytes(32, 0).on_sources(sources).all()
+        retried = Chunker.by_bytes(32, 0).with_file_retry(3, 0.01).on_sources(sources).all()
+        assert retried == plain
+        assert retried
+
+    def test_missing_file_is_not_retried(self, tmp_path):
+        chunker = Chunker.by_bytes(32, 0).with_file_retry(3, 10.0)
+        started = time.monotonic()
+        with pytest.raises(ValueError, match="No such file"):
+            list(chunker.on_file(str(tmp_path / "missing.txt")))
+        assert time.monotonic() - started < 1
+
+    def test_invalid_arguments_raise(self):
+        with pytest.raises(ValueError):
+            Chunker.by_bytes(32, 0).with_file_retry(0)
+        with pytest.raises(ValueError):
+            Chunker.by_bytes(32, 0).with_file_retry(3, -1.0)
+
+
+class TestKiruTransliteration:
+    def test_accented_text_makes_ascii_chunks(self):
+        text = "Größe über alles — naïve café 🚀 Ærøskøbing. " * 100
+        chunks = Chunker.by_bytes(64, 8).with_transliteration().on_string(text).all()
+        assert len(chunks) > 10
+        assert all(chunk.isascii() for chunk in chunks)
+        assert all(len(chunk) == 64 for chunk in chunks[:-1])
+        assert "Grosse uber alles - naive cafe" in chunks[0]
+
+    def test_transliteration_is_off_by_default(self):
+        assert Chunker.by_bytes(64, 8).on_string("café").all() == ["café"]
+
+
+class TestKiruConfig:
+    def test_config_round_trips(self):
+        text = "Größe über alles. Naïve café 🚀 and plain ASCII too!\n" * 100
+        builders = [
+            Chunker.by_bytes(200, 20, overlap_schedule=[(3, 50)], max_lines=3)
+            .with_prefix("doc: ")
+            .with_metadata(line_numbers=True),
+            Chunker.by_characters(100, 10, overlap_bytes=12)
+            .with_size_band(0.5, 1.0)
+            .with_metadata(),
+        ]
+        for builder in builders:
+            config = json.loads(json.dumps(builder.config()))
+            rebuilt = Chunker.from_config(config)
+            assert rebuilt.config() == builder.config()
+            chunks = [(c.text, c.start, c.end, c.start_line) for c in builder.on_string(text)]
+            assert [(c.text, c.start, c.end, c.start_line) for c in rebuilt.on_string(text)] == chunks
+
+    def test_options_left_out_keep_their_default(self):
+        chunker = Chunker.from_config({"strategy": "characters", "chunk_size": 4, "overlap": 1})
+        assert chunker.on_string("abcdefghij").all() == ["abcd", "defg", "ghij"]
+
+    def test_invalid_configs_raise(self):
+        with pytest.raises(ValueError):
+            Chunker.from_config({"strategy": "bytes", "chunk_size": 4, "overlap": 4})
+        with pytest.raises(ValueError):
+            Chunker.by_boundaries([10, 20], 0).config()
+
+
+class TestKiruBridgeChunks:
+    def test_bridges_straddle_main_chunk_boundaries(self):
+        text = "Größe über alles — naïve café 🚀 and plain ASCII too. " * 40
+        encoded = text.encode()
+        chunks = (
+            Chunker.by_bytes(100, 20).with_bridge_chunks(margin=10).with_metadata().on_string(text).all()
+        )
+        mains = [c for c in chunks if c.kind == "main"]
+        bridges = [c for c in chunks if c.kind == "bridge"]
+        assert len(mains) > 10
+        assert len(bridges) == len(mains) - 1
+        for previous, bridge, following in zip(mains, bridges, mains[1:]):
+            assert encoded[bridge.start : bridge.end].decode() == bridge.text
+            assert previous.start <= bridge.start <= following.start
+            assert previous.end <= bridge.end <= following.end
+        assert "".join(c.unique_text for c in chunks) == text
+        assert all(bridge.unique_text == "" for bridge in bridges)
+
+    def test_no_bridges_by_default(self):
+        chunks = Chunker.by_bytes(10, 2).with_metadata().on_string("x" * 100).all()
+        assert all(c.kind == "main" for c in chunks)
+
+
+class TestKiruFilesConcatenated:
+    """Test suite for chunking several files as one source."""
+
+    def test_chunk_straddling_files_reports_both(self, tmp_path):
+        """Offsets run across the files, and each chunk lists the file parts it spans."""
+        first, second = tmp_path / "a.txt", tmp_path / "b.txt"
+        first.write_text("abcdefghij", encoding="utf-8")
+        second.write_text("klmnopqrst", encoding="utf-8")
+        builder = Chunker.by_bytes(chunk_size=8, overlap=0).with_metadata()
+
+        chunks = builder.on_files_concatenated([str(first), str(second)]).all()
+
+        assert [c.text for c in chunks] == ["abcdefgh", "ijklmnop", "qrst"]
+        assert chunks[1].start == 8
+        assert chunks[1].files == [(str(first), 8, 10), (str(second), 0, 6)]
+        assert chunks[2].files == [(str(second), 6, 10)]
+
+    def test_missing_file_fails_up_front(self, tmp_path):
+        """A missing file raises before any chunk is cut."""
+        with pytest.raises(IOError):
+            Chunker.by_bytes(chunk_size=8, overlap=0).on_files_concatenated(
+                [str(tmp_path / "missing.txt")]
+            )
+
+
+class TestKiruBoundaryCache:
+    """Test suite for caching chunk boundaries to disk."""
+
+    def test_cache_round_trip(self, tmp_path):
+        """Slicing at cached boundaries gives the chunks of chunking again."""
+        path = tmp_path / "corpus.txt"
+        path.write_text("Grüße aus Köln, schön hier. " * 500, encoding="utf-8")
+        cache_path = str(tmp_path / "boundaries.json")
+        builder = Chunker.by_bytes(chunk_size=100, overlap=20).with_metadata()
+
+        count = builder.precompute_boundaries(str(path), "bytes:100:20", cache_path)
+        cached = builder.from_cached_boundaries(str(path), "bytes:100:20", cache_path).all()
+        chunked = builder.on_file(str(path)).all()
+
+        assert count == len(chunked)
+        assert [(c.text, c.start, c.end, c.index) for c in cached] == [
+            (c.text, c.start, c.end, c.index) for c in chunked
+        ]
+
+    def test_changed_source_invalidates_cache(self, tmp_path):
+        """A cache is rejected once its source changed."""
+        path = tmp_path / "corpus.txt"
+        path.write_text("Some words. " * 100, encoding="utf-8")
+        cache_path = str(tmp_path / "boundaries.json")
+        builder = Chunker.by_bytes(chunk_size=100, overlap=20)
+        builder.precompute_boundaries(str(path), "bytes:100:20", cache_path)
+
+        with pytest.raises(ValueError, match="parameters"):
+            builder.from_cached_boundaries(str(path), "bytes:100:10", cache_path)
+        path.write_text("Other words. " * 100, encoding="utf-8")
+        with pytest.raises(ValueError, match="changed"):
+            builder.from_cached_boundaries(str(path), "bytes:100:20", cache_path)
+
+
+class TestKiruOutputSize:
+    """Test suite for the worst-case output size estimate."""
+
+    def test_estimate_bounds_actual_output(self):
+        """The estimate is at least the total bytes of the chunks, overlap included."""
+        text = "Grüße aus Köln, schön hier. " * 200
+        for builder in [
+            Chunker.by_bytes(chunk_size=100, overlap=20),
+            Chunker.by_characters(chunk_size=64, overlap=60),
+        ]:
+            actual = sum(len(c.encode()) for c in builder.on_string(text))
+
+            assert builder.output_size_estimate(len(text.encode())) >= actual
+
+    def test_unbounded_strategy_has_no_estimate(self):
+        """A strategy without a chunk size bound gives no estimate."""
+        builder = Chunker.by_transcript(window_secs=30.0, overlap_secs=5.0)
+
+        assert builder.output_size_estimate(1000) is None
+
+
+class TestKiruCountFile:
+    """Test suite for counting the chunks of a file."""
+
+    def test_count_matches_chunks(self, tmp_path):
+        """The count equals the number of chunks, for ASCII and other files alike."""
+        for text in ["Some words. " * 500, "Grüße aus Köln. " * 500]:
+            path = tmp_path / "doc.txt"
+            path.write_text(text, encoding="utf-8")
+            for builder in [
+                Chunker.by_bytes(chunk_size=100, overlap=20),
+                Chunker.by_characters(chunk_size=64, overlap=60),
+            ]:
+                expected = len(builder.on_file(str(path)).all())
+
+                assert builder.count_file(str(path)) == expected
+                if text.isascii():
+                    assert builder.count_file(str(path), ascii=True) == expected
+
+def bytes_read():
+    """Bytes read by this process so far, from /proc/self/io."""
+    with open("/proc/self/io", encoding="ascii") as io:
+        return next(int(line.split()[1]) for line in io if line.startswith("rchar:"))
+
+
+@pytest.mark.skipif(not os.path.exists("/proc/self/io"), reason="needs /proc")
+class TestKiruFirst:
+    """Test suite for getting only the first chunk of a source."""
+
+    def test_first_reads_only_the_first_buffer_fill(self, tmp_path):
+        """The first chunk of a large file is cut without reading the rest of it."""
+        path = tmp_path / "big.txt"
+        path.write_text("Some words and more words. " * 400_000, encoding="utf-8")
+        builder = Chunker.by_bytes(chunk_size=1000, overlap=100)
+
+        before = bytes_read()
+        first = builder.first(f"file://{path}")
+        read = bytes_read() - before
+
+        assert first == ("Some words and more words. " * 40)[:1000]
+        assert read < 64 * 1024, f"read {read} bytes of {path.stat().st_size}"
+        assert builder.first("") is None
+
+
+class TestKiruEnumerated:
+    """Test suite for enumerated iteration."""
+
+    def test_enumerated_yields_positions_and_texts(self):
+        """Positions follow the chunks, including those consumed before enumerating."""
+        chunker = Chunker.by_characters(chunk_size=4, overlap=0)
+        texts = chunker.on_string("abcdefghijklmnop").all()
+
+        chunks = chunker.on_string("abcdefghijklmnop")
+        next(chunks)
+        pairs = list(chunks.enumerated())
+
+        assert pairs == list(enumerate(texts))[1:]
+        assert chunker.on_string("abcdefgh").enumerated().all() == [
+            (0, "abcd"),
+            (1, "efgh"),
+        ]
+
+
+class TestKiruJsonl:
+    """Test suite for writing chunks to sharded JSON lines files."""
+
+    def test_to_jsonl_sharded(self, tmp_path):
+        """Chunks are spread over full shards plus a final partial one."""
+        chunks = Chunker.by_bytes(chunk_size=10, overlap=0).on_string("0123456789" * 23)
+
+        paths = chunks.to_jsonl_sharded(str(tmp_path / "out"), 5)
+
+        assert [os.path.basename(p) for p in paths] == [
+            f"shard_{i:04}.jsonl" for i in range(5)
+        ]
+        lines = [open(p, encoding="utf-8").read().splitlines() for p in paths]
+        assert [len(shard) for shard in lines] == [5, 5, 5, 5, 3]
+        indices = [json.loads(line)["index"] for shard in lines for line in shard]
+        assert indices == list(range(23))
+
+    def test_to_jsonl_store_modes(self, tmp_path):
+        """Core texts concatenate to the input, and both rebuilds the overlapped chunks."""
+        text = "Größe über alles, naïve café. " * 20
+        chunker = Chunker.by_bytes(chunk_size=40, overlap=12)
+        full = chunker.on_string(text).all()
+
+        lines = {}
+        for mode in ["full", "core", "both"]:
+            path = tmp_path / f"{mode}.jsonl"
+            assert chunker.on_string(text).to_jsonl(str(path), store_mode=mode) == len(full)
+            lines[mode] = [json.loads(line) for line in open(path, encoding="utf-8")]
+
+        assert [line["text"] for line in lines["full"]] == full
+        assert "".join(line["text"] for line in lines["core"]) == text
+        assert [line["overlap"] + line["text"] for line in lines["both"]] == full
+
+    def test_to_jsonl_rejects_unknown_store_mode(self, tmp_path):
+        """Store modes other than full, core and both are rejected."""
+        chunks = Chunker.by_bytes(chunk_size=10, overlap=0).on_string("0123456789")
+
+        with pytest.raises(ValueError):
+            chunks.to_jsonl(str(tmp_path / "chunks.jsonl"), store_mode="unique")
+
+    def test_to_jsonl_gz(self, tmp_path):
+        """The gzip file reads back as the chunks of all()."""
+        text = "Größe über alles, naïve café. " * 200
+        path = tmp_path / "chunks.jsonl.gz"
+
+        written = Chunker.by_bytes(chunk_size=64, overlap=8).on_string(text).to_jsonl_gz(
+            str(path), level=9
+        )
+
+        expected = Chunker.by_bytes(chunk_size=64, overlap=8).on_string(text).all()
+        with gzip.open(path, "rt", encoding="utf-8") as f:
+            texts = [json.loads(line)["text"] for line in f]
+        assert written == len(expected)
+        assert texts == expected
+
+    def test_to_jsonl_gz_rejects_invalid_level(self, tmp_path):
+        """Levels above 9 are rejected."""
+        chunks = Chunker.by_bytes(chunk_size=10, overlap=0).on_string("0123456789")
+
+        with pytest.raises(ValueError):
+            chunks.to_jsonl_gz(str(tmp_path / "chunks.jsonl.gz"), level=10)
+
+
+class TestKiruChunkSequence:
+    """Test suite for indexing the chunks of a string."""
+
+    def test_index_matches_iteration(self):
+        """Indexing, counting and iterating agree, in any order and any number of times."""
+        text = "Größe über alles, naïve café. " * 40
+        for chunker in [
+            Chunker.by_bytes(chunk_size=50, overlap=10),
+            Chunker.by_characters(chunk_size=50, overlap=10),
+        ]:
+            c = chunker.chunks(text)
+
+            third = c[3]
+            assert third == list(c)[3]
+            assert len(c) == len(chunker.on_string(text).all())
+            assert list(c) == chunker.on_string(text).all()
+            assert c[-1] == list(c)[-1]
+
+    def test_out_of_range_index_raises(self):
+        """Indices past either end raise IndexError."""
+        c = Chunker.by_bytes(chunk_size=4, overlap=0).chunks("abcdefghij")
+
+        assert len(c) == 3
+        with pytest.raises(IndexError):
+            c[3]
+        with pytest.raises(IndexError):
+            c[-4]
+
+    def test_chunk_objects(self):
+        """Chunks come out as set by the output, like from on_string()."""
+        c = Chunker.by_bytes(chunk_size=4, overlap=0).with_metadata().chunks("abcdefghij")
+
+        assert (c[1].text, c[1].start, c[1].index) == ("efgh", 4, 1)
+
+
+class TestKiruEffectiveStep:
+    """Test suite for reporting the step between consecutive chunks."""
+
+    def test_step_matches_chunk_starts(self):
+        """The reported step is the smallest difference between chunk start offsets."""
+        sample = "Ünïcödé wörds, " * 40
+        chunks = list(
+            Chunker.by_bytes(chunk_size=50, overlap=10)
+            .with_metadata()
+            .on_string(sample)
+        )
+        steps = [b.start - a.start for a, b in zip(chunks, chunks[1:])]
+
+        assert effective_step(sample, 50, 10, "bytes") == min(steps)
+        assert effective_step(sample, 50, 10, "characters") == 40
+        assert effective_step("short", 50, 10) is None
+
+
+class TestKiruSuggestChunkSize:
+    """Test suite for suggesting the chunk size that makes a number of chunks."""
+
+    def test_suggested_size_yields_about_the_target_count(self):
+        """Chunking with the suggested size makes the target count, within one."""
+        text = "The quick brown fox jumps over the lazy dog. " * 300
+        for target in [1, 7, 33, 100]:
+            for overlap in [0, 40]:
+                size = suggest_chunk_size(len(text), target, overlap)
+                count = len(Chunker.by_bytes(size, overlap).on_string(text).all())
+                assert abs(count - target) <= 1
+
+    def test_zero_target_raises(self):
+        """A target of no chunks raises ValueError."""
+        with pytest.raises(ValueError):
+            suggest_chunk_size(1000, 0, 10)
+
+
+class TestKiruSourceStats:
+    """Test suite for counting the bytes, characters and lines of a file."""
+
+    def test_multibyte_file_counts(self, tmp_path):
+        """Counts match those of the decoded text."""
+        text = "Größe über alles\nnaïve café — 東京 🦀\n" * 1000 + "no newline"
+        path = tmp_path / "doc.txt"
+        path.write_bytes(text.encode("utf-8"))
+
+        assert source_stats(str(path)) == {
+            "bytes": len(text.encode("utf-8")),
+            "chars": len(text),
+            "lines": len(text.splitlines()),
+        }
+
+    def test_missing_file_raises(self, tmp_path):
+        """A file that cannot be read raises IOError."""
+        with pytest.raises(IOError):
+            source_stats(str(tmp_path / "missing.txt"))
+
+
+class TestKiruFollow:
+    """Test suite for following a file as it is written."""
+
+    def test_follow_chunks_content_written_later(self, tmp_path):
+        """Content appended after the end of the file is chunked until stopped."""
+        path = tmp_path / "growing.txt"
+        first = "first part of the log. " * 20
+        path.write_text(first, encoding="utf-8")
+        later = [b"then a caf\xc3", b"\xa9 opened. ", ("and later lines arrive, " * 20).encode()]
+        stop = StopSignal()
+
+        def write():
+            with open(path, "ab") as f:
+                for piece in later:
+                    time.sleep(0.05)
+                    f.write(piece)
+                    f.flush()
+            time.sleep(0.05)
+            stop.stop()
+
+        chunks = Chunker.by_characters(chunk_size=16, overlap=0).on_file(
+            str(path), follow=True, poll_interval=0.005, stop=stop
+        )
+        writer = threading.Thread(target=write)
+        writer.start()
+        text = "".join(chunks)
+        writer.join()
+
+        assert stop.is_stopped
+        assert text == first + b"".join(later).decode("utf-8")
+
+    def test_follow_requires_stop_signal(self, tmp_path):
+        """Following without a way to stop is rejected."""
+        path = tmp_path / "growing.txt"
+        path.write_text("abc", encoding="utf-8")
+
+        with pytest.raises(ValueError):
+            Chunker.by_bytes(chunk_size=4, overlap=0).on_file(str(path), follow=True)
+
+
+class TestKiruHierarchical:
+    """Test suite for parent and child chunks cut in one pass."""
+
+    def test_children_are_within_their_parents(self):
+        """Every child's text is contained within the parent it references."""
+        text = "Größe über alles, naïve café. " * 40
+        chunks = hierarchical(text, 200, 50, 10, strategy="characters", source_type="string").all()
+
+        parents = [c for c in chunks if c.level == 0]
+        children = [c for c in chunks if c.level == 1]
+        assert len(children) > len(parents) > 1
+        assert all(p.parent_index is None for p in parents)
+        for child in children:
+            parent = parents[child.parent_index]
+            assert child.text in parent.text
+            assert parent.start <= child.start and child.end <= parent.end
+
+    def test_child_size_must_be_less_than_parent_size(self):
+        """Children as large as their parents are rejected."""
+        with pytest.raises(ValueError):
+            hierarchical("abc", 10, 10, 0, source_type="string")
+
+
+class TestKiruVerifyLossless:
+    """Test suite for checking that chunking without overlap loses nothing."""
+
+    def test_multibyte_file_is_lossless(self, tmp_path):
+        """Both strategies restore a multibyte file exactly."""
+        path = tmp_path / "doc.txt"
+        path.write_text("Größe über alles — naïve café 🚀, 日本語. " * 500, encoding="utf-8")
+
+        for strategy in ["bytes", "characters"]:
+            assert verify_lossless(str(path), 100, strategy) == {"lossless": True}
+
+    def test_invalid_source_type_raises(self):
+        """An unknown source type raises ValueError."""
+        with pytest.raises(ValueError):
+            verify_lossless("text", 10, source_type="ftp")
+
+
+class TestKiruDiffBoundaries:
+    """Test suite for comparing two chunkings."""
+
+    def test_known_boundary_sets(self):
+        """Moved, new and dropped cuts are told apart, and kept chunks counted."""
+        a = [(0, 100), (100, 200), (200, 300)]
+        b = [(0, 100), (100, 150), (150, 220), (220, 300)]
+
+        diff = diff_boundaries(a, b)
+
+        assert diff["shifted"] == [(200, 220)]
+        assert diff["added"] == [150]
+        assert diff["removed"] == []
+        assert diff["unchanged_chunks"] == 1
+        assert diff["unchanged_fraction"] == 0.25
+
+
 #     def test_unicode_characters(self):
 #         """Test with unicode characters."""
 #         text = "Hello 世界! Café naïve résumé 🚀🎉 " * 10
@@ -1,9 +1,31 @@
 use ::kiru as kiru_core;
+use arrow_array::ffi_stream::FFI_ArrowArrayStream;
+use arrow_array::{RecordBatch, RecordBatchReader};
+use arrow_schema::{ArrowError, SchemaRef};
+use kiru_core::Chunker as _;
 use kiru_core::{
-    BytesChunker, CharactersChunker, ChunkerBuilder, ChunkerWithStrategy, HigherOrderSource,
-    Source, SourceGenerator,
+    content_hash as hash_content, diff_boundaries as diff_chunk_boundaries, from_cached_boundaries,
+    precompute_boundaries, record_batches, run_cli, source_stats as count_source,
+    suggest_chunk_size as suggest_size, term_counts, unique_chunks, write_jsonl, write_jsonl_gz,
+    write_jsonl_sharded, write_parquet, BoundariesChunker, BoundaryPredicate, BreakPredicate,
+    BytesChunker, CharactersChunker, Chunk, ChunkBatches, ChunkKind, ChunkerBuilder, ChunkerConfig,
+    ChunkerWithStrategy, ChunkingError, ConcatenatedFiles, ConfiguredChunker, FileOffsets,
+    GraphemesChunker, HigherOrderSource, IncompleteUtf8, JsonArrayChunker, LastChunk, LineEnding,
+    LinesChunker, LinesPackedChunker, OverlapDirection, ParagraphsChunker, PredicateChunker,
+    RecursiveChunker, RegexChunker, RetryPolicy, SemanticChunker, SemanticUnit,
+    SentenceTerminators, SentencesChunker, Source, SourceGenerator, StoreMode, TokensChunker,
+    TranscriptChunker, WordsChunker, BLOCK_SIZE,
 };
+use pyo3::exceptions::PyUserWarning;
 use pyo3::prelude::*;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyCapsule, PyDict};
+use std::collections::{HashMap, HashSet};
+use std::ffi::CString;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 // ============================================================================
 // Utility Functions
@@ -46,6 +68,236 @@ fn parse_source_strings(source_strings: Vec<String>) -> PyResult<Vec<HigherOrder
         .collect::<PyResult<Vec<HigherOrderSource>>>()
 }
 
+/// Parse source strings and expand their globs, keeping the paths skipped by the globs.
+fn expand_source_strings(
+    source_strings: Vec<String>,
+    follow_symlinks: bool,
+) -> PyResult<(Vec<Source>, Vec<String>)> {
+    let higher_order_sources = parse_source_strings(source_strings)?;
+    let expanded = HigherOrderSource::expand(higher_order_sources, follow_symlinks)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+    let skipped = expanded
+        .skipped
+        .iter()
+        .map(|p| p.to_string_lossy().into_owned())
+        .collect();
+    Ok((expanded.sources, skipped))
+}
+
+/// A Python `is_boundary(prev_char, next_char, pos)` callback used as a boundary predicate.
+///
+/// The callback is called for every pair of adjacent characters, but the GIL is taken once
+/// per block of text handed over by the chunker rather than once per call.
+#[derive(Clone)]
+struct PyPredicate {
+    callback: Arc<Py<PyAny>>,
+    /// First exception raised by the callback, re-raised by the iterator consuming the chunks.
+    error: Arc<Mutex<Option<PyErr>>>,
+}
+
+impl BoundaryPredicate for PyPredicate {
+    fn boundaries(&self, previous: Option<char>, text: &str, offset: usize) -> Vec<usize> {
+        Python::attach(|py| {
+            let mut error = self.error.lock().unwrap();
+            // After a failure the iteration is about to end, skip the remaining calls
+            if error.is_some() {
+                return Vec::new();
+            }
+
+            let callback = self.callback.bind(py);
+            let mut previous = previous;
+            let mut boundaries = Vec::new();
+            for (i, c) in text.char_indices() {
+                if let Some(p) = previous {
+                    match callback
+                        .call1((p, c, offset + i))
+                        .and_then(|r| r.is_truthy())
+                    {
+                        Ok(true) => boundaries.push(offset + i),
+                        Ok(false) => {}
+                        Err(e) => {
+                            *error = Some(e);
+                            break;
+                        }
+                    }
+                }
+                previous = Some(c);
+            }
+            boundaries
+        })
+    }
+}
+
+/// A Python `should_break(current_chunk, candidate_next)` callback used to finalize semantic
+/// chunks.
+///
+/// The callback is called for every candidate unit, but the GIL is taken once per batch of
+/// candidates handed over by the chunker rather than once per call.
+#[derive(Clone)]
+struct PyBreak {
+    callback: Arc<Py<PyAny>>,
+    /// First exception raised by the callback, re-raised by the iterator consuming the chunks.
+    error: Arc<Mutex<Option<PyErr>>>,
+}
+
+impl BreakPredicate for PyBreak {
+    fn first_break(&self, text: &str, accepted: usize, ends: &[usize]) -> Option<usize> {
+        Python::attach(|py| {
+            let mut error = self.error.lock().unwrap();
+            // After a failure the iteration is about to end, break right away
+            if error.is_some() {
+                return Some(0);
+            }
+
+            let callback = self.callback.bind(py);
+            let mut end = accepted;
+            for (i, &next_end) in ends.iter().enumerate() {
+                match callback
+                    .call1((&text[..end], &text[end..next_end]))
+                    .and_then(|r| r.is_truthy())
+                {
+                    Ok(true) => return Some(i),
+                    Ok(false) => {}
+                    Err(e) => {
+                        *error = Some(e);
+                        return Some(i);
+                    }
+                }
+                end = next_end;
+            }
+            None
+        })
+    }
+}
+
+/// Parse the `overlap_unit` argument of the semantic strategy.
+fn parse_semantic_unit(unit: &str) -> PyResult<SemanticUnit> {
+    match unit {
+        "character" => Ok(SemanticUnit::Character),
+        "sentence" => Ok(SemanticUnit::Sentence),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "invalid overlap unit '{other}': expected 'character' or 'sentence'"
+        ))),
+    }
+}
+
+/// Parse the `terminators` (single chars) and `cjk` arguments of the sentences strategy.
+fn parse_terminators(
+    terminators: Option<HashSet<String>>,
+    cjk: bool,
+) -> PyResult<SentenceTerminators> {
+    let terminators = match terminators {
+        Some(terminators) => SentenceTerminators::new(
+            terminators
+                .iter()
+                .map(|terminator| {
+                    let mut chars = terminator.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => Ok(c),
+                        _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                            "invalid terminator '{terminator}': expected a single character"
+                        ))),
+                    }
+                })
+                .collect::<PyResult<Vec<_>>>()?,
+        ),
+        None => SentenceTerminators::default(),
+    };
+    Ok(terminators.with_cjk(cjk))
+}
+
+/// Parse the `overlap_direction` argument of the bytes and characters strategies.
+fn parse_overlap_direction(direction: &str) -> PyResult<OverlapDirection> {
+    match direction {
+        "backward" => Ok(OverlapDirection::Backward),
+        "forward" => Ok(OverlapDirection::Forward),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "invalid overlap direction '{other}': expected 'backward' or 'forward'"
+        ))),
+    }
+}
+
+/// Parse the `line_ending` argument of `by_lines`.
+fn parse_line_ending(line_ending: &str) -> PyResult<LineEnding> {
+    match line_ending {
+        "\n" => Ok(LineEnding::Lf),
+        "\r\n" => Ok(LineEnding::CrLf),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "invalid line ending {other:?}: expected '\\n' or '\\r\\n'"
+        ))),
+    }
+}
+
+/// Parse the `policy` argument of `with_incomplete_utf8`.
+fn parse_incomplete_utf8(policy: &str) -> PyResult<IncompleteUtf8> {
+    match policy {
+        "drop" => Ok(IncompleteUtf8::Drop),
+        "replace" => Ok(IncompleteUtf8::Replace),
+        "error" => Ok(IncompleteUtf8::Error),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "invalid incomplete UTF-8 policy '{other}': expected 'drop', 'replace' or 'error'"
+        ))),
+    }
+}
+
+/// Parse the `policy` argument of `with_last_chunk`.
+fn parse_last_chunk(policy: &str) -> PyResult<LastChunk> {
+    match policy {
+        "keep" => Ok(LastChunk::Keep),
+        "drop_if_redundant" => Ok(LastChunk::DropIfRedundant),
+        "merge" => Ok(LastChunk::Merge),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "invalid last chunk policy '{other}': expected 'keep', 'drop_if_redundant' or 'merge'"
+        ))),
+    }
+}
+
+fn parse_store_mode(mode: &str) -> PyResult<StoreMode> {
+    match mode {
+        "full" => Ok(StoreMode::Full),
+        "core" => Ok(StoreMode::Core),
+        "both" => Ok(StoreMode::Both),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "invalid store mode '{other}': expected 'full', 'core' or 'both'"
+        ))),
+    }
+}
+
+/// Parse the name of a `ChunkOutput`, as returned by `ChunkOutput::name`.
+fn parse_chunk_output(name: &str) -> PyResult<ChunkOutput> {
+    match name {
+        "text" => Ok(ChunkOutput::Text),
+        "chunk" => Ok(ChunkOutput::Chunk),
+        "namedtuple" => Ok(ChunkOutput::NamedTuple),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "invalid output '{other}'"
+        ))),
+    }
+}
+
+/// Raise IO failures as IOError and anything else as ValueError.
+fn chunking_error(e: ChunkingError) -> PyErr {
+    match e {
+        ChunkingError::Io(_) => pyo3::exceptions::PyIOError::new_err(e.to_string()),
+        _ => pyo3::exceptions::PyValueError::new_err(e.to_string()),
+    }
+}
+
+/// Memory available to the process without swapping, in bytes (`MemAvailable` of
+/// `/proc/meminfo`), or `None` where it is not known.
+fn available_memory() -> Option<usize> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let kib = meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemAvailable:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<usize>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
 // ============================================================================
 // Python Classes
 // ============================================================================
@@ -55,21 +307,438 @@ fn parse_source_strings(source_strings: Vec<String>) -> PyResult<Vec<HigherOrder
 pub struct Chunker;
 
 /// Internal enum storing a concrete, static-dispatch builder.
+#[derive(Clone)]
 enum PyChunker {
     Bytes(ChunkerWithStrategy<BytesChunker>),
     Chars(ChunkerWithStrategy<CharactersChunker>),
+    Boundaries(ChunkerWithStrategy<BoundariesChunker>),
+    Predicate(ChunkerWithStrategy<PredicateChunker<PyPredicate>>),
+    Lines(ChunkerWithStrategy<LinesChunker>),
+    LinesPacked(ChunkerWithStrategy<LinesPackedChunker>),
+    JsonArray(ChunkerWithStrategy<JsonArrayChunker>),
+    Transcript(ChunkerWithStrategy<TranscriptChunker>),
+    Semantic(ChunkerWithStrategy<SemanticChunker<PyBreak>>),
+    Words(ChunkerWithStrategy<WordsChunker>),
+    Graphemes(ChunkerWithStrategy<GraphemesChunker>),
+    Paragraphs(ChunkerWithStrategy<ParagraphsChunker>),
+    Sentences(ChunkerWithStrategy<SentencesChunker>),
+    Regex(ChunkerWithStrategy<RegexChunker>),
+    Recursive(ChunkerWithStrategy<RecursiveChunker>),
+    Tokens(ChunkerWithStrategy<TokensChunker>),
+}
+
+/// Evaluate `$body` with `$c` bound to the concrete builder behind a `PyChunker`.
+macro_rules! with_chunker {
+    ($inner:expr, $c:ident => $body:expr) => {
+        match $inner {
+            PyChunker::Bytes($c) => $body,
+            PyChunker::Chars($c) => $body,
+            PyChunker::Boundaries($c) => $body,
+            PyChunker::Predicate($c) => $body,
+            PyChunker::Lines($c) => $body,
+            PyChunker::LinesPacked($c) => $body,
+            PyChunker::JsonArray($c) => $body,
+            PyChunker::Transcript($c) => $body,
+            PyChunker::Semantic($c) => $body,
+            PyChunker::Words($c) => $body,
+            PyChunker::Graphemes($c) => $body,
+            PyChunker::Paragraphs($c) => $body,
+            PyChunker::Sentences($c) => $body,
+            PyChunker::Regex($c) => $body,
+            PyChunker::Recursive($c) => $body,
+            PyChunker::Tokens($c) => $body,
+        }
+    };
+}
+
+/// Rebuild a `PyChunker` from `$body`, evaluated with `$c` bound to its concrete builder.
+macro_rules! map_chunker {
+    ($inner:expr, $c:ident => $body:expr) => {
+        match $inner {
+            PyChunker::Bytes($c) => PyChunker::Bytes($body),
+            PyChunker::Chars($c) => PyChunker::Chars($body),
+            PyChunker::Boundaries($c) => PyChunker::Boundaries($body),
+            PyChunker::Predicate($c) => PyChunker::Predicate($body),
+            PyChunker::Lines($c) => PyChunker::Lines($body),
+            PyChunker::LinesPacked($c) => PyChunker::LinesPacked($body),
+            PyChunker::JsonArray($c) => PyChunker::JsonArray($body),
+            PyChunker::Transcript($c) => PyChunker::Transcript($body),
+            PyChunker::Semantic($c) => PyChunker::Semantic($body),
+            PyChunker::Words($c) => PyChunker::Words($body),
+            PyChunker::Graphemes($c) => PyChunker::Graphemes($body),
+            PyChunker::Paragraphs($c) => PyChunker::Paragraphs($body),
+            PyChunker::Sentences($c) => PyChunker::Sentences($body),
+            PyChunker::Regex($c) => PyChunker::Regex($body),
+            PyChunker::Recursive($c) => PyChunker::Recursive($body),
+            PyChunker::Tokens($c) => PyChunker::Tokens($body),
+        }
+    };
 }
 
 /// A wrapper around a chunker strategy, providing methods to chunk various sources.
 #[pyclass]
 pub struct ChunkerBuilderWrapper {
     inner: PyChunker,
+    /// What chunks are yielded as.
+    output: ChunkOutput,
+    /// Attach term counts to `Chunk` objects.
+    term_counts: bool,
+    /// Fraction of the available memory above which chunking a file warns.
+    memory_warning_fraction: f64,
+    /// Slot of the first exception raised by the block transform, if there is one.
+    transform_error: Option<Arc<Mutex<Option<PyErr>>>>,
+}
+
+/// Default of `ChunkerBuilderWrapper::memory_warning_fraction`.
+const DEFAULT_MEMORY_WARNING_FRACTION: f64 = 0.5;
+
+/// What the chunks of an iterator are yielded as.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ChunkOutput {
+    /// Plain strings.
+    Text,
+    /// `Chunk` objects, with metadata.
+    Chunk,
+    /// `ChunkTuple(text, start, end, index)` namedtuples.
+    NamedTuple,
+}
+
+impl ChunkOutput {
+    fn name(self) -> &'static str {
+        match self {
+            ChunkOutput::Text => "text",
+            ChunkOutput::Chunk => "chunk",
+            ChunkOutput::NamedTuple => "namedtuple",
+        }
+    }
+}
+
+/// The `ChunkTuple` namedtuple class, created once and exported by the module so that its
+/// instances can be pickled.
+fn chunk_tuple_class(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static CLASS: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+    CLASS
+        .get_or_try_init(py, || {
+            let kwargs = PyDict::new(py);
+            kwargs.set_item("module", "kiru")?;
+            let class = py.import("collections")?.getattr("namedtuple")?.call(
+                ("ChunkTuple", ["text", "start", "end", "index"]),
+                Some(&kwargs),
+            )?;
+            Ok(class.unbind())
+        })
+        .map(|class| class.bind(py))
+}
+
+type ChunkStream = Box<dyn Iterator<Item = Result<Chunk, ChunkingError>> + Send + Sync>;
+
+/// Chunks as Arrow record batches, exported through the Arrow PyCapsule stream interface.
+#[pyclass]
+pub struct ArrowChunks {
+    batches: Option<CallbackCheckedBatches>,
+}
+
+/// Record batches that end with the exception of the boundary callback, if one raised.
+struct CallbackCheckedBatches {
+    batches: ChunkBatches<ChunkStream>,
+    callback_error: Option<Arc<Mutex<Option<PyErr>>>>,
+}
+
+impl Iterator for CallbackCheckedBatches {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let batch = self.batches.next();
+        let error = self
+            .callback_error
+            .as_ref()
+            .and_then(|slot| slot.lock().unwrap().take());
+        match error {
+            Some(e) => Some(Err(ArrowError::ExternalError(Box::new(e)))),
+            None => batch,
+        }
+    }
+}
+
+impl RecordBatchReader for CallbackCheckedBatches {
+    fn schema(&self) -> SchemaRef {
+        self.batches.schema()
+    }
+}
+
+/// Seconds since the Unix epoch of `time`, computed like `os.stat().st_mtime`.
+fn unix_seconds(time: SystemTime) -> f64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_secs() as f64 + d.subsec_nanos() as f64 * 1e-9,
+        Err(e) => -(e.duration().as_secs() as f64 + e.duration().subsec_nanos() as f64 * 1e-9),
+    }
+}
+
+/// A chunk of text with its position in the source and the metadata of that source.
+#[pyclass(name = "Chunk", frozen, get_all)]
+pub struct PyChunk {
+    text: String,
+    /// The part of `text` not covered by the previous chunk of the same source.
+    unique_text: String,
+    start: usize,
+    end: usize,
+    /// Same as `start` and `end`, next to the character offsets.
+    start_byte: usize,
+    end_byte: usize,
+    index: usize,
+    start_line: Option<usize>,
+    end_line: Option<usize>,
+    start_char: Option<usize>,
+    end_char: Option<usize>,
+    /// Lowercased word counts of `text`, when requested.
+    term_counts: Option<HashMap<String, usize>>,
+    /// `(path, start, end)` of the part of each file the chunk spans, for concatenated files.
+    files: Option<Vec<(String, usize, usize)>>,
+    /// "main", or "bridge" for a bridge chunk around the boundary of two main chunks.
+    kind: &'static str,
+    /// FNV-1a hash of the text of every chunk yielded so far, this one included.
+    running_hash: Option<u64>,
+    /// Indices of the chunks before and after this one in its source, when requested.
+    prev_index: Option<usize>,
+    next_index: Option<usize>,
+    /// 0 for a parent chunk and 1 for a child chunk, with the index of its parent, for
+    /// hierarchical chunks.
+    level: Option<u8>,
+    parent_index: Option<usize>,
+    /// Modification time, in seconds since the Unix epoch, and size in bytes of the file
+    /// the chunk was cut from, when requested.
+    source_mtime: Option<f64>,
+    source_size: Option<u64>,
+    metadata: Option<Py<PyDict>>,
 }
 
 /// An iterator over chunks produced from one or more sources.
 #[pyclass]
 pub struct ChunkerIterator {
-    inner: Box<dyn Iterator<Item = String> + Send + Sync>,
+    inner: ChunkStream,
+    output: ChunkOutput,
+    /// Attach term counts to `Chunk` objects.
+    term_counts: bool,
+    /// Copied once from the caller and shared by every chunk of the source.
+    metadata: Option<Py<PyDict>>,
+    /// End offset of the previous chunk of the current source.
+    previous_end: Option<usize>,
+    /// Where a boundary callback stores the exception it raised.
+    callback_error: Option<Arc<Mutex<Option<PyErr>>>>,
+    /// Yield `(position, chunk)` tuples instead of chunks.
+    enumerated: bool,
+    /// Number of chunks yielded so far.
+    position: usize,
+    /// Paths matched by a glob but not chunked.
+    skipped: Vec<String>,
+    /// Where each file starts, when chunking concatenated files.
+    file_offsets: Option<FileOffsets>,
+    /// Path or URL of the source, when chunking a single file or URL.
+    source: Option<String>,
+}
+
+/// The chunks of a string, pulled as they are first indexed and kept, so that they can be
+/// indexed, counted and iterated over any number of times.
+#[pyclass]
+pub struct ChunkSequence {
+    chunks: ChunkerIterator,
+    /// Chunks pulled so far, in order.
+    pulled: Vec<Py<PyAny>>,
+    done: bool,
+}
+
+impl ChunkSequence {
+    /// Pull chunks until chunk `index` is pulled, or all of them for `None`.
+    fn pull(&mut self, py: Python<'_>, index: Option<usize>) -> PyResult<()> {
+        while !self.done && index.is_none_or(|index| self.pulled.len() <= index) {
+            let inner = &mut self.chunks.inner;
+            let chunk = py.detach(|| inner.next());
+            self.chunks.raise_callback_error()?;
+            match chunk {
+                Some(chunk) => self.pulled.push(self.chunks.emit(py, chunk)?),
+                None => self.done = true,
+            }
+        }
+        Ok(())
+    }
+
+    /// Chunk `index`, or `None` past the last chunk.
+    fn get(&mut self, py: Python<'_>, index: usize) -> PyResult<Option<Py<PyAny>>> {
+        self.pull(py, Some(index))?;
+        Ok(self.pulled.get(index).map(|chunk| chunk.clone_ref(py)))
+    }
+}
+
+/// Tells `on_file(..., follow=True)` to stop waiting for more data once it reaches the end
+/// of the file, e.g. from the thread writing it.
+#[pyclass(frozen)]
+pub struct StopSignal {
+    stop: Arc<AtomicBool>,
+}
+
+#[pymethods]
+impl StopSignal {
+    #[new]
+    fn new() -> Self {
+        StopSignal {
+            stop: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Stop following: the data written so far is still chunked, then iteration ends.
+    fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether `stop` was called.
+    #[getter]
+    fn is_stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+}
+
+/// A HuggingFace tokenizer loaded once, to share between `by_tokens` chunkers.
+///
+/// Chunkers only read it while encoding, so builders sharing it can chunk sources on
+/// different threads at once.
+#[pyclass(name = "Tokenizer", frozen)]
+pub struct PyTokenizer {
+    inner: Arc<tokenizers::Tokenizer>,
+}
+
+#[pymethods]
+impl PyTokenizer {
+    /// Load a tokenizer from a `tokenizer.json` file, as saved by the `tokenizers` library.
+    ///
+    /// Raises:
+    ///     ValueError: If the tokenizer cannot be loaded.
+    #[staticmethod]
+    fn from_file(path: PathBuf) -> PyResult<Self> {
+        let inner = TokensChunker::load_tokenizer(path)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(PyTokenizer { inner })
+    }
+}
+
+/// The tokenizer argument of `by_tokens`: a shared tokenizer, or the path to load one from.
+#[derive(FromPyObject)]
+enum TokenizerArg<'py> {
+    Shared(PyRef<'py, PyTokenizer>),
+    Path(PathBuf),
+}
+
+/// An iterator over a `ChunkSequence`, from its first chunk.
+#[pyclass]
+pub struct ChunkSequenceIterator {
+    sequence: Py<ChunkSequence>,
+    position: usize,
+}
+
+impl ChunkerIterator {
+    fn empty() -> Self {
+        ChunkerIterator {
+            inner: Box::new(std::iter::empty()),
+            output: ChunkOutput::Text,
+            term_counts: false,
+            metadata: None,
+            previous_end: None,
+            callback_error: None,
+            enumerated: false,
+            position: 0,
+            skipped: Vec::new(),
+            file_offsets: None,
+            source: None,
+        }
+    }
+
+    /// Raise the exception of the boundary or break callback, if any, and end the iteration.
+    fn raise_callback_error(&mut self) -> PyResult<()> {
+        let error = self
+            .callback_error
+            .as_ref()
+            .and_then(|slot| slot.lock().unwrap().take());
+        match error {
+            Some(e) => {
+                self.inner = Box::new(std::iter::empty());
+                Err(e)
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn emit(&mut self, py: Python<'_>, chunk: Result<Chunk, ChunkingError>) -> PyResult<Py<PyAny>> {
+        // A source that failed midway surfaces its error after the chunks read before it
+        let chunk = self.chunk_object(py, chunk.map_err(chunking_error)?)?;
+
+        let position = self.position;
+        self.position += 1;
+        if self.enumerated {
+            return Ok((position, chunk).into_pyobject(py)?.into_any().unbind());
+        }
+        Ok(chunk)
+    }
+
+    /// The chunk as a string, a `Chunk` object or a `ChunkTuple`, as set by the output.
+    fn chunk_object(&mut self, py: Python<'_>, chunk: Chunk) -> PyResult<Py<PyAny>> {
+        match self.output {
+            ChunkOutput::Text => return Ok(chunk.text.into_pyobject(py)?.into_any().unbind()),
+            ChunkOutput::NamedTuple => {
+                let fields = (chunk.text, chunk.start, chunk.end, chunk.index);
+                return Ok(chunk_tuple_class(py)?.call1(fields)?.unbind());
+            }
+            ChunkOutput::Chunk => {}
+        }
+
+        // Bridges share the index of the chunk before them, and only repeat main chunks
+        let main = chunk.kind == ChunkKind::Main;
+        if main && chunk.index == 0 {
+            self.previous_end = None;
+        }
+        let unique_text = match main {
+            true => chunk.unique_text(self.previous_end).to_string(),
+            false => String::new(),
+        };
+        let term_counts = self.term_counts.then(|| term_counts(chunk.content()));
+        let files = self.file_offsets.as_ref().map(|offsets| {
+            offsets
+                .spans(chunk.start, chunk.end)
+                .into_iter()
+                .map(|span| (span.path, span.start, span.end))
+                .collect()
+        });
+        if main {
+            self.previous_end = Some(chunk.end);
+        }
+
+        let chunk = PyChunk {
+            unique_text,
+            text: chunk.text,
+            start: chunk.start,
+            end: chunk.end,
+            start_byte: chunk.start,
+            end_byte: chunk.end,
+            index: chunk.index,
+            start_line: chunk.lines.map(|l| l.start),
+            end_line: chunk.lines.map(|l| l.end),
+            start_char: chunk.chars.map(|c| c.start),
+            end_char: chunk.chars.map(|c| c.end),
+            term_counts,
+            files,
+            kind: match chunk.kind {
+                ChunkKind::Main => "main",
+                ChunkKind::Bridge => "bridge",
+            },
+            running_hash: chunk.running_hash,
+            prev_index: chunk.links.and_then(|l| l.prev_index),
+            next_index: chunk.links.and_then(|l| l.next_index),
+            level: chunk.hierarchy.map(|h| h.level),
+            parent_index: chunk.hierarchy.and_then(|h| h.parent_index),
+            source_mtime: chunk.source_stamp.map(|s| unix_seconds(s.mtime)),
+            source_size: chunk.source_stamp.map(|s| s.size),
+            metadata: self.metadata.as_ref().map(|m| m.clone_ref(py)),
+        };
+        Ok(Py::new(py, chunk)?.into_any())
+    }
 }
 
 // ============================================================================
@@ -83,18 +752,145 @@ impl Chunker {
     /// Args:
     ///     chunk_size (int): The size of each chunk in bytes.
     ///     overlap (int): The number of bytes to overlap between chunks (must be less than chunk_size).
+    ///     overlap_sentences (Optional[int]): Start each chunk at the N-th last sentence of the previous
+    ///         chunk instead of overlapping by bytes (falls back to `overlap` when fewer sentences fit).
+    ///     overlap_chars (Optional[int]): Overlap by at least the last N characters of the previous
+    ///         chunk instead of `overlap` bytes (sentence overlap takes precedence when it applies).
+    ///     overlap_whole_words (bool): Move the overlap start back to the beginning of its word
+    ///         (searching at most 64 characters back).
+    ///     snap_to_word (bool): Move a chunk end that falls inside a word back to the beginning of
+    ///         that word (searching at most 64 characters back), so chunks end with complete words.
+    ///         Shortened chunks are counted in `stats()`.
+    ///     soft_max_extra (Optional[int]): Let a chunk grow up to this many bytes past chunk_size
+    ///         to finish its current sentence (hard cut at chunk_size when no sentence ends in time).
+    ///     grid_align (Optional[int]): End chunks on multiples of this many bytes of the source
+    ///         (the last grid line each chunk reaches that is a character boundary), so chunks of
+    ///         documents sharing a prefix line up. Chunks reaching no grid line end as usual.
+    ///     overlap_direction (str): "backward" to repeat the end of the previous chunk at the start
+    ///         of each chunk, or "forward" to end each chunk with a lookahead into the next one.
+    ///         With "forward", chunks hold their own chunk_size units plus the overlap and start
+    ///         every chunk_size units; the last chunk has no lookahead.
+    ///     ascii (bool): Trust that the input is pure ASCII, skipping UTF-8 decoding of files and
+    ///         URLs (only checked to be ASCII) and character boundary checks. Iterating a file or
+    ///         URL with another byte raises ValueError; on strings, a wrong assertion panics in
+    ///         debug builds and may fail otherwise.
+    ///     context_before_units (int): Extend each chunk with up to this many bytes of the text
+    ///         before it. Only the emitted text and offsets grow, not the step between chunks.
+    ///     context_after_units (int): Extend each chunk with up to this many bytes of the text
+    ///         after it, likewise.
+    ///     overlap_schedule (Optional[List[Tuple[int, int]]]): `(from_index, overlap)` steps
+    ///         setting the overlap in bytes of chunk `from_index` and the chunks after it with
+    ///         the chunk before them, until the next step (`overlap` before the first step).
+    ///     max_lines (Optional[int]): End a chunk right after its N-th line when it reaches N
+    ///         lines before chunk_size bytes (whichever limit is hit first ends the chunk).
+    ///     buffer_capacity (Optional[int]): Bytes to allocate up front for the buffer that files
+    ///         and URLs are streamed through (defaults to 10 * chunk_size bytes, or at least its
+    ///         usual peak).
     ///
     /// Returns:
     ///     ChunkerBuilderWrapper: A wrapper for chunking sources.
     ///
     /// Raises:
-    ///     ValueError: If chunk_size is 0 or overlap is not less than chunk_size.
+    ///     ValueError: If chunk_size is 0, overlap or a scheduled overlap is not less than
+    ///         chunk_size or the overlap direction is unknown.
     #[staticmethod]
-    fn by_bytes(chunk_size: usize, overlap: usize) -> PyResult<ChunkerBuilderWrapper> {
-        let chunker = ChunkerBuilder::by_bytes(chunk_size, overlap)
+    #[pyo3(signature = (
+        chunk_size,
+        overlap,
+        *,
+        overlap_sentences = None,
+        overlap_chars = None,
+        overlap_whole_words = false,
+        snap_to_word = false,
+        soft_max_extra = None,
+        grid_align = None,
+        overlap_direction = "backward",
+        ascii = false,
+        context_before_units = 0,
+        context_after_units = 0,
+        overlap_schedule = None,
+        max_lines = None,
+        buffer_capacity = None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn by_bytes(
+        chunk_size: usize,
+        overlap: usize,
+        overlap_sentences: Option<usize>,
+        overlap_chars: Option<usize>,
+        overlap_whole_words: bool,
+        snap_to_word: bool,
+        soft_max_extra: Option<usize>,
+        grid_align: Option<usize>,
+        overlap_direction: &str,
+        ascii: bool,
+        context_before_units: usize,
+        context_after_units: usize,
+        overlap_schedule: Option<Vec<(usize, usize)>>,
+        max_lines: Option<usize>,
+        buffer_capacity: Option<usize>,
+    ) -> PyResult<ChunkerBuilderWrapper> {
+        let chunker = BytesChunker::new(chunk_size, overlap)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?
+            .with_overlap_sentences(overlap_sentences.unwrap_or(0))
+            .with_overlap_chars(overlap_chars.unwrap_or(0))
+            .with_overlap_whole_words(overlap_whole_words)
+            .with_snap_to_word(snap_to_word)
+            .with_soft_max_extra(soft_max_extra.unwrap_or(0))
+            .with_grid_align(grid_align.unwrap_or(0))
+            .with_max_lines(max_lines.unwrap_or(0))
+            .with_buffer_capacity(buffer_capacity.unwrap_or(0))
+            .with_overlap_direction(parse_overlap_direction(overlap_direction)?)
+            .with_ascii(ascii)
+            .with_context_before_units(context_before_units)
+            .with_context_after_units(context_after_units)
+            .with_overlap_schedule(overlap_schedule.unwrap_or_default())
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(ChunkerBuilderWrapper {
+            inner: PyChunker::Bytes(ChunkerWithStrategy::new(chunker)),
+            output: ChunkOutput::Text,
+            term_counts: false,
+            memory_warning_fraction: DEFAULT_MEMORY_WARNING_FRACTION,
+            transform_error: None,
+        })
+    }
+
+    /// Create a chunker that sizes chunks by an estimated token budget, without loading a
+    /// tokenizer.
+    ///
+    /// Tokens are estimated at chars_per_token characters each (about 4 for English text
+    /// and GPT tokenizers). The budget is converted to bytes, one per character of ASCII text
+    /// and erring on the short side for other text, and chunks end and overlap on whole
+    /// words.
+    ///
+    /// Args:
+    ///     target_tokens (int): The estimated number of tokens in each chunk.
+    ///     overlap_tokens (int): The estimated number of tokens repeated at the start of the
+    ///         next chunk.
+    ///     chars_per_token (float): Characters per token (default: 4.0).
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A wrapper for chunking sources.
+    ///
+    /// Raises:
+    ///     ValueError: If target_tokens is 0, overlap_tokens is not less than target_tokens or
+    ///         chars_per_token is not a positive number.
+    #[staticmethod]
+    #[pyo3(signature = (target_tokens, overlap_tokens, chars_per_token = 4.0))]
+    fn by_approx_tokens(
+        target_tokens: usize,
+        overlap_tokens: usize,
+        chars_per_token: f64,
+    ) -> PyResult<ChunkerBuilderWrapper> {
+        let chunker =
+            ChunkerBuilder::by_approx_tokens(target_tokens, overlap_tokens, chars_per_token)
+                .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
         Ok(ChunkerBuilderWrapper {
             inner: PyChunker::Bytes(chunker),
+            output: ChunkOutput::Text,
+            term_counts: false,
+            memory_warning_fraction: DEFAULT_MEMORY_WARNING_FRACTION,
+            transform_error: None,
         })
     }
 
@@ -103,136 +899,1758 @@ impl Chunker {
     /// Args:
     ///     chunk_size (int): The size of each chunk in characters.
     ///     overlap (int): The number of characters to overlap between chunks (must be less than chunk_size).
+    ///     overlap_sentences (Optional[int]): Start each chunk at the N-th last sentence of the previous
+    ///         chunk instead of overlapping by characters (falls back to `overlap` when fewer sentences fit).
+    ///     overlap_direction (str): "backward" to repeat the end of the previous chunk at the start
+    ///         of each chunk, or "forward" to end each chunk with a lookahead into the next one.
+    ///         With "forward", chunks hold their own chunk_size units plus the overlap and start
+    ///         every chunk_size units; the last chunk has no lookahead.
+    ///     ascii (bool): Trust that the input is pure ASCII, skipping UTF-8 decoding of files and
+    ///         URLs (only checked to be ASCII) and character boundary checks. Iterating a file or
+    ///         URL with another byte raises ValueError; on strings, a wrong assertion panics in
+    ///         debug builds and may fail otherwise.
+    ///     context_before_units (int): Extend each chunk with up to this many characters of the
+    ///         text before it. Only the emitted text and offsets grow, not the step between chunks.
+    ///     context_after_units (int): Extend each chunk with up to this many characters of the
+    ///         text after it, likewise.
+    ///     overlap_schedule (Optional[List[Tuple[int, int]]]): `(from_index, overlap)` steps
+    ///         setting the overlap in characters of chunk `from_index` and the chunks after it
+    ///         with the chunk before them, until the next step (`overlap` before the first step).
+    ///     overlap_bytes (Optional[int]): Overlap by as many of the last characters of the
+    ///         previous chunk as fit in this many bytes, instead of `overlap` characters and the
+    ///         schedule (sentence overlap takes precedence when it applies).
+    ///     buffer_capacity (Optional[int]): Bytes to allocate up front for the buffer that files
+    ///         and URLs are streamed through (defaults to 10 * chunk_size bytes, or at least its
+    ///         usual peak).
     ///
     /// Returns:
     ///     ChunkerBuilderWrapper: A wrapper for chunking sources.
     ///
     /// Raises:
-    ///     ValueError: If chunk_size is 0 or overlap is not less than chunk_size.
+    ///     ValueError: If chunk_size is 0, overlap, a scheduled overlap or overlap_bytes is not
+    ///         less than chunk_size or the overlap direction is unknown.
     #[staticmethod]
-    fn by_characters(chunk_size: usize, overlap: usize) -> PyResult<ChunkerBuilderWrapper> {
-        let chunker = ChunkerBuilder::by_characters(chunk_size, overlap)
+    #[pyo3(signature = (
+        chunk_size,
+        overlap,
+        *,
+        overlap_sentences = None,
+        overlap_direction = "backward",
+        ascii = false,
+        context_before_units = 0,
+        context_after_units = 0,
+        overlap_schedule = None,
+        overlap_bytes = None,
+        buffer_capacity = None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn by_characters(
+        chunk_size: usize,
+        overlap: usize,
+        overlap_sentences: Option<usize>,
+        overlap_direction: &str,
+        ascii: bool,
+        context_before_units: usize,
+        context_after_units: usize,
+        overlap_schedule: Option<Vec<(usize, usize)>>,
+        overlap_bytes: Option<usize>,
+        buffer_capacity: Option<usize>,
+    ) -> PyResult<ChunkerBuilderWrapper> {
+        let mut chunker = CharactersChunker::new(chunk_size, overlap)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?
+            .with_overlap_sentences(overlap_sentences.unwrap_or(0))
+            .with_buffer_capacity(buffer_capacity.unwrap_or(0))
+            .with_overlap_direction(parse_overlap_direction(overlap_direction)?)
+            .with_ascii(ascii)
+            .with_context_before_units(context_before_units)
+            .with_context_after_units(context_after_units)
+            .with_overlap_schedule(overlap_schedule.unwrap_or_default())
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        if let Some(bytes) = overlap_bytes {
+            chunker = chunker
+                .with_overlap_bytes(bytes)
+                .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        }
         Ok(ChunkerBuilderWrapper {
-            inner: PyChunker::Chars(chunker),
+            inner: PyChunker::Chars(ChunkerWithStrategy::new(chunker)),
+            output: ChunkOutput::Text,
+            term_counts: false,
+            memory_warning_fraction: DEFAULT_MEMORY_WARNING_FRACTION,
+            transform_error: None,
         })
     }
-}
 
-// Small helper to de-duplicate single-source handling.
-impl ChunkerBuilderWrapper {
-    fn on_source_internal(&self, source: Source) -> PyResult<ChunkerIterator> {
-        match &self.inner {
-            PyChunker::Bytes(b) => {
-                let inner_iter = b
-                    .on_source(source)
-                    .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-                Ok(ChunkerIterator {
-                    inner: Box::new(inner_iter),
-                })
-            }
-            PyChunker::Chars(c) => {
-                let inner_iter = c
-                    .on_source(source)
-                    .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-                Ok(ChunkerIterator {
-                    inner: Box::new(inner_iter),
-                })
+    /// Rebuild a chunker from the dict returned by `config()` of a builder, e.g. one logged
+    /// with an experiment. Options left out keep their default.
+    ///
+    /// Args:
+    ///     config (dict): The strategy ("bytes" or "characters"), `chunk_size`, `overlap` and
+    ///         any other option, named after the argument or method that sets it.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A builder chunking like the one the config describes.
+    ///
+    /// Raises:
+    ///     ValueError: If the config is invalid or describes invalid parameters.
+    #[staticmethod]
+    fn from_config(config: &Bound<'_, PyDict>) -> PyResult<ChunkerBuilderWrapper> {
+        let config = config.copy()?;
+        let output = match config.get_item("output")? {
+            Some(output) => parse_chunk_output(output.extract()?)?,
+            None => ChunkOutput::Text,
+        };
+        let term_counts = match config.get_item("term_counts")? {
+            Some(term_counts) => term_counts.extract()?,
+            None => false,
+        };
+        let memory_warning_fraction = match config.get_item("memory_warning_fraction")? {
+            Some(fraction) => fraction.extract()?,
+            None => DEFAULT_MEMORY_WARNING_FRACTION,
+        };
+        for key in ["output", "term_counts", "memory_warning_fraction"] {
+            if config.contains(key)? {
+                config.del_item(key)?;
             }
         }
+
+        let json: String = config
+            .py()
+            .import("json")?
+            .call_method1("dumps", (config,))?
+            .extract()?;
+        let inner = match ChunkerConfig::from_json(&json)
+            .and_then(|config| config.build())
+            .map_err(chunking_error)?
+        {
+            ConfiguredChunker::Bytes(c) => PyChunker::Bytes(c),
+            ConfiguredChunker::Characters(c) => PyChunker::Chars(c),
+        };
+        Ok(ChunkerBuilderWrapper {
+            inner,
+            output,
+            term_counts,
+            memory_warning_fraction,
+            transform_error: None,
+        })
     }
 
-    fn on_sources_internal(&self, sources: Vec<Source>) -> PyResult<ChunkerIterator> {
-        match &self.inner {
-            PyChunker::Bytes(b) => {
-                let inner_iter = b
-                    .on_sources(sources)
-                    .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-                Ok(ChunkerIterator {
-                    inner: Box::new(inner_iter),
-                })
-            }
-            PyChunker::Chars(c) => {
-                let inner_iter = c
-                    .on_sources(sources)
-                    .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-                Ok(ChunkerIterator {
-                    inner: Box::new(inner_iter),
-                })
-            }
-        }
+    /// Create a chunker that cuts at externally computed byte offsets.
+    ///
+    /// Each chunk spans from one boundary to the next; boundaries inside a multi-byte
+    /// character are snapped back to the previous character boundary.
+    ///
+    /// Args:
+    ///     boundaries (List[int]): Strictly increasing byte offsets at which to cut.
+    ///     overlap (int): The number of bytes preceding each cut to repeat at the start of the next chunk.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A wrapper for chunking sources.
+    ///
+    /// Raises:
+    ///     ValueError: If the boundaries are not strictly increasing.
+    #[staticmethod]
+    fn by_boundaries(boundaries: Vec<usize>, overlap: usize) -> PyResult<ChunkerBuilderWrapper> {
+        let chunker = ChunkerBuilder::by_boundaries(boundaries, overlap)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(ChunkerBuilderWrapper {
+            inner: PyChunker::Boundaries(chunker),
+            output: ChunkOutput::Text,
+            term_counts: false,
+            memory_warning_fraction: DEFAULT_MEMORY_WARNING_FRACTION,
+            transform_error: None,
+        })
     }
-    fn on_sources_par_internal(
+
+    /// Create a chunker that counts chunk_size and overlap in lines.
+    ///
+    /// Lines keep their terminator, so chunks without overlap concatenate to the source. The
+    /// last chunk holds the remaining lines, however few, the last of which may have no
+    /// terminator.
+    ///
+    /// Args:
+    ///     chunk_size (int): The number of lines in each chunk.
+    ///     overlap (int): The number of last lines of a chunk repeated at the start of the
+    ///         next one.
+    ///     line_ending (str): "\n" (also ending "\r\n" lines, their "\r" kept in the line) or
+    ///         "\r\n" (a lone "\n" is then part of the line, e.g. in a quoted CSV field).
+    ///     repeat_header (bool): Take the first line as a header (e.g. CSV column names), held by
+    ///         the first chunk besides its chunk_size lines and repeated at the top of every
+    ///         later chunk.
+    ///     collapse_separators (bool): Drop blank lines, so that a run of line endings ends a
+    ///         single line and no line is blank. Offsets are then positions in the collapsed
+    ///         text, not in the source.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A wrapper for chunking sources.
+    ///
+    /// Raises:
+    ///     ValueError: If chunk_size is 0, overlap is not less than chunk_size or the line
+    ///         ending is neither "\n" nor "\r\n".
+    #[staticmethod]
+    #[pyo3(signature = (
+        chunk_size,
+        overlap,
+        *,
+        line_ending = "\n",
+        repeat_header = false,
+        collapse_separators = false
+    ))]
+    fn by_lines(
+        chunk_size: usize,
+        overlap: usize,
+        line_ending: &str,
+        repeat_header: bool,
+        collapse_separators: bool,
+    ) -> PyResult<ChunkerBuilderWrapper> {
+        let chunker = LinesChunker::new(chunk_size, overlap)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?
+            .with_line_ending(parse_line_ending(line_ending)?)
+            .with_repeat_header(repeat_header)
+            .with_collapse_separators(collapse_separators);
+        Ok(ChunkerBuilderWrapper {
+            inner: PyChunker::Lines(ChunkerWithStrategy::new(chunker)),
+            output: ChunkOutput::Text,
+            term_counts: false,
+            memory_warning_fraction: DEFAULT_MEMORY_WARNING_FRACTION,
+            transform_error: None,
+        })
+    }
+
+    /// Create a chunker that packs whole lines into chunks of a byte budget.
+    ///
+    /// Lines are accumulated until the next one would exceed target_bytes, so chunks never
+    /// end mid-line; a single line longer than target_bytes becomes a chunk of its own.
+    ///
+    /// Args:
+    ///     target_bytes (int): The maximum size of each chunk in bytes.
+    ///     overlap_lines (int): The number of last lines of a chunk to repeat at the start of the
+    ///         next one (fewer when the next line would not fit otherwise).
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A wrapper for chunking sources.
+    ///
+    /// Raises:
+    ///     ValueError: If target_bytes is 0.
+    #[staticmethod]
+    fn by_lines_packed(
+        target_bytes: usize,
+        overlap_lines: usize,
+    ) -> PyResult<ChunkerBuilderWrapper> {
+        let chunker = ChunkerBuilder::by_lines_packed(target_bytes, overlap_lines)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(ChunkerBuilderWrapper {
+            inner: PyChunker::LinesPacked(chunker),
+            output: ChunkOutput::Text,
+            term_counts: false,
+            memory_warning_fraction: DEFAULT_MEMORY_WARNING_FRACTION,
+            transform_error: None,
+        })
+    }
+
+    /// Create a chunker that counts chunk_size and overlap in extended grapheme clusters,
+    /// what a reader sees as one character, so that chunks never cut an accented letter
+    /// written with a combining mark, a flag or an emoji joined by ZWJs.
+    ///
+    /// Chunks without overlap concatenate to the source. When chunking a file, a cluster
+    /// cut by the end of a block is never split.
+    ///
+    /// Args:
+    ///     chunk_size (int): The number of grapheme clusters in each chunk.
+    ///     overlap (int): The number of last clusters of a chunk repeated at the start of the
+    ///         next one.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A wrapper for chunking sources.
+    ///
+    /// Raises:
+    ///     ValueError: If chunk_size is 0 or overlap is not less than chunk_size.
+    #[staticmethod]
+    fn by_graphemes(chunk_size: usize, overlap: usize) -> PyResult<ChunkerBuilderWrapper> {
+        let chunker = ChunkerBuilder::by_graphemes(chunk_size, overlap)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(ChunkerBuilderWrapper {
+            inner: PyChunker::Graphemes(chunker),
+            output: ChunkOutput::Text,
+            term_counts: false,
+            memory_warning_fraction: DEFAULT_MEMORY_WARNING_FRACTION,
+            transform_error: None,
+        })
+    }
+
+    /// Create a chunker that counts chunk_size and overlap in words, so that chunks never
+    /// cut a word in half.
+    ///
+    /// Words are runs of non-whitespace characters, separated by any Unicode whitespace.
+    /// Chunks keep the whitespace between their words as it is and end with the whitespace
+    /// after their last word, so that chunks without overlap concatenate to the source. The
+    /// last chunk holds the remaining words, however few.
+    ///
+    /// Args:
+    ///     chunk_size (int): The number of words in each chunk.
+    ///     overlap (int): The number of last words of a chunk repeated at the start of the
+    ///         next one.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A wrapper for chunking sources.
+    ///
+    /// Raises:
+    ///     ValueError: If chunk_size is 0 or overlap is not less than chunk_size.
+    #[staticmethod]
+    fn by_words(chunk_size: usize, overlap: usize) -> PyResult<ChunkerBuilderWrapper> {
+        let chunker = ChunkerBuilder::by_words(chunk_size, overlap)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(ChunkerBuilderWrapper {
+            inner: PyChunker::Words(chunker),
+            output: ChunkOutput::Text,
+            term_counts: false,
+            memory_warning_fraction: DEFAULT_MEMORY_WARNING_FRACTION,
+            transform_error: None,
+        })
+    }
+
+    /// Create a chunker that counts chunk_size and overlap in paragraphs.
+    ///
+    /// Paragraphs are separated by one or more blank lines, i.e. by whitespace holding two or
+    /// more newlines, so "\r\n" line endings and lines of spaces separate them too. Chunks
+    /// keep the separators between and after their paragraphs as they are; only the blank
+    /// lines before the first paragraph and after the last one are left out. The last chunk
+    /// holds the remaining paragraphs, however few.
+    ///
+    /// Args:
+    ///     chunk_size (int): The number of paragraphs in each chunk.
+    ///     overlap (int): The number of last paragraphs of a chunk repeated at the start of
+    ///         the next one.
+    ///     collapse_separators (bool): Collapse each run of blank lines into its first one, so
+    ///         that paragraphs are separated by a single blank line. Offsets are then
+    ///         positions in the collapsed text, not in the source.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A wrapper for chunking sources.
+    ///
+    /// Raises:
+    ///     ValueError: If chunk_size is 0 or overlap is not less than chunk_size.
+    #[staticmethod]
+    #[pyo3(signature = (chunk_size, overlap, *, collapse_separators = false))]
+    fn by_paragraphs(
+        chunk_size: usize,
+        overlap: usize,
+        collapse_separators: bool,
+    ) -> PyResult<ChunkerBuilderWrapper> {
+        let chunker = ParagraphsChunker::new(chunk_size, overlap)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?
+            .with_collapse_separators(collapse_separators);
+        Ok(ChunkerBuilderWrapper {
+            inner: PyChunker::Paragraphs(ChunkerWithStrategy::new(chunker)),
+            output: ChunkOutput::Text,
+            term_counts: false,
+            memory_warning_fraction: DEFAULT_MEMORY_WARNING_FRACTION,
+            transform_error: None,
+        })
+    }
+
+    /// Create a chunker that counts chunk_size and overlap in sentences.
+    ///
+    /// A sentence ends after a terminator, '.', '!' or '?' by default (optionally closed by
+    /// quotes or brackets), followed by whitespace, which it keeps, so chunks without overlap
+    /// concatenate to the source. The last chunk holds the remaining sentences, however few.
+    ///
+    /// `max_bytes` is a hard cap on the size of a chunk: a chunk ends before the first
+    /// sentence that would take it past the cap, and a sentence longer than the cap (e.g.
+    /// minified code) falls back to chunks of at most `max_bytes` bytes, counted as
+    /// `hard_splits` in `stats()`.
+    ///
+    /// Args:
+    ///     chunk_size (int): The number of sentences in each chunk.
+    ///     overlap (int): The number of last sentences of a chunk repeated at the start of
+    ///         the next one.
+    ///     max_bytes (Optional[int]): A hard cap on the size of a chunk, in bytes.
+    ///     terminators (Optional[set[str]]): The chars that end a sentence when whitespace
+    ///         follows them, '.', '!' and '?' by default.
+    ///     cjk (bool): Whether '。', '！', '？' and '｡' also end a sentence, whitespace
+    ///         following them or not.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A wrapper for chunking sources.
+    ///
+    /// Raises:
+    ///     ValueError: If chunk_size is 0, overlap is not less than chunk_size, or a
+    ///         terminator is not a single character.
+    #[staticmethod]
+    #[pyo3(signature = (
+        chunk_size,
+        overlap,
+        *,
+        max_bytes = None,
+        terminators = None,
+        cjk = false
+    ))]
+    fn by_sentences(
+        chunk_size: usize,
+        overlap: usize,
+        max_bytes: Option<usize>,
+        terminators: Option<HashSet<String>>,
+        cjk: bool,
+    ) -> PyResult<ChunkerBuilderWrapper> {
+        let mut chunker = SentencesChunker::new(chunk_size, overlap)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?
+            .with_terminators(parse_terminators(terminators, cjk)?);
+        if let Some(max_bytes) = max_bytes {
+            chunker = chunker.with_max_bytes(max_bytes);
+        }
+        Ok(ChunkerBuilderWrapper {
+            inner: PyChunker::Sentences(ChunkerWithStrategy::new(chunker)),
+            output: ChunkOutput::Text,
+            term_counts: false,
+            memory_warning_fraction: DEFAULT_MEMORY_WARNING_FRACTION,
+            transform_error: None,
+        })
+    }
+
+    /// Create a chunker that splits the text on the matches of a regex and counts chunk_size
+    /// and overlap in the resulting pieces, e.g. sections between `---` rules or records
+    /// between delimiter lines.
+    ///
+    /// Pieces are the non-empty texts between matches, so consecutive separators make one.
+    /// With keep_separator, a piece keeps the separators that follow it, so chunks without
+    /// overlap concatenate to the source; otherwise a chunk runs from the text of its first
+    /// piece to the end of the text of its last one, dropping the separators around it.
+    ///
+    /// Args:
+    ///     pattern (str): The regex matching separators (Rust regex syntax).
+    ///     chunk_size (int): The number of pieces in each chunk.
+    ///     overlap (int): The number of last pieces of a chunk repeated at the start of the
+    ///         next one.
+    ///     keep_separator (bool): Whether pieces keep the separators that follow them.
+    ///     repeat_header (bool): Take the first piece and its separator as a header, held by the
+    ///         first chunk besides its chunk_size pieces and repeated at the top of every later
+    ///         chunk.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A wrapper for chunking sources.
+    ///
+    /// Raises:
+    ///     ValueError: If the pattern is not a valid regex, chunk_size is 0 or overlap is not
+    ///         less than chunk_size.
+    #[staticmethod]
+    #[pyo3(signature = (pattern, chunk_size, overlap, *, keep_separator = true, repeat_header = false))]
+    fn by_regex(
+        pattern: &str,
+        chunk_size: usize,
+        overlap: usize,
+        keep_separator: bool,
+        repeat_header: bool,
+    ) -> PyResult<ChunkerBuilderWrapper> {
+        let chunker = RegexChunker::new(pattern, chunk_size, overlap)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?
+            .with_keep_separator(keep_separator)
+            .with_repeat_header(repeat_header);
+        Ok(ChunkerBuilderWrapper {
+            inner: PyChunker::Regex(ChunkerWithStrategy::new(chunker)),
+            output: ChunkOutput::Text,
+            term_counts: false,
+            memory_warning_fraction: DEFAULT_MEMORY_WARNING_FRACTION,
+            transform_error: None,
+        })
+    }
+
+    /// Create a chunker that splits the text on the first of a list of separators found in
+    /// it, packs the splits back into chunks of at most chunk_size bytes, and splits the splits
+    /// still too long on the next separators, like LangChain's RecursiveCharacterTextSplitter.
+    ///
+    /// Splits keep the separator that ends them, so chunks are slices of the source. The empty
+    /// separator splits between characters; without it, a split holding none of the
+    /// separators is a chunk however long. The source is read whole before chunking.
+    ///
+    /// Args:
+    ///     chunk_size (int): The maximum size of each chunk in bytes.
+    ///     overlap (int): The maximum number of bytes of whole trailing splits of a chunk
+    ///         repeated at the start of the next one.
+    ///     separators (Optional[List[str]]): The separators to try in order (default:
+    ///         `["\n\n", "\n", " ", ""]`, i.e. paragraphs, lines, words, then characters).
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A wrapper for chunking sources.
+    ///
+    /// Raises:
+    ///     ValueError: If chunk_size is 0 or overlap is not less than chunk_size.
+    #[staticmethod]
+    #[pyo3(signature = (chunk_size, overlap, *, separators = None))]
+    fn by_recursive(
+        chunk_size: usize,
+        overlap: usize,
+        separators: Option<Vec<String>>,
+    ) -> PyResult<ChunkerBuilderWrapper> {
+        let mut chunker = RecursiveChunker::new(chunk_size, overlap)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        if let Some(separators) = separators {
+            chunker = chunker.with_separators(separators);
+        }
+        Ok(ChunkerBuilderWrapper {
+            inner: PyChunker::Recursive(ChunkerWithStrategy::new(chunker)),
+            output: ChunkOutput::Text,
+            term_counts: false,
+            memory_warning_fraction: DEFAULT_MEMORY_WARNING_FRACTION,
+            transform_error: None,
+        })
+    }
+
+    /// Create a chunker that measures chunks in tokens of a HuggingFace tokenizer, e.g. to
+    /// fill the context window of a model.
+    ///
+    /// Each chunk holds at most chunk_size tokens (the last one the tokens left, however
+    /// few) and starts with the last `overlap` tokens of the previous one. Its text is the
+    /// source text its tokens were encoded from, so chunks are slices of the source, and a
+    /// character encoded as several tokens is held whole. The source is read whole and
+    /// encoded without special tokens, truncation or padding.
+    ///
+    /// Args:
+    ///     tokenizer (Tokenizer | str): A tokenizer loaded with `Tokenizer.from_file`, which
+    ///         builders share without reloading it, or the path to a `tokenizer.json` file,
+    ///         as saved by the `tokenizers` library, to load for this builder alone.
+    ///     chunk_size (int): The maximum number of tokens in each chunk.
+    ///     overlap (int): The number of tokens to repeat between chunks.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A wrapper for chunking sources.
+    ///
+    /// Raises:
+    ///     ValueError: If the tokenizer cannot be loaded, or chunk_size is 0 or overlap is
+    ///         not less than chunk_size. The iterators raise it if the tokenizer fails to
+    ///         encode a source.
+    #[staticmethod]
+    fn by_tokens(
+        tokenizer: TokenizerArg<'_>,
+        chunk_size: usize,
+        overlap: usize,
+    ) -> PyResult<ChunkerBuilderWrapper> {
+        let chunker = match tokenizer {
+            TokenizerArg::Shared(tokenizer) => {
+                TokensChunker::new(tokenizer.inner.clone(), chunk_size, overlap)
+            }
+            TokenizerArg::Path(path) => TokensChunker::from_file(path, chunk_size, overlap),
+        }
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(ChunkerBuilderWrapper {
+            inner: PyChunker::Tokens(ChunkerWithStrategy::new(chunker)),
+            output: ChunkOutput::Text,
+            term_counts: false,
+            memory_warning_fraction: DEFAULT_MEMORY_WARNING_FRACTION,
+            transform_error: None,
+        })
+    }
+
+    /// Create a chunker that groups the elements of a JSON array, e.g. a file holding one
+    /// large array of records, without loading the whole array.
+    ///
+    /// Each chunk is a JSON array of chunk_size elements (fewer for the last one), re-serialized
+    /// without whitespace, so that it parses on its own. Elements may be any JSON values,
+    /// nested ones included.
+    ///
+    /// Args:
+    ///     chunk_size (int): The number of elements in each chunk.
+    ///     overlap (int): The number of last elements of a chunk to repeat at the start of the
+    ///         next one (must be less than chunk_size).
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A wrapper for chunking sources.
+    ///
+    /// Raises:
+    ///     ValueError: If overlap >= chunk_size.
+    #[staticmethod]
+    fn by_json_array(chunk_size: usize, overlap: usize) -> PyResult<ChunkerBuilderWrapper> {
+        let chunker = ChunkerBuilder::by_json_array(chunk_size, overlap).map_err(chunking_error)?;
+        Ok(ChunkerBuilderWrapper {
+            inner: PyChunker::JsonArray(chunker),
+            output: ChunkOutput::Text,
+            term_counts: false,
+            memory_warning_fraction: DEFAULT_MEMORY_WARNING_FRACTION,
+            transform_error: None,
+        })
+    }
+
+    /// Create a chunker that cuts time-stamped transcript lines (e.g. SRT or VTT subtitles)
+    /// into windows of seconds of content.
+    ///
+    /// A chunk starts at a timed line and takes every following line up to the first timed
+    /// line at least window_secs later; lines without a timestamp attach to the timed line
+    /// before them. The next chunk starts overlap_secs before the end of the window.
+    ///
+    /// Args:
+    ///     window_secs (float): The duration of content in each chunk, in seconds.
+    ///     overlap_secs (float): The duration shared with the previous chunk (less than window_secs).
+    ///     timestamp_pattern (Optional[str]): Regex finding the timestamp of a line, taken from its
+    ///         first capture group and read as `[[hours:]minutes:]seconds[.fraction]` (default:
+    ///         SRT/VTT cue times and `[mm:ss]` prefixes at the start of a line).
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A wrapper for chunking sources.
+    ///
+    /// Raises:
+    ///     ValueError: If the window is not positive, the overlap is not in [0, window_secs),
+    ///         or the pattern is not a valid regex.
+    #[staticmethod]
+    #[pyo3(signature = (window_secs, overlap_secs, *, timestamp_pattern = None))]
+    fn by_transcript(
+        window_secs: f64,
+        overlap_secs: f64,
+        timestamp_pattern: Option<&str>,
+    ) -> PyResult<ChunkerBuilderWrapper> {
+        let mut chunker =
+            TranscriptChunker::new(window_secs, overlap_secs).map_err(chunking_error)?;
+        if let Some(pattern) = timestamp_pattern {
+            chunker = chunker
+                .with_timestamp_pattern(pattern)
+                .map_err(chunking_error)?;
+        }
+        Ok(ChunkerBuilderWrapper {
+            inner: PyChunker::Transcript(ChunkerWithStrategy::new(chunker)),
+            output: ChunkOutput::Text,
+            term_counts: false,
+            memory_warning_fraction: DEFAULT_MEMORY_WARNING_FRACTION,
+            transform_error: None,
+        })
+    }
+
+    /// Create a chunker that cuts wherever a callback says so, then packs the resulting
+    /// segments into chunks.
+    ///
+    /// `is_boundary(prev_char, next_char, pos)` is called for every pair of adjacent
+    /// characters, `pos` being the byte offset of `next_char`; returning True cuts between
+    /// them. Consecutive segments are packed into chunks of at most chunk_size bytes (longer
+    /// segments are hard cut), and each chunk repeats the trailing segments of the previous
+    /// one that fit within `overlap` bytes, or its last `overlap_segments` segments.
+    ///
+    /// The callback is called in batches, taking the GIL once per block of text. An
+    /// exception it raises ends the iteration and is re-raised by the iterator.
+    ///
+    /// Args:
+    ///     is_boundary (Callable[[str, str, int], bool]): The boundary predicate.
+    ///     chunk_size (int): The maximum size of each chunk in bytes.
+    ///     overlap (int): The maximum number of bytes of whole segments to repeat between chunks.
+    ///     overlap_segments (Optional[int]): Repeat this many trailing segments of the previous
+    ///         chunk instead, each with the delimiter cut after (fewer when the previous chunk
+    ///         holds fewer, or to leave room for the next segment).
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A wrapper for chunking sources.
+    ///
+    /// Raises:
+    ///     TypeError: If is_boundary is not callable.
+    ///     ValueError: If chunk_size is 0 or overlap is not less than chunk_size.
+    #[staticmethod]
+    #[pyo3(signature = (is_boundary, chunk_size, overlap, *, overlap_segments = None))]
+    fn by_predicate(
+        is_boundary: Bound<'_, PyAny>,
+        chunk_size: usize,
+        overlap: usize,
+        overlap_segments: Option<usize>,
+    ) -> PyResult<ChunkerBuilderWrapper> {
+        if !is_boundary.is_callable() {
+            return Err(pyo3::exceptions::PyTypeError::new_err(
+                "is_boundary must be callable",
+            ));
+        }
+        let predicate = PyPredicate {
+            callback: Arc::new(is_boundary.unbind()),
+            error: Arc::default(),
+        };
+        let chunker = PredicateChunker::new(predicate, chunk_size, overlap)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?
+            .with_overlap_segments(overlap_segments.unwrap_or(0));
+        Ok(ChunkerBuilderWrapper {
+            inner: PyChunker::Predicate(ChunkerWithStrategy::new(chunker)),
+            output: ChunkOutput::Text,
+            term_counts: false,
+            memory_warning_fraction: DEFAULT_MEMORY_WARNING_FRACTION,
+            transform_error: None,
+        })
+    }
+
+    /// Create a chunker that grows each chunk unit by unit until a callback says to stop.
+    ///
+    /// `should_break(current_chunk, candidate_next)` is called with the chunk so far and the
+    /// unit (a sentence or a character) that would join it next; returning True finalizes
+    /// the chunk without it. The first unit always joins a chunk. Each chunk after the
+    /// first repeats the last `overlap` units of the previous one (all but the first unit
+    /// of a chunk of `overlap` units or less).
+    ///
+    /// The callback is called in batches, taking the GIL once per block of text. An
+    /// exception it raises ends the iteration and is re-raised by the iterator. A chunk is
+    /// held whole in memory until the callback breaks it.
+    ///
+    /// Args:
+    ///     should_break (Callable[[str, str], bool]): The break callback.
+    ///     overlap_unit (str): The unit chunks grow and overlap by, "sentence" or "character".
+    ///     overlap (int): The number of units to repeat between chunks.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A wrapper for chunking sources.
+    ///
+    /// Raises:
+    ///     TypeError: If should_break is not callable.
+    ///     ValueError: If overlap_unit is not "sentence" or "character".
+    #[staticmethod]
+    #[pyo3(signature = (should_break, overlap_unit = "sentence", overlap = 0))]
+    fn by_semantic(
+        should_break: Bound<'_, PyAny>,
+        overlap_unit: &str,
+        overlap: usize,
+    ) -> PyResult<ChunkerBuilderWrapper> {
+        if !should_break.is_callable() {
+            return Err(pyo3::exceptions::PyTypeError::new_err(
+                "should_break must be callable",
+            ));
+        }
+        let predicate = PyBreak {
+            callback: Arc::new(should_break.unbind()),
+            error: Arc::default(),
+        };
+        let chunker =
+            ChunkerBuilder::by_semantic(predicate, parse_semantic_unit(overlap_unit)?, overlap);
+        Ok(ChunkerBuilderWrapper {
+            inner: PyChunker::Semantic(chunker),
+            output: ChunkOutput::Text,
+            term_counts: false,
+            memory_warning_fraction: DEFAULT_MEMORY_WARNING_FRACTION,
+            transform_error: None,
+        })
+    }
+}
+
+// Small helper to de-duplicate single-source handling.
+impl ChunkerBuilderWrapper {
+    /// Slot of the first exception raised by the callback of the strategy or the block
+    /// transform, if there is one. A block transform shares the slot of the strategy.
+    fn callback_error(&self) -> Option<Arc<Mutex<Option<PyErr>>>> {
+        match &self.inner {
+            PyChunker::Predicate(c) => Some(c.chunker().predicate().error.clone()),
+            PyChunker::Semantic(c) => Some(c.chunker().predicate().error.clone()),
+            _ => self.transform_error.clone(),
+        }
+    }
+
+    fn iterator(
+        &self,
+        inner: impl Iterator<Item = Result<Chunk, ChunkingError>> + Send + Sync + 'static,
+        metadata: Option<Py<PyDict>>,
+    ) -> ChunkerIterator {
+        let callback_error = self.callback_error();
+        ChunkerIterator {
+            inner: Box::new(inner),
+            output: self.output,
+            term_counts: self.term_counts,
+            metadata,
+            previous_end: None,
+            callback_error,
+            enumerated: false,
+            position: 0,
+            skipped: Vec::new(),
+            file_offsets: None,
+            source: None,
+        }
+    }
+
+    fn on_source_internal(
+        &self,
+        source: Source,
+        metadata: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<ChunkerIterator> {
+        let metadata = metadata.map(|m| m.copy()).transpose()?.map(Bound::unbind);
+        let name = match &source {
+            Source::File(path) | Source::Http(path) => Some(path.clone()),
+            Source::Text(_) => None,
+        };
+        let mut chunks = with_chunker!(&self.inner, c => {
+            let inner_iter = c
+                .on_source_with_offsets(source)
+                .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+            self.iterator(inner_iter, metadata)
+        });
+        chunks.source = name;
+        Ok(chunks)
+    }
+
+    /// Warn if chunking the file at `path` buffers it whole, because a chunk is larger than
+    /// the file, or is estimated to use more than `memory_warning_fraction` of the available
+    /// memory. Files that fit in one read block are read whole anyway. Only chunk sizes in
+    /// bytes compare with the size of the file.
+    fn warn_on_file_memory(&self, py: Python<'_>, path: &str) -> PyResult<()> {
+        let Ok(len) = std::fs::metadata(path).map(|m| m.len() as usize) else {
+            // Reported when the file is opened
+            return Ok(());
+        };
+        let chunk_size = match &self.inner {
+            PyChunker::Bytes(c) => c.chunker().chunk_size(),
+            PyChunker::LinesPacked(c) => c.chunker().chunk_size(),
+            _ => None,
+        };
+        let estimate = with_chunker!(&self.inner, c => c.estimate_memory(len));
+
+        let message = match chunk_size {
+            Some(chunk_size) if chunk_size > len && len > BLOCK_SIZE => format!(
+                "chunk_size {chunk_size} exceeds the size of '{path}' ({len} bytes): \
+                 the whole file is buffered in memory instead of streamed"
+            ),
+            _ => match available_memory() {
+                Some(available)
+                    if estimate as f64 > self.memory_warning_fraction * available as f64 =>
+                {
+                    format!(
+                        "chunking '{path}' is estimated to use {estimate} bytes, more than \
+                         {} of the {available} bytes of available memory",
+                        self.memory_warning_fraction
+                    )
+                }
+                _ => return Ok(()),
+            },
+        };
+        let message = CString::new(message)?;
+        PyErr::warn(py, &py.get_type::<PyUserWarning>(), &message, 1)
+    }
+
+    fn on_sources_internal(&self, sources: Vec<Source>) -> PyResult<ChunkerIterator> {
+        with_chunker!(&self.inner, c => {
+            let inner_iter = c
+                .on_sources_with_offsets(sources)
+                .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+            Ok(self.iterator(inner_iter, None))
+        })
+    }
+
+    fn on_sources_round_robin_internal(&self, sources: Vec<Source>) -> PyResult<ChunkerIterator> {
+        with_chunker!(&self.inner, c => {
+            let inner_iter = c
+                .on_sources_round_robin_with_offsets(sources)
+                .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+            Ok(self.iterator(inner_iter, None))
+        })
+    }
+
+    fn on_sources_par_internal(
+        &self,
+        sources: Vec<Source>,
+        channel_size: usize,
+    ) -> PyResult<ChunkerIterator> {
+        with_chunker!(&self.inner, c => {
+            let inner_iter = c
+                .on_sources_par_stream_with_offsets(sources, channel_size)
+                .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+            Ok(self.iterator(inner_iter, None))
+        })
+    }
+}
+
+#[pymethods]
+impl ChunkerBuilderWrapper {
+    /// Yield `Chunk` objects (text, byte offsets, index and source metadata) instead of strings.
+    ///
+    /// Args:
+    ///     line_numbers (bool): Also report the 1-based range of lines each chunk spans
+    ///         (`start_line` and `end_line`).
+    ///     term_counts (bool): Also report the lowercased word counts of each chunk's content
+    ///         (`term_counts`), computed in Rust. Words are runs of alphanumeric characters.
+    ///     char_offsets (bool): Also report the offsets of each chunk in characters
+    ///         (`start_char` and `end_char`), to slice a `str` where `start_byte` and `end_byte`
+    ///         slice its UTF-8 bytes.
+    ///     running_hash (bool): Also report the 64-bit FNV-1a hash of the text of every chunk
+    ///         yielded so far, this one included (`running_hash`), i.e. `content_hash` of their
+    ///         concatenation, to check that two runs yield the same chunks up to a point.
+    ///     links (bool): Also report the indices of the chunks before and after each chunk in
+    ///         its source (`prev_index` and `next_index`, None at the ends), e.g. to build a
+    ///         graph of chunks. Each chunk is held back until the next one is cut.
+    ///     source_stamps (bool): Also report the modification time and size of the file each
+    ///         chunk was cut from (`source_mtime` and `source_size`, None for other sources),
+    ///         read once per file, e.g. to skip files unchanged since the last run.
+    ///     output (str): "chunk" to yield `Chunk` objects, or "namedtuple" to yield plain
+    ///         `ChunkTuple(text, start, end, index)` namedtuples, which unpack and pickle like
+    ///         tuples but carry no other metadata.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A copy of this builder with metadata output enabled.
+    ///
+    /// Raises:
+    ///     ValueError: If the output is unknown.
+    #[pyo3(signature = (
+        *,
+        line_numbers = false,
+        term_counts = false,
+        char_offsets = false,
+        running_hash = false,
+        links = false,
+        source_stamps = false,
+        output = "chunk"
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn with_metadata(
+        &self,
+        line_numbers: bool,
+        term_counts: bool,
+        char_offsets: bool,
+        running_hash: bool,
+        links: bool,
+        source_stamps: bool,
+        output: &str,
+    ) -> PyResult<ChunkerBuilderWrapper> {
+        let output = match parse_chunk_output(output)? {
+            ChunkOutput::Text => {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "invalid output 'text'",
+                ))
+            }
+            output => output,
+        };
+        let mut inner = self.inner.clone();
+        if line_numbers {
+            inner = map_chunker!(&inner, c => c.clone().with_line_numbers());
+        }
+        if char_offsets {
+            inner = map_chunker!(&inner, c => c.clone().with_char_offsets());
+        }
+        if running_hash {
+            inner = map_chunker!(&inner, c => c.clone().with_running_hash(true));
+        }
+        if links {
+            inner = map_chunker!(&inner, c => c.clone().with_links(true));
+        }
+        if source_stamps {
+            inner = map_chunker!(&inner, c => c.clone().with_source_stamps(true));
+        }
+        Ok(ChunkerBuilderWrapper {
+            inner,
+            output,
+            term_counts,
+            memory_warning_fraction: self.memory_warning_fraction,
+            transform_error: self.transform_error.clone(),
+        })
+    }
+
+    /// Set when `on_file` warns about memory: when the estimated memory use of chunking the
+    /// file (see `estimate_memory`) exceeds this fraction of the available memory. The
+    /// available memory is only known on Linux.
+    ///
+    /// Args:
+    ///     fraction (float): Fraction of the available memory (default: 0.5).
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A copy of this builder with the threshold set.
+    ///
+    /// Raises:
+    ///     ValueError: If the fraction is not positive.
+    #[pyo3(signature = (fraction = DEFAULT_MEMORY_WARNING_FRACTION))]
+    fn with_memory_warning(&self, fraction: f64) -> PyResult<ChunkerBuilderWrapper> {
+        if fraction.is_nan() || fraction <= 0.0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "invalid memory warning fraction {fraction}"
+            )));
+        }
+        Ok(ChunkerBuilderWrapper {
+            inner: self.inner.clone(),
+            output: self.output,
+            term_counts: self.term_counts,
+            memory_warning_fraction: fraction,
+            transform_error: self.transform_error.clone(),
+        })
+    }
+
+    /// Drop chunks that are near-duplicates of a recently seen chunk.
+    ///
+    /// Chunks are compared through 64-bit SimHash fingerprints of their rolling hashes, so
+    /// chunks differing by a few edits are caught, not only exact copies.
+    ///
+    /// Args:
+    ///     max_distance (int): Maximum number of differing fingerprint bits (out of 64) for a
+    ///         chunk to count as a near-duplicate (default: 3).
+    ///     capacity (int): Number of recent fingerprints to remember, least recently used
+    ///         evicted first (default: 1024).
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A copy of this builder with near-duplicate suppression.
+    #[pyo3(signature = (max_distance = 3, capacity = 1024))]
+    fn with_near_dedup(&self, max_distance: u32, capacity: usize) -> ChunkerBuilderWrapper {
+        ChunkerBuilderWrapper {
+            inner: map_chunker!(&self.inner, c => c.clone().with_near_dedup(max_distance, capacity)),
+            output: self.output,
+            term_counts: self.term_counts,
+            memory_warning_fraction: self.memory_warning_fraction,
+            transform_error: self.transform_error.clone(),
+        }
+    }
+
+    /// Yield a single empty string for a source that produces no chunks (an empty input),
+    /// instead of nothing. Non-empty inputs are unaffected.
+    ///
+    /// Args:
+    ///     emit_empty (bool): Whether to yield the empty chunk (default: True).
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A copy of this builder with the option set.
+    #[pyo3(signature = (emit_empty = true))]
+    fn with_emit_empty(&self, emit_empty: bool) -> ChunkerBuilderWrapper {
+        ChunkerBuilderWrapper {
+            inner: map_chunker!(&self.inner, c => c.clone().with_emit_empty(emit_empty)),
+            output: self.output,
+            term_counts: self.term_counts,
+            memory_warning_fraction: self.memory_warning_fraction,
+            transform_error: self.transform_error.clone(),
+        }
+    }
+
+    /// Apply a callable to every block of text read from a source, before it is chunked.
+    ///
+    /// Files and URLs are transformed as they stream, a block (about 8 KiB) at a time,
+    /// instead of as a whole. Blocks are cut at arbitrary character boundaries, so the
+    /// transform should work character by character (e.g. lowercasing or unescaping).
+    ///
+    /// Offsets are those of the transformed text: a transform that changes the length of the
+    /// text shifts them off the source.
+    ///
+    /// Args:
+    ///     transform (Callable[[str], str]): Called with each block, returns its replacement.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A copy of this builder with the transform set.
+    fn with_block_transform(&self, transform: Py<PyAny>) -> ChunkerBuilderWrapper {
+        let error = self.callback_error().unwrap_or_default();
+        let transform = Arc::new(transform);
+        let apply = {
+            let error = error.clone();
+            move |block: &str| {
+                Python::attach(|py| {
+                    let mut error = error.lock().unwrap();
+                    // After a failure the iteration is about to end, drop the remaining blocks
+                    if error.is_some() {
+                        return String::new();
+                    }
+                    match transform
+                        .bind(py)
+                        .call1((block,))
+                        .and_then(|text| text.extract::<String>())
+                    {
+                        Ok(text) => text,
+                        Err(e) => {
+                            *error = Some(e);
+                            String::new()
+                        }
+                    }
+                })
+            }
+        };
+        ChunkerBuilderWrapper {
+            inner: map_chunker!(&self.inner, c => c.clone().with_block_transform(apply.clone())),
+            output: self.output,
+            term_counts: self.term_counts,
+            memory_warning_fraction: self.memory_warning_fraction,
+            transform_error: Some(error),
+        }
+    }
+
+    /// Choose what happens to the last chunk of a source when it holds nothing beyond the
+    /// previous chunk, e.g. when context after the chunk before it reaches the end of the
+    /// text.
+    ///
+    /// Only consecutive chunks are compared, so a stride leaves the last chunk alone.
+    ///
+    /// Args:
+    ///     policy (str): "keep" to yield it anyway (the default), "drop_if_redundant" to
+    ///         leave it out, or "merge" to fold it into the previous chunk, which also folds
+    ///         in a last chunk adding less new text than it repeats (the previous chunk then
+    ///         grows past the chunk size by less than the overlap).
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A copy of this builder with the policy set.
+    ///
+    /// Raises:
+    ///     ValueError: If the policy is unknown.
+    fn with_last_chunk(&self, policy: &str) -> PyResult<ChunkerBuilderWrapper> {
+        let policy = parse_last_chunk(policy)?;
+        Ok(ChunkerBuilderWrapper {
+            inner: map_chunker!(&self.inner, c => c.clone().with_last_chunk(policy)),
+            output: self.output,
+            term_counts: self.term_counts,
+            memory_warning_fraction: self.memory_warning_fraction,
+            transform_error: self.transform_error.clone(),
+        })
+    }
+
+    /// Choose what happens to the bytes of a character cut off by the end of a file or URL,
+    /// e.g. a file truncated mid-character.
+    ///
+    /// Such sources are counted in `stats()["truncated_sources"]` whatever the policy.
+    ///
+    /// Args:
+    ///     policy (str): "drop" to leave the bytes out (the default), "replace" to end the
+    ///         text with a U+FFFD replacement character, or "error" to raise ValueError
+    ///         after the chunks of the rest of the source.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A copy of this builder with the policy set.
+    ///
+    /// Raises:
+    ///     ValueError: If the policy is unknown.
+    fn with_incomplete_utf8(&self, policy: &str) -> PyResult<ChunkerBuilderWrapper> {
+        let policy = parse_incomplete_utf8(policy)?;
+        Ok(ChunkerBuilderWrapper {
+            inner: map_chunker!(&self.inner, c => c.clone().with_incomplete_utf8(policy)),
+            output: self.output,
+            term_counts: self.term_counts,
+            memory_warning_fraction: self.memory_warning_fraction,
+            transform_error: self.transform_error.clone(),
+        })
+    }
+
+    /// Retry opening and reading files, e.g. those matched by a glob, when they fail with a
+    /// transient error such as a timeout on a network share. A missing file or a denied
+    /// permission fails at once.
+    ///
+    /// Args:
+    ///     attempts (int): Times an open or read is tried in all, at least 1.
+    ///     delay (float): Seconds to wait after the first failure; the wait doubles after
+    ///         each one.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A copy of this builder with the retries set.
+    ///
+    /// Raises:
+    ///     ValueError: If attempts is 0 or delay is negative.
+    #[pyo3(signature = (attempts, delay = 0.1))]
+    fn with_file_retry(&self, attempts: usize, delay: f64) -> PyResult<ChunkerBuilderWrapper> {
+        if attempts == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "attempts must be at least 1",
+            ));
+        }
+        let delay = Duration::try_from_secs_f64(delay)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        let policy = RetryPolicy::new(attempts, delay);
+        Ok(ChunkerBuilderWrapper {
+            inner: map_chunker!(&self.inner, c => c.clone().with_file_retry(policy)),
+            output: self.output,
+            term_counts: self.term_counts,
+            memory_warning_fraction: self.memory_warning_fraction,
+            transform_error: self.transform_error.clone(),
+        })
+    }
+
+    /// Transliterate text to ASCII as it streams, so that chunks only hold ASCII.
+    ///
+    /// This is lossy: accents are removed (`é` becomes `e`), common letters and punctuation
+    /// are spelled in ASCII (`ß` becomes `ss`, `—` becomes `-`), and other characters (CJK,
+    /// emoji, ...) are dropped. Chunks are sized, and offsets given, in the transliterated
+    /// text, so offsets no longer line up with the source. Runs after the block transform.
+    ///
+    /// Args:
+    ///     enabled (bool): Whether to transliterate (default: True).
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A copy of this builder with the option set.
+    #[pyo3(signature = (enabled = true))]
+    fn with_transliteration(&self, enabled: bool) -> ChunkerBuilderWrapper {
+        ChunkerBuilderWrapper {
+            inner: map_chunker!(&self.inner, c => c.clone().with_transliteration(enabled)),
+            output: self.output,
+            term_counts: self.term_counts,
+            memory_warning_fraction: self.memory_warning_fraction,
+            transform_error: self.transform_error.clone(),
+        }
+    }
+
+    /// Add a bridge chunk between every two consecutive chunks that overlap or touch, so that
+    /// queries spanning their boundary match one chunk.
+    ///
+    /// A bridge spans the overlap of the two chunks (or the point where they meet) and up to
+    /// `margin` bytes on each side, without leaving them. It comes right after the chunk
+    /// before it, with its index; with metadata output, its `kind` is "bridge" instead of
+    /// "main".
+    ///
+    /// Args:
+    ///     margin (int): Bytes the bridge extends past the overlap on each side (default: 32).
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A copy of this builder adding bridge chunks.
+    #[pyo3(signature = (margin = 32))]
+    fn with_bridge_chunks(&self, margin: usize) -> ChunkerBuilderWrapper {
+        ChunkerBuilderWrapper {
+            inner: map_chunker!(&self.inner, c => c.clone().with_bridge_chunks(margin)),
+            output: self.output,
+            term_counts: self.term_counts,
+            memory_warning_fraction: self.memory_warning_fraction,
+            transform_error: self.transform_error.clone(),
+        }
+    }
+
+    /// Only keep every N-th chunk of each source, e.g. for a quick look at a huge file.
+    ///
+    /// Chunks at indices 0, stride, 2 * stride, ... are kept, with their index. The whole
+    /// source is still read, but the bytes and characters strategies do not build the text
+    /// of the chunks in between. Sampling runs before all the other options, and line
+    /// numbers are not reported.
+    ///
+    /// Args:
+    ///     stride (int): Keep one chunk out of this many.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A copy of this builder sampling chunks.
+    ///
+    /// Raises:
+    ///     ValueError: If stride is 0.
+    fn with_stride(&self, stride: usize) -> PyResult<ChunkerBuilderWrapper> {
+        Ok(ChunkerBuilderWrapper {
+            inner: map_chunker!(&self.inner, c => c.clone().with_stride(stride).map_err(chunking_error)?),
+            output: self.output,
+            term_counts: self.term_counts,
+            memory_warning_fraction: self.memory_warning_fraction,
+            transform_error: self.transform_error.clone(),
+        })
+    }
+
+    /// Merge runs of consecutive small chunks into larger ones.
+    ///
+    /// Every run of consecutive chunks that are each smaller than min_chunk_size is merged
+    /// into chunks of at most max_chunk_size, measured in the unit of the strategy. A merged
+    /// chunk spans from the start of the first chunk of its run to the end of the last one,
+    /// so its overlap with the chunks around it is unchanged. Chunks are renumbered.
+    ///
+    /// Args:
+    ///     min_chunk_size (int): Size below which a chunk is merged with its small neighbours.
+    ///     max_chunk_size (int): Maximum size of a merged chunk, typically the chunk size.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A copy of this builder with small chunks merged.
+    fn with_merge_small(
+        &self,
+        min_chunk_size: usize,
+        max_chunk_size: usize,
+    ) -> ChunkerBuilderWrapper {
+        ChunkerBuilderWrapper {
+            inner: map_chunker!(&self.inner, c => c.clone().with_merge_small(min_chunk_size, max_chunk_size)),
+            output: self.output,
+            term_counts: self.term_counts,
+            memory_warning_fraction: self.memory_warning_fraction,
+            transform_error: self.transform_error.clone(),
+        }
+    }
+
+    /// Attach the whitespace after the text of a chunk, e.g. trailing blank lines, to that
+    /// chunk instead of starting the next chunk with it.
+    ///
+    /// A chunk adding only whitespace to the previous one is merged into it, so blank lines
+    /// never make a chunk of their own. Chunks after a merged one are renumbered.
+    ///
+    /// Args:
+    ///     enabled (bool): Whether to attach trailing whitespace (default: True).
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A copy of this builder with the option set.
+    #[pyo3(signature = (enabled = true))]
+    fn with_attach_trailing_whitespace(&self, enabled: bool) -> ChunkerBuilderWrapper {
+        ChunkerBuilderWrapper {
+            inner: map_chunker!(&self.inner, c => c.clone().with_attach_trailing_whitespace(enabled)),
+            output: self.output,
+            term_counts: self.term_counts,
+            memory_warning_fraction: self.memory_warning_fraction,
+            transform_error: self.transform_error.clone(),
+        }
+    }
+
+    /// Prepend a string (e.g. the document title) to the text of every chunk.
+    ///
+    /// The prefix does not count towards the chunk size, the size band or near-duplicate
+    /// detection, and chunk offsets still span the content only.
+    ///
+    /// Args:
+    ///     prefix (str): The text to prepend.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A copy of this builder with the prefix set.
+    fn with_prefix(&self, prefix: String) -> ChunkerBuilderWrapper {
+        ChunkerBuilderWrapper {
+            inner: map_chunker!(&self.inner, c => c.clone().with_prefix(prefix.clone())),
+            output: self.output,
+            term_counts: self.term_counts,
+            memory_warning_fraction: self.memory_warning_fraction,
+            transform_error: self.transform_error.clone(),
+        }
+    }
+
+    /// Prepend a preamble (e.g. the import block of a code file, as context) to the text of
+    /// every chunk, after the prefix.
+    ///
+    /// Like the prefix, the preamble does not count towards the chunk size, and chunk offsets
+    /// still span the content only.
+    ///
+    /// Args:
+    ///     preamble (str): The text to prepend, typically several lines.
+    ///     skip_first (bool): Leave it out of chunks starting at the start of their source,
+    ///         which already hold it when it is the head of the source.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A copy of this builder with the preamble set.
+    #[pyo3(signature = (preamble, skip_first = false))]
+    fn with_preamble(&self, preamble: String, skip_first: bool) -> ChunkerBuilderWrapper {
+        ChunkerBuilderWrapper {
+            inner: map_chunker!(&self.inner, c => c.clone().with_preamble(preamble.clone(), skip_first)),
+            output: self.output,
+            term_counts: self.term_counts,
+            memory_warning_fraction: self.memory_warning_fraction,
+            transform_error: self.transform_error.clone(),
+        }
+    }
+
+    /// Append a string to the text of every chunk, like `with_prefix`.
+    ///
+    /// Args:
+    ///     suffix (str): The text to append.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A copy of this builder with the suffix set.
+    fn with_suffix(&self, suffix: String) -> ChunkerBuilderWrapper {
+        ChunkerBuilderWrapper {
+            inner: map_chunker!(&self.inner, c => c.clone().with_suffix(suffix.clone())),
+            output: self.output,
+            term_counts: self.term_counts,
+            memory_warning_fraction: self.memory_warning_fraction,
+            transform_error: self.transform_error.clone(),
+        }
+    }
+
+    /// Balance the bidi embedding, override and isolate controls within each chunk, so that
+    /// right-to-left text renders the same on its own: closers whose opener is in an earlier
+    /// chunk are removed, and openers still open at the end of a chunk are closed there.
+    ///
+    /// Args:
+    ///     enabled (bool): Whether to balance the controls (default: True).
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A copy of this builder with the option set.
+    #[pyo3(signature = (enabled = true))]
+    fn with_bidi_balance(&self, enabled: bool) -> ChunkerBuilderWrapper {
+        ChunkerBuilderWrapper {
+            inner: map_chunker!(&self.inner, c => c.clone().with_bidi_balance(enabled)),
+            output: self.output,
+            term_counts: self.term_counts,
+            memory_warning_fraction: self.memory_warning_fraction,
+            transform_error: self.transform_error.clone(),
+        }
+    }
+
+    /// Only keep chunks whose size lies within a band around the chunk size.
+    ///
+    /// Sizes are measured in the unit of the strategy (bytes or characters). Dropped chunks
+    /// are counted in `stats()`.
+    ///
+    /// Args:
+    ///     min_ratio (float): Smallest size kept, as a fraction of chunk_size.
+    ///     max_ratio (float): Largest size kept, as a fraction of chunk_size.
+    ///
+    /// Returns:
+    ///     ChunkerBuilderWrapper: A copy of this builder filtering chunks by size.
+    ///
+    /// Raises:
+    ///     ValueError: If the ratios are negative or out of order, or the strategy has no
+    ///         chunk size (boundaries).
+    fn with_size_band(&self, min_ratio: f64, max_ratio: f64) -> PyResult<ChunkerBuilderWrapper> {
+        let inner = match &self.inner {
+            PyChunker::Bytes(c) => c
+                .clone()
+                .with_size_band(min_ratio, max_ratio)
+                .map(PyChunker::Bytes),
+            PyChunker::Chars(c) => c
+                .clone()
+                .with_size_band(min_ratio, max_ratio)
+                .map(PyChunker::Chars),
+            PyChunker::Boundaries(c) => c
+                .clone()
+                .with_size_band(min_ratio, max_ratio)
+                .map(PyChunker::Boundaries),
+            PyChunker::Predicate(c) => c
+                .clone()
+                .with_size_band(min_ratio, max_ratio)
+                .map(PyChunker::Predicate),
+            PyChunker::Lines(c) => c
+                .clone()
+                .with_size_band(min_ratio, max_ratio)
+                .map(PyChunker::Lines),
+            PyChunker::LinesPacked(c) => c
+                .clone()
+                .with_size_band(min_ratio, max_ratio)
+                .map(PyChunker::LinesPacked),
+            PyChunker::JsonArray(c) => c
+                .clone()
+                .with_size_band(min_ratio, max_ratio)
+                .map(PyChunker::JsonArray),
+            PyChunker::Transcript(c) => c
+                .clone()
+                .with_size_band(min_ratio, max_ratio)
+                .map(PyChunker::Transcript),
+            PyChunker::Semantic(c) => c
+                .clone()
+                .with_size_band(min_ratio, max_ratio)
+                .map(PyChunker::Semantic),
+            PyChunker::Words(c) => c
+                .clone()
+                .with_size_band(min_ratio, max_ratio)
+                .map(PyChunker::Words),
+            PyChunker::Graphemes(c) => c
+                .clone()
+                .with_size_band(min_ratio, max_ratio)
+                .map(PyChunker::Graphemes),
+            PyChunker::Paragraphs(c) => c
+                .clone()
+                .with_size_band(min_ratio, max_ratio)
+                .map(PyChunker::Paragraphs),
+            PyChunker::Sentences(c) => c
+                .clone()
+                .with_size_band(min_ratio, max_ratio)
+                .map(PyChunker::Sentences),
+            PyChunker::Regex(c) => c
+                .clone()
+                .with_size_band(min_ratio, max_ratio)
+                .map(PyChunker::Regex),
+            PyChunker::Recursive(c) => c
+                .clone()
+                .with_size_band(min_ratio, max_ratio)
+                .map(PyChunker::Recursive),
+            PyChunker::Tokens(c) => c
+                .clone()
+                .with_size_band(min_ratio, max_ratio)
+                .map(PyChunker::Tokens),
+        }
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(ChunkerBuilderWrapper {
+            inner,
+            output: self.output,
+            term_counts: self.term_counts,
+            memory_warning_fraction: self.memory_warning_fraction,
+            transform_error: self.transform_error.clone(),
+        })
+    }
+
+    /// Counts of the chunks produced and dropped so far by iterators of this builder.
+    ///
+    /// Returns:
+    ///     dict: `chunks` (yielded), `dropped_out_of_band` (size band),
+    ///         `dropped_near_duplicates` (near-duplicate suppression), `boundary_adjustments`
+    ///         (chunk ends and overlap starts moved onto a char boundary by the bytes strategy),
+    ///         `word_snap_adjustments` (chunks shortened by `snap_to_word`), `word_snap_bytes`
+    ///         (bytes cut from them in total), `word_snap_mean_bytes` (per shortened chunk),
+    ///         `hard_splits` (sentences cut at the `max_bytes` cap of the sentences strategy)
+    ///         and `truncated_sources` (files and URLs ending mid-character).
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let stats = with_chunker!(&self.inner, c => c.stats());
+        let dict = PyDict::new(py);
+        dict.set_item("chunks", stats.chunks)?;
+        dict.set_item("dropped_out_of_band", stats.dropped_out_of_band)?;
+        dict.set_item("dropped_near_duplicates", stats.dropped_near_duplicates)?;
+        dict.set_item("boundary_adjustments", stats.boundary_adjustments)?;
+        dict.set_item("word_snap_adjustments", stats.word_snap_adjustments)?;
+        dict.set_item("word_snap_bytes", stats.word_snap_bytes)?;
+        dict.set_item("word_snap_mean_bytes", stats.word_snap_mean_bytes())?;
+        dict.set_item("hard_splits", stats.hard_splits)?;
+        dict.set_item("truncated_sources", stats.truncated_sources)?;
+        Ok(dict)
+    }
+
+    /// Parameters of this builder as a JSON-serializable dict, e.g. to log with an
+    /// experiment, which `Chunker.from_config` rebuilds the builder from.
+    ///
+    /// The dict holds the strategy ("bytes" or "characters"), `chunk_size`, `overlap` and
+    /// every other option, named after the argument or method that sets it (`0` or None
+    /// when unset). The size band is given as the smallest and largest chunk size.
+    ///
+    /// Returns:
+    ///     dict: The parameters of the builder.
+    ///
+    /// Raises:
+    ///     ValueError: If the strategy is not bytes or characters, or a block transform is
+    ///         set, since callables cannot be described.
+    fn config<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let config = with_chunker!(&self.inner, c => c.config()).map_err(chunking_error)?;
+        let dict = py
+            .import("json")?
+            .call_method1("loads", (config.to_json(),))?;
+        dict.set_item("output", self.output.name())?;
+        dict.set_item("term_counts", self.term_counts)?;
+        dict.set_item("memory_warning_fraction", self.memory_warning_fraction)?;
+        Ok(dict)
+    }
+
+    /// Estimate the peak memory used to stream a file or URL of the given size.
+    ///
+    /// The estimate follows the streaming buffer sizes of the strategy (plus its character
+    /// positions for the characters strategy) and excludes chunks kept by the caller. Chunking
+    /// a string additionally holds the string itself.
+    ///
+    /// Args:
+    ///     source_len (int): Size of the source in bytes.
+    ///
+    /// Returns:
+    ///     int: Approximate peak heap usage in bytes.
+    fn estimate_memory(&self, source_len: usize) -> usize {
+        with_chunker!(&self.inner, c => c.estimate_memory(source_len))
+    }
+
+    /// Worst-case total bytes of the chunks of a source of the given size, for capacity
+    /// planning: the source itself, every repeated overlap and the prefix, preamble and suffix
+    /// of every chunk.
+    ///
+    /// Args:
+    ///     source_len (int): Size of the source in bytes.
+    ///
+    /// Returns:
+    ///     Optional[int]: Upper bound of the output in bytes, or None if the strategy does not
+    ///         bound chunk sizes (json_array, lines_packed, semantic and transcript).
+    fn output_size_estimate(&self, source_len: usize) -> Option<usize> {
+        with_chunker!(&self.inner, c => c.output_size_estimate(source_len))
+    }
+
+    /// Number of chunks of a file. For an ASCII file, the count follows from the file's size
+    /// alone, without reading it; other files are chunked to count their chunks.
+    ///
+    /// Args:
+    ///     path (str): The path of the file.
+    ///     ascii (bool): Trust that the file is ASCII instead of checking it, which would
+    ///         read it once.
+    ///
+    /// Returns:
+    ///     int: The number of chunks `on_file` yields for the file.
+    ///
+    /// Raises:
+    ///     IOError: If reading the file failed.
+    #[pyo3(signature = (path, ascii=false))]
+    fn count_file(&self, py: Python<'_>, path: PathBuf, ascii: bool) -> PyResult<usize> {
+        let count = py.detach(|| with_chunker!(&self.inner, c => c.count_file(&path, ascii)));
+        if let Some(e) = self
+            .callback_error()
+            .and_then(|slot| slot.lock().unwrap().take())
+        {
+            return Err(e);
+        }
+        count.map_err(chunking_error)
+    }
+
+    /// Chunk a single string input.
+    ///
+    /// Args:
+    ///     text (str): The input text to chunk.
+    ///     metadata (Optional[dict]): Key/values attached to every chunk when metadata output is enabled.
+    ///
+    /// Returns:
+    ///     ChunkerIterator: An iterator over the chunks.
+    ///
+    /// Raises:
+    ///     ValueError: If the input cannot be processed.
+    #[pyo3(signature = (text, metadata = None))]
+    fn on_string(
+        &self,
+        text: String,
+        metadata: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<ChunkerIterator> {
+        let source = Source::Text(text);
+        self.on_source_internal(source, metadata)
+    }
+
+    /// Chunk a string into a sequence that can be indexed, counted and iterated over any
+    /// number of times, e.g. to look at chunks in a notebook.
+    ///
+    /// Chunks are cut as they are first indexed, up to the chunk indexed, and kept: counting
+    /// them or a negative index cuts them all.
+    ///
+    /// Args:
+    ///     text (str): The input text to chunk.
+    ///     metadata (Optional[dict]): Key/values attached to every chunk when metadata output is enabled.
+    ///
+    /// Returns:
+    ///     ChunkSequence: The chunks of the text.
+    ///
+    /// Raises:
+    ///     ValueError: If the input cannot be processed.
+    #[pyo3(signature = (text, metadata = None))]
+    fn chunks(
+        &self,
+        text: String,
+        metadata: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<ChunkSequence> {
+        Ok(ChunkSequence {
+            chunks: self.on_source_internal(Source::Text(text), metadata)?,
+            pulled: Vec::new(),
+            done: false,
+        })
+    }
+
+    /// Chunk a single file from a local path.
+    ///
+    /// With `follow=True`, the file is followed as it is written, like `tail -f`: at the end
+    /// of the data written so far, it is polled for more every `poll_interval` seconds until
+    /// `stop.stop()` is called, after which the rest of the file is chunked and iteration
+    /// ends. A chunk is yielded once the text after it fills the chunker's buffer, a few
+    /// chunks.
+    ///
+    /// Args:
+    ///     path (str): The path to the file (e.g., "path/to/file.txt").
+    ///     metadata (Optional[dict]): Key/values attached to every chunk when metadata output is enabled.
+    ///     follow (bool): Wait for more data at the end of the file. Defaults to False.
+    ///     poll_interval (float): Seconds between polls for more data when following. Defaults to 0.1.
+    ///     stop (Optional[StopSignal]): Ends following once stopped; required with `follow=True`.
+    ///
+    /// Returns:
+    ///     ChunkerIterator: An iterator over the chunks.
+    ///
+    /// Raises:
+    ///     ValueError: If the file cannot be read (e.g., does not exist), or `follow=True`
+    ///         is given without `stop` or with a negative `poll_interval`.
+    ///
+    /// Warns:
+    ///     UserWarning: If a chunk size in bytes (of `by_bytes` or `by_lines_packed`)
+    ///         exceeds the size of the file, so that it is buffered whole instead of streamed, or the estimated memory use exceeds the fraction of
+    ///         the available memory set by `with_memory_warning`.
+    #[pyo3(signature = (path, metadata = None, *, follow = false, poll_interval = 0.1, stop = None))]
+    fn on_file(
+        &self,
+        py: Python<'_>,
+        path: String,
+        metadata: Option<&Bound<'_, PyDict>>,
+        follow: bool,
+        poll_interval: f64,
+        stop: Option<&StopSignal>,
+    ) -> PyResult<ChunkerIterator> {
+        if follow {
+            let stop = stop.ok_or_else(|| {
+                pyo3::exceptions::PyValueError::new_err("follow=True requires a stop signal")
+            })?;
+            let poll_interval = Duration::try_from_secs_f64(poll_interval)
+                .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+            let metadata = metadata.map(|m| m.copy()).transpose()?.map(Bound::unbind);
+            let mut chunks = with_chunker!(&self.inner, c => {
+                let inner_iter = c
+                    .on_file_follow(path.clone(), poll_interval, stop.stop.clone())
+                    .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+                self.iterator(inner_iter, metadata)
+            });
+            chunks.source = Some(path);
+            return Ok(chunks);
+        }
+        self.warn_on_file_memory(py, &path)?;
+        let source = Source::File(path);
+        self.on_source_internal(source, metadata)
+    }
+
+    /// Chunk a file and cache the boundaries of its chunks to disk, so that later runs can
+    /// slice the file with `from_cached_boundaries` instead of chunking it again.
+    ///
+    /// The cache records a hash of `params` along with the size and modification time of
+    /// the file, which `from_cached_boundaries` checks before using it.
+    ///
+    /// Args:
+    ///     path (str): The path of the file to chunk.
+    ///     params (str): Any description of the chunking parameters, e.g. "bytes:1024:128".
+    ///     cache_path (str): The path of the cache file to write.
+    ///
+    /// Returns:
+    ///     int: The number of chunks.
+    ///
+    /// Raises:
+    ///     IOError: If reading the file or writing the cache failed.
+    fn precompute_boundaries(
         &self,
-        sources: Vec<Source>,
-        channel_size: usize,
-    ) -> PyResult<ChunkerIterator> {
-        match &self.inner {
-            PyChunker::Bytes(b) => {
-                let inner_iter = b
-                    .on_sources_par_stream(sources, channel_size)
-                    .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-                Ok(ChunkerIterator {
-                    inner: Box::new(inner_iter),
-                })
-            }
-            PyChunker::Chars(c) => {
-                let inner_iter = c
-                    .on_sources_par_stream(sources, channel_size)
-                    .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-                Ok(ChunkerIterator {
-                    inner: Box::new(inner_iter),
-                })
-            }
+        py: Python<'_>,
+        path: PathBuf,
+        params: &str,
+        cache_path: PathBuf,
+    ) -> PyResult<usize> {
+        let count = py.detach(|| {
+            with_chunker!(&self.inner, c => precompute_boundaries(c, &path, params, &cache_path))
+        });
+        if let Some(e) = self
+            .callback_error()
+            .and_then(|slot| slot.lock().unwrap().take())
+        {
+            return Err(e);
         }
+        count.map_err(chunking_error)
     }
-}
 
-#[pymethods]
-impl ChunkerBuilderWrapper {
-    /// Chunk a single string input.
+    /// Chunk a file by slicing it at the boundaries cached by `precompute_boundaries`,
+    /// without chunking it again.
+    ///
+    /// Chunks carry the offsets and indices they were cached with, and their text is read
+    /// straight from the file, so prefixes and suffixes of this builder are not applied.
     ///
     /// Args:
-    ///     text (str): The input text to chunk.
+    ///     path (str): The path of the file.
+    ///     params (str): The description of the chunking parameters the cache was made with.
+    ///     cache_path (str): The path of the cache file.
+    ///     metadata (Optional[dict]): Key/values attached to every chunk when metadata output is enabled.
     ///
     /// Returns:
     ///     ChunkerIterator: An iterator over the chunks.
     ///
     /// Raises:
-    ///     ValueError: If the input cannot be processed.
-    fn on_string(&self, text: String) -> PyResult<ChunkerIterator> {
-        let source = Source::Text(text);
-        self.on_source_internal(source)
+    ///     ValueError: If the cache was made with other params, or the file changed since.
+    ///     IOError: If reading the file or the cache failed.
+    #[pyo3(signature = (path, params, cache_path, metadata = None))]
+    #[allow(clippy::wrong_self_convention)]
+    fn from_cached_boundaries(
+        &self,
+        path: PathBuf,
+        params: &str,
+        cache_path: PathBuf,
+        metadata: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<ChunkerIterator> {
+        let metadata = metadata.map(|m| m.copy()).transpose()?.map(Bound::unbind);
+        let chunks = from_cached_boundaries(path, params, cache_path).map_err(chunking_error)?;
+        Ok(self.iterator(chunks, metadata))
     }
 
-    /// Chunk a single file from a local path.
+    /// Chunk several files as one source, their contents concatenated in order.
+    ///
+    /// Offsets (`start` and `end`) run across the concatenation. With metadata output, each
+    /// chunk also reports `files`: the `(path, start, end)` part of every file it spans, with
+    /// offsets within that file, so a chunk straddling two files lists both parts.
     ///
     /// Args:
-    ///     path (str): The path to the file (e.g., "path/to/file.txt").
+    ///     paths (List[str]): The paths of the files, in order.
+    ///     metadata (Optional[dict]): Key/values attached to every chunk when metadata output is enabled.
     ///
     /// Returns:
     ///     ChunkerIterator: An iterator over the chunks.
     ///
     /// Raises:
-    ///     ValueError: If the file cannot be read (e.g., does not exist).
-    fn on_file(&self, path: String) -> PyResult<ChunkerIterator> {
-        let source = Source::File(path);
-        self.on_source_internal(source)
+    ///     IOError: If a file cannot be opened, or reading one failed midway.
+    #[pyo3(signature = (paths, metadata = None))]
+    fn on_files_concatenated(
+        &self,
+        paths: Vec<String>,
+        metadata: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<ChunkerIterator> {
+        let metadata = metadata.map(|m| m.copy()).transpose()?.map(Bound::unbind);
+        let files = ConcatenatedFiles::open(paths).map_err(chunking_error)?;
+        let file_offsets = files.offsets();
+        let mut chunks = with_chunker!(&self.inner, c => {
+            self.iterator(c.on_reader_with_offsets(files), metadata)
+        });
+        chunks.file_offsets = Some(file_offsets);
+        Ok(chunks)
     }
 
     /// Chunk content from an HTTP/HTTPS URL.
     ///
     /// Args:
     ///     url (str): The URL to fetch content from (e.g., "http://example.com/text").
+    ///     metadata (Optional[dict]): Key/values attached to every chunk when metadata output is enabled.
     ///
     /// Returns:
     ///     ChunkerIterator: An iterator over the chunks.
     ///
     /// Raises:
     ///     ValueError: If the URL cannot be fetched or content cannot be processed.
-    fn on_http(&self, url: String) -> PyResult<ChunkerIterator> {
+    #[pyo3(signature = (url, metadata = None))]
+    fn on_http(
+        &self,
+        url: String,
+        metadata: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<ChunkerIterator> {
         let source = Source::Http(url);
-        self.on_source_internal(source)
+        self.on_source_internal(source, metadata)
     }
 
     /// Chunk multiple sources specified as strings with prefixes.
@@ -246,6 +2664,8 @@ impl ChunkerBuilderWrapper {
     ///
     /// Args:
     ///     source_strings (List[str]): A list of source strings with optional prefixes.
+    ///     follow_symlinks (bool): Chunk the files behind symlinks matched by a glob, each file
+    ///         once. Otherwise symlinks and what lies under symlinked directories are skipped.
     ///
     /// Returns:
     ///     ChunkerIterator: An iterator over the chunks from all sources.
@@ -253,20 +2673,90 @@ impl ChunkerBuilderWrapper {
     /// Raises:
     ///     ValueError: If any source has an invalid prefix, uses an unsupported type (e.g., "sitemap://"),
     ///                 or cannot be processed (e.g., file not found, invalid glob).
-    fn on_sources(&self, source_strings: Vec<String>) -> PyResult<ChunkerIterator> {
+    #[pyo3(signature = (source_strings, *, follow_symlinks = false))]
+    fn on_sources(
+        &self,
+        source_strings: Vec<String>,
+        follow_symlinks: bool,
+    ) -> PyResult<ChunkerIterator> {
         if source_strings.is_empty() {
             // Return an empty iterator for empty input
-            return Ok(ChunkerIterator {
-                inner: Box::new(std::iter::empty()),
-            });
+            return Ok(ChunkerIterator::empty());
         }
 
-        let higher_order_sources = parse_source_strings(source_strings)?;
+        let (sources, skipped) = expand_source_strings(source_strings, follow_symlinks)?;
 
+        let mut chunks = self.on_sources_internal(sources)?;
+        chunks.skipped = skipped;
+        Ok(chunks)
+    }
+
+    /// Get only the first chunk of a source, e.g. to preview a document.
+    ///
+    /// A file or URL is only read as far as needed to cut the first chunk. The source string
+    /// takes the same prefixes as `on_sources`; a glob yields the first chunk of its first file.
+    ///
+    /// Args:
+    ///     source (str): A source string with an optional prefix.
+    ///
+    /// Returns:
+    ///     Optional[str]: The first chunk, or None if the source produces no chunks.
+    ///
+    /// Raises:
+    ///     ValueError: If the source has an invalid prefix or cannot be opened.
+    ///     IOError: If reading the source failed before the first chunk.
+    fn first(&self, py: Python<'_>, source: String) -> PyResult<Option<String>> {
+        let higher_order_sources = parse_source_strings(vec![source])?;
         let sources = HigherOrderSource::into_flattened_sources(higher_order_sources)
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
 
-        self.on_sources_internal(sources)
+        let mut chunks = self.on_sources_internal(sources)?;
+        let inner = &mut chunks.inner;
+        let first = py.detach(|| inner.next());
+        chunks.raise_callback_error()?;
+        let first = first.transpose().map_err(chunking_error)?;
+        Ok(first.map(|chunk| chunk.text))
+    }
+
+    /// Chunk multiple sources in turn, one chunk from each source at a time.
+    ///
+    /// Sources are advanced round-robin, skipping exhausted ones, until all are done. Chunks
+    /// are streamed: a source is only read as its chunks are pulled.
+    ///
+    /// Supported prefixes:
+    /// - `file://` for local files (e.g., "file://path/to/file.txt").
+    /// - `http://` or `https://` for URLs (e.g., "http://example.com/text").
+    /// - `text://` for raw text (e.g., "text://Hello world").
+    /// - `glob://` for glob patterns (e.g., "glob://*.txt").
+    /// - No prefix assumes a local file path (e.g., "path/to/file.txt").
+    ///
+    /// Args:
+    ///     source_strings (List[str]): A list of source strings with optional prefixes.
+    ///     follow_symlinks (bool): Chunk the files behind symlinks matched by a glob, each file
+    ///         once. Otherwise symlinks and what lies under symlinked directories are skipped.
+    ///
+    /// Returns:
+    ///     ChunkerIterator: An iterator over the interleaved chunks of all sources.
+    ///
+    /// Raises:
+    ///     ValueError: If any source has an invalid prefix, uses an unsupported type (e.g., "sitemap://"),
+    ///                 or cannot be processed (e.g., file not found, invalid glob).
+    #[pyo3(signature = (source_strings, *, follow_symlinks = false))]
+    fn round_robin(
+        &self,
+        source_strings: Vec<String>,
+        follow_symlinks: bool,
+    ) -> PyResult<ChunkerIterator> {
+        if source_strings.is_empty() {
+            // Return an empty iterator for empty input
+            return Ok(ChunkerIterator::empty());
+        }
+
+        let (sources, skipped) = expand_source_strings(source_strings, follow_symlinks)?;
+
+        let mut chunks = self.on_sources_round_robin_internal(sources)?;
+        chunks.skipped = skipped;
+        Ok(chunks)
     }
 
     /// Chunk multiple sources in parallel, specified as strings with prefixes.
@@ -281,6 +2771,8 @@ impl ChunkerBuilderWrapper {
     /// Args:
     ///     source_strings (List[str]): A list of source strings with optional prefixes.
     ///     channel_size (Optional[int]): Number of chunks to buffer in the channel (default: 100).
+    ///     follow_symlinks (bool): Chunk the files behind symlinks matched by a glob, each file
+    ///         once. Otherwise symlinks and what lies under symlinked directories are skipped.
     ///
     /// Returns:
     ///     ChunkerIterator: An iterator over the chunks from all sources.
@@ -288,24 +2780,23 @@ impl ChunkerBuilderWrapper {
     /// Raises:
     ///     ValueError: If any source has an invalid prefix, uses an unsupported type (e.g., "sitemap://"),
     ///                 or cannot be processed (e.g., file not found, invalid glob).
+    #[pyo3(signature = (source_strings, channel_size = None, *, follow_symlinks = false))]
     fn on_sources_par(
         &self,
         source_strings: Vec<String>,
         channel_size: Option<usize>,
+        follow_symlinks: bool,
     ) -> PyResult<ChunkerIterator> {
         if source_strings.is_empty() {
             // Return an empty iterator for empty input
-            return Ok(ChunkerIterator {
-                inner: Box::new(std::iter::empty()),
-            });
+            return Ok(ChunkerIterator::empty());
         }
 
-        let higher_order_sources = parse_source_strings(source_strings)?;
-
-        let sources = HigherOrderSource::into_flattened_sources(higher_order_sources)
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        let (sources, skipped) = expand_source_strings(source_strings, follow_symlinks)?;
 
-        self.on_sources_par_internal(sources, channel_size.unwrap_or(1000))
+        let mut chunks = self.on_sources_par_internal(sources, channel_size.unwrap_or(1000))?;
+        chunks.skipped = skipped;
+        Ok(chunks)
     }
 }
 
@@ -314,9 +2805,205 @@ impl ChunkerIterator {
     /// Collect all chunks into a list.
     ///
     /// Returns:
-    ///     List[str]: A list of all chunks.
-    fn all(mut slf: PyRefMut<Self>) -> Vec<String> {
-        slf.inner.by_ref().collect()
+    ///     List[str | Chunk]: A list of all chunks.
+    ///
+    /// Raises:
+    ///     IOError: If reading a source failed midway.
+    ///     Exception: Whatever a boundary callback raised.
+    fn all(mut slf: PyRefMut<Self>) -> PyResult<Vec<Py<PyAny>>> {
+        let py = slf.py();
+        let inner = &mut slf.inner;
+        let chunks: Vec<_> = py.detach(|| inner.by_ref().collect());
+        slf.raise_callback_error()?;
+        chunks
+            .into_iter()
+            .map(|chunk| slf.emit(py, chunk))
+            .collect()
+    }
+
+    /// Write the remaining chunks as JSON lines into rolling files `shard_0000.jsonl`,
+    /// `shard_0001.jsonl`, ... of a directory, without going through Python objects.
+    ///
+    /// Each line holds a chunk's `text`, `start`, `end` and `index` (plus `lines` when line
+    /// numbers are enabled); source metadata is not written.
+    ///
+    /// Args:
+    ///     dir (str): Output directory, created if missing.
+    ///     shard_size (int): Maximum number of chunks per file; the last file holds the rest.
+    ///
+    /// Returns:
+    ///     List[str]: The paths written, in order (empty when there are no chunks).
+    ///
+    /// Raises:
+    ///     ValueError: If shard_size is 0.
+    ///     IOError: If reading a source or writing a file failed.
+    fn to_jsonl_sharded(
+        mut slf: PyRefMut<Self>,
+        dir: PathBuf,
+        shard_size: usize,
+    ) -> PyResult<Vec<String>> {
+        let py = slf.py();
+        let inner = &mut slf.inner;
+        let paths = py.detach(|| write_jsonl_sharded(inner.by_ref(), dir, shard_size));
+        slf.raise_callback_error()?;
+        let paths = paths.map_err(chunking_error)?;
+        Ok(paths
+            .into_iter()
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect())
+    }
+
+    /// Write the remaining chunks as JSON lines to a file, without going through Python
+    /// objects, storing the overlap of each chunk as `store_mode` says, e.g. to keep it out
+    /// of a vector index.
+    ///
+    /// Lines are those of `to_jsonl_sharded()`. With "core", `text` is only the part of the
+    /// chunk not covered by the previous chunk of its source, and `start` where that part
+    /// starts, so the texts of the main chunks of a source concatenate to it; bridge chunks
+    /// are stored whole. "both" adds the covered text in an `overlap` field, the chunk being
+    /// `overlap` followed by `text`. The overlap assumes no prefix, preamble or suffix.
+    ///
+    /// Args:
+    ///     path (str): Path of the file, overwritten if it exists.
+    ///     store_mode (str): "full" (the whole chunk), "core" or "both".
+    ///
+    /// Returns:
+    ///     int: The number of chunks written.
+    ///
+    /// Raises:
+    ///     ValueError: If the store mode is unknown.
+    ///     IOError: If reading a source or writing the file failed.
+    #[pyo3(signature = (path, store_mode = "full"))]
+    fn to_jsonl(mut slf: PyRefMut<Self>, path: PathBuf, store_mode: &str) -> PyResult<usize> {
+        let mode = parse_store_mode(store_mode)?;
+        let py = slf.py();
+        let inner = &mut slf.inner;
+        let written = py.detach(|| write_jsonl(inner.by_ref(), path, mode));
+        slf.raise_callback_error()?;
+        written.map_err(chunking_error)
+    }
+
+    /// Write the remaining chunks as JSON lines to a gzip file, compressed as they are
+    /// written, without going through Python objects.
+    ///
+    /// Lines are those of `to_jsonl_sharded()`; source metadata is not written.
+    ///
+    /// Args:
+    ///     path (str): Path of the file, e.g. `chunks.jsonl.gz`, overwritten if it exists.
+    ///     level (int): Compression level, from 0 (none) to 9 (smallest output).
+    ///
+    /// Returns:
+    ///     int: The number of chunks written.
+    ///
+    /// Raises:
+    ///     ValueError: If level is more than 9.
+    ///     IOError: If reading a source or writing the file failed.
+    #[pyo3(signature = (path, level = 6))]
+    fn to_jsonl_gz(mut slf: PyRefMut<Self>, path: PathBuf, level: u32) -> PyResult<usize> {
+        let py = slf.py();
+        let inner = &mut slf.inner;
+        let written = py.detach(|| write_jsonl_gz(inner.by_ref(), path, level));
+        slf.raise_callback_error()?;
+        written.map_err(chunking_error)
+    }
+
+    /// Write the remaining chunks to a Parquet file, with columns `text` (string), `start`,
+    /// `end` and `index` (uint64) and `source`, without going through Python objects.
+    ///
+    /// `source` holds the path or URL chunked by `on_file()` or `on_http()`, and is null for
+    /// text and for several sources. Chunks are cut one row group at a time, so the whole
+    /// table is never held in memory. Source metadata is not written.
+    ///
+    /// Args:
+    ///     path (str): Path of the Parquet file, overwritten if it exists.
+    ///     row_group_size (int): Maximum number of chunks per row group.
+    ///
+    /// Returns:
+    ///     int: The number of chunks written.
+    ///
+    /// Raises:
+    ///     ValueError: If row_group_size is 0.
+    ///     IOError: If reading a source or writing the file failed.
+    #[pyo3(signature = (path, row_group_size = 65536))]
+    fn to_parquet(
+        mut slf: PyRefMut<Self>,
+        path: PathBuf,
+        row_group_size: usize,
+    ) -> PyResult<usize> {
+        let py = slf.py();
+        let source = slf.source.clone();
+        let inner = &mut slf.inner;
+        let written =
+            py.detach(|| write_parquet(inner.by_ref(), source.as_deref(), path, row_group_size));
+        slf.raise_callback_error()?;
+        written.map_err(chunking_error)
+    }
+
+    /// Gather the remaining chunks into Arrow record batches, with columns `text`, `start`,
+    /// `end` and `index`, without going through Python objects.
+    ///
+    /// The result implements the Arrow PyCapsule stream interface, so it can be read by
+    /// `pyarrow.table()`, `pyarrow.RecordBatchReader.from_stream()` or `polars.DataFrame()`.
+    /// Chunks are cut as the batches are read. Source metadata is not included.
+    ///
+    /// Args:
+    ///     batch_size (int): Maximum number of chunks per record batch.
+    ///
+    /// Returns:
+    ///     ArrowChunks: The record batches, to be read once.
+    ///
+    /// Raises:
+    ///     ValueError: If batch_size is 0.
+    #[pyo3(signature = (batch_size = 65536))]
+    fn to_arrow(mut slf: PyRefMut<Self>, batch_size: usize) -> PyResult<ArrowChunks> {
+        let inner = std::mem::replace(&mut slf.inner, Box::new(std::iter::empty()));
+        let batches = record_batches(inner, batch_size).map_err(chunking_error)?;
+        Ok(ArrowChunks {
+            batches: Some(CallbackCheckedBatches {
+                batches,
+                callback_error: slf.callback_error.clone(),
+            }),
+        })
+    }
+
+    /// Yield `(position, chunk)` tuples from now on, `position` counting every chunk of
+    /// this iterator from 0 (including those already consumed).
+    ///
+    /// Returns:
+    ///     ChunkerIterator: The iterator itself.
+    fn enumerated(mut slf: PyRefMut<Self>) -> PyRefMut<Self> {
+        slf.enumerated = true;
+        slf
+    }
+
+    /// Yield only the chunks whose text was not yielded before from now on, in order.
+    ///
+    /// Texts are remembered by a 64-bit hash, so memory grows by 8 bytes per distinct chunk
+    /// rather than by its size. Chunks are compared across all the sources of the iterator.
+    ///
+    /// Returns:
+    ///     ChunkerIterator: The iterator itself.
+    fn unique(mut slf: PyRefMut<Self>) -> PyRefMut<Self> {
+        let inner = std::mem::replace(&mut slf.inner, Box::new(std::iter::empty()));
+        slf.inner = Box::new(unique_chunks(inner));
+        slf
+    }
+
+    /// Paths matched by a glob source but not chunked: directories, FIFOs, devices and
+    /// sockets, unreadable paths, symlinks that are broken or not followed, and files
+    /// already reached through another symlink.
+    ///
+    /// Returns:
+    ///     List[str]: The skipped paths, in the order the globs matched them.
+    fn skipped(&self) -> Vec<String> {
+        self.skipped.clone()
+    }
+
+    /// Stop the iteration and release what it holds (open files, connections, worker
+    /// threads) right away instead of when the iterator is garbage collected. Further
+    /// iteration yields no chunks; closing twice is a no-op.
+    fn close(&mut self) {
+        self.inner = Box::new(std::iter::empty());
     }
 
     /// Return an iterator over the chunks.
@@ -330,12 +3017,418 @@ impl ChunkerIterator {
     /// Get the next chunk.
     ///
     /// Returns:
-    ///     Optional[str]: The next chunk, or None if exhausted.
-    fn __next__(mut slf: PyRefMut<Self>) -> Option<String> {
-        slf.inner.next()
+    ///     Optional[str | Chunk]: The next chunk, or None if exhausted.
+    ///
+    /// Raises:
+    ///     IOError: If reading a source failed midway, once the chunks read before the failure
+    ///         have been yielded.
+    ///     Exception: Whatever a boundary callback raised.
+    fn __next__(mut slf: PyRefMut<Self>) -> PyResult<Option<Py<PyAny>>> {
+        let py = slf.py();
+        // Release the GIL while chunking, so that parallel sources can call back into Python
+        let inner = &mut slf.inner;
+        let chunk = py.detach(|| inner.next());
+        slf.raise_callback_error()?;
+        chunk.map(|chunk| slf.emit(py, chunk)).transpose()
+    }
+}
+
+#[pymethods]
+impl ChunkSequence {
+    /// Number of chunks, cutting them all.
+    ///
+    /// Raises:
+    ///     Exception: Whatever a boundary callback raised.
+    fn __len__(&mut self, py: Python<'_>) -> PyResult<usize> {
+        self.pull(py, None)?;
+        Ok(self.pulled.len())
+    }
+
+    /// Get a chunk by index, negative indices counting from the end.
+    ///
+    /// Raises:
+    ///     IndexError: If there is no chunk at the index.
+    ///     Exception: Whatever a boundary callback raised.
+    fn __getitem__(&mut self, py: Python<'_>, index: isize) -> PyResult<Py<PyAny>> {
+        let index = match usize::try_from(index) {
+            Ok(index) => Some(index),
+            Err(_) => {
+                self.pull(py, None)?;
+                self.pulled.len().checked_sub(index.unsigned_abs())
+            }
+        };
+        let chunk = match index {
+            Some(index) => self.get(py, index)?,
+            None => None,
+        };
+        chunk.ok_or_else(|| pyo3::exceptions::PyIndexError::new_err("chunk index out of range"))
+    }
+
+    /// Return an iterator over the chunks, from the first one.
+    fn __iter__(slf: Bound<'_, Self>) -> ChunkSequenceIterator {
+        ChunkSequenceIterator {
+            sequence: slf.unbind(),
+            position: 0,
+        }
+    }
+}
+
+#[pymethods]
+impl ChunkSequenceIterator {
+    fn __iter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    /// Get the next chunk, cutting it if it was not yet.
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<Py<PyAny>>> {
+        let chunk = self.sequence.borrow_mut(py).get(py, self.position)?;
+        self.position += 1;
+        Ok(chunk)
+    }
+}
+
+#[pymethods]
+impl ArrowChunks {
+    /// Export the record batches as an `ArrowArrayStream` PyCapsule. A requested schema is
+    /// ignored: batches always have the `text`, `start`, `end` and `index` columns.
+    ///
+    /// Raises:
+    ///     ValueError: If the stream was already exported.
+    #[pyo3(signature = (requested_schema = None))]
+    fn __arrow_c_stream__<'py>(
+        &mut self,
+        py: Python<'py>,
+        requested_schema: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        let _ = requested_schema;
+        let batches = self.batches.take().ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err("the Arrow stream was already exported")
+        })?;
+        let stream = FFI_ArrowArrayStream::new(Box::new(batches));
+        let name = CString::new("arrow_array_stream").expect("Bug: nul in capsule name");
+        PyCapsule::new(py, stream, Some(name))
+    }
+}
+
+#[pymethods]
+impl PyChunk {
+    fn __repr__(&self) -> String {
+        format!(
+            "Chunk(index={}, start={}, end={}, text={:?})",
+            self.index, self.start, self.end, self.text
+        )
+    }
+}
+
+/// Report the step a strategy actually takes between consecutive chunks of a sample.
+///
+/// Snapping to char boundaries can make the step differ from `chunk_size - overlap`, so
+/// chunking a representative sample before a big run shows the step to expect.
+///
+/// Args:
+///     text_sample (str): Representative text to chunk.
+///     chunk_size (int): The size of each chunk.
+///     overlap (int): The overlap between chunks (must be less than chunk_size).
+///     strategy (str): "bytes" or "characters" (also "chars").
+///
+/// Returns:
+///     Optional[int]: The smallest step between the start offsets of consecutive chunks, in
+///         bytes or characters, or None if the sample makes fewer than two chunks.
+///
+/// Raises:
+///     ValueError: If the arguments are invalid or the strategy is unknown.
+#[pyfunction]
+#[pyo3(signature = (text_sample, chunk_size, overlap, strategy = "bytes"))]
+fn effective_step(
+    text_sample: &str,
+    chunk_size: usize,
+    overlap: usize,
+    strategy: &str,
+) -> PyResult<Option<usize>> {
+    match strategy {
+        "bytes" => Ok(BytesChunker::new(chunk_size, overlap)
+            .map_err(chunking_error)?
+            .effective_step(text_sample)),
+        "characters" | "chars" => Ok(CharactersChunker::new(chunk_size, overlap)
+            .map_err(chunking_error)?
+            .effective_step(text_sample)),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "invalid strategy '{other}'"
+        ))),
+    }
+}
+
+/// Suggest the chunk size that cuts a source into about `target_chunks` chunks.
+///
+/// Inverts the chunk count of the bytes and characters strategies, accounting for the
+/// overlap. The size is rounded up, so ASCII text never makes more than `target_chunks`
+/// chunks; snapping to char boundaries can add one on other text.
+///
+/// Args:
+///     source_len (int): Length of the source, in bytes or characters.
+///     target_chunks (int): The number of chunks wanted.
+///     overlap (int): The overlap the chunks will have, in the same unit.
+///
+/// Returns:
+///     int: The chunk size to chunk with, always greater than the overlap.
+///
+/// Raises:
+///     ValueError: If `target_chunks` is 0.
+#[pyfunction]
+fn suggest_chunk_size(source_len: usize, target_chunks: usize, overlap: usize) -> PyResult<usize> {
+    suggest_size(source_len, target_chunks, overlap).map_err(chunking_error)
+}
+
+/// Count the bytes, characters and lines of a file in one streaming pass, e.g. to gauge from
+/// `chars / bytes` how much multi-byte text it holds before choosing a strategy.
+///
+/// Characters are counted as the bytes starting a UTF-8 sequence, without validating the
+/// file. Lines are those ending with a newline, plus a last line without one.
+///
+/// Args:
+///     path (str): The path of the file.
+///
+/// Returns:
+///     Dict[str, int]: `bytes`, `chars` and `lines`.
+///
+/// Raises:
+///     IOError: If reading the file failed.
+#[pyfunction]
+fn source_stats<'py>(py: Python<'py>, path: PathBuf) -> PyResult<Bound<'py, PyDict>> {
+    let stats = py.detach(|| count_source(path)).map_err(chunking_error)?;
+
+    let dict = PyDict::new(py);
+    dict.set_item("bytes", stats.bytes)?;
+    dict.set_item("chars", stats.chars)?;
+    dict.set_item("lines", stats.lines)?;
+    Ok(dict)
+}
+
+/// Hash a text the way `running_hash` hashes chunks.
+///
+/// Args:
+///     text (str): The text to hash.
+///
+/// Returns:
+///     int: The 64-bit FNV-1a hash of the UTF-8 bytes of `text`, stable across runs and
+///         platforms. The running hash of chunks is the hash of their concatenation.
+#[pyfunction]
+fn content_hash(text: &str) -> u64 {
+    hash_content(text)
+}
+
+/// Compare two chunkings, e.g. before and after tuning chunk_size or overlap, to tell
+/// which cached chunks stay valid.
+///
+/// The boundaries of a chunking are the starts and ends of its chunks. Between two offsets
+/// that both chunkings cut at, the changed boundaries of each are paired in order as shifted
+/// ones, the side with more of them leaving out those farther from their counterpart, which
+/// are added or removed.
+///
+/// Args:
+///     boundaries_a (List[Tuple[int, int]]): `(start, end)` of every chunk of the first chunking.
+///     boundaries_b (List[Tuple[int, int]]): `(start, end)` of every chunk of the second one.
+///
+/// Returns:
+///     Dict[str, Any]: `added` and `removed` offsets, `shifted` `(old, new)` offset pairs,
+///         `unchanged_chunks` (chunks of the second chunking with the same span as one of the
+///         first) and `unchanged_fraction` (their share of the second chunking, 1.0 if empty).
+#[pyfunction]
+fn diff_boundaries<'py>(
+    py: Python<'py>,
+    boundaries_a: Vec<(usize, usize)>,
+    boundaries_b: Vec<(usize, usize)>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let diff = diff_chunk_boundaries(&boundaries_a, &boundaries_b);
+
+    let dict = PyDict::new(py);
+    dict.set_item("added", diff.added)?;
+    dict.set_item("removed", diff.removed)?;
+    dict.set_item("shifted", diff.shifted)?;
+    dict.set_item("unchanged_chunks", diff.unchanged_chunks)?;
+    dict.set_item("unchanged_fraction", diff.unchanged_fraction)?;
+    Ok(dict)
+}
+
+/// Describe how a strategy sizes and overlaps chunks.
+///
+/// Args:
+///     name (str): Strategy name, as in the `Chunker.by_*` constructors (e.g. "bytes",
+///         "lines_packed").
+///
+/// Returns:
+///     Dict[str, Any]: `unit` and `overlap_unit` (e.g. "bytes", "characters", "lines",
+///         "seconds"), `overlap_semantics` ("exact", "at_least", "at_most" or "approximate",
+///         relating the produced overlap to the configured one), `splits_graphemes` (whether a
+///         cut may fall inside a grapheme cluster) and `streaming_supported`.
+///
+/// Raises:
+///     ValueError: If the strategy is unknown.
+#[pyfunction]
+fn strategy_info<'py>(py: Python<'py>, name: &str) -> PyResult<Bound<'py, PyDict>> {
+    let info = kiru_core::strategy_info(name).ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err(format!("invalid strategy '{name}'"))
+    })?;
+
+    let dict = PyDict::new(py);
+    dict.set_item("unit", info.unit)?;
+    dict.set_item("overlap_unit", info.overlap_unit)?;
+    dict.set_item("overlap_semantics", info.overlap_semantics.as_str())?;
+    dict.set_item("splits_graphemes", info.splits_graphemes)?;
+    dict.set_item("streaming_supported", info.streaming_supported)?;
+    Ok(dict)
+}
+
+/// Check that chunking a source without overlap loses nothing, i.e. that its chunks
+/// concatenate to the source.
+///
+/// The chunks are compared as they are cut with a second read of the source, so neither is
+/// held whole from a file or URL; a URL is fetched twice.
+///
+/// Args:
+///     source (str): The path, URL or text to chunk, as told by `source_type`.
+///     chunk_size (int): The size of each chunk.
+///     strategy (str): "bytes" or "characters" (also "chars").
+///     source_type (str): "file", "http" or "string".
+///
+/// Returns:
+///     Dict[str, Any]: `lossless`, and unless it is True `offset` (byte offset in the source
+///         of the first character that differs), `expected` and `found` (up to 32 bytes of the
+///         source and of the concatenated chunks from there).
+///
+/// Raises:
+///     ValueError: If the arguments are invalid or the strategy or source type is unknown.
+///     IOError: If the source cannot be read.
+#[pyfunction]
+#[pyo3(signature = (source, chunk_size, strategy = "bytes", source_type = "file"))]
+fn verify_lossless<'py>(
+    py: Python<'py>,
+    source: String,
+    chunk_size: usize,
+    strategy: &str,
+    source_type: &str,
+) -> PyResult<Bound<'py, PyDict>> {
+    let source = parse_source(source, source_type)?;
+    let mismatch = match strategy {
+        "bytes" => py.detach(|| kiru_core::verify_lossless(source, chunk_size, BytesChunker::new)),
+        "characters" | "chars" => {
+            py.detach(|| kiru_core::verify_lossless(source, chunk_size, CharactersChunker::new))
+        }
+        other => {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "invalid strategy '{other}'"
+            )))
+        }
+    }
+    .map_err(chunking_error)?;
+
+    let dict = PyDict::new(py);
+    dict.set_item("lossless", mismatch.is_none())?;
+    if let Some(mismatch) = mismatch {
+        dict.set_item("offset", mismatch.offset)?;
+        dict.set_item("expected", mismatch.expected)?;
+        dict.set_item("found", mismatch.found)?;
+    }
+    Ok(dict)
+}
+
+/// Chunk a source into parent chunks and each parent into smaller child chunks, reading it
+/// once, e.g. to retrieve small chunks and hand their parent to a model.
+///
+/// Each parent is yielded followed by its children, which are cut from its text. Parents
+/// have `level` 0, children `level` 1 and the `parent_index` of their parent, and children
+/// are indexed among the children of the source. Offsets of children are within the source.
+///
+/// Args:
+///     source (str): The path, URL or text to chunk, as told by `source_type`.
+///     parent_size (int): The size of each parent chunk.
+///     child_size (int): The size of each child chunk, less than `parent_size`.
+///     overlap (int): The overlap between consecutive parents, and between consecutive
+///         children of a parent.
+///     strategy (str): "bytes" or "characters" (also "chars").
+///     source_type (str): "file", "http" or "string".
+///     metadata (Optional[dict]): Key/values attached to every chunk.
+///
+/// Returns:
+///     ChunkerIterator: An iterator over `Chunk` objects.
+///
+/// Raises:
+///     ValueError: If the arguments are invalid or the strategy or source type is unknown.
+///     IOError: If the source cannot be read.
+#[pyfunction]
+#[pyo3(signature = (source, parent_size, child_size, overlap, strategy = "bytes", source_type = "file", metadata = None))]
+fn hierarchical(
+    source: String,
+    parent_size: usize,
+    child_size: usize,
+    overlap: usize,
+    strategy: &str,
+    source_type: &str,
+    metadata: Option<&Bound<'_, PyDict>>,
+) -> PyResult<ChunkerIterator> {
+    let name = (source_type != "string").then(|| source.clone());
+    let source = parse_source(source, source_type)?;
+    let inner: ChunkStream = match strategy {
+        "bytes" => Box::new(
+            kiru_core::hierarchical(source, parent_size, child_size, overlap, BytesChunker::new)
+                .map_err(chunking_error)?,
+        ),
+        "characters" | "chars" => Box::new(
+            kiru_core::hierarchical(
+                source,
+                parent_size,
+                child_size,
+                overlap,
+                CharactersChunker::new,
+            )
+            .map_err(chunking_error)?,
+        ),
+        other => {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "invalid strategy '{other}'"
+            )))
+        }
+    };
+    Ok(ChunkerIterator {
+        inner,
+        output: ChunkOutput::Chunk,
+        metadata: metadata.map(|m| m.copy()).transpose()?.map(Bound::unbind),
+        source: name,
+        ..ChunkerIterator::empty()
+    })
+}
+
+/// The source `source_type` ("file", "http" or "string") tells `source` is.
+fn parse_source(source: String, source_type: &str) -> PyResult<Source> {
+    match source_type {
+        "file" => Ok(Source::File(source)),
+        "http" => Ok(Source::Http(source)),
+        "string" => Ok(Source::Text(source)),
+        other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "invalid source type '{other}'"
+        ))),
     }
 }
 
+/// Run the `kiru` command line with the arguments of `sys.argv`.
+///
+/// This is the entry point of the `kiru` console script, e.g.
+/// `kiru chunk --strategy characters --chunk-size 1000 --overlap 100 file.txt`.
+///
+/// Raises:
+///     SystemExit: With code 2 for invalid arguments and 1 when chunking fails.
+#[pyfunction]
+fn main(py: Python<'_>) -> PyResult<()> {
+    let argv: Vec<String> = py.import("sys")?.getattr("argv")?.extract()?;
+    let args = argv.get(1..).unwrap_or_default();
+
+    py.detach(|| run_cli(args, &mut std::io::stdout().lock()))
+        .map_err(|e| {
+            eprintln!("kiru: {e}");
+            pyo3::exceptions::PySystemExit::new_err(e.exit_code())
+        })
+}
+
 // ============================================================================
 // Python Module
 // ============================================================================
@@ -345,5 +3438,21 @@ fn kiru(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<Chunker>()?;
     m.add_class::<ChunkerBuilderWrapper>()?;
     m.add_class::<ChunkerIterator>()?;
+    m.add_class::<ChunkSequence>()?;
+    m.add_class::<ChunkSequenceIterator>()?;
+    m.add_class::<StopSignal>()?;
+    m.add_class::<PyTokenizer>()?;
+    m.add_class::<PyChunk>()?;
+    m.add_class::<ArrowChunks>()?;
+    m.add("ChunkTuple", chunk_tuple_class(m.py())?)?;
+    m.add_function(wrap_pyfunction!(effective_step, m)?)?;
+    m.add_function(wrap_pyfunction!(suggest_chunk_size, m)?)?;
+    m.add_function(wrap_pyfunction!(content_hash, m)?)?;
+    m.add_function(wrap_pyfunction!(source_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(diff_boundaries, m)?)?;
+    m.add_function(wrap_pyfunction!(strategy_info, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_lossless, m)?)?;
+    m.add_function(wrap_pyfunction!(hierarchical, m)?)?;
+    m.add_function(wrap_pyfunction!(main, m)?)?;
     Ok(())
 }